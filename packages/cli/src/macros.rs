@@ -1,116 +1,145 @@
 macro_rules! register_plugins {
     (
-        post: { $($post_id:ident($post_plugin:ty) => $post_initializer:expr),* $(,)? },
-        calendar: { $($calendar_id:ident($calendar_plugin:ty) => $calendar_initializer:expr),* $(,)? }
+        post: { $($(#[$post_meta:meta])* $post_id:ident($post_plugin:ty) => $post_initializer:expr),* $(,)? },
+        calendar: { $($(#[$calendar_meta:meta])* $calendar_id:ident($calendar_plugin:ty) => $calendar_initializer:expr),* $(,)? }
     ) => {
         enum SsufidPluginRegistry {
-            $($post_id($post_plugin),)*
-            $($calendar_id($calendar_plugin),)*
+            $($(#[$post_meta])* $post_id($post_plugin),)*
+            $($(#[$calendar_meta])* $calendar_id($calendar_plugin),)*
         }
 
         impl SsufidPluginRegistry {
+            fn identifier(&self) -> &'static str {
+                match self {
+                    $($(#[$post_meta])* Self::$post_id(_) => <$post_plugin>::IDENTIFIER,)*
+                    $($(#[$calendar_meta])* Self::$calendar_id(_) => <$calendar_plugin>::IDENTIFIER,)*
+                }
+            }
+
             async fn save_run(
-                self,
+                &self,
                 core: Arc<ssufid::SsufidCore>,
                 out_dir: &Path,
                 calendar_out_dir: &Path,
-                posts_limit: u32,
-                calendar_range: ssufid::core::CalendarCrawlRange,
-                retry_count: u32,
-            ) -> eyre::Result<()> {
-                let _ = &calendar_range;
+                save_options: &crate::SaveOptions,
+                calendar_range: &ssufid::core::CalendarCrawlRange,
+            ) -> eyre::Result<Option<usize>> {
+                let _ = (&calendar_out_dir, &calendar_range);
                 match self {
-                    $(Self::$post_id(plugin) => {
-                        crate::save_run(core, out_dir, plugin, posts_limit, retry_count).await
+                    $($(#[$post_meta])* Self::$post_id(plugin) => {
+                        crate::save_run(core, out_dir, plugin, save_options).await.map(Some)
                     },)*
-                    $(Self::$calendar_id(plugin) => {
+                    $($(#[$calendar_meta])* Self::$calendar_id(plugin) => {
                         crate::save_calendar_run(
                             core,
                             calendar_out_dir,
                             plugin,
-                            calendar_range,
-                            retry_count,
-                        ).await
+                            calendar_range.clone(),
+                            save_options,
+                        ).await.map(|()| None)
+                    },)*
+                }
+            }
+
+            /// The cost of crawling the plugin with `options`, or `None` for calendars.
+            fn estimate_cost(
+                &self,
+                options: &ssufid::core::CrawlOptions,
+            ) -> Option<ssufid::core::CostEstimate> {
+                let _ = &options;
+                match self {
+                    $($(#[$post_meta])* Self::$post_id(plugin) => Some(plugin.estimate_cost(options)),)*
+                    $($(#[$calendar_meta])* Self::$calendar_id(_) => None,)*
+                }
+            }
+
+            async fn check(
+                &self,
+                client: &reqwest::Client,
+                deep: bool,
+            ) -> Result<ssufid::core::HealthStatus, ssufid::PluginError> {
+                match self {
+                    $($(#[$post_meta])* Self::$post_id(plugin) => {
+                        if deep {
+                            plugin.health_check().await
+                        } else {
+                            ssufid::fetch::ping::<$post_plugin>(client).await
+                        }
+                    },)*
+                    $($(#[$calendar_meta])* Self::$calendar_id(_) => {
+                        ssufid::fetch::ping::<$calendar_plugin>(client).await
+                    },)*
+                }
+            }
+
+            async fn validate(
+                &self,
+                core: &ssufid::SsufidCore,
+                count: u32,
+            ) -> eyre::Result<Vec<ssufid::core::ValidationWarning>> {
+                let _ = (&core, &count);
+                match self {
+                    $($(#[$post_meta])* Self::$post_id(plugin) => crate::validate_sample(core, plugin, count).await,)*
+                    $($(#[$calendar_meta])* Self::$calendar_id(_) => {
+                        eyre::bail!("{} has no posts to validate", <$calendar_plugin>::IDENTIFIER)
+                    },)*
+                }
+            }
+
+            async fn refresh_fixtures(
+                &self,
+                client: &reqwest::Client,
+                count: u32,
+                dir: &Path,
+            ) -> eyre::Result<Vec<ssufid::fixtures::FixtureDiff>> {
+                let _ = (&client, &count, &dir);
+                match self {
+                    $($(#[$post_meta])* Self::$post_id(plugin) => {
+                        let urls = plugin.fixture_urls(count).await?;
+                        Ok(ssufid::fixtures::refresh::<$post_plugin>(client, &urls, dir).await?)
+                    },)*
+                    $($(#[$calendar_meta])* Self::$calendar_id(_) => {
+                        eyre::bail!("{} has no fixtures to refresh", <$calendar_plugin>::IDENTIFIER)
                     },)*
                 }
             }
         }
 
-        fn construct_tasks(
-            core: Arc<SsufidCore>,
-            out_dir: &Path,
-            calendar_out_dir: &Path,
-            options: SsufidDaemonOptions,
-            calendar_range: ssufid::core::CalendarCrawlRange,
-        ) -> Vec<impl std::future::Future<Output = eyre::Result<()>>> {
-            let include: Option<HashSet<String>> = options
+        /// The identifiers of the plugins compiled in, in registration order. Each entry may be
+        /// gated with `#[cfg(feature = "plugin-...")]`, leaving it out of builds without it.
+        fn registered_identifiers() -> Vec<&'static str> {
+            [
+                $($(#[$post_meta])* <$post_plugin>::IDENTIFIER,)*
+                $($(#[$calendar_meta])* <$calendar_plugin>::IDENTIFIER,)*
+            ]
+            .to_vec()
+        }
+
+        /// The plugins selected by `--include` or `--exclude`.
+        fn construct_plugins(options: &SsufidDaemonOptions) -> Vec<SsufidPluginRegistry> {
+            let include: Option<HashSet<&str>> = options
                 .include
                 .is_empty()
                 .not()
-                .then_some(HashSet::from_iter(options.include));
-            let exclude: Option<HashSet<String>> = options
+                .then(|| options.include.iter().map(String::as_str).collect());
+            let exclude: Option<HashSet<&str>> = options
                 .exclude
                 .is_empty()
                 .not()
-                .then_some(HashSet::from_iter(options.exclude));
-            let tasks = [
-                $(
-                    (
-                        <$post_plugin>::IDENTIFIER,
-                        SsufidPluginRegistry::$post_id($post_initializer),
-                    ),
-                )*
-                $(
-                    (
-                        <$calendar_plugin>::IDENTIFIER,
-                        SsufidPluginRegistry::$calendar_id($calendar_initializer),
-                    ),
-                )*
+                .then(|| options.exclude.iter().map(String::as_str).collect());
+            let plugins = [
+                $($(#[$post_meta])* SsufidPluginRegistry::$post_id($post_initializer),)*
+                $($(#[$calendar_meta])* SsufidPluginRegistry::$calendar_id($calendar_initializer),)*
             ];
 
-            if let Some(include) = include {
-                tasks
-                    .into_iter()
-                    .filter_map(|(id, task)| {
-                        include.contains(id).then_some(task.save_run(
-                            core.clone(),
-                            out_dir,
-                            calendar_out_dir,
-                            options.posts_limit,
-                            calendar_range.clone(),
-                            options.retry_count,
-                        ))
-                    })
-                    .collect()
-            } else if let Some(exclude) = exclude {
-                tasks
-                    .into_iter()
-                    .filter_map(|(id, task)| {
-                        exclude.contains(id).not().then_some(task.save_run(
-                            core.clone(),
-                            out_dir,
-                            calendar_out_dir,
-                            options.posts_limit,
-                            calendar_range.clone(),
-                            options.retry_count,
-                        ))
-                    })
-                    .collect()
-            } else {
-                tasks
-                    .into_iter()
-                    .map(|(_, task)| {
-                        task.save_run(
-                            core.clone(),
-                            out_dir,
-                            calendar_out_dir,
-                            options.posts_limit,
-                            calendar_range.clone(),
-                            options.retry_count,
-                        )
-                    })
-                    .collect()
-            }
+            plugins
+                .into_iter()
+                .filter(|plugin| {
+                    let id = plugin.identifier();
+                    include.as_ref().is_none_or(|include| include.contains(id))
+                        && exclude.as_ref().is_none_or(|exclude| !exclude.contains(id))
+                })
+                .collect()
         }
     };
 }
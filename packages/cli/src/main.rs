@@ -1,35 +1,41 @@
-use std::{collections::HashSet, fs::File, io::BufWriter, ops::Not, path::Path, sync::Arc};
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::BufWriter,
+    ops::Not,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::Arc,
+};
 
-use clap::Parser;
-use futures::future::join_all;
+use clap::{Parser, Subcommand, ValueEnum, builder::PossibleValuesParser};
+use futures::{StreamExt as _, future::join_all};
+use ssufid::content::{DirectoryImageSink, SanitizePolicy, externalize_data_images, sanitize};
+use ssufid::core::rss::FeedOptions;
 use ssufid::core::{
-    CalendarCrawlRange, SsufidCalendarPlugin, SsufidCore, SsufidPlugin, SsufidPostPlugin,
+    CalendarCrawlRange, CostEstimate, CrawlOptions, CrawlReport, MessageTemplate, PluginRunResult,
+    RunManifest, SsufidCalendarPlugin, SsufidCore, SsufidPlugin, SsufidPost, SsufidPostPlugin,
+    SsufidSiteData, ValidationWarning, validate_attachments, validate_posts,
+};
+use ssufid::enrich::{HttpTranslator, TranslationCache, translate_posts};
+use ssufid::fetch::{DevCache, RequestLimiter, RequestLog};
+use ssufid::html::{
+    RedirectPattern, dedupe_attachment_links, dedupe_leading_title, redact_contacts,
+    store_tables_in_metadata, trim_empty_edges, unwrap_redirect_links, wrap_tables,
+};
+use ssufid::output::{
+    MERGED_FEED_DESCRIPTION, MERGED_FEED_TITLE, SigningKey, VerifyingKey, load_site_outputs,
+    merge_sites, verify, write_ndjson,
 };
-use ssufid_biz::BizPlugin;
-use ssufid_chemeng::ChemEngPlugin;
-use ssufid_common::sites::*;
-use ssufid_ee::EePlugin;
-use ssufid_infocom::InfocomPlugin;
-use ssufid_inso::InsoPlugin;
-use ssufid_materials::MaterialsPlugin;
-use ssufid_media::MediaPlugin;
-use ssufid_mediamba::MediambaPlugin;
-use ssufid_oasis::OasisPlugin;
-use ssufid_ssfilm::SsfilmPlugin;
-use ssufid_ssu_academic_calendar::SsuAcademicCalendarPlugin;
-use ssufid_ssucatch::SsuCatchPlugin;
-use ssufid_ssudorm::SsuDormPlugin;
-use ssufid_ssupath::{SsuPathCredential, SsuPathPlugin};
-use ssufid_startup::StartupPlugin;
-use ssufid_stu::StuPlugin;
-use ssufid_study::StudyPlugin;
 use time::{
     Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset,
     macros::{format_description, offset},
 };
 use tokio::io::AsyncWriteExt;
 use tracing::level_filters::LevelFilter;
-use tracing_subscriber::{Layer, filter, layer::SubscriberExt as _, util::SubscriberInitExt};
+use tracing_subscriber::{
+    Layer, filter, fmt::writer::BoxMakeWriter, layer::SubscriberExt as _, util::SubscriberInitExt,
+};
 
 use crate::macros::register_plugins;
 
@@ -42,6 +48,9 @@ mod macros;
     version
 )]
 struct SsufidDaemonOptions {
+    #[command(subcommand)]
+    command: Option<SsufidCommand>,
+
     /// The output directory for the fetched data.
     #[arg(short = 'o', long = "out", default_value = "./out")]
     out_dir: String,
@@ -62,6 +71,28 @@ struct SsufidDaemonOptions {
     #[arg(short = 'l', long = "limit", default_value_t = SsufidCore::POST_COUNT_LIMIT)]
     posts_limit: u32,
 
+    /// Skip posts created more than this many days ago.
+    #[arg(long = "max-age-days")]
+    max_age_days: Option<u64>,
+
+    /// Send a HEAD request to each plugin's base URL before crawling it, failing at once when
+    /// its host is unreachable.
+    #[arg(long = "preflight")]
+    preflight: bool,
+
+    /// The maximum number of items in each feed.
+    #[arg(long = "feed-max-items", default_value_t = FeedOptions::DEFAULT_MAX_ITEMS)]
+    feed_max_items: usize,
+
+    /// Leave items created more than this many days ago out of each feed.
+    #[arg(long = "feed-max-age-days")]
+    feed_max_age_days: Option<u64>,
+
+    /// Keep posts that were taken down from their board in `data.json` and each feed, marked
+    /// with `metadata.removed_at`, e.g. for an audit export.
+    #[arg(long = "include-removed")]
+    include_removed: bool,
+
     /// Calendar crawl start date in YYYY-MM-DD.
     #[arg(long = "calendar-start-date")]
     calendar_start_date: Option<String>,
@@ -77,136 +108,847 @@ struct SsufidDaemonOptions {
     #[arg(short = 'e', long, value_delimiter = ',')]
     /// The sites to exclude from the fetch.
     exclude: Vec<String>,
+
+    /// Move inline base64 images into `<out>/<site>/images` and link them under this base URL,
+    /// e.g. `https://example.com/ssufid` links `https://example.com/ssufid/<site>/images/<file>`.
+    #[arg(long = "externalize-images", value_name = "BASE_URL")]
+    externalize_images: Option<String>,
+
+    /// Add English translations of titles and descriptions to post metadata, using the endpoint
+    /// in `SSUFID_TRANSLATE_ENDPOINT` and the key in `SSUFID_TRANSLATE_API_KEY`.
+    #[arg(long)]
+    translate: bool,
+
+    /// Store the tables of each post as JSON rows in its `tables` metadata.
+    #[arg(long = "extract-tables")]
+    extract_tables: bool,
+
+    /// Strip blank edges (`<p>&nbsp;</p>`, `<br>`, ...) from the start and end of each post's
+    /// content.
+    #[arg(long = "trim-content")]
+    trim_content: bool,
+
+    /// Wrap each table in each post's content in a `<div class="table-scroll">`, so that a
+    /// frontend can let wide tables scroll on mobile.
+    #[arg(long = "wrap-tables")]
+    wrap_tables: bool,
+
+    /// Remove a heading at the start of each post's content that repeats the post's title.
+    #[arg(long = "dedupe-title")]
+    dedupe_title: bool,
+
+    /// Mask email addresses and phone numbers in each post's content, e.g. for a public mirror.
+    #[arg(long = "redact-contacts")]
+    redact_contacts: bool,
+
+    /// Unwrap links in each post's content that point to one of its attachments, keeping their
+    /// text, for boards that repeat their file list in the content.
+    #[arg(long = "dedupe-attachments")]
+    dedupe_attachments: bool,
+
+    /// Rewrite links in each post's content that go through a known redirect tracker, such as
+    /// `https://www.google.com/url?q=...`, to point at their destination.
+    #[arg(long = "unwrap-redirects")]
+    unwrap_redirects: bool,
+
+    /// Strip scripts, event handlers and markup outside an allowlist from each post's content,
+    /// keeping the tables, inline colors and sizes, and YouTube or Vimeo embeds notices use.
+    #[arg(long)]
+    sanitize: bool,
+
+    /// Include debugging context of failures, such as the HTML a selector failed on, in each
+    /// plugin's report.
+    #[arg(long = "verbose-errors")]
+    verbose_errors: bool,
+
+    /// Sign the checksum of each `data.json` with the Ed25519 key in `SSUFID_SIGNING_KEY`, for
+    /// `verify --public-key`.
+    #[arg(long)]
+    sign: bool,
+
+    /// Where crawled posts are written. `ndjson` prints every crawled post to stdout, one per
+    /// line as soon as each is fetched, instead of writing `data.json` and `rss.xml`; logs go
+    /// to stderr.
+    #[arg(long = "output", value_enum, default_value_t = OutputFormat::Files)]
+    output: OutputFormat,
+
+    /// How many more times to run the plugins that failed, once every plugin has run.
+    #[arg(long = "plugin-retries", default_value_t = 1)]
+    plugin_retries: u32,
+
+    /// The maximum number of HTTP requests in flight across all plugins.
+    #[arg(long = "max-in-flight-requests", default_value_t = RequestLimiter::DEFAULT_MAX_IN_FLIGHT)]
+    max_in_flight_requests: usize,
+
+    /// The maximum number of HTTP requests in flight to any one host across all plugins.
+    #[arg(long = "max-in-flight-per-host", default_value_t = RequestLimiter::DEFAULT_MAX_PER_HOST)]
+    max_in_flight_per_host: usize,
+
+    /// Print how many requests each selected plugin would issue, then exit without crawling.
+    #[arg(long = "dry-run")]
+    dry_run: bool,
+
+    /// Refuse to crawl when the selected plugins are estimated to issue more requests than this,
+    /// or with `--limit 0`, which has no estimate, unless `--yes` is given.
+    #[arg(long = "max-requests")]
+    max_requests: Option<u32>,
+
+    /// Crawl even when the estimate exceeds `--max-requests`.
+    #[arg(long)]
+    yes: bool,
+
+    /// Record the method, URL, status, duration and size of every request in
+    /// `<cache>/requests/<run id>.jsonl`, keeping the logs of the last 30 runs.
+    #[arg(long = "request-log")]
+    request_log: bool,
+
+    /// Serve pages fetched in earlier runs from this directory, fetching and storing only the
+    /// pages it lacks, to speed up re-running a plugin under development. Defaults to
+    /// `SSUFID_DEV_CACHE`, with a warning at startup; pages never expire, so do not use it for
+    /// a real crawl.
+    #[arg(long = "dev-cache", value_name = "DIR")]
+    dev_cache: Option<PathBuf>,
+
+    /// Log a message for each new post under the `notification` target, with placeholders for
+    /// the post's fields, e.g. `[{plugin}] {title} ({created_at:%m/%d}) {url}`. Nothing is
+    /// logged on a board's first crawl.
+    #[arg(long = "notify-template", value_name = "TEMPLATE")]
+    notify_template: Option<MessageTemplate>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum OutputFormat {
+    Files,
+    Ndjson,
+}
+
+/// Options shared by every plugin run.
+#[derive(Debug, Clone)]
+pub(crate) struct SaveOptions {
+    crawl: CrawlOptions,
+    feed: FeedOptions,
+    retry_count: u32,
+    image_base_url: Option<String>,
+    translator: Option<Arc<HttpTranslator>>,
+    extract_tables: bool,
+    trim_content: bool,
+    wrap_tables: bool,
+    dedupe_title: bool,
+    redact_contacts: bool,
+    dedupe_attachments: bool,
+    unwrap_redirects: bool,
+    sanitize: bool,
+    verbose_errors: bool,
+    signing_key: Option<SigningKey>,
+    output: OutputFormat,
+    notify_template: Option<MessageTemplate>,
+}
+
+impl From<&SsufidDaemonOptions> for SaveOptions {
+    fn from(options: &SsufidDaemonOptions) -> Self {
+        let mut crawl = CrawlOptions::new(options.posts_limit).preflight(options.preflight);
+        if let Some(days) = options.max_age_days {
+            crawl = crawl.max_age(days_to_duration(days));
+        }
+        let mut feed = FeedOptions::default()
+            .max_items(options.feed_max_items)
+            .include_removed(options.include_removed);
+        if let Some(days) = options.feed_max_age_days {
+            feed = feed.max_age(days_to_duration(days));
+        }
+        Self {
+            crawl,
+            feed,
+            retry_count: options.retry_count,
+            image_base_url: options.externalize_images.clone(),
+            translator: options
+                .translate
+                .then(HttpTranslator::from_env)
+                .flatten()
+                .map(Arc::new),
+            extract_tables: options.extract_tables,
+            trim_content: options.trim_content,
+            wrap_tables: options.wrap_tables,
+            dedupe_title: options.dedupe_title,
+            redact_contacts: options.redact_contacts,
+            dedupe_attachments: options.dedupe_attachments,
+            unwrap_redirects: options.unwrap_redirects,
+            sanitize: options.sanitize,
+            verbose_errors: options.verbose_errors,
+            signing_key: options.sign.then(SigningKey::from_env).and_then(Result::ok),
+            output: options.output,
+            notify_template: options.notify_template.clone(),
+        }
+    }
+}
+
+fn days_to_duration(days: u64) -> std::time::Duration {
+    std::time::Duration::from_secs(days * 24 * 60 * 60)
+}
+
+#[derive(Subcommand, Debug)]
+enum SsufidCommand {
+    /// Print the JSON Schema of an output type.
+    Schema {
+        #[arg(value_parser = PossibleValuesParser::new(ssufid::schema::SCHEMA_TYPES))]
+        name: String,
+    },
+    /// Print the identifiers of the plugins built into this binary, one per line.
+    List,
+    /// Combine the outputs of every plugin into one feed.
+    Merge {
+        /// The output directory of a previous run.
+        #[arg(long = "in-dir", default_value = "./out")]
+        in_dir: PathBuf,
+
+        /// The file to write the combined feed to.
+        #[arg(long)]
+        out: PathBuf,
+
+        #[arg(long, value_enum, default_value_t = MergeFormat::Rss)]
+        format: MergeFormat,
+
+        /// The maximum number of items in the combined feed.
+        #[arg(long = "max-items", default_value_t = 100)]
+        max_items: usize,
+
+        /// The link of the combined feed.
+        #[arg(long, default_value = "https://github.com/yourssu/ssufid")]
+        link: String,
+    },
+    /// Print posts per plugin, month and category, and frequent title keywords, of every post
+    /// cached for the selected plugins as JSON. Unlike the outputs, the cache keeps posts past
+    /// the post limit.
+    Stats,
+    /// Check the `data.json` of a previous run against its checksum, and its signature if a
+    /// public key is given. Exits with an error if the posts were changed since.
+    Verify {
+        /// The `data.json` to check.
+        path: PathBuf,
+
+        /// The Ed25519 public key of the signer, in standard base64.
+        #[arg(long = "public-key")]
+        public_key: Option<String>,
+    },
+    /// Check that the selected plugins' sites are reachable.
+    Check {
+        /// Crawl the first list page of each post plugin and check that it still parses,
+        /// instead of only requesting the base URL.
+        #[arg(long)]
+        deep: bool,
+    },
+    /// Crawl a few posts of a plugin and check them for signs of selector drift, such as empty
+    /// titles, links to another host or attachments that cannot be downloaded. Exits with an
+    /// error if any are found.
+    Validate {
+        /// The identifier of the plugin, e.g. `ssudorm.ssu.ac.kr`.
+        identifier: String,
+
+        /// The number of posts to crawl.
+        #[arg(long, default_value_t = 5)]
+        count: u32,
+    },
+    /// Maintain the recorded test fixtures of a plugin.
+    Fixtures {
+        #[command(subcommand)]
+        command: FixturesCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum FixturesCommand {
+    /// Download fresh copies of a plugin's fixture pages and summarize how they changed.
+    Refresh {
+        /// The identifier of the plugin, e.g. `ssudorm.ssu.ac.kr`.
+        identifier: String,
+
+        /// The number of post pages to record besides the list page.
+        #[arg(long, default_value_t = 3)]
+        count: u32,
+
+        /// The directory holding each plugin's fixtures in a subdirectory named after its
+        /// identifier. Defaults to the conformance suite's fixtures, relative to the workspace
+        /// root.
+        #[arg(
+            long = "fixtures-dir",
+            default_value = "./packages/conformance/fixtures"
+        )]
+        fixtures_dir: PathBuf,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum MergeFormat {
+    Rss,
+    Json,
 }
 
 #[tokio::main]
-async fn main() -> eyre::Result<()> {
-    setup_tracing()?;
+async fn main() -> ExitCode {
+    match run().await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(report) => {
+            eprintln!("Error: {report:?}");
+            ExitCode::from(exit_code(&report))
+        }
+    }
+}
 
-    color_eyre::install()?;
+/// The exit code for `report`: see [`ssufid::Error::exit_code`], the code shared by the
+/// failures of a [`RunFailed`], and `1` for errors outside the library's.
+fn exit_code(report: &eyre::Report) -> u8 {
+    if let Some(error) = report.downcast_ref::<RunFailed>() {
+        error.exit_code
+    } else if let Some(error) = report.downcast_ref::<ssufid::Error>() {
+        error.exit_code()
+    } else if let Some(error) = report.downcast_ref::<ssufid::PluginError>() {
+        error.exit_code()
+    } else {
+        1
+    }
+}
+
+async fn run() -> eyre::Result<()> {
     let options = SsufidDaemonOptions::parse();
+    if let Some(dir) = &options.dev_cache {
+        DevCache::init_global(DevCache::new(dir));
+    }
+    if let Some(command) = &options.command {
+        return run_command(&options, command).await;
+    }
+
+    setup_tracing(options.output)?;
+    DevCache::global();
+
+    color_eyre::install()?;
 
     if !options.include.is_empty() && !options.exclude.is_empty() {
-        eyre::bail!("You cannot use both --include and --exclude options at the same time.");
+        eyre::bail!(ssufid::Error::Config(
+            "You cannot use both --include and --exclude options at the same time.".to_string()
+        ));
     }
     validate_calendar_range_flags(&options)?;
+    if options.translate && std::env::var(HttpTranslator::API_KEY_ENV).is_err() {
+        eyre::bail!(ssufid::Error::Config(format!(
+            "--translate requires the {} environment variable.",
+            HttpTranslator::API_KEY_ENV
+        )));
+    }
+    if options.sign {
+        SigningKey::from_env()?;
+    }
+
+    RequestLimiter::init_global_with(
+        RequestLimiter::new(options.max_in_flight_requests)
+            .max_per_host(options.max_in_flight_per_host),
+    );
+
+    let core = Arc::new(SsufidCore::new(&options.cache_dir));
 
-    let calendar_range = calendar_crawl_range_from_options(&options)?;
+    let calendar_range = calendar_crawl_range_from_options(&options, core.now())?;
     let out_dir = Path::new(&options.out_dir).to_owned();
     let calendar_out_dir = Path::new(&options.calendar_out_dir).to_owned();
 
-    let core = Arc::new(SsufidCore::new(&options.cache_dir));
+    let plugins = construct_plugins(&options);
+    let save_options = SaveOptions::from(&options);
 
-    let tasks = construct_tasks(
-        core.clone(),
-        &out_dir,
-        &calendar_out_dir,
-        options,
-        calendar_range,
-    );
-    let tasks_len = tasks.len();
+    let estimate = plugins
+        .iter()
+        .filter_map(|plugin| plugin.estimate_cost(&save_options.crawl))
+        .sum::<CostEstimate>();
+    if options.dry_run {
+        for plugin in &plugins {
+            if let Some(estimate) = plugin.estimate_cost(&save_options.crawl) {
+                println!("{}\t{estimate}", plugin.identifier());
+            }
+        }
+        println!("total\t{estimate}");
+        return Ok(());
+    }
+    check_request_budget(
+        &estimate,
+        options.posts_limit,
+        options.max_requests,
+        options.yes,
+    )?;
 
-    // Run all tasks and collect errors
-    let errors: Vec<eyre::Report> = join_all(tasks)
-        .await
-        .into_iter()
-        .filter_map(|r| r.err())
+    let started_at = core.now();
+    if let Some(log) = open_request_log(&options, started_at)? {
+        RequestLog::init_global(log);
+    }
+    let outcomes = run_with_plugin_retries(
+        &plugins,
+        options.plugin_retries,
+        PLUGIN_RETRY_DELAY,
+        |plugin| {
+            plugin.save_run(
+                core.clone(),
+                &out_dir,
+                &calendar_out_dir,
+                &save_options,
+                &calendar_range,
+            )
+        },
+    )
+    .await;
+
+    let results = outcomes
+        .iter()
+        .map(|(plugin, outcome)| match outcome {
+            Ok(items) => PluginRunResult::success(plugin.identifier(), *items),
+            Err(err) => {
+                tracing::error!(plugin = plugin.identifier(), "{err:?}");
+                PluginRunResult::failure(plugin.identifier(), err.to_string())
+            }
+        })
         .collect();
+    let manifest = RunManifest::new(started_at, core.now(), results);
+    manifest.save(Path::new(&options.cache_dir)).await?;
 
     core.save_cache().await?;
+    save_metrics().await?;
 
-    if errors.is_empty() {
+    exit_status(
+        &manifest,
+        outcomes
+            .iter()
+            .filter_map(|(_, outcome)| outcome.as_ref().err()),
+    )
+}
+
+/// Opens this run's request log when `--request-log` is given, pruning the logs of older runs.
+fn open_request_log(
+    options: &SsufidDaemonOptions,
+    started_at: OffsetDateTime,
+) -> eyre::Result<Option<RequestLog>> {
+    if !options.request_log {
+        return Ok(None);
+    }
+    let cache_dir = Path::new(&options.cache_dir);
+    let log = RequestLog::create(cache_dir, &RunManifest::run_id_of(started_at))?;
+    RequestLog::prune(cache_dir, RequestLog::DEFAULT_RETENTION)?;
+    Ok(Some(log))
+}
+
+/// Fails when `estimate` exceeds `max_requests`, unless the operator confirmed with `--yes`.
+/// A `posts_limit` of 0 lets some plugins crawl their whole board, which no estimate bounds, so
+/// it always needs the confirmation under a budget.
+fn check_request_budget(
+    estimate: &CostEstimate,
+    posts_limit: u32,
+    max_requests: Option<u32>,
+    yes: bool,
+) -> eyre::Result<()> {
+    match max_requests {
+        Some(max_requests) if posts_limit == 0 && !yes => Err(ssufid::Error::Config(format!(
+            "--limit 0 crawls without a limit, which cannot be checked against --max-requests \
+             {max_requests}. Pass --yes to crawl anyway."
+        ))
+        .into()),
+        Some(max_requests) if estimate.total_requests() > max_requests && !yes => {
+            Err(ssufid::Error::Config(format!(
+                "The crawl is estimated at {estimate}, more than --max-requests {max_requests}. \
+                 Pass --yes to crawl anyway."
+            ))
+            .into())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Some plugins of a run failed.
+#[derive(Debug)]
+struct RunFailed {
+    failed: usize,
+    total: usize,
+    /// The exit code every failure shares, e.g. [`ssufid::Error::EXIT_UNAVAILABLE`] when all
+    /// the failed sites were down, or `1` for mixed failures.
+    exit_code: u8,
+}
+
+impl std::fmt::Display for RunFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} of {} Run failed", self.failed, self.total)
+    }
+}
+
+impl std::error::Error for RunFailed {}
+
+/// Fails when any plugin in `manifest` failed, with `errors` the errors of the failed plugins.
+fn exit_status<'a>(
+    manifest: &RunManifest,
+    errors: impl IntoIterator<Item = &'a eyre::Report>,
+) -> eyre::Result<()> {
+    let codes = errors.into_iter().map(exit_code).collect::<Vec<_>>();
+    match manifest.failures().count() {
+        0 => Ok(()),
+        failed => Err(RunFailed {
+            failed,
+            total: manifest.plugin_results.len(),
+            exit_code: match codes.split_first() {
+                Some((first, rest)) if rest.iter().all(|code| code == first) => *first,
+                _ => 1,
+            },
+        }
+        .into()),
+    }
+}
+
+/// Checks every selected plugin concurrently, printing one line per plugin.
+async fn check_plugins(options: &SsufidDaemonOptions, deep: bool) -> eyre::Result<()> {
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .init();
+    if !options.include.is_empty() && !options.exclude.is_empty() {
+        eyre::bail!(ssufid::Error::Config(
+            "You cannot use both --include and --exclude options at the same time.".to_string()
+        ));
+    }
+    let client = ssufid::fetch::default_client();
+    let plugins = construct_plugins(options);
+    let results = join_all(plugins.iter().map(|plugin| plugin.check(&client, deep))).await;
+
+    let mut unhealthy = 0;
+    for (plugin, result) in plugins.iter().zip(results) {
+        match result {
+            Ok(status) => {
+                unhealthy += usize::from(!status.ok);
+                println!(
+                    "{}\t{}\t{} posts\t{}ms",
+                    plugin.identifier(),
+                    if status.ok { "ok" } else { "FAIL" },
+                    status.posts_seen,
+                    status.latency.as_millis()
+                );
+                for name in &status.broken_selectors {
+                    println!("{}\tFAIL\t{name} selector matched 0", plugin.identifier());
+                }
+            }
+            Err(e) => {
+                unhealthy += 1;
+                println!("{}\tFAIL\t{e}", plugin.identifier());
+            }
+        }
+    }
+
+    if unhealthy == 0 {
         Ok(())
     } else {
-        for err in &errors {
-            tracing::error!("{err:?}");
-        }
-        Err(eyre::eyre!("{} of {} Run failed", errors.len(), tasks_len))
-    }
-}
-
-register_plugins! {
-    post: {
-    Accounting(AccountingPlugin) => AccountingPlugin::new(),
-    Actx(ActxPlugin) => ActxPlugin::new(),
-    Biz(BizPlugin) => BizPlugin::new(),
-    Bioinfo(BioinfoPlugin) => BioinfoPlugin::new(),
-    Chem(ChemPlugin) => ChemPlugin::new(),
-    ChemEng(ChemEngPlugin) => ChemEngPlugin::new(),
-    Chilan(ChilanPlugin) => ChilanPlugin::new(),
-    CseBachelor(CseBachelorPlugin) => CseBachelorPlugin::new(),
-    CseGraduate(CseGraduatePlugin) => CseGraduatePlugin::new(),
-    CseEmployment(CseEmploymentPlugin) => CseEmploymentPlugin::new(),
-    Docs(DocsPlugin) => DocsPlugin::new(),
-    Ee(EePlugin) => EePlugin::default(),
-    Eco(EcoPlugin) => EcoPlugin::new(),
-    Englan(EnglanPlugin) => EnglanPlugin::new(),
-    Ensb(EnsbPlugin) => EnsbPlugin::new(),
-    Finance(FinancePlugin) => FinancePlugin::new(),
-    France(FrancePlugin) => FrancePlugin::new(),
-    Gerlan(GerlanPlugin) => GerlanPlugin::new(),
-    Gtrade(GtradePlugin) => GtradePlugin::new(),
-    History(HistoryPlugin) => HistoryPlugin::new(),
-    Iise(IisePlugin) => IisePlugin::new(),
-    Inso(InsoPlugin) => InsoPlugin::new(),
-    Itrans(ItransPlugin) => ItransPlugin::new(),
-    Infocom(InfocomPlugin) => InfocomPlugin::new(),
-    Japanstu(JapanstuPlugin) => JapanstuPlugin::new(),
-    Korlan(KorlanPlugin) => KorlanPlugin::new(),
-    Law(LawPlugin) => LawPlugin::new(),
-    Lawyer(LawyerPlugin) => LawyerPlugin::new(),
-    LifelongEdu(LifelongEduPlugin) => LifelongEduPlugin::new(),
-    Materials(MaterialsPlugin) => MaterialsPlugin::new(),
-    Masscom(MasscomPlugin) => MasscomPlugin::new(),
-    Math(MathPlugin) => MathPlugin::new(),
-    Media(MediaPlugin) => MediaPlugin,
-    Mediamba(MediambaPlugin) => MediambaPlugin,
-    Mysoongsil(MysoongsilPlugin) => MysoongsilPlugin::new(),
-    Oasis(OasisPlugin) => OasisPlugin,
-    Philo(PhiloPlugin) => PhiloPlugin::new(),
-    Physics(PhysicsPlugin) => PhysicsPlugin::new(),
-    Politics(PoliticsPlugin) => PoliticsPlugin::new(),
-    Pubad(PubadPlugin) => PubadPlugin::new(),
-    Sec(SecPlugin) => SecPlugin::new(),
-    Sls(SlsPlugin) => SlsPlugin::new(),
-    Soar(SoarPlugin) => SoarPlugin::new(),
-    Ssfilm(SsfilmPlugin) => SsfilmPlugin,
-    SsuCatch(SsuCatchPlugin) => SsuCatchPlugin::new(),
-    SsuDorm(SsuDormPlugin) => SsuDormPlugin::new(),
-    SsuPath(SsuPathPlugin) => SsuPathPlugin::new(SsuPathCredential::Password(
-        std::env::var("SSU_ID").unwrap_or_default(),
-        std::env::var("SSU_PASSWORD").unwrap_or_default()
-    )),
-    Startup(StartupPlugin) => StartupPlugin,
-    Stu(StuPlugin) => StuPlugin,
-    Study(StudyPlugin) => StudyPlugin,
-    Sports(SportsPlugin) => SportsPlugin::new(),
-    SwBachelor(SwBachelorPlugin) => SwBachelorPlugin::new(),
-    SwGraduate(SwGraduatePlugin) => SwGraduatePlugin::new(),
-    },
-    calendar: {
-    SsuAcademicCalendar(SsuAcademicCalendarPlugin) => SsuAcademicCalendarPlugin,
+        Err(eyre::eyre!(
+            "{unhealthy} of {} plugins are unhealthy",
+            plugins.len()
+        ))
+    }
+}
+
+async fn validate_plugin(
+    options: &SsufidDaemonOptions,
+    identifier: &str,
+    count: u32,
+) -> eyre::Result<()> {
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .init();
+    let plugin = construct_plugins(options)
+        .into_iter()
+        .find(|plugin| plugin.identifier() == identifier)
+        .ok_or_else(|| ssufid::Error::Config(format!("Unknown plugin: {identifier}")))?;
+    let warnings = plugin
+        .validate(&SsufidCore::new(&options.cache_dir), count)
+        .await?;
+    report_validation(identifier, &warnings)
+}
+
+/// Crawls `count` posts of `plugin` and checks them with [`validate_posts`] and
+/// [`validate_attachments`].
+pub(crate) async fn validate_sample<T: SsufidPostPlugin + Sync>(
+    core: &SsufidCore,
+    plugin: &T,
+    count: u32,
+) -> eyre::Result<Vec<ValidationWarning>> {
+    let now = core.now();
+    let posts = plugin
+        .crawl_with_options(&CrawlOptions::new(count).now(now))
+        .await?;
+    let mut warnings = validate_posts::<T>(&posts, now);
+    warnings.extend(validate_attachments::<T>(&ssufid::fetch::default_client(), &posts).await);
+    Ok(warnings)
+}
+
+/// Prints `warnings` and fails if there are any, so that a cron job alerts on them.
+fn report_validation(identifier: &str, warnings: &[ValidationWarning]) -> eyre::Result<()> {
+    for warning in warnings {
+        println!("{identifier}\t{warning}");
+    }
+    if warnings.is_empty() {
+        Ok(())
+    } else {
+        Err(eyre::eyre!(
+            "{} validation warnings for {identifier}",
+            warnings.len()
+        ))
+    }
+}
+
+async fn print_stats(options: &SsufidDaemonOptions) -> eyre::Result<()> {
+    let core = SsufidCore::new(&options.cache_dir);
+    let mut sites = Vec::new();
+    for plugin in construct_plugins(options) {
+        let posts = core.cached_posts(plugin.identifier()).await?;
+        sites.push((plugin.identifier(), posts));
+    }
+    let stats = ssufid::stats::aggregate(
+        sites
+            .iter()
+            .flat_map(|(plugin, posts)| posts.iter().map(move |post| (*plugin, post))),
+    );
+    println!("{}", serde_json::to_string_pretty(&stats)?);
+    Ok(())
+}
+
+async fn refresh_fixtures(
+    options: &SsufidDaemonOptions,
+    command: &FixturesCommand,
+) -> eyre::Result<()> {
+    let FixturesCommand::Refresh {
+        identifier,
+        count,
+        fixtures_dir,
+    } = command;
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .init();
+    let plugin = construct_plugins(options)
+        .into_iter()
+        .find(|plugin| plugin.identifier() == identifier)
+        .ok_or_else(|| ssufid::Error::Config(format!("Unknown plugin: {identifier}")))?;
+    let client = ssufid::fetch::default_client();
+    let diffs = plugin
+        .refresh_fixtures(&client, *count, &fixtures_dir.join(identifier))
+        .await?;
+    for diff in diffs {
+        println!("{diff}");
+    }
+    Ok(())
+}
+
+/// How long to wait before rerunning failed plugins, so that a briefly unreachable host has a
+/// chance to recover.
+const PLUGIN_RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Runs every plugin concurrently, then reruns the ones that failed after `delay`, up to
+/// `retries` more times. Returns the final outcome of every plugin, in the order of `plugins`:
+/// its first success, or its last error if it failed every time.
+async fn run_with_plugin_retries<'a, P, T, F, Fut>(
+    plugins: &'a [P],
+    retries: u32,
+    delay: std::time::Duration,
+    run: F,
+) -> Vec<(&'a P, eyre::Result<T>)>
+where
+    F: Fn(&'a P) -> Fut,
+    Fut: std::future::Future<Output = eyre::Result<T>>,
+{
+    let mut outcomes = Vec::with_capacity(plugins.len());
+    let mut pending = plugins.iter().enumerate().collect::<Vec<_>>();
+    let mut failures = Vec::new();
+    for pass in 0..=retries {
+        if pass > 0 {
+            if pending.is_empty() {
+                break;
+            }
+            tracing::warn!(
+                "Retrying {} failed plugins in {}s",
+                pending.len(),
+                delay.as_secs()
+            );
+            tokio::time::sleep(delay).await;
+        }
+        let results = join_all(pending.iter().map(|(_, plugin)| run(plugin))).await;
+        failures = Vec::new();
+        for ((index, plugin), result) in pending.into_iter().zip(results) {
+            match result {
+                Ok(value) => outcomes.push((index, plugin, Ok(value))),
+                Err(err) => failures.push((index, plugin, err)),
+            }
+        }
+        pending = failures
+            .iter()
+            .map(|(index, plugin, _)| (*index, *plugin))
+            .collect();
+    }
+    outcomes.extend(
+        failures
+            .into_iter()
+            .map(|(index, plugin, err)| (index, plugin, Err(err))),
+    );
+    outcomes.sort_by_key(|(index, _, _)| *index);
+    outcomes
+        .into_iter()
+        .map(|(_, plugin, outcome)| (plugin, outcome))
+        .collect()
+}
+
+ssufid_registry::registered_plugins!(register_plugins);
+
+async fn run_command(options: &SsufidDaemonOptions, command: &SsufidCommand) -> eyre::Result<()> {
+    match command {
+        SsufidCommand::Schema { name } => {
+            let schema = ssufid::schema::schema_of(name)
+                .ok_or_else(|| ssufid::Error::Config(format!("Unknown schema type: {name}")))?;
+            println!("{}", serde_json::to_string_pretty(&schema)?);
+        }
+        SsufidCommand::List => {
+            for identifier in registered_identifiers() {
+                println!("{identifier}");
+            }
+        }
+        SsufidCommand::Merge {
+            in_dir,
+            out,
+            format,
+            max_items,
+            link,
+        } => {
+            tracing_subscriber::fmt()
+                .with_writer(std::io::stderr)
+                .init();
+            let sites = load_site_outputs(in_dir)?;
+            let site = merge_sites(sites, MERGED_FEED_TITLE, link, MERGED_FEED_DESCRIPTION);
+            let feed = FeedOptions::default().max_items(*max_items);
+            let contents = match format {
+                MergeFormat::Rss => {
+                    let buf = site.to_rss_with(&feed).pretty_write_to(
+                        BufWriter::new(Vec::new()),
+                        b' ',
+                        2,
+                    )?;
+                    String::from_utf8(buf.into_inner()?)?
+                }
+                MergeFormat::Json => {
+                    let now = SsufidCore::new(&options.cache_dir).now();
+                    let items = feed.apply(site.items().to_vec(), now);
+                    let mut site = SsufidSiteData::new(
+                        MERGED_FEED_TITLE,
+                        link,
+                        MERGED_FEED_DESCRIPTION,
+                        items,
+                    );
+                    let signing_key = options.sign.then(SigningKey::from_env).transpose()?;
+                    site.seal(signing_key.as_ref(), now)?;
+                    serde_json::to_string_pretty(&site)?
+                }
+            };
+            std::fs::write(out, contents)?;
+        }
+        SsufidCommand::Stats => print_stats(options).await?,
+        SsufidCommand::Verify { path, public_key } => {
+            let public_key = public_key
+                .as_deref()
+                .map(VerifyingKey::from_base64)
+                .transpose()?;
+            let result = verify(path, public_key.as_ref())?;
+            if !result.is_valid() {
+                eyre::bail!("{}: {result}", path.display());
+            }
+            println!("{}: {result}", path.display());
+        }
+        SsufidCommand::Check { deep } => check_plugins(options, *deep).await?,
+        SsufidCommand::Validate { identifier, count } => {
+            validate_plugin(options, identifier, *count).await?
+        }
+        SsufidCommand::Fixtures { command } => refresh_fixtures(options, command).await?,
     }
+    Ok(())
 }
 
-pub(crate) async fn save_run<T: SsufidPostPlugin>(
+pub(crate) async fn save_run<T: SsufidPostPlugin + Sync>(
     core: Arc<SsufidCore>,
     base_out_dir: &Path,
-    plugin: T,
-    posts_limit: u32,
-    retry_count: u32,
-) -> eyre::Result<()> {
-    let site = core
-        .run_with_retry(&plugin, posts_limit, retry_count)
+    plugin: &T,
+    options: &SaveOptions,
+) -> eyre::Result<usize> {
+    // The daemon crawls several times in one process, so report only this run's increase
+    let mojibake = || {
+        ssufid::metrics::counter(
+            ssufid::metrics::MOJIBAKE_TOTAL,
+            &[("plugin", T::IDENTIFIER)],
+        )
+    };
+    let mojibake_before = mojibake();
+    let started_at = core.now();
+    let result = save_posts(core.clone(), base_out_dir, plugin, options).await;
+    let finished_at = core.now();
+    let report = match &result {
+        Ok(items) => CrawlReport::success::<T>(started_at, finished_at, *items)
+            .with_mojibake(mojibake().saturating_sub(mojibake_before) as usize),
+        Err(e) => CrawlReport::failure::<T>(started_at, finished_at, e.to_string())
+            .with_error_detail(options.verbose_errors.then(|| error_detail(e)).flatten()),
+    };
+    save_report(&base_out_dir.join(T::IDENTIFIER), &report).await?;
+    result
+}
+
+/// The [`PluginError::detail`](ssufid::PluginError::detail) behind `error`, if any.
+fn error_detail(error: &eyre::Report) -> Option<String> {
+    error
+        .downcast_ref::<ssufid::Error>()
+        .and_then(ssufid::Error::plugin_error)
+        .and_then(ssufid::PluginError::detail)
+        .map(str::to_string)
+}
+
+/// Crawls `plugin` and writes its outputs, returning the number of posts written.
+async fn save_posts<T: SsufidPostPlugin + Sync>(
+    core: Arc<SsufidCore>,
+    base_out_dir: &Path,
+    plugin: &T,
+    options: &SaveOptions,
+) -> eyre::Result<usize> {
+    if options.output == OutputFormat::Ndjson {
+        return stream_posts(core, base_out_dir, plugin, options, std::io::stdout()).await;
+    }
+    let known_ids = match options.notify_template {
+        Some(_) => Some(core.cached_post_ids(T::IDENTIFIER).await?),
+        None => None,
+    };
+    let mut site = core
+        .run_with_retry(plugin, &options.crawl, options.retry_count)
         .await?;
+    if let (Some(template), Some(known_ids)) = (&options.notify_template, &known_ids) {
+        for message in
+            notification_messages(template, T::IDENTIFIER, known_ids, site.items(), core.now())
+        {
+            tracing::info!(target: "notification", plugin = T::IDENTIFIER, "{message}");
+        }
+    }
+    if !options.feed.include_removed {
+        site.drop_removed();
+    }
+    let items = site.items().len();
+    let out_dir = base_out_dir.join(T::IDENTIFIER);
+
+    let newest = site.items().iter().map(|post| post.created_at).max();
+    if T::CADENCE.is_unusually_quiet(newest, core.now()) {
+        tracing::warn!(
+            plugin = T::IDENTIFIER,
+            newest = ?newest,
+            "No new post within {:?} on a {:?} board; its list page may have changed",
+            T::CADENCE.quiet_threshold(),
+            T::CADENCE,
+        );
+    }
+
+    process_posts::<T>(&core, &out_dir, site.items_mut(), options).await?;
+
+    site.seal(options.signing_key.as_ref(), core.now())?;
     let json = serde_json::to_string_pretty(&site)?;
 
-    let buf = site
-        .to_rss()
-        .pretty_write_to(BufWriter::new(Vec::new()), b' ', 2)?;
+    let buf =
+        site.to_rss_with(&options.feed)
+            .pretty_write_to(BufWriter::new(Vec::new()), b' ', 2)?;
     let rss = String::from_utf8(buf.into_inner()?)?;
 
-    let out_dir = base_out_dir.join(T::IDENTIFIER);
     tokio::fs::create_dir_all(&out_dir).await?;
 
     let mut json_file = tokio::fs::File::create(out_dir.join("data.json")).await?;
@@ -214,6 +956,170 @@ pub(crate) async fn save_run<T: SsufidPostPlugin>(
 
     let mut rss_file = tokio::fs::File::create(out_dir.join("rss.xml")).await?;
     rss_file.write_all(rss.as_bytes()).await?;
+    Ok(items)
+}
+
+/// Writes every post of `plugin` to `out` as NDJSON, each as soon as [`SsufidCore::stream`]
+/// yields it. A crawl that fails part-way is retried up to `retry_count` times in all, skipping
+/// the posts already written. With a notification template, new posts are also logged and
+/// added to the cache as in [`save_posts`]. Returns the number of posts written.
+async fn stream_posts<T: SsufidPostPlugin + Sync>(
+    core: Arc<SsufidCore>,
+    base_out_dir: &Path,
+    plugin: &T,
+    options: &SaveOptions,
+    mut out: impl std::io::Write,
+) -> eyre::Result<usize> {
+    let known_ids = match options.notify_template {
+        Some(_) => Some(core.cached_post_ids(T::IDENTIFIER).await?),
+        None => None,
+    };
+    let out_dir = base_out_dir.join(T::IDENTIFIER);
+    let mut seen = HashSet::new();
+    let mut written = Vec::new();
+    let mut last_error = None;
+    let mut finished = false;
+    for _ in 0..options.retry_count {
+        let mut stream = std::pin::pin!(core.stream(plugin, &options.crawl));
+        last_error = loop {
+            let mut post = match stream.next().await {
+                Some(Ok(post)) => post,
+                Some(Err(error)) => break Some(error),
+                None => break None,
+            };
+            if !seen.insert(post.id.clone()) {
+                continue;
+            }
+            process_posts::<T>(&core, &out_dir, std::slice::from_mut(&mut post), options).await?;
+            write_ndjson(&mut out, [&post])?;
+            if known_ids.is_some() {
+                written.push(post);
+            }
+        };
+        if last_error.is_none() {
+            finished = true;
+            break;
+        }
+    }
+    if !finished {
+        return Err(ssufid::Error::AttemptsExceeded {
+            plugin: T::IDENTIFIER,
+            attempts: options.retry_count,
+            source: last_error.map(Box::new),
+        }
+        .into());
+    }
+    if let (Some(template), Some(known_ids)) = (&options.notify_template, &known_ids) {
+        for message in
+            notification_messages(template, T::IDENTIFIER, known_ids, &written, core.now())
+        {
+            tracing::info!(target: "notification", plugin = T::IDENTIFIER, "{message}");
+        }
+        core.cache_new_posts::<T>(written).await?;
+    }
+    Ok(seen.len())
+}
+
+/// Applies the content options of `options` to `posts` of `T`, whose outputs go to `out_dir`.
+async fn process_posts<T: SsufidPostPlugin + Sync>(
+    core: &SsufidCore,
+    out_dir: &Path,
+    posts: &mut [SsufidPost],
+    options: &SaveOptions,
+) -> eyre::Result<()> {
+    if options.sanitize {
+        for post in posts.iter_mut() {
+            post.content = sanitize(&post.content, &SanitizePolicy::DEFAULT)?;
+        }
+    }
+
+    if let Some(base_url) = &options.image_base_url {
+        let mut sink = DirectoryImageSink::new(
+            out_dir.join("images"),
+            format!(
+                "{}/{}/images",
+                base_url.trim_end_matches('/'),
+                T::IDENTIFIER
+            ),
+        );
+        for post in posts.iter_mut() {
+            post.content = externalize_data_images(&post.content, &mut sink)?;
+        }
+    }
+
+    if options.dedupe_attachments {
+        for post in posts.iter_mut() {
+            post.content = dedupe_attachment_links(&post.content, &post.url, &post.attachments)?;
+        }
+    }
+
+    if options.unwrap_redirects {
+        for post in posts.iter_mut() {
+            post.content = unwrap_redirect_links(&post.content, RedirectPattern::DEFAULTS)?;
+        }
+    }
+
+    if options.dedupe_title {
+        for post in posts.iter_mut() {
+            post.content = dedupe_leading_title(&post.content, &post.title);
+        }
+    }
+
+    if options.trim_content {
+        for post in posts.iter_mut() {
+            post.content = trim_empty_edges(&post.content);
+        }
+    }
+
+    if options.redact_contacts {
+        for post in posts.iter_mut() {
+            post.content = redact_contacts(&post.content)?;
+        }
+    }
+
+    if options.wrap_tables {
+        for post in posts.iter_mut() {
+            post.content = wrap_tables(&post.content)?;
+        }
+    }
+
+    if options.extract_tables {
+        posts.iter_mut().for_each(store_tables_in_metadata);
+    }
+
+    if let Some(translator) = &options.translator {
+        let state_dir = Path::new(core.cache_dir());
+        let mut cache = TranslationCache::load(state_dir, T::IDENTIFIER).await?;
+        translate_posts(translator.as_ref(), posts, &mut cache).await;
+        cache.save(state_dir, T::IDENTIFIER).await?;
+    }
+    Ok(())
+}
+
+/// Renders `template` for each post not among `known_ids`, oldest first. A board without known
+/// posts is being crawled for the first time, so none of its posts count as new.
+fn notification_messages(
+    template: &MessageTemplate,
+    plugin: &str,
+    known_ids: &std::collections::HashSet<String>,
+    posts: &[SsufidPost],
+    now: OffsetDateTime,
+) -> Vec<String> {
+    if known_ids.is_empty() {
+        return vec![];
+    }
+    posts
+        .iter()
+        .rev()
+        .filter(|post| !post.is_removed() && !known_ids.contains(&post.id))
+        .map(|post| template.render(plugin, post, now))
+        .collect()
+}
+
+async fn save_report(out_dir: &Path, report: &CrawlReport) -> eyre::Result<()> {
+    tokio::fs::create_dir_all(out_dir).await?;
+    let json = serde_json::to_string_pretty(report)?;
+    tokio::fs::write(out_dir.join("report.json"), json).await?;
     Ok(())
 }
 
@@ -221,13 +1127,14 @@ pub(crate) async fn save_run<T: SsufidPostPlugin>(
 pub(crate) async fn save_calendar_run<T: SsufidCalendarPlugin>(
     core: Arc<SsufidCore>,
     base_out_dir: &Path,
-    plugin: T,
+    plugin: &T,
     calendar_range: CalendarCrawlRange,
-    retry_count: u32,
+    options: &SaveOptions,
 ) -> eyre::Result<()> {
-    let site = core
-        .run_calendar_with_retry(&plugin, &calendar_range, retry_count)
+    let mut site = core
+        .run_calendar_with_retry(plugin, &calendar_range, options.retry_count)
         .await?;
+    site.seal(options.signing_key.as_ref(), core.now())?;
     let json = serde_json::to_string_pretty(&site)?;
     let ics = site.to_ics();
 
@@ -242,17 +1149,27 @@ pub(crate) async fn save_calendar_run<T: SsufidCalendarPlugin>(
     Ok(())
 }
 
+async fn save_metrics() -> eyre::Result<()> {
+    let json = serde_json::to_string_pretty(&ssufid::metrics::snapshot())?;
+    tokio::fs::write("reports/metrics.json", json).await?;
+    Ok(())
+}
+
 fn validate_calendar_range_flags(options: &SsufidDaemonOptions) -> eyre::Result<()> {
     match (&options.calendar_start_date, &options.calendar_end_date) {
         (Some(_), Some(_)) | (None, None) => Ok(()),
         _ => {
-            eyre::bail!("--calendar-start-date and --calendar-end-date must be provided together.")
+            eyre::bail!(ssufid::Error::Config(
+                "--calendar-start-date and --calendar-end-date must be provided together."
+                    .to_string()
+            ))
         }
     }
 }
 
 fn calendar_crawl_range_from_options(
     options: &SsufidDaemonOptions,
+    now: OffsetDateTime,
 ) -> eyre::Result<CalendarCrawlRange> {
     match (&options.calendar_start_date, &options.calendar_end_date) {
         (Some(start), Some(end)) => CalendarCrawlRange::new(
@@ -260,13 +1177,13 @@ fn calendar_crawl_range_from_options(
             parse_calendar_end_date(end)?,
         )
         .map_err(eyre::Error::msg),
-        (None, None) => default_calendar_crawl_range(),
+        (None, None) => default_calendar_crawl_range(now),
         _ => unreachable!("calendar range flags should have been validated"),
     }
 }
 
-fn default_calendar_crawl_range() -> eyre::Result<CalendarCrawlRange> {
-    let now = OffsetDateTime::now_utc().to_offset(kst_offset());
+fn default_calendar_crawl_range(now: OffsetDateTime) -> eyre::Result<CalendarCrawlRange> {
+    let now = now.to_offset(kst_offset());
     let year = now.year();
     let start_date = Date::from_calendar_date(year, Month::January, 1)?;
     let end_date = Date::from_calendar_date(year + 1, Month::December, 31)?;
@@ -286,7 +1203,8 @@ fn parse_calendar_end_date(date: &str) -> eyre::Result<OffsetDateTime> {
 
 fn parse_cli_date(date: &str) -> eyre::Result<Date> {
     let format = format_description!("[year]-[month]-[day]");
-    Date::parse(date, &format).map_err(|e| eyre::eyre!("Invalid date '{date}': {e}"))
+    Date::parse(date, &format)
+        .map_err(|e| ssufid::Error::Config(format!("Invalid date '{date}': {e}")).into())
 }
 
 fn kst_offset() -> UtcOffset {
@@ -297,7 +1215,7 @@ fn end_of_day() -> Time {
     Time::from_hms(23, 59, 59).expect("valid end of day time")
 }
 
-fn setup_tracing() -> eyre::Result<()> {
+fn setup_tracing(output: OutputFormat) -> eyre::Result<()> {
     std::fs::create_dir_all("reports").or_else(|e| {
         if e.kind() == std::io::ErrorKind::AlreadyExists {
             Ok(())
@@ -305,7 +1223,13 @@ fn setup_tracing() -> eyre::Result<()> {
             Err(e)
         }
     })?;
+    // Keep stdout clean for the posts themselves when they are streamed there.
+    let log_writer = match output {
+        OutputFormat::Files => BoxMakeWriter::new(std::io::stdout),
+        OutputFormat::Ndjson => BoxMakeWriter::new(std::io::stderr),
+    };
     let stdout_log = tracing_subscriber::fmt::layer()
+        .with_writer(log_writer)
         .with_ansi(true)
         .with_level(true)
         .with_filter(
@@ -338,3 +1262,492 @@ fn setup_tracing() -> eyre::Result<()> {
         .init();
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use futures::TryStreamExt as _;
+    use ssufid::{PluginError, core::SsufidPost};
+
+    use super::*;
+
+    /// Fails its first crawl, as if its host were briefly unreachable.
+    struct FlakyPlugin {
+        attempts: AtomicU32,
+    }
+
+    impl SsufidPlugin for FlakyPlugin {
+        const TITLE: &'static str = "Flaky";
+        const IDENTIFIER: &'static str = "flaky.example.com";
+        const DESCRIPTION: &'static str = "Fails once, then succeeds";
+        const BASE_URL: &'static str = "https://flaky.example.com";
+    }
+
+    impl SsufidPostPlugin for FlakyPlugin {
+        async fn crawl(&self, _posts_limit: u32) -> Result<Vec<SsufidPost>, PluginError> {
+            match self.attempts.fetch_add(1, Ordering::SeqCst) {
+                0 => Err(PluginError::request::<Self>("connection reset".to_string())),
+                _ => Ok(vec![]),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_plugin_is_retried_after_first_pass() {
+        let dir = std::env::temp_dir().join(format!("ssufid-cli-retry-{}", std::process::id()));
+        let core = Arc::new(SsufidCore::new(dir.join("cache").to_str().unwrap()));
+        let options = SaveOptions {
+            crawl: CrawlOptions::new(10),
+            feed: FeedOptions::default(),
+            retry_count: 1,
+            image_base_url: None,
+            translator: None,
+            extract_tables: false,
+            trim_content: false,
+            wrap_tables: false,
+            dedupe_title: false,
+            redact_contacts: false,
+            dedupe_attachments: false,
+            unwrap_redirects: false,
+            sanitize: false,
+            verbose_errors: false,
+            signing_key: None,
+            output: OutputFormat::Files,
+            notify_template: None,
+        };
+        let plugins = [FlakyPlugin {
+            attempts: AtomicU32::new(0),
+        }];
+
+        let outcomes = run_with_plugin_retries(&plugins, 1, std::time::Duration::ZERO, |plugin| {
+            save_run(core.clone(), &dir, plugin, &options)
+        })
+        .await;
+
+        let report =
+            std::fs::read_to_string(dir.join(FlakyPlugin::IDENTIFIER).join("report.json")).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(matches!(outcomes[..], [(_, Ok(0))]));
+        assert_eq!(plugins[0].attempts.load(Ordering::SeqCst), 2);
+        let report: CrawlReport = serde_json::from_str(&report).unwrap();
+        assert!(report.is_success());
+    }
+
+    #[tokio::test]
+    async fn test_persistent_failure_is_returned() {
+        let plugins = ["ok", "down"];
+        let attempts = AtomicU32::new(0);
+        let outcomes = run_with_plugin_retries(&plugins, 2, std::time::Duration::ZERO, |plugin| {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            async move {
+                match *plugin {
+                    "down" => Err(eyre::eyre!("{plugin} is down")),
+                    _ => Ok(()),
+                }
+            }
+        })
+        .await;
+        assert_eq!(attempts.load(Ordering::SeqCst), 4);
+        assert_eq!(outcomes.len(), 2);
+        assert!(matches!(outcomes[0], (&"ok", Ok(()))));
+        assert!(matches!(outcomes[1], (&"down", Err(_))));
+    }
+
+    /// Lists posts with the given titles, all linking to its own host.
+    struct TitledPlugin(&'static [&'static str]);
+
+    impl SsufidPlugin for TitledPlugin {
+        const TITLE: &'static str = "Titled";
+        const IDENTIFIER: &'static str = "titled.example.com";
+        const DESCRIPTION: &'static str = "Lists posts with fixed titles";
+        const BASE_URL: &'static str = "https://titled.example.com/board";
+    }
+
+    impl SsufidPostPlugin for TitledPlugin {
+        async fn crawl(&self, posts_limit: u32) -> Result<Vec<SsufidPost>, PluginError> {
+            Ok(self
+                .0
+                .iter()
+                .take(posts_limit as usize)
+                .enumerate()
+                .map(|(i, title)| SsufidPost {
+                    id: i.to_string(),
+                    url: format!("https://titled.example.com/board/{i}"),
+                    url_kind: Default::default(),
+                    author: None,
+                    title: title.to_string(),
+                    description: None,
+                    category: vec![],
+                    created_at: OffsetDateTime::now_utc(),
+                    updated_at: None,
+                    thumbnail: None,
+                    content: String::new(),
+                    attachments: vec![],
+                    metadata: None,
+                    author_detail: None,
+                })
+                .collect())
+        }
+    }
+
+    fn ndjson_options(retry_count: u32) -> SaveOptions {
+        SaveOptions {
+            crawl: CrawlOptions::new(10),
+            feed: FeedOptions::default(),
+            retry_count,
+            image_base_url: None,
+            translator: None,
+            extract_tables: false,
+            trim_content: false,
+            wrap_tables: false,
+            dedupe_title: false,
+            redact_contacts: false,
+            dedupe_attachments: false,
+            unwrap_redirects: false,
+            sanitize: false,
+            verbose_errors: false,
+            signing_key: None,
+            output: OutputFormat::Ndjson,
+            notify_template: None,
+        }
+    }
+
+    fn ndjson_ids(out: Vec<u8>) -> Vec<String> {
+        String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<SsufidPost>(line).unwrap().id)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_ndjson_streams_every_crawled_post() {
+        let dir = std::env::temp_dir().join(format!("ssufid-cli-ndjson-{}", std::process::id()));
+        let core = Arc::new(SsufidCore::new(dir.join("cache").to_str().unwrap()));
+        let options = ndjson_options(1);
+        let plugin = TitledPlugin(&["수강신청 안내", "졸업식 안내", "장학금 안내"]);
+        let cached = plugin.crawl(1).await.unwrap();
+        core.cache_new_posts::<TitledPlugin>(cached).await.unwrap();
+
+        // Cached posts are written too, on every run.
+        for _ in 0..2 {
+            let mut out = Vec::new();
+            let written = stream_posts(core.clone(), &dir, &plugin, &options, &mut out)
+                .await
+                .unwrap();
+            assert_eq!(written, 3);
+            assert_eq!(ndjson_ids(out), ["0", "1", "2"]);
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(
+            core.cached_post_ids(TitledPlugin::IDENTIFIER)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    /// Streams the posts of a [`TitledPlugin`], failing after the first post on the first
+    /// attempt.
+    struct FlakyStreamPlugin {
+        inner: TitledPlugin,
+        attempts: AtomicU32,
+    }
+
+    impl SsufidPlugin for FlakyStreamPlugin {
+        const TITLE: &'static str = "Flaky stream";
+        const IDENTIFIER: &'static str = "flaky-stream.example.com";
+        const DESCRIPTION: &'static str = "Fails part-way once, then succeeds";
+        const BASE_URL: &'static str = "https://flaky-stream.example.com";
+    }
+
+    impl SsufidPostPlugin for FlakyStreamPlugin {
+        async fn crawl(&self, posts_limit: u32) -> Result<Vec<SsufidPost>, PluginError> {
+            self.inner.crawl(posts_limit).await
+        }
+
+        fn crawl_stream(
+            &self,
+            posts_limit: u32,
+        ) -> impl futures::Stream<Item = Result<SsufidPost, PluginError>> + Send {
+            let fail = self.attempts.fetch_add(1, Ordering::SeqCst) == 0;
+            futures::stream::once(self.inner.crawl(posts_limit))
+                .map_ok(move |posts| {
+                    let mut items = posts.into_iter().map(Ok).collect::<Vec<_>>();
+                    if fail {
+                        items.truncate(1);
+                        items.push(Err(PluginError::request::<Self>(
+                            "connection reset".to_string(),
+                        )));
+                    }
+                    futures::stream::iter(items)
+                })
+                .try_flatten()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ndjson_retries_without_repeating_posts() {
+        let dir =
+            std::env::temp_dir().join(format!("ssufid-cli-ndjson-retry-{}", std::process::id()));
+        let core = Arc::new(SsufidCore::new(dir.join("cache").to_str().unwrap()));
+        let plugin = FlakyStreamPlugin {
+            inner: TitledPlugin(&["수강신청 안내", "졸업식 안내"]),
+            attempts: AtomicU32::new(0),
+        };
+
+        let mut out = Vec::new();
+        let written = stream_posts(core.clone(), &dir, &plugin, &ndjson_options(2), &mut out)
+            .await
+            .unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(plugin.attempts.load(Ordering::SeqCst), 2);
+        assert_eq!(written, 2);
+        assert_eq!(ndjson_ids(out), ["0", "1"]);
+    }
+
+    #[tokio::test]
+    async fn test_report_counts_mojibake_per_run() {
+        let dir = std::env::temp_dir().join(format!("ssufid-cli-mojibake-{}", std::process::id()));
+        let core = Arc::new(SsufidCore::new(dir.join("cache").to_str().unwrap()));
+        let options = SaveOptions {
+            crawl: CrawlOptions::new(10),
+            feed: FeedOptions::default(),
+            retry_count: 1,
+            image_base_url: None,
+            translator: None,
+            extract_tables: false,
+            trim_content: false,
+            wrap_tables: false,
+            dedupe_title: false,
+            redact_contacts: false,
+            dedupe_attachments: false,
+            unwrap_redirects: false,
+            sanitize: false,
+            verbose_errors: false,
+            signing_key: None,
+            output: OutputFormat::Files,
+            notify_template: None,
+        };
+        let plugin = TitledPlugin(&["ㅅㅜㅇㅅㅣㄹ ㄷㅐㅎㅏㄱㄱㅛ 공지", "졸업식 안내"]);
+
+        // Running twice in one process, as the daemon does, records only each run's counts
+        let mut counts = vec![];
+        for _ in 0..2 {
+            save_run(core.clone(), &dir, &plugin, &options)
+                .await
+                .unwrap();
+            let report =
+                std::fs::read_to_string(dir.join(TitledPlugin::IDENTIFIER).join("report.json"))
+                    .unwrap();
+            counts.push(
+                serde_json::from_str::<CrawlReport>(&report)
+                    .unwrap()
+                    .mojibake,
+            );
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(counts, [1, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_validate_fails_on_warnings() {
+        let core = SsufidCore::new(std::env::temp_dir().to_str().unwrap());
+        let drifted = TitledPlugin(&["수강신청 안내", ""]);
+        let warnings = validate_sample(&core, &drifted, 5).await.unwrap();
+        assert_eq!(warnings.len(), 1);
+        // Warnings make main return an error and exit with a non-zero code
+        assert!(report_validation(TitledPlugin::IDENTIFIER, &warnings).is_err());
+
+        let healthy = TitledPlugin(&["수강신청 안내", "졸업식 안내"]);
+        let warnings = validate_sample(&core, &healthy, 5).await.unwrap();
+        assert!(report_validation(TitledPlugin::IDENTIFIER, &warnings).is_ok());
+    }
+
+    #[test]
+    fn test_notify_template_is_validated_on_load() {
+        let error = SsufidDaemonOptions::try_parse_from([
+            "ssufid",
+            "--notify-template",
+            "[{plugin}] {subject}",
+        ])
+        .unwrap_err();
+        assert!(error.to_string().contains("unknown placeholder {subject}"));
+
+        let options = SsufidDaemonOptions::try_parse_from([
+            "ssufid",
+            "--notify-template",
+            "[{plugin}] {title} ({created_at:%m/%d})",
+        ])
+        .unwrap();
+        let template = options.notify_template.unwrap();
+        let now = time::macros::datetime!(2025-03-13 09:00 +9);
+        let post = |id: &str, title: &str, day: u8| SsufidPost {
+            id: id.to_string(),
+            url: format!("https://example.com/{id}"),
+            url_kind: ssufid::core::UrlKind::Canonical,
+            author: None,
+            title: title.to_string(),
+            description: None,
+            category: vec![],
+            created_at: time::Date::from_calendar_date(2025, time::Month::March, day)
+                .unwrap()
+                .with_hms(12, 0, 0)
+                .unwrap()
+                .assume_offset(ssufid::datetime::KST),
+            updated_at: None,
+            thumbnail: None,
+            content: String::new(),
+            attachments: vec![],
+            metadata: None,
+            author_detail: None,
+        };
+        // Newest first
+        let posts = [
+            post("3", "휴강 안내", 12),
+            post("2", "보강 안내", 11),
+            post("1", "개강 안내", 3),
+        ];
+
+        let known = ["1".to_string()].into_iter().collect();
+        assert_eq!(
+            notification_messages(&template, "sw.ssu.ac.kr", &known, &posts, now),
+            [
+                "[sw.ssu.ac.kr] 보강 안내 (03/11)",
+                "[sw.ssu.ac.kr] 휴강 안내 (03/12)"
+            ]
+        );
+        // Every post on a board crawled for the first time is new, so nothing is notified
+        assert!(
+            notification_messages(&template, "sw.ssu.ac.kr", &Default::default(), &posts, now)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_request_log_is_opt_in() {
+        let dir =
+            std::env::temp_dir().join(format!("ssufid-cli-request-log-{}", std::process::id()));
+        let cache = dir.to_str().unwrap();
+        let started_at = OffsetDateTime::now_utc();
+
+        let options = SsufidDaemonOptions::parse_from(["ssufid", "--cache", cache]);
+        assert!(open_request_log(&options, started_at).unwrap().is_none());
+        assert!(!dir.join(RequestLog::DIR).exists());
+
+        let options =
+            SsufidDaemonOptions::parse_from(["ssufid", "--cache", cache, "--request-log"]);
+        let log = open_request_log(&options, started_at).unwrap().unwrap();
+        assert_eq!(
+            log.path(),
+            dir.join(RequestLog::DIR)
+                .join(format!("{}.jsonl", RunManifest::run_id_of(started_at)))
+        );
+        assert!(log.path().exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_request_budget_requires_confirmation() {
+        let estimate = [
+            TitledPlugin(&[]).estimate_cost(&CrawlOptions::new(100)),
+            TitledPlugin(&[]).estimate_cost(&CrawlOptions::new(100)),
+        ]
+        .into_iter()
+        .sum::<CostEstimate>();
+        assert_eq!(estimate.total_requests(), 220);
+
+        assert!(check_request_budget(&estimate, 100, None, false).is_ok());
+        assert!(check_request_budget(&estimate, 100, Some(220), false).is_ok());
+        // Over the budget, nothing is crawled without --yes
+        let error = check_request_budget(&estimate, 100, Some(200), false).unwrap_err();
+        assert_eq!(exit_code(&error), ssufid::Error::EXIT_CONFIG);
+        assert!(check_request_budget(&estimate, 100, Some(200), true).is_ok());
+
+        // --limit 0 can crawl without bound, so it asks regardless of the estimate
+        let unbounded = TitledPlugin(&[]).estimate_cost(&CrawlOptions::new(0));
+        let error = check_request_budget(&unbounded, 0, Some(200), false).unwrap_err();
+        assert_eq!(exit_code(&error), ssufid::Error::EXIT_CONFIG);
+        assert!(check_request_budget(&unbounded, 0, Some(200), true).is_ok());
+        assert!(check_request_budget(&unbounded, 0, None, false).is_ok());
+    }
+
+    #[test]
+    fn test_exit_code_follows_error_source() {
+        let report = parse_cli_date("2025-13-01").unwrap_err();
+        assert_eq!(exit_code(&report), ssufid::Error::EXIT_CONFIG);
+
+        let report = eyre::Report::from(ssufid::Error::from(std::io::Error::other("disk full")));
+        assert_eq!(exit_code(&report), ssufid::Error::EXIT_IO);
+        let report = eyre::Report::from(PluginError::request::<TitledPlugin>(
+            "connection refused".to_string(),
+        ));
+        assert_eq!(exit_code(&report), ssufid::Error::EXIT_UNAVAILABLE);
+        assert_eq!(exit_code(&eyre::eyre!("2 of 5 Run failed")), 1);
+    }
+
+    #[test]
+    fn test_run_exit_code_follows_shared_failure() {
+        let now = OffsetDateTime::now_utc();
+        let manifest = RunManifest::new(
+            now,
+            now,
+            vec![
+                PluginRunResult::failure("a.example.com", "down"),
+                PluginRunResult::failure("b.example.com", "down"),
+            ],
+        );
+        let unreachable = || {
+            eyre::Report::from(PluginError::request::<TitledPlugin>(
+                "timed out".to_string(),
+            ))
+        };
+
+        // Exits with the transient error code when every plugin fails with a network error
+        let report = exit_status(&manifest, &[unreachable(), unreachable()]).unwrap_err();
+        assert_eq!(report.to_string(), "2 of 2 Run failed");
+        assert_eq!(exit_code(&report), ssufid::Error::EXIT_UNAVAILABLE);
+
+        let report = exit_status(
+            &manifest,
+            &[
+                unreachable(),
+                eyre::Report::from(PluginError::parse::<TitledPlugin>("no table".to_string())),
+            ],
+        )
+        .unwrap_err();
+        assert_eq!(exit_code(&report), 1);
+    }
+
+    #[test]
+    fn test_list_shows_only_enabled_plugins() {
+        let identifiers = registered_identifiers();
+        assert_eq!(
+            identifiers.contains(&"infocom.ssu.ac.kr"),
+            cfg!(feature = "plugin-infocom")
+        );
+        assert_eq!(
+            identifiers.contains(&"ssudorm.ssu.ac.kr"),
+            cfg!(feature = "plugin-ssudorm")
+        );
+        assert_eq!(
+            identifiers.contains(&"accounting.ssu.ac.kr"),
+            cfg!(feature = "plugin-common")
+        );
+        let unique = identifiers.iter().collect::<HashSet<_>>();
+        assert_eq!(unique.len(), identifiers.len());
+
+        // Plugins missing from the list do not run even if --include picks them
+        let options = SsufidDaemonOptions::parse_from(["ssufid", "-i", "infocom.ssu.ac.kr"]);
+        let selected = construct_plugins(&options)
+            .iter()
+            .map(SsufidPluginRegistry::identifier)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            selected.len(),
+            usize::from(cfg!(feature = "plugin-infocom"))
+        );
+    }
+}
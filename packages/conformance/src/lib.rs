@@ -0,0 +1,214 @@
+//! Offline conformance checks run against every registered plugin.
+//!
+//! A plugin is covered when the fixtures directory has a directory named after its identifier,
+//! as written by `ssufid fixtures refresh`. [`check_plugin`] crawls the plugin against those pages
+//! on a mock server and checks the posts with [`check_posts`].
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use ssufid::{
+    PluginError,
+    core::{SsufidPost, SsufidPostPlugin},
+    datetime::KST,
+    fixtures::fixture_file_name,
+};
+use time::OffsetDateTime;
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+/// A broken invariant of a crawl, found by [`check_posts`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    /// The crawl returned no posts, e.g. because the list selector no longer matches.
+    NoPosts,
+    /// The post at `index` has an empty id.
+    EmptyId { index: usize },
+    /// A post's URL is not absolute.
+    RelativeUrl { id: String, url: String },
+    /// A post's creation date is not in KST (`+09:00`).
+    NotKst {
+        id: String,
+        created_at: OffsetDateTime,
+    },
+    /// A post is newer than the one before it, while crawls list posts newest first.
+    Unsorted { id: String },
+    /// Several posts share an id.
+    DuplicateId { id: String },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoPosts => write!(f, "no posts were crawled"),
+            Self::EmptyId { index } => write!(f, "post #{index} has an empty id"),
+            Self::RelativeUrl { id, url } => write!(f, "post {id:?} has a relative url: {url}"),
+            Self::NotKst { id, created_at } => {
+                write!(f, "post {id:?} is not dated in KST: {created_at}")
+            }
+            Self::Unsorted { id } => write!(f, "post {id:?} is newer than the post before it"),
+            Self::DuplicateId { id } => write!(f, "several posts have the id {id:?}"),
+        }
+    }
+}
+
+/// Checks the posts of one crawl for empty ids, relative URLs, dates outside KST, posts out
+/// of newest-first order and duplicate ids.
+pub fn check_posts(posts: &[SsufidPost]) -> Vec<Violation> {
+    if posts.is_empty() {
+        return vec![Violation::NoPosts];
+    }
+    let mut violations = Vec::new();
+    let mut seen = HashSet::new();
+    for (index, post) in posts.iter().enumerate() {
+        if post.id.trim().is_empty() {
+            violations.push(Violation::EmptyId { index });
+        } else if !seen.insert(post.id.as_str()) {
+            violations.push(Violation::DuplicateId {
+                id: post.id.clone(),
+            });
+        }
+        if url::Url::parse(&post.url).is_err() {
+            violations.push(Violation::RelativeUrl {
+                id: post.id.clone(),
+                url: post.url.clone(),
+            });
+        }
+        if post.created_at.offset() != KST {
+            violations.push(Violation::NotKst {
+                id: post.id.clone(),
+                created_at: post.created_at,
+            });
+        }
+        if index > 0 && post.created_at > posts[index - 1].created_at {
+            violations.push(Violation::Unsorted {
+                id: post.id.clone(),
+            });
+        }
+    }
+    violations
+}
+
+/// A mock server answering with the fixtures of one plugin, for requests routed to it with
+/// [`with_all_origins_routed_to`](ssufid::fetch::with_all_origins_routed_to): a request for
+/// `/<host>/<path>?<query>` is answered with the fixture of `https://<host>/<path>?<query>`,
+/// and with `404` if there is none.
+pub struct FixtureServer {
+    server: MockServer,
+}
+
+struct FixtureResponder {
+    pages: HashMap<String, String>,
+}
+
+impl Respond for FixtureResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let mut url = format!("https:/{}", request.url.path());
+        if let Some(query) = request.url.query() {
+            url.push('?');
+            url.push_str(query);
+        }
+        match self.pages.get(&fixture_file_name(&url)) {
+            Some(html) => ResponseTemplate::new(200).set_body_raw(html.clone(), "text/html"),
+            None => ResponseTemplate::new(404),
+        }
+    }
+}
+
+impl FixtureServer {
+    /// Serves the `.html` files in `dir`.
+    pub async fn start(dir: &Path) -> std::io::Result<Self> {
+        let mut pages = HashMap::new();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "html") {
+                let name = path.file_name().unwrap().to_string_lossy().into_owned();
+                pages.insert(name, std::fs::read_to_string(&path)?);
+            }
+        }
+        let server = MockServer::start().await;
+        Mock::given(wiremock::matchers::any())
+            .respond_with(FixtureResponder { pages })
+            .mount(&server)
+            .await;
+        Ok(Self { server })
+    }
+
+    pub fn uri(&self) -> String {
+        self.server.uri()
+    }
+}
+
+/// The result of checking one plugin.
+#[derive(Debug)]
+pub enum Outcome {
+    /// The plugin has no fixtures.
+    Uncovered,
+    Passed {
+        posts: usize,
+    },
+    Failed(Vec<Violation>),
+    /// The crawl itself failed, or the fixtures could not be read.
+    Errored(PluginError),
+}
+
+impl Outcome {
+    /// Whether the plugin has fixtures and they broke an invariant or the crawl.
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Self::Failed(_) | Self::Errored(_))
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Uncovered => write!(f, "uncovered"),
+            Self::Passed { posts } => write!(f, "passed ({posts} posts)"),
+            Self::Failed(violations) => {
+                let violations = violations
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>();
+                write!(f, "failed: {}", violations.join("; "))
+            }
+            Self::Errored(error) => write!(f, "errored: {error}"),
+        }
+    }
+}
+
+/// The committed fixtures of the registered plugins, `packages/conformance/fixtures`, also the
+/// default directory of `ssufid fixtures refresh` when run from the workspace root.
+pub fn fixtures_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures")
+}
+
+/// Crawls up to `posts_limit` posts of `plugin` against its fixtures in
+/// `fixtures_root/<identifier>` and checks them, see the [crate docs](crate).
+pub async fn check_plugin<T>(plugin: &T, fixtures_root: &Path, posts_limit: u32) -> Outcome
+where
+    T: SsufidPostPlugin + Sync,
+{
+    let dir = fixtures_root.join(T::IDENTIFIER);
+    if !dir.is_dir() {
+        return Outcome::Uncovered;
+    }
+    let server = match FixtureServer::start(&dir).await {
+        Ok(server) => server,
+        Err(e) => {
+            return Outcome::Errored(PluginError::custom::<T>(
+                "Fixtures".to_string(),
+                format!("Failed to read fixtures in {}: {e}", dir.display()),
+            ));
+        }
+    };
+    let crawl = plugin.crawl(posts_limit);
+    match ssufid::fetch::with_all_origins_routed_to(&server.uri(), crawl).await {
+        Ok(posts) => match check_posts(&posts) {
+            violations if violations.is_empty() => Outcome::Passed { posts: posts.len() },
+            violations => Outcome::Failed(violations),
+        },
+        Err(e) => Outcome::Errored(e),
+    }
+}
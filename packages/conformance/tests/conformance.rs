@@ -0,0 +1,198 @@
+use std::path::{Path, PathBuf};
+
+use scraper::{Html, Selector};
+use ssufid::{
+    PluginError,
+    core::{SsufidPlugin, SsufidPost, SsufidPostPlugin, UrlKind},
+    datetime::parse_kst,
+    fetch::{default_client, fetch_text},
+    paginate::Paginator,
+};
+use ssufid_conformance::{Outcome, Violation, check_plugin, fixtures_root};
+
+const POSTS_LIMIT: u32 = 5;
+
+/// Checks each plugin of the registry in turn, in registration order.
+macro_rules! check_registered_plugins {
+    (
+        post: { $($(#[$meta:meta])* $id:ident($plugin:ty) => $initializer:expr),* $(,)? },
+        calendar: { $($calendar:tt)* }
+    ) => {{
+        let root = fixtures_root();
+        vec![$($(#[$meta])* (<$plugin>::IDENTIFIER, check_plugin(&$initializer, &root, POSTS_LIMIT).await),)*]
+    }};
+}
+
+#[tokio::test]
+async fn test_registered_plugins_conform() {
+    let outcomes: Vec<(&str, Outcome)> =
+        ssufid_registry::registered_plugins!(check_registered_plugins);
+
+    let mut failures = Vec::new();
+    for (identifier, outcome) in &outcomes {
+        match outcome {
+            // Plugins without fixtures only warn, to show the coverage without failing
+            Outcome::Uncovered => eprintln!("warning: {identifier} is uncovered"),
+            outcome if outcome.is_failure() => failures.push(format!("{identifier}: {outcome}")),
+            outcome => eprintln!("{identifier}: {outcome}"),
+        }
+    }
+    let covered = outcomes
+        .iter()
+        .filter(|(_, outcome)| !matches!(outcome, Outcome::Uncovered))
+        .count();
+    eprintln!("{covered} of {} plugins covered", outcomes.len());
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+
+    // A misspelled fixture directory would be skipped silently, so check all are used
+    let mut fixture_dirs = Vec::new();
+    collect_fixture_dirs(&fixtures_root(), &mut fixture_dirs);
+    assert!(!fixture_dirs.is_empty());
+    for dir in fixture_dirs {
+        assert!(
+            outcomes.iter().any(|(identifier, outcome)| {
+                *identifier == dir && !matches!(outcome, Outcome::Uncovered)
+            }),
+            "fixtures/{dir} belongs to no registered plugin"
+        );
+    }
+}
+
+/// The paths, relative to `root`, of the directories under it holding `.html` fixtures.
+fn collect_fixture_dirs(root: &Path, dirs: &mut Vec<String>) {
+    fn visit(root: &Path, dir: &Path, dirs: &mut Vec<String>) {
+        let mut has_fixtures = false;
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                visit(root, &path, dirs);
+            } else if path.extension().is_some_and(|ext| ext == "html") {
+                has_fixtures = true;
+            }
+        }
+        if has_fixtures {
+            let relative = dir.strip_prefix(root).unwrap();
+            dirs.push(relative.to_string_lossy().replace('\\', "/"));
+        }
+    }
+    visit(root, root, dirs);
+}
+
+/// A sacrificial plugin whose list selector can be broken on purpose, crawling only its list
+/// page.
+struct BoardPlugin {
+    row_selector: &'static str,
+}
+
+impl SsufidPlugin for BoardPlugin {
+    const TITLE: &'static str = "Board";
+    const IDENTIFIER: &'static str = "board.example.com";
+    const DESCRIPTION: &'static str = "Sacrificial plugin for the conformance suite";
+    const BASE_URL: &'static str = "https://board.example.com/notice/list.php";
+}
+
+impl SsufidPostPlugin for BoardPlugin {
+    async fn crawl(&self, posts_limit: u32) -> Result<Vec<SsufidPost>, PluginError> {
+        let html = fetch_text::<Self>(&default_client(), Self::BASE_URL).await?;
+        Ok(parse_rows(&html, self.row_selector, Self::BASE_URL)
+            .into_iter()
+            .take(posts_limit as usize)
+            .collect())
+    }
+}
+
+/// A paginated board that repeats its pinned posts at the top of every page.
+struct PinnedBoardPlugin;
+
+impl SsufidPlugin for PinnedBoardPlugin {
+    const TITLE: &'static str = "Pinned board";
+    const IDENTIFIER: &'static str = "pinned.example.com";
+    const DESCRIPTION: &'static str = "Sacrificial plugin for the conformance suite";
+    const BASE_URL: &'static str = "https://pinned.example.com/notice/list.php";
+}
+
+impl SsufidPostPlugin for PinnedBoardPlugin {
+    async fn crawl(&self, posts_limit: u32) -> Result<Vec<SsufidPost>, PluginError> {
+        let client = default_client();
+        let mut paginator = Paginator::new(posts_limit);
+        while let Some(page) = paginator.next_page() {
+            let page_url = format!("{}?page={page}", Self::BASE_URL);
+            let html = fetch_text::<Self>(&client, &page_url).await?;
+            let rows = parse_rows(&html, "table.board > tbody > tr", Self::BASE_URL);
+            paginator.push_page(rows, |post| post.id.clone());
+        }
+        let mut posts = paginator.into_items();
+        posts.sort_by_key(|post| std::cmp::Reverse(post.created_at));
+        Ok(posts)
+    }
+}
+
+/// The posts in the rows matching `row_selector` of a list page, each linking its post by `idx`.
+fn parse_rows(html: &str, row_selector: &str, base_url: &str) -> Vec<SsufidPost> {
+    let document = Html::parse_document(html);
+    let row = Selector::parse(row_selector).unwrap();
+    let link = Selector::parse("td.subject > a").unwrap();
+    let date = Selector::parse("td.date").unwrap();
+    let base_url = url::Url::parse(base_url).unwrap();
+    document
+        .select(&row)
+        .filter_map(|row| {
+            let link = row.select(&link).next()?;
+            let url = base_url.join(link.value().attr("href")?).ok()?;
+            let id = url.query_pairs().find(|(key, _)| key == "idx")?.1;
+            Some(SsufidPost {
+                id: id.into_owned(),
+                url: url.to_string(),
+                url_kind: UrlKind::Canonical,
+                author: None,
+                title: link.text().collect::<String>(),
+                description: None,
+                category: vec![],
+                created_at: parse_kst(&row.select(&date).next()?.text().collect::<String>())?,
+                updated_at: None,
+                thumbnail: None,
+                content: String::new(),
+                attachments: vec![],
+                metadata: None,
+                author_detail: None,
+            })
+        })
+        .collect()
+}
+
+fn sacrificial_fixtures() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+#[tokio::test]
+async fn test_broken_selector_is_caught() {
+    let working = BoardPlugin {
+        row_selector: "table.board > tbody > tr",
+    };
+    let outcome = check_plugin(&working, &sacrificial_fixtures(), POSTS_LIMIT).await;
+    assert!(matches!(outcome, Outcome::Passed { posts: 3 }), "{outcome}");
+
+    // The board was redesigned and the table class changed
+    let broken = BoardPlugin {
+        row_selector: "table.bbs-list > tbody > tr",
+    };
+    let outcome = check_plugin(&broken, &sacrificial_fixtures(), POSTS_LIMIT).await;
+    assert!(outcome.is_failure());
+    assert!(
+        matches!(&outcome, Outcome::Failed(violations) if violations == &[Violation::NoPosts]),
+        "{outcome}"
+    );
+
+    let outcome = check_plugin(&working, Path::new("/nonexistent"), POSTS_LIMIT).await;
+    assert!(matches!(outcome, Outcome::Uncovered));
+}
+
+#[tokio::test]
+async fn test_pinned_posts_count_once_towards_limit() {
+    // A board repeating the same three pinned notices at the top of every page
+    let outcome = check_plugin(&PinnedBoardPlugin, &sacrificial_fixtures(), 10).await;
+    assert!(
+        matches!(outcome, Outcome::Passed { posts: 10 }),
+        "{outcome}"
+    );
+}
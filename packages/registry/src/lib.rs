@@ -0,0 +1,141 @@
+//! The plugins shipped with ssufid, for the CLI and the conformance suite.
+//!
+//! Each entry is gated with `#[cfg(feature = "plugin-...")]`, evaluated in the crate expanding
+//! [`registered_plugins!`], which must declare those features.
+
+/// Calls the macro `$callback` with every plugin, in registration order:
+///
+/// ```ignore
+/// $callback! {
+///     post: { $(#[cfg(...)] Variant(PluginType) => initializer,)* },
+///     calendar: { $(#[cfg(...)] Variant(PluginType) => initializer,)* }
+/// }
+/// ```
+#[macro_export]
+macro_rules! registered_plugins {
+    ($callback:ident) => {
+        $callback! {
+            post: {
+                #[cfg(feature = "plugin-common")]
+                Accounting(ssufid_common::sites::AccountingPlugin) => ssufid_common::sites::AccountingPlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                Actx(ssufid_common::sites::ActxPlugin) => ssufid_common::sites::ActxPlugin::new(),
+                #[cfg(feature = "plugin-biz")]
+                Biz(ssufid_biz::BizPlugin) => ssufid_biz::BizPlugin::new(),
+                #[cfg(feature = "plugin-biz")]
+                BizJob(ssufid_biz::BizJobPlugin) => ssufid_biz::BizJobPlugin::new(),
+                #[cfg(feature = "plugin-biz")]
+                BizScholarship(ssufid_biz::BizScholarshipPlugin) => ssufid_biz::BizScholarshipPlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                Bioinfo(ssufid_common::sites::BioinfoPlugin) => ssufid_common::sites::BioinfoPlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                Chem(ssufid_common::sites::ChemPlugin) => ssufid_common::sites::ChemPlugin::new(),
+                #[cfg(feature = "plugin-chemeng")]
+                ChemEng(ssufid_chemeng::ChemEngPlugin) => ssufid_chemeng::ChemEngPlugin::default(),
+                #[cfg(feature = "plugin-common")]
+                Chilan(ssufid_common::sites::ChilanPlugin) => ssufid_common::sites::ChilanPlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                CseBachelor(ssufid_common::sites::CseBachelorPlugin) => ssufid_common::sites::CseBachelorPlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                CseGraduate(ssufid_common::sites::CseGraduatePlugin) => ssufid_common::sites::CseGraduatePlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                CseEmployment(ssufid_common::sites::CseEmploymentPlugin) => ssufid_common::sites::CseEmploymentPlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                Docs(ssufid_common::sites::DocsPlugin) => ssufid_common::sites::DocsPlugin::new(),
+                #[cfg(feature = "plugin-ee")]
+                Ee(ssufid_ee::EePlugin) => ssufid_ee::EePlugin::default(),
+                #[cfg(feature = "plugin-common")]
+                Eco(ssufid_common::sites::EcoPlugin) => ssufid_common::sites::EcoPlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                Englan(ssufid_common::sites::EnglanPlugin) => ssufid_common::sites::EnglanPlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                Ensb(ssufid_common::sites::EnsbPlugin) => ssufid_common::sites::EnsbPlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                Finance(ssufid_common::sites::FinancePlugin) => ssufid_common::sites::FinancePlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                France(ssufid_common::sites::FrancePlugin) => ssufid_common::sites::FrancePlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                Gerlan(ssufid_common::sites::GerlanPlugin) => ssufid_common::sites::GerlanPlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                Gtrade(ssufid_common::sites::GtradePlugin) => ssufid_common::sites::GtradePlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                History(ssufid_common::sites::HistoryPlugin) => ssufid_common::sites::HistoryPlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                Iise(ssufid_common::sites::IisePlugin) => ssufid_common::sites::IisePlugin::new(),
+                #[cfg(feature = "plugin-inso")]
+                Inso(ssufid_inso::InsoPlugin) => ssufid_inso::InsoPlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                Itrans(ssufid_common::sites::ItransPlugin) => ssufid_common::sites::ItransPlugin::new(),
+                #[cfg(feature = "plugin-infocom")]
+                Infocom(ssufid_infocom::InfocomPlugin) => ssufid_infocom::InfocomPlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                Japanstu(ssufid_common::sites::JapanstuPlugin) => ssufid_common::sites::JapanstuPlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                Korlan(ssufid_common::sites::KorlanPlugin) => ssufid_common::sites::KorlanPlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                Law(ssufid_common::sites::LawPlugin) => ssufid_common::sites::LawPlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                Lawyer(ssufid_common::sites::LawyerPlugin) => ssufid_common::sites::LawyerPlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                LifelongEdu(ssufid_common::sites::LifelongEduPlugin) => ssufid_common::sites::LifelongEduPlugin::new(),
+                #[cfg(feature = "plugin-materials")]
+                Materials(ssufid_materials::MaterialsPlugin) => ssufid_materials::MaterialsPlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                Masscom(ssufid_common::sites::MasscomPlugin) => ssufid_common::sites::MasscomPlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                Math(ssufid_common::sites::MathPlugin) => ssufid_common::sites::MathPlugin::new(),
+                #[cfg(feature = "plugin-media")]
+                Media(ssufid_media::MediaPlugin) => ssufid_media::MediaPlugin,
+                #[cfg(feature = "plugin-mediamba")]
+                Mediamba(ssufid_mediamba::MediambaPlugin) => ssufid_mediamba::MediambaPlugin,
+                #[cfg(feature = "plugin-common")]
+                Mysoongsil(ssufid_common::sites::MysoongsilPlugin) => ssufid_common::sites::MysoongsilPlugin::new(),
+                #[cfg(feature = "plugin-oasis")]
+                Oasis(ssufid_oasis::OasisPlugin) => ssufid_oasis::OasisPlugin,
+                #[cfg(feature = "plugin-common")]
+                Philo(ssufid_common::sites::PhiloPlugin) => ssufid_common::sites::PhiloPlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                Physics(ssufid_common::sites::PhysicsPlugin) => ssufid_common::sites::PhysicsPlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                Politics(ssufid_common::sites::PoliticsPlugin) => ssufid_common::sites::PoliticsPlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                Pubad(ssufid_common::sites::PubadPlugin) => ssufid_common::sites::PubadPlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                Sec(ssufid_common::sites::SecPlugin) => ssufid_common::sites::SecPlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                Sls(ssufid_common::sites::SlsPlugin) => ssufid_common::sites::SlsPlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                Soar(ssufid_common::sites::SoarPlugin) => ssufid_common::sites::SoarPlugin::new(),
+                #[cfg(feature = "plugin-ssfilm")]
+                Ssfilm(ssufid_ssfilm::SsfilmPlugin) => ssufid_ssfilm::SsfilmPlugin,
+                #[cfg(feature = "plugin-ssucatch")]
+                SsuCatch(ssufid_ssucatch::SsuCatchPlugin) => ssufid_ssucatch::SsuCatchPlugin::new(),
+                #[cfg(feature = "plugin-ssudorm")]
+                SsuDorm(ssufid_ssudorm::SsuDormPlugin) => ssufid_ssudorm::SsuDormPlugin::new(),
+                #[cfg(feature = "plugin-ssupath")]
+                SsuPath(ssufid_ssupath::SsuPathPlugin) => ssufid_ssupath::SsuPathPlugin::new(
+                    ssufid_ssupath::SsuPathCredential::Password(
+                        std::env::var("SSU_ID").unwrap_or_default(),
+                        std::env::var("SSU_PASSWORD").unwrap_or_default(),
+                    ),
+                ),
+                #[cfg(feature = "plugin-startup")]
+                Startup(ssufid_startup::StartupPlugin) => ssufid_startup::StartupPlugin,
+                #[cfg(feature = "plugin-stu")]
+                Stu(ssufid_stu::StuPlugin) => ssufid_stu::StuPlugin,
+                #[cfg(feature = "plugin-study")]
+                Study(ssufid_study::StudyPlugin) => ssufid_study::StudyPlugin,
+                #[cfg(feature = "plugin-common")]
+                Sports(ssufid_common::sites::SportsPlugin) => ssufid_common::sites::SportsPlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                SwBachelor(ssufid_common::sites::SwBachelorPlugin) => ssufid_common::sites::SwBachelorPlugin::new(),
+                #[cfg(feature = "plugin-common")]
+                SwGraduate(ssufid_common::sites::SwGraduatePlugin) => ssufid_common::sites::SwGraduatePlugin::new(),
+            },
+            calendar: {
+                #[cfg(feature = "plugin-ssu-academic-calendar")]
+                SsuAcademicCalendar(ssufid_ssu_academic_calendar::SsuAcademicCalendarPlugin) => ssufid_ssu_academic_calendar::SsuAcademicCalendarPlugin,
+            }
+        }
+    };
+}
@@ -0,0 +1,181 @@
+//! Crawls two boards from `examples/fixtures`, combines their posts and writes JSON and RSS.
+//!
+//! ```sh
+//! cargo run -p ssufid --example aggregate -- ./out
+//! ```
+
+use std::path::PathBuf;
+
+use scraper::{Html, Selector};
+use ssufid::{
+    PluginError, SsufidCore,
+    core::{
+        CrawlOptions, PluginInfo, SsufidPlugin, SsufidPost, SsufidPostPlugin, UrlKind,
+        rss::FeedOptions,
+    },
+    output::combine,
+};
+use time::{Date, format_description::BorrowedFormatItem, macros::format_description};
+
+const DATE_FORMAT: &[BorrowedFormatItem<'_>] = format_description!("[year]-[month]-[day]");
+
+/// Parses a fixture list page whose items carry their own content.
+fn parse_board<T: SsufidPlugin>(
+    html: &str,
+    posts_limit: u32,
+) -> Result<Vec<SsufidPost>, PluginError> {
+    let item = ssufid::html::parse_selector::<T>("ul.board-list > li.item")?;
+    let link = ssufid::html::parse_selector::<T>("a")?;
+    let date = ssufid::html::parse_selector::<T>("span.date")?;
+    let content = ssufid::html::parse_selector::<T>("div.content")?;
+    let base_url = url::Url::parse(T::BASE_URL)
+        .map_err(|e| PluginError::parse::<T>(format!("Invalid base URL: {e}")))?;
+
+    let document = Html::parse_document(html);
+    document
+        .select(&item)
+        .take(posts_limit as usize)
+        .map(|item| {
+            let first = |selector: &Selector, expected: &str| {
+                item.select(selector)
+                    .next()
+                    .ok_or_else(|| PluginError::structure::<T>(expected, "a list item"))
+            };
+            let link = first(&link, "a post link")?;
+            let href = link.value().attr("href").unwrap_or_default();
+            let url = base_url
+                .join(href)
+                .map_err(|e| PluginError::parse::<T>(format!("Invalid post URL {href}: {e}")))?;
+            let id = url
+                .query_pairs()
+                .find_map(|(key, value)| (key == "idx").then(|| value.into_owned()))
+                .ok_or_else(|| PluginError::parse::<T>(format!("No idx in {url}")))?;
+            let date_text = first(&date, "a date")?.text().collect::<String>();
+            let created_at = Date::parse(date_text.trim(), DATE_FORMAT)
+                .map_err(|e| PluginError::parse::<T>(format!("Invalid date {date_text}: {e}")))?
+                .midnight()
+                .assume_offset(time::macros::offset!(+9));
+
+            Ok(SsufidPost {
+                id,
+                url: url.to_string(),
+                url_kind: UrlKind::Canonical,
+                author: None,
+                title: link.text().collect(),
+                description: None,
+                category: vec![],
+                created_at,
+                updated_at: None,
+                thumbnail: None,
+                content: first(&content, "the post content")?.inner_html(),
+                attachments: vec![],
+                metadata: None,
+                author_detail: None,
+            })
+        })
+        .collect()
+}
+
+struct DepartmentPlugin;
+
+impl SsufidPlugin for DepartmentPlugin {
+    const TITLE: &'static str = "예제 학과 공지사항";
+    const IDENTIFIER: &'static str = "department.example.com";
+    const DESCRIPTION: &'static str = "예제 학과의 공지사항입니다.";
+    const BASE_URL: &'static str = "https://department.example.com/notice";
+}
+
+impl SsufidPostPlugin for DepartmentPlugin {
+    async fn crawl(&self, posts_limit: u32) -> Result<Vec<SsufidPost>, PluginError> {
+        parse_board::<Self>(include_str!("fixtures/notices.html"), posts_limit)
+    }
+}
+
+struct DormitoryPlugin;
+
+impl SsufidPlugin for DormitoryPlugin {
+    const TITLE: &'static str = "예제 기숙사 공지사항";
+    const IDENTIFIER: &'static str = "dorm.example.com";
+    const DESCRIPTION: &'static str = "예제 기숙사의 공지사항입니다.";
+    const BASE_URL: &'static str = "https://dorm.example.com/dorm";
+}
+
+impl SsufidPostPlugin for DormitoryPlugin {
+    async fn crawl(&self, posts_limit: u32) -> Result<Vec<SsufidPost>, PluginError> {
+        parse_board::<Self>(include_str!("fixtures/dorm.html"), posts_limit)
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let out_dir = std::env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| std::env::temp_dir().join("ssufid-aggregate"));
+    std::fs::create_dir_all(&out_dir)?;
+
+    // The core keeps a per-plugin cache so that reruns only add new or changed posts.
+    let core = SsufidCore::new(out_dir.join("cache").to_str().ok_or("non UTF-8 path")?);
+    let options = CrawlOptions::new(10);
+
+    // Plugins are independent, so they are crawled concurrently.
+    let (department, dormitory) = tokio::join!(
+        core.run(&DepartmentPlugin, &options),
+        core.run(&DormitoryPlugin, &options),
+    );
+    let (department, dormitory) = (department?, dormitory?);
+    core.save_cache().await?;
+
+    for (identifier, site) in [
+        (DepartmentPlugin::IDENTIFIER, &department),
+        (DormitoryPlugin::IDENTIFIER, &dormitory),
+    ] {
+        let mojibake = site
+            .items()
+            .iter()
+            .filter(|post| post.looks_mojibake())
+            .count();
+        println!(
+            "{identifier}: {} posts, {mojibake} mis-decoded",
+            site.items().len()
+        );
+    }
+
+    let feed = combine(vec![
+        (
+            PluginInfo::of::<DepartmentPlugin>(),
+            department.items().to_vec(),
+        ),
+        (
+            PluginInfo::of::<DormitoryPlugin>(),
+            dormitory.items().to_vec(),
+        ),
+    ]);
+    std::fs::write(
+        out_dir.join("department.json"),
+        serde_json::to_string_pretty(&department)?,
+    )?;
+    std::fs::write(
+        out_dir.join("department.xml"),
+        department.to_rss().to_string(),
+    )?;
+    std::fs::write(
+        out_dir.join("combined.json"),
+        serde_json::to_string_pretty(&feed)?,
+    )?;
+    let channel = feed.to_channel(
+        "예제 통합 공지사항",
+        "https://example.com",
+        "학과와 기숙사 공지사항을 모았습니다.",
+        &FeedOptions::default(),
+        time::OffsetDateTime::now_utc(),
+    );
+    std::fs::write(out_dir.join("combined.xml"), channel.to_string())?;
+
+    println!(
+        "Wrote {} combined posts to {}",
+        feed.items.len(),
+        out_dir.display()
+    );
+    Ok(())
+}
@@ -0,0 +1,257 @@
+use scraper::{ElementRef, Html};
+
+/// Elements that start a new line of text.
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "blockquote",
+    "br",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "figcaption",
+    "figure",
+    "footer",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "li",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "tr",
+    "ul",
+];
+
+/// A run of lines that differs between two versions of a post, with both sides' 0-based
+/// starting line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffHunk {
+    pub old_start: usize,
+    pub removed: Vec<String>,
+    pub new_start: usize,
+    pub added: Vec<String>,
+}
+
+/// Splits the text of an HTML fragment into lines at block elements and `<br>`, with
+/// whitespace collapsed and empty lines dropped.
+pub fn text_lines(html: &str) -> Vec<String> {
+    fn walk(element: ElementRef<'_>, current: &mut String, lines: &mut Vec<String>) {
+        let is_block = BLOCK_ELEMENTS.contains(&element.value().name());
+        if is_block {
+            lines.push(std::mem::take(current));
+        }
+        for child in element.children() {
+            if let Some(child) = ElementRef::wrap(child) {
+                walk(child, current, lines);
+            } else if let Some(text) = child.value().as_text() {
+                current.push_str(text);
+                current.push(' ');
+            }
+        }
+        if is_block {
+            lines.push(std::mem::take(current));
+        }
+    }
+
+    let fragment = Html::parse_fragment(html);
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    walk(fragment.root_element(), &mut current, &mut lines);
+    lines.push(current);
+    lines
+        .iter()
+        .map(|line| crate::text::clean_text(line))
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// The largest LCS table [`text_diff`] builds, in cells. Past it, the changed middle of the
+/// two versions is reported as a single hunk rather than aligned line by line.
+const MAX_LCS_CELLS: usize = 1 << 20;
+
+/// Compares the [text lines](text_lines) of two versions of a post's content, returning the
+/// runs of lines that changed in order. Markup-only edits produce no hunks.
+pub fn text_diff(old_html: &str, new_html: &str) -> Vec<DiffHunk> {
+    let old = text_lines(old_html);
+    let new = text_lines(new_html);
+
+    // Strip common leading and trailing lines to keep the LCS table small
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+    if (old_mid.len() + 1).saturating_mul(new_mid.len() + 1) > MAX_LCS_CELLS {
+        return vec![DiffHunk {
+            old_start: prefix,
+            removed: old_mid.to_vec(),
+            new_start: prefix,
+            added: new_mid.to_vec(),
+        }];
+    }
+
+    // lcs[i][j]: length of the longest common subsequence of old_mid[i..] and new_mid[j..]
+    let mut lcs = vec![vec![0usize; new_mid.len() + 1]; old_mid.len() + 1];
+    for i in (0..old_mid.len()).rev() {
+        for j in (0..new_mid.len()).rev() {
+            lcs[i][j] = if old_mid[i] == new_mid[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut hunks = Vec::new();
+    let mut hunk: Option<DiffHunk> = None;
+    let (mut i, mut j) = (0, 0);
+    while i < old_mid.len() || j < new_mid.len() {
+        if i < old_mid.len() && j < new_mid.len() && old_mid[i] == new_mid[j] {
+            hunks.extend(hunk.take());
+            i += 1;
+            j += 1;
+            continue;
+        }
+        let current = hunk.get_or_insert_with(|| DiffHunk {
+            old_start: prefix + i,
+            removed: vec![],
+            new_start: prefix + j,
+            added: vec![],
+        });
+        if j == new_mid.len() || (i < old_mid.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            current.removed.push(old_mid[i].clone());
+            i += 1;
+        } else {
+            current.added.push(new_mid[j].clone());
+            j += 1;
+        }
+    }
+    hunks.extend(hunk);
+    hunks
+}
+
+/// Renders hunks as a compact "변경 전/후" snippet of at most `max_lines` lines, ending with
+/// the number of lines left out, e.g. for an update notice.
+pub fn render_diff(hunks: &[DiffHunk], max_lines: usize) -> String {
+    let lines = hunks
+        .iter()
+        .flat_map(|hunk| {
+            let removed = hunk.removed.iter().map(|line| format!("변경 전: {line}"));
+            let added = hunk.added.iter().map(|line| format!("변경 후: {line}"));
+            removed.chain(added)
+        })
+        .collect::<Vec<_>>();
+    if lines.len() <= max_lines {
+        return lines.join("\n");
+    }
+    let mut snippet = lines[..max_lines].to_vec();
+    snippet.push(format!("(외 {}줄)", lines.len() - max_lines));
+    snippet.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn long_notice(deadline: &str) -> String {
+        let body = (1..=40)
+            .map(|i| format!("<p>{i}. 제출 서류 및 유의 사항 안내</p>"))
+            .collect::<String>();
+        format!(
+            "<h3>2025학년도 1학기 국가장학금 신청 안내</h3><p>신청 기간: {deadline}</p>{body}<p>문의: 학생지원팀</p>"
+        )
+    }
+
+    #[test]
+    fn test_only_changed_lines_appear() {
+        let hunks = text_diff(
+            &long_notice("2025. 3. 10.(월) ~ 3. 14.(금)"),
+            &long_notice("2025. 3. 10.(월) ~ 3. 21.(금)"),
+        );
+
+        assert_eq!(
+            hunks,
+            [DiffHunk {
+                old_start: 1,
+                removed: vec!["신청 기간: 2025. 3. 10.(월) ~ 3. 14.(금)".to_string()],
+                new_start: 1,
+                added: vec!["신청 기간: 2025. 3. 10.(월) ~ 3. 21.(금)".to_string()],
+            }]
+        );
+        assert_eq!(
+            render_diff(&hunks, 5),
+            "변경 전: 신청 기간: 2025. 3. 10.(월) ~ 3. 14.(금)\n변경 후: 신청 기간: 2025. 3. 10.(월) ~ 3. 21.(금)"
+        );
+    }
+
+    #[test]
+    fn test_markup_only_edit_has_no_hunks() {
+        assert!(
+            text_diff(
+                "<p>공지<br>내용</p>",
+                "<div><b>공지</b></div><div>내용</div>"
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_added_and_removed_lines() {
+        let hunks = text_diff("<p>가</p><p>나</p><p>다</p>", "<p>가</p><p>다</p><p>라</p>");
+        assert_eq!(
+            hunks,
+            [
+                DiffHunk {
+                    old_start: 1,
+                    removed: vec!["나".to_string()],
+                    new_start: 1,
+                    added: vec![],
+                },
+                DiffHunk {
+                    old_start: 3,
+                    removed: vec![],
+                    new_start: 2,
+                    added: vec!["라".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_long_rewrite_is_one_hunk() {
+        // A long body that differs on every line is one changed block
+        let lines = |prefix: &str| {
+            (0..1500)
+                .map(|i| format!("<p>{prefix} {i}</p>"))
+                .collect::<String>()
+        };
+        let hunks = text_diff(
+            &format!("<p>머리말</p>{}", lines("변경 전")),
+            &format!("<p>머리말</p>{}", lines("변경 후")),
+        );
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(hunks[0].old_start, 1);
+        assert_eq!(hunks[0].new_start, 1);
+        assert_eq!(hunks[0].removed.len(), 1500);
+        assert_eq!(hunks[0].added.len(), 1500);
+    }
+
+    #[test]
+    fn test_render_diff_is_capped() {
+        let hunks = text_diff("", "<p>1</p><p>2</p><p>3</p>");
+        assert_eq!(render_diff(&hunks, 2), "변경 후: 1\n변경 후: 2\n(외 1줄)");
+    }
+}
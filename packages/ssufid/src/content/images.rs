@@ -0,0 +1,257 @@
+use std::path::PathBuf;
+
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use lol_html::{RewriteStrSettings, element, rewrite_str};
+use sha2::{Digest, Sha256};
+
+/// An image decoded from a `data:image/...;base64,` URI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataImage {
+    pub mime_type: String,
+    pub data: Vec<u8>,
+}
+
+impl DataImage {
+    /// Decodes `uri`, returning `None` for anything other than a base64 encoded image.
+    pub fn from_data_uri(uri: &str) -> Option<Self> {
+        let (meta, payload) = uri.trim().strip_prefix("data:")?.split_once(',')?;
+        let mime_type = meta.strip_suffix(";base64")?;
+        if !mime_type.starts_with("image/") {
+            return None;
+        }
+        let payload = payload
+            .chars()
+            .filter(|c| !c.is_ascii_whitespace())
+            .collect::<String>();
+        let data = STANDARD.decode(payload).ok()?;
+        Some(Self {
+            mime_type: mime_type.to_string(),
+            data,
+        })
+    }
+
+    /// A content-addressed file name, so the same image is only stored once.
+    pub fn file_name(&self) -> String {
+        let digest = Sha256::digest(&self.data);
+        let hash = digest[..8]
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect::<String>();
+        let extension = mime_guess::get_mime_extensions_str(&self.mime_type)
+            .and_then(|exts| exts.first())
+            .copied()
+            .unwrap_or("bin");
+        format!("{hash}.{extension}")
+    }
+}
+
+/// Destination for images extracted from post content.
+pub trait ImageSink {
+    /// Stores `image` and returns the URL that should replace its data URI.
+    fn store(&mut self, image: &DataImage) -> std::io::Result<String>;
+}
+
+/// Writes images into `dir` and links them as `<url_prefix>/<file name>`.
+pub struct DirectoryImageSink {
+    dir: PathBuf,
+    url_prefix: String,
+}
+
+impl DirectoryImageSink {
+    pub fn new(dir: impl Into<PathBuf>, url_prefix: impl Into<String>) -> Self {
+        Self {
+            dir: dir.into(),
+            url_prefix: url_prefix.into(),
+        }
+    }
+}
+
+impl ImageSink for DirectoryImageSink {
+    fn store(&mut self, image: &DataImage) -> std::io::Result<String> {
+        let file_name = image.file_name();
+        let path = self.dir.join(&file_name);
+        if !path.exists() {
+            std::fs::create_dir_all(&self.dir)?;
+            std::fs::write(&path, &image.data)?;
+        }
+        Ok(format!(
+            "{}/{file_name}",
+            self.url_prefix.trim_end_matches('/')
+        ))
+    }
+}
+
+/// Attributes lazy-loading scripts read an image's real URL from, in order of preference.
+const LAZY_SOURCE_ATTRIBUTES: &[&str] = &["data-src", "data-original", "data-lazy-src"];
+
+/// File names of the stand-ins boards show until a lazy-loading script swaps in the real image.
+const PLACEHOLDER_NAMES: &[&str] = &[
+    "loading",
+    "blank",
+    "spacer",
+    "placeholder",
+    "lazy",
+    "transparent",
+    "1x1",
+];
+
+/// Whether `src` is a lazy-loading stand-in: empty, an inline GIF (the usual 1x1 pixel) or an
+/// image named like `loading.gif` or `blank.png`.
+pub fn is_placeholder_src(src: &str) -> bool {
+    let src = src.trim();
+    if src.is_empty() || src.starts_with("data:image/gif") {
+        return true;
+    }
+    let path = src.split(['?', '#']).next().unwrap_or_default();
+    let file_name = path.rsplit('/').next().unwrap_or_default().to_lowercase();
+    file_name
+        .rsplit_once('.')
+        .is_some_and(|(stem, _)| PLACEHOLDER_NAMES.iter().any(|name| stem.contains(name)))
+}
+
+/// Puts the real URL of a lazily loaded `<img>` in its `src` when `src` is a placeholder,
+/// leaving every other attribute, such as `alt`, as it is.
+fn resolve_lazy_source(
+    el: &mut lol_html::html_content::Element<'_, '_>,
+) -> lol_html::HandlerResult {
+    let src = el.get_attribute("src").unwrap_or_default();
+    if !is_placeholder_src(&src) {
+        return Ok(());
+    }
+    let lazy = LAZY_SOURCE_ATTRIBUTES.iter().find_map(|name| {
+        el.get_attribute(name)
+            .filter(|value| !value.trim().is_empty())
+            .map(|value| (*name, value))
+    });
+    if let Some((name, value)) = lazy {
+        el.set_attribute("src", value.trim())?;
+        el.remove_attribute(name);
+    }
+    Ok(())
+}
+
+/// Replaces the placeholder `src` of lazily loaded images in `content` with the URL in their
+/// `data-src` (or `data-original`, `data-lazy-src`), keeping their `alt` text.
+pub fn resolve_lazy_images(content: &str) -> Result<String, lol_html::errors::RewritingError> {
+    rewrite_str(
+        content,
+        RewriteStrSettings {
+            element_content_handlers: vec![element!("img", resolve_lazy_source)],
+            ..RewriteStrSettings::new()
+        },
+    )
+}
+
+/// Replaces inline base64 `<img>` sources in `content` with URLs returned by `sink`.
+///
+/// Lazily loaded images are [resolved](resolve_lazy_images) first, so that placeholder pixels
+/// are not stored. Data URIs that are not base64 images are left untouched.
+pub fn externalize_data_images(
+    content: &str,
+    sink: &mut impl ImageSink,
+) -> Result<String, lol_html::errors::RewritingError> {
+    rewrite_str(
+        content,
+        RewriteStrSettings {
+            element_content_handlers: vec![element!("img", |el| {
+                resolve_lazy_source(el)?;
+                if !el
+                    .get_attribute("src")
+                    .is_some_and(|src| src.starts_with("data:"))
+                {
+                    return Ok(());
+                }
+                let Some(image) = el
+                    .get_attribute("src")
+                    .and_then(|src| DataImage::from_data_uri(&src))
+                else {
+                    return Ok(());
+                };
+                let url = sink.store(&image)?;
+                el.set_attribute("src", &url)?;
+                Ok(())
+            })],
+            ..RewriteStrSettings::new()
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct MemorySink(Vec<DataImage>);
+
+    impl ImageSink for MemorySink {
+        fn store(&mut self, image: &DataImage) -> std::io::Result<String> {
+            self.0.push(image.clone());
+            Ok(format!("https://cdn.example.com/{}", image.file_name()))
+        }
+    }
+
+    // 1x1 transparent PNG
+    const PIXEL: &str = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAQAAAC1HAwCAAAAC0lEQVR42mNkYAAAAAYAAjCB0C8AAAAASUVORK5CYII=";
+
+    #[test]
+    fn test_externalize_data_image() {
+        let content = format!(
+            r#"<p>안내</p><img alt="포스터" src="data:image/png;base64,{PIXEL}"><img src="/a.png">"#
+        );
+        let mut sink = MemorySink::default();
+        let result = externalize_data_images(&content, &mut sink).unwrap();
+
+        assert_eq!(sink.0.len(), 1);
+        assert_eq!(sink.0[0].mime_type, "image/png");
+        let url = format!("https://cdn.example.com/{}", sink.0[0].file_name());
+        assert_eq!(
+            result,
+            format!(r#"<p>안내</p><img alt="포스터" src="{url}"><img src="/a.png">"#)
+        );
+        assert!(url.ends_with(".png"));
+    }
+
+    #[test]
+    fn test_lazy_placeholder_is_replaced_keeping_alt() {
+        let content = r#"<p><img alt="2025 축제 포스터" src="/images/common/loading.gif" data-src="/upload/poster.jpg?w=800&amp;h=600"></p><img alt="로고" src="/logo.png" data-src="/logo@2x.png">"#;
+        assert_eq!(
+            resolve_lazy_images(content).unwrap(),
+            r#"<p><img alt="2025 축제 포스터" src="/upload/poster.jpg?w=800&amp;h=600"></p><img alt="로고" src="/logo.png" data-src="/logo@2x.png">"#
+        );
+
+        // A 1x1 transparent GIF placeholder is not saved as a file
+        let content = r#"<img alt="안내도" src="data:image/gif;base64,R0lGODlhAQABAAAAACw=" data-original="/map.png">"#;
+        let mut sink = MemorySink::default();
+        assert_eq!(
+            externalize_data_images(content, &mut sink).unwrap(),
+            r#"<img alt="안내도" src="/map.png">"#
+        );
+        assert!(sink.0.is_empty());
+    }
+
+    #[test]
+    fn test_non_image_data_uri_is_kept() {
+        let content = r#"<img src="data:text/plain,hello">"#;
+        let mut sink = MemorySink::default();
+        assert_eq!(
+            externalize_data_images(content, &mut sink).unwrap(),
+            content
+        );
+        assert!(sink.0.is_empty());
+    }
+
+    #[test]
+    fn test_directory_sink_writes_file() {
+        let dir = std::env::temp_dir().join(format!("ssufid-images-{}", std::process::id()));
+        let mut sink = DirectoryImageSink::new(&dir, "images/");
+        let image = DataImage::from_data_uri(&format!("data:image/png;base64,{PIXEL}")).unwrap();
+
+        let url = sink.store(&image).unwrap();
+        assert_eq!(url, format!("images/{}", image.file_name()));
+        assert_eq!(
+            std::fs::read(dir.join(image.file_name())).unwrap(),
+            image.data
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
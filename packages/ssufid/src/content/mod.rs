@@ -0,0 +1,31 @@
+//! Opt-in transforms applied to the HTML stored in [`SsufidPost::content`](crate::core::SsufidPost).
+
+mod diff;
+mod images;
+mod sanitize;
+
+pub use diff::{DiffHunk, render_diff, text_diff, text_lines};
+pub use images::{
+    DataImage, DirectoryImageSink, ImageSink, externalize_data_images, is_placeholder_src,
+    resolve_lazy_images,
+};
+pub use sanitize::{SanitizePolicy, sanitize};
+
+/// Collects the `src` of every `<img>` in an HTML fragment, as written.
+pub fn image_sources(html: &str) -> Vec<String> {
+    let fragment = scraper::Html::parse_fragment(html);
+    let selector = scraper::Selector::parse("img[src]").unwrap();
+    fragment
+        .select(&selector)
+        .filter_map(|img| img.value().attr("src"))
+        .map(|src| src.trim().to_string())
+        .filter(|src| !src.is_empty())
+        .collect()
+}
+
+/// Extracts the text of an HTML fragment, with whitespace collapsed.
+pub fn plain_text(html: &str) -> String {
+    let fragment = scraper::Html::parse_fragment(html);
+    let text = fragment.root_element().text().collect::<Vec<_>>().join(" ");
+    crate::text::clean_text(&text)
+}
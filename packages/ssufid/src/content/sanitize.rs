@@ -0,0 +1,351 @@
+use lol_html::{RewriteStrSettings, doc_comments, element, html_content::Element, rewrite_str};
+
+/// What [`sanitize`] keeps of a post's HTML. Everything else is unwrapped, keeping its text,
+/// except for [`REMOVED_TAGS`](Self::REMOVED_TAGS), which go with their content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SanitizePolicy<'a> {
+    pub tags: &'a [&'a str],
+    /// Attributes allowed on every tag in `tags`.
+    pub global_attributes: &'a [&'a str],
+    /// Attributes allowed on one tag only.
+    pub tag_attributes: &'a [(&'a str, &'a [&'a str])],
+    /// CSS properties kept in `style` attributes.
+    pub style_properties: &'a [&'a str],
+    /// Hosts whose `<iframe>` embeds are kept; other iframes are removed.
+    pub embed_hosts: &'a [&'a str],
+}
+
+impl SanitizePolicy<'static> {
+    /// Elements removed along with their content whatever the policy.
+    pub const REMOVED_TAGS: &'static [&'static str] = &[
+        "script", "style", "noscript", "template", "object", "embed", "applet", "iframe", "frame",
+        "frameset", "head", "title", "meta", "link", "base", "svg", "math", "input", "button",
+        "select", "textarea",
+    ];
+
+    /// An allowlist for notice boards, which lay out their notices with tables, `<font>` and
+    /// inline colors and sizes, and embed videos: stripping those would garble most notices.
+    pub const DEFAULT: Self = Self {
+        tags: &[
+            "a",
+            "abbr",
+            "b",
+            "big",
+            "blockquote",
+            "br",
+            "caption",
+            "center",
+            "cite",
+            "code",
+            "col",
+            "colgroup",
+            "dd",
+            "del",
+            "div",
+            "dl",
+            "dt",
+            "em",
+            "figcaption",
+            "figure",
+            "font",
+            "h1",
+            "h2",
+            "h3",
+            "h4",
+            "h5",
+            "h6",
+            "hr",
+            "i",
+            "iframe",
+            "img",
+            "ins",
+            "li",
+            "mark",
+            "ol",
+            "p",
+            "pre",
+            "q",
+            "s",
+            "small",
+            "span",
+            "strike",
+            "strong",
+            "sub",
+            "sup",
+            "table",
+            "tbody",
+            "td",
+            "tfoot",
+            "th",
+            "thead",
+            "tr",
+            "u",
+            "ul",
+        ],
+        global_attributes: &["style", "align", "title", "lang", "dir"],
+        tag_attributes: &[
+            ("a", &["href", "target", "name"]),
+            ("img", &["src", "alt", "width", "height", "border"]),
+            ("font", &["color", "size", "face"]),
+            (
+                "table",
+                &[
+                    "border",
+                    "cellpadding",
+                    "cellspacing",
+                    "width",
+                    "height",
+                    "bgcolor",
+                    "summary",
+                ],
+            ),
+            ("tr", &["bgcolor", "height", "valign"]),
+            (
+                "td",
+                &[
+                    "colspan", "rowspan", "width", "height", "bgcolor", "valign", "nowrap",
+                ],
+            ),
+            (
+                "th",
+                &[
+                    "colspan", "rowspan", "width", "height", "bgcolor", "valign", "scope",
+                ],
+            ),
+            ("col", &["span", "width"]),
+            ("colgroup", &["span", "width"]),
+            ("ol", &["start", "type"]),
+            ("ul", &["type"]),
+            ("li", &["value"]),
+            (
+                "iframe",
+                &[
+                    "src",
+                    "width",
+                    "height",
+                    "frameborder",
+                    "allow",
+                    "allowfullscreen",
+                ],
+            ),
+        ],
+        style_properties: &[
+            "color",
+            "background-color",
+            "font-size",
+            "font-weight",
+            "font-style",
+            "font-family",
+            "text-align",
+            "text-decoration",
+            "vertical-align",
+            "line-height",
+            "width",
+            "height",
+            "border",
+            "border-collapse",
+            "border-color",
+            "border-style",
+            "border-width",
+            "padding",
+            "margin",
+        ],
+        embed_hosts: &[
+            "www.youtube.com",
+            "youtube.com",
+            "www.youtube-nocookie.com",
+            "player.vimeo.com",
+        ],
+    };
+}
+
+impl Default for SanitizePolicy<'static> {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl SanitizePolicy<'_> {
+    fn allows_attribute(&self, tag: &str, name: &str) -> bool {
+        self.global_attributes.contains(&name)
+            || self
+                .tag_attributes
+                .iter()
+                .any(|(t, names)| *t == tag && names.contains(&name))
+    }
+
+    fn allows_embed(&self, src: &str) -> bool {
+        let src = src.trim();
+        let src = match src.strip_prefix("//") {
+            Some(rest) => format!("https://{rest}"),
+            None => src.to_string(),
+        };
+        url::Url::parse(&src).is_ok_and(|url| {
+            matches!(url.scheme(), "http" | "https")
+                && url
+                    .host_str()
+                    .is_some_and(|host| self.embed_hosts.contains(&host))
+        })
+    }
+
+    /// The declarations of `style` whose property is allowed, or `None` if none are.
+    fn filter_style(&self, style: &str) -> Option<String> {
+        let declarations = style
+            .split(';')
+            .filter_map(|declaration| {
+                let (property, value) = declaration.split_once(':')?;
+                let property = property.trim().to_ascii_lowercase();
+                let value = value.trim();
+                let lower = value.to_ascii_lowercase();
+                (self.style_properties.contains(&property.as_str())
+                    && !value.is_empty()
+                    && !lower.contains("url(")
+                    && !lower.contains("expression"))
+                .then(|| format!("{property}: {value}"))
+            })
+            .collect::<Vec<_>>();
+        (!declarations.is_empty()).then(|| declarations.join("; "))
+    }
+
+    fn sanitize_element(&self, el: &mut Element<'_, '_>) -> lol_html::HandlerResult {
+        let tag = el.tag_name();
+        let removed = SanitizePolicy::REMOVED_TAGS.contains(&tag.as_str());
+        if tag == "iframe" && self.tags.contains(&"iframe") {
+            if !el
+                .get_attribute("src")
+                .is_some_and(|src| self.allows_embed(&src))
+            {
+                el.remove();
+                return Ok(());
+            }
+        } else if removed {
+            el.remove();
+            return Ok(());
+        } else if !self.tags.contains(&tag.as_str()) {
+            el.remove_and_keep_content();
+        }
+
+        let attributes = el
+            .attributes()
+            .iter()
+            .map(|attribute| (attribute.name(), attribute.value()))
+            .collect::<Vec<_>>();
+        for (name, value) in attributes {
+            let keep = self.allows_attribute(&tag, &name)
+                && match name.as_str() {
+                    "href" | "src" => is_safe_url(&value),
+                    "style" => match self.filter_style(&value) {
+                        Some(style) => {
+                            el.set_attribute("style", &style)?;
+                            true
+                        }
+                        None => false,
+                    },
+                    _ => true,
+                };
+            if !keep {
+                el.remove_attribute(&name);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether a link or image URL is safe to keep: relative, or `http(s)`, `mailto`, `tel`, or
+/// a `data:image/` URI. Attribute values reach the rewriter with their character references
+/// undecoded, so a reference before the path, such as `java&#x09;script:`, is rejected.
+fn is_safe_url(url: &str) -> bool {
+    let url = url
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace() && !c.is_ascii_control())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    let prefix = url.split(['/', '?', '#']).next().unwrap_or_default();
+    if prefix.contains('&') {
+        return false;
+    }
+    match prefix.split_once(':') {
+        Some((scheme, _)) => {
+            matches!(scheme, "http" | "https" | "mailto" | "tel") || url.starts_with("data:image/")
+        }
+        None => true,
+    }
+}
+
+/// Strips scripts, event handlers, comments and unsafe URLs from an HTML fragment, keeping
+/// only what `policy` allows. Elements outside the allowlist are unwrapped rather than removed,
+/// so their text survives.
+pub fn sanitize(
+    html: &str,
+    policy: &SanitizePolicy<'_>,
+) -> Result<String, lol_html::errors::RewritingError> {
+    rewrite_str(
+        html,
+        RewriteStrSettings {
+            element_content_handlers: vec![element!("*", |el| policy.sanitize_element(el))],
+            document_content_handlers: vec![doc_comments!(|comment| {
+                comment.remove();
+                Ok(())
+            })],
+            ..RewriteStrSettings::new()
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clean(html: &str) -> String {
+        sanitize(html, &SanitizePolicy::default()).unwrap()
+    }
+
+    #[test]
+    fn test_styled_table_is_preserved() {
+        let table = r##"<table border="1" cellpadding="3" style="border-collapse: collapse; width: 100%"><tbody><tr><td colspan="2" bgcolor="#eeeeee" style="text-align: center"><font color="red" size="4"><b>신청 기간</b></font></td></tr><tr><td><span style="color: rgb(0, 0, 255); font-size: 14px">2025. 3. 4.(화)</span></td><td>~ 3. 7.(금)</td></tr></tbody></table>"##;
+        assert_eq!(clean(table), table);
+    }
+
+    #[test]
+    fn test_youtube_embed_is_kept_and_others_removed() {
+        let youtube = r#"<iframe src="https://www.youtube.com/embed/abc123" width="560" height="315" frameborder="0" allowfullscreen></iframe>"#;
+        assert_eq!(clean(youtube), youtube);
+        let relative = r#"<iframe src="//player.vimeo.com/video/42"></iframe>"#;
+        assert_eq!(clean(relative), relative);
+
+        assert_eq!(
+            clean(r#"<p>지도</p><iframe src="https://evil.example.com/embed"></iframe>"#),
+            "<p>지도</p>"
+        );
+        assert_eq!(clean("<iframe srcdoc=\"<script></script>\"></iframe>"), "");
+    }
+
+    #[test]
+    fn test_scripts_and_handlers_are_removed() {
+        assert_eq!(
+            clean(
+                r#"<p onclick="steal()">안내<script>alert(1)</script></p><!-- 주석 --><style>p { display: none }</style>"#
+            ),
+            "<p>안내</p>"
+        );
+        assert_eq!(
+            clean(r#"<a href="javascript:alert(1)" onmouseover="x()">링크</a>"#),
+            "<a>링크</a>"
+        );
+        assert_eq!(
+            clean(r#"<a href=" JaVa&#x09;script:alert(1)">링크</a>"#),
+            "<a>링크</a>"
+        );
+        // Disallowed tags keep only their content
+        assert_eq!(
+            clean(r#"<form action="/x"><label>이름</label></form>"#),
+            "이름"
+        );
+        assert_eq!(
+            clean(
+                r#"<span style="position: fixed; color: red; background: url(x.png)">강조</span>"#
+            ),
+            r#"<span style="color: red">강조</span>"#
+        );
+    }
+}
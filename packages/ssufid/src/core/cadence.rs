@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+/// How often a board usually gets new posts, see [`SsufidPlugin::CADENCE`](super::SsufidPlugin::CADENCE).
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Cadence {
+    /// Several posts a week or more, e.g. a job board.
+    Daily,
+    /// About one post a week.
+    #[default]
+    Weekly,
+    /// A few posts a semester.
+    Sporadic,
+}
+
+impl Cadence {
+    /// The default time between two crawls of a board with this cadence.
+    pub fn crawl_interval(self) -> Duration {
+        match self {
+            Cadence::Daily => Duration::from_secs(15 * 60),
+            Cadence::Weekly => Duration::from_secs(2 * 60 * 60),
+            Cadence::Sporadic => Duration::from_secs(12 * 60 * 60),
+        }
+    }
+
+    /// How long a board with this cadence may go without a new post before it looks broken.
+    pub fn quiet_threshold(self) -> Duration {
+        const DAY: u64 = 24 * 60 * 60;
+        match self {
+            Cadence::Daily => Duration::from_secs(7 * DAY),
+            Cadence::Weekly => Duration::from_secs(42 * DAY),
+            Cadence::Sporadic => Duration::from_secs(180 * DAY),
+        }
+    }
+
+    /// Whether a newest post created at `newest`, or no post at all, is unusually old at `now`.
+    pub fn is_unusually_quiet(self, newest: Option<OffsetDateTime>, now: OffsetDateTime) -> bool {
+        newest.is_none_or(|newest| now - newest > self.quiet_threshold())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    #[test]
+    fn test_crawl_interval_follows_cadence() {
+        assert_eq!(Cadence::Daily.crawl_interval(), Duration::from_secs(900));
+        assert_eq!(
+            Cadence::Sporadic.crawl_interval(),
+            Duration::from_secs(12 * 60 * 60)
+        );
+        assert!(Cadence::Daily.crawl_interval() < Cadence::Weekly.crawl_interval());
+        assert!(Cadence::Weekly.crawl_interval() < Cadence::Sporadic.crawl_interval());
+    }
+
+    #[test]
+    fn test_quiet_threshold_follows_cadence() {
+        let now = datetime!(2025-03-10 12:00 +9);
+        let week_ago = Some(datetime!(2025-03-01 12:00 +9));
+
+        // A week without posts is unusual for a daily board but normal for a sparse one
+        assert!(Cadence::Daily.is_unusually_quiet(week_ago, now));
+        assert!(!Cadence::Weekly.is_unusually_quiet(week_ago, now));
+        assert!(!Cadence::Sporadic.is_unusually_quiet(week_ago, now));
+
+        let last_year = Some(datetime!(2024-03-01 12:00 +9));
+        assert!(Cadence::Sporadic.is_unusually_quiet(last_year, now));
+        assert!(Cadence::Sporadic.is_unusually_quiet(None, now));
+    }
+}
@@ -71,6 +71,9 @@ pub struct SsufidCalendarSiteData {
     pub(crate) source: String,
     pub(crate) description: String,
     pub(crate) items: Vec<SsufidCalendar>,
+    /// The checksum of the calendar, set by [`seal`](Self::seal).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) integrity: Option<crate::output::Integrity>,
 }
 
 #[cfg(feature = "ics")]
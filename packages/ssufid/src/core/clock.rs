@@ -0,0 +1,29 @@
+use std::fmt;
+
+use time::OffsetDateTime;
+
+/// The source of the current time for [`SsufidCore`](super::SsufidCore), so that time-dependent
+/// logic such as stamping `updated_at` can be tested at a fixed instant.
+pub trait Clock: fmt::Debug + Send + Sync {
+    fn now(&self) -> OffsetDateTime;
+}
+
+/// The system clock, used unless another clock is [configured](super::SsufidCore::with_clock).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> OffsetDateTime {
+        OffsetDateTime::now_utc()
+    }
+}
+
+/// A clock that always reads the same instant, for tests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedClock(pub OffsetDateTime);
+
+impl Clock for FixedClock {
+    fn now(&self) -> OffsetDateTime {
+        self.0
+    }
+}
@@ -0,0 +1,65 @@
+use std::{fmt, iter::Sum, ops::Add};
+
+/// How many requests a crawl will issue, estimated by
+/// [`SsufidPostPlugin::estimate_cost`](super::SsufidPostPlugin::estimate_cost) without sending
+/// any, so that operators can size a backfill before running it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CostEstimate {
+    pub list_requests: u32,
+    pub detail_requests: u32,
+    /// A rough total of the bytes downloaded, from typical page sizes.
+    pub approx_bytes: u64,
+}
+
+impl CostEstimate {
+    /// The typical size of a list page, in bytes.
+    pub const LIST_PAGE_BYTES: u64 = 64 * 1024;
+    /// The typical size of a post's detail page, in bytes.
+    pub const DETAIL_PAGE_BYTES: u64 = 48 * 1024;
+
+    /// An estimate of `list_requests` list pages and `detail_requests` detail pages of typical
+    /// size.
+    pub fn new(list_requests: u32, detail_requests: u32) -> Self {
+        Self {
+            list_requests,
+            detail_requests,
+            approx_bytes: u64::from(list_requests) * Self::LIST_PAGE_BYTES
+                + u64::from(detail_requests) * Self::DETAIL_PAGE_BYTES,
+        }
+    }
+
+    pub fn total_requests(&self) -> u32 {
+        self.list_requests + self.detail_requests
+    }
+}
+
+impl Add for CostEstimate {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            list_requests: self.list_requests + other.list_requests,
+            detail_requests: self.detail_requests + other.detail_requests,
+            approx_bytes: self.approx_bytes + other.approx_bytes,
+        }
+    }
+}
+
+impl Sum for CostEstimate {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
+
+impl fmt::Display for CostEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} requests ({} list, {} detail), ~{} KiB",
+            self.total_requests(),
+            self.list_requests,
+            self.detail_requests,
+            self.approx_bytes.div_ceil(1024)
+        )
+    }
+}
@@ -0,0 +1,118 @@
+use std::{collections::HashMap, fmt};
+
+/// A username and password for a board behind a login form, see
+/// [`SsufidPostPlugin::authenticate`](super::SsufidPostPlugin::authenticate).
+///
+/// Neither part is ever printed: `Debug` only shows that credentials are present, so they can
+/// not leak into logs through a `?credentials` field.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Credentials {
+    username: String,
+    password: String,
+}
+
+impl Credentials {
+    pub fn new(username: impl Into<String>, password: impl Into<String>) -> Self {
+        Self {
+            username: username.into(),
+            password: password.into(),
+        }
+    }
+
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    pub fn password(&self) -> &str {
+        &self.password
+    }
+
+    /// Reads `<prefix>_USERNAME` and `<prefix>_PASSWORD`, with the prefix given by
+    /// [`env_prefix`](Self::env_prefix). Both must be set.
+    pub fn from_env(identifier: &str) -> Option<Self> {
+        let prefix = Self::env_prefix(identifier);
+        let username = std::env::var(format!("{prefix}_USERNAME")).ok()?;
+        let password = std::env::var(format!("{prefix}_PASSWORD")).ok()?;
+        Some(Self::new(username, password))
+    }
+
+    /// The environment variable prefix of the plugin `identifier`: `SSUFID_` followed by the
+    /// identifier in upper case with every other character replaced by `_`, e.g.
+    /// `SSUFID_INTRANET_EXAMPLE_COM` for `intranet.example.com`.
+    pub fn env_prefix(identifier: &str) -> String {
+        let identifier = identifier
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_uppercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect::<String>();
+        format!("SSUFID_{identifier}")
+    }
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Credentials(<redacted>)")
+    }
+}
+
+/// Credentials by plugin identifier, consulted by [`SsufidCore::run`](super::SsufidCore::run).
+#[derive(Debug, Clone, Default)]
+pub struct CredentialStore {
+    entries: HashMap<String, Credentials>,
+    from_env: bool,
+}
+
+impl CredentialStore {
+    /// A store holding only the credentials [inserted](Self::insert) into it.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A store that falls back to [`Credentials::from_env`] for plugins without inserted
+    /// credentials.
+    pub fn from_env() -> Self {
+        Self {
+            entries: HashMap::new(),
+            from_env: true,
+        }
+    }
+
+    pub fn insert(mut self, identifier: impl Into<String>, credentials: Credentials) -> Self {
+        self.entries.insert(identifier.into(), credentials);
+        self
+    }
+
+    pub fn get(&self, identifier: &str) -> Option<Credentials> {
+        self.entries.get(identifier).cloned().or_else(|| {
+            self.from_env
+                .then(|| Credentials::from_env(identifier))
+                .flatten()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_credentials_are_redacted_and_looked_up_by_identifier() {
+        let credentials = Credentials::new("20250001", "s3cret");
+        let store = CredentialStore::new().insert("intranet.example.com", credentials.clone());
+        let debug = format!("{store:?}");
+        assert!(!debug.contains("20250001"));
+        assert!(!debug.contains("s3cret"));
+
+        assert_eq!(store.get("intranet.example.com"), Some(credentials));
+        assert_eq!(store.get("other.example.com"), None);
+        assert_eq!(
+            Credentials::env_prefix("intranet.example.com"),
+            "SSUFID_INTRANET_EXAMPLE_COM"
+        );
+    }
+}
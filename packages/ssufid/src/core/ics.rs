@@ -121,6 +121,7 @@ mod tests {
                     url: None,
                 },
             ],
+            integrity: None,
         };
 
         let ics = to_ics(&site);
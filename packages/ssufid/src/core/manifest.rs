@@ -0,0 +1,211 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use time::{OffsetDateTime, macros::format_description};
+
+use crate::error::Error;
+
+/// The outcome of one plugin in a [`RunManifest`].
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct PluginRunResult {
+    pub identifier: String,
+    /// Number of items written, or `None` when the plugin failed or writes no post count, e.g.
+    /// a calendar.
+    pub items: Option<usize>,
+    pub error: Option<String>,
+}
+
+impl PluginRunResult {
+    pub fn success(identifier: impl Into<String>, items: Option<usize>) -> Self {
+        Self {
+            identifier: identifier.into(),
+            items,
+            error: None,
+        }
+    }
+
+    pub fn failure(identifier: impl Into<String>, error: impl Into<String>) -> Self {
+        Self {
+            identifier: identifier.into(),
+            items: None,
+            error: Some(error.into()),
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// What happened in one run over every selected plugin, persisted in the state directory by
+/// [`save`](Self::save) as `runs/<run_id>.json`.
+///
+/// Run ids sort in the order the runs started, and `plugin_results` are sorted by identifier,
+/// so that two manifests of the same plugins compare line by line.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct RunManifest {
+    pub run_id: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub started_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub finished_at: OffsetDateTime,
+    pub plugin_results: Vec<PluginRunResult>,
+}
+
+impl RunManifest {
+    /// The directory of the state directory holding one manifest per run.
+    pub const DIR: &'static str = "runs";
+
+    pub fn new(
+        started_at: OffsetDateTime,
+        finished_at: OffsetDateTime,
+        mut plugin_results: Vec<PluginRunResult>,
+    ) -> Self {
+        plugin_results.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+        Self {
+            run_id: Self::run_id_of(started_at),
+            started_at,
+            finished_at,
+            plugin_results,
+        }
+    }
+
+    /// The id of a run started at `started_at`, e.g. `20250310T090000Z`.
+    pub fn run_id_of(started_at: OffsetDateTime) -> String {
+        started_at
+            .to_offset(time::UtcOffset::UTC)
+            .format(format_description!(
+                "[year][month][day]T[hour][minute][second]Z"
+            ))
+            .expect("a UTC date-time always formats")
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &PluginRunResult> {
+        self.plugin_results
+            .iter()
+            .filter(|result| !result.is_success())
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.failures().next().is_none()
+    }
+
+    /// Writes the manifest to `runs/<run_id>.json` in `state_dir` through a temporary file, so
+    /// that an interrupted write never leaves a partial manifest behind.
+    pub async fn save(&self, state_dir: &Path) -> Result<PathBuf, Error> {
+        let dir = state_dir.join(Self::DIR);
+        tokio::fs::create_dir_all(&dir).await?;
+        let path = dir.join(format!("{}.json", self.run_id));
+        let tmp = dir.join(format!("{}.json.tmp", self.run_id));
+        tokio::fs::write(&tmp, serde_json::to_string_pretty(self)?).await?;
+        tokio::fs::rename(&tmp, &path).await?;
+        Ok(path)
+    }
+
+    pub async fn load(path: &Path) -> Result<Self, Error> {
+        let json = tokio::fs::read_to_string(path).await?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// Every readable manifest in `state_dir`, oldest first. A manifest that fails to parse,
+    /// e.g. one truncated by a full disk, is skipped with a warning.
+    pub async fn load_all(state_dir: &Path) -> Result<Vec<Self>, Error> {
+        let mut entries = match tokio::fs::read_dir(state_dir.join(Self::DIR)).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e.into()),
+        };
+        let mut paths = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        let mut manifests = Vec::with_capacity(paths.len());
+        for path in paths {
+            match Self::load(&path).await {
+                Ok(manifest) => manifests.push(manifest),
+                Err(e) => {
+                    tracing::warn!(path = %path.display(), "Ignoring unreadable run manifest: {e}")
+                }
+            }
+        }
+        Ok(manifests)
+    }
+
+    /// The most recent readable manifest in `state_dir`, see [`load_all`](Self::load_all).
+    pub async fn load_latest(state_dir: &Path) -> Result<Option<Self>, Error> {
+        Ok(Self::load_all(state_dir).await?.pop())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    fn manifest(started_at: OffsetDateTime) -> RunManifest {
+        RunManifest::new(
+            started_at,
+            started_at + time::Duration::minutes(3),
+            vec![
+                PluginRunResult::failure("stu.ssu.ac.kr", "HTTP 503"),
+                PluginRunResult::success("infocom.ssu.ac.kr", Some(12)),
+                PluginRunResult::success("calendar.ssu.ac.kr", None),
+            ],
+        )
+    }
+
+    #[tokio::test]
+    async fn test_manifest_round_trip() {
+        let dir = std::env::temp_dir().join(format!("ssufid-manifest-{}", std::process::id()));
+        let first = manifest(datetime!(2025-03-10 09:00 +9));
+        let second = manifest(datetime!(2025-03-10 11:00 +9));
+        second.save(&dir).await.unwrap();
+        let path = first.save(&dir).await.unwrap();
+
+        let loaded = RunManifest::load(&path).await.unwrap();
+        let all = RunManifest::load_all(&dir).await.unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(loaded, first);
+        assert_eq!(first.run_id, "20250310T000000Z");
+        assert_eq!(
+            first
+                .plugin_results
+                .iter()
+                .map(|result| result.identifier.as_str())
+                .collect::<Vec<_>>(),
+            ["calendar.ssu.ac.kr", "infocom.ssu.ac.kr", "stu.ssu.ac.kr"]
+        );
+        assert_eq!(all, [first.clone(), second]);
+        assert!(!first.is_success());
+        assert_eq!(first.failures().count(), 1);
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_truncated_manifest_is_ignored() {
+        let dir =
+            std::env::temp_dir().join(format!("ssufid-manifest-truncated-{}", std::process::id()));
+        let complete = manifest(datetime!(2025-03-10 09:00 +9));
+        complete.save(&dir).await.unwrap();
+        let truncated = manifest(datetime!(2025-03-10 11:00 +9));
+        let json = serde_json::to_string_pretty(&truncated).unwrap();
+        let path = dir
+            .join(RunManifest::DIR)
+            .join(format!("{}.json", truncated.run_id));
+        std::fs::write(&path, &json[..json.len() / 2]).unwrap();
+
+        let latest = RunManifest::load_latest(&dir).await.unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        // Falls back to the previous intact manifest instead of the truncated latest one
+        assert_eq!(latest, Some(complete));
+        assert!(logs_contain("Ignoring unreadable run manifest"));
+    }
+}
@@ -1,52 +1,116 @@
 use std::{
+    borrow::Cow,
     collections::HashMap,
     path::{Path, PathBuf},
     sync::Arc,
 };
 
+use futures::{Stream, TryStreamExt, stream};
 use indexmap::IndexMap;
 use tokio::sync::RwLock;
 use tokio::{io::AsyncWriteExt, time::Instant};
 
 use crate::error::{Error, PluginError};
 
+mod cadence;
 mod calendar;
+mod clock;
+mod cost;
+mod credentials;
+mod manifest;
+mod options;
 pub mod post;
+mod report;
+mod retry;
+mod takedown;
+mod template;
+mod validate;
 
+pub use cadence::Cadence;
 pub use calendar::{CalendarCrawlRange, SsufidCalendar, SsufidCalendarSiteData};
-pub use post::{Attachment, SsufidPost, SsufidSiteData};
+pub use clock::{Clock, FixedClock, SystemClock};
+pub use cost::CostEstimate;
+pub use credentials::{CredentialStore, Credentials};
+pub use manifest::{PluginRunResult, RunManifest};
+pub use options::CrawlOptions;
+pub use post::{
+    Attachment, AttachmentChanges, AttachmentRename, Author, PostField, PostIdOrd, SsufidPost,
+    SsufidSiteData, UrlKind, normalize_mime_type, post_eq_ignoring,
+};
+pub use report::{CrawlReport, HealthStatus, PluginInfo};
+pub use retry::{PostFailure, RetryEntry, RetryQueue, report_post_failure};
+pub use takedown::report_pinned_post;
+pub use template::{MessageTemplate, TemplateError};
+pub use validate::{ValidationWarning, validate_attachments, validate_posts};
 
 pub struct SsufidCore {
     cache: Arc<RwLock<HashMap<String, Vec<SsufidPost>>>>,
+    /// The plugin version that produced each entry of `cache`.
+    cache_versions: Arc<RwLock<HashMap<String, u32>>>,
     calendar_cache: Arc<RwLock<HashMap<String, Vec<SsufidCalendar>>>>,
     cache_dir: String,
+    /// Loaded from `cache_dir` on the first run.
+    retry_queue: Arc<RwLock<Option<RetryQueue>>>,
+    credentials: CredentialStore,
+    clock: Arc<dyn Clock>,
 }
 
 impl SsufidCore {
     pub const POST_COUNT_LIMIT: u32 = 100;
     pub const CALENDAR_DAY_LIMIT: u32 = 30;
     pub const RETRY_COUNT: u32 = 3;
+    /// How many queued posts of a plugin are fetched again per run, see [`RetryQueue`].
+    pub const RETRY_QUEUE_DRAIN_LIMIT: usize = 10;
 
     pub fn new(cache_dir: &str) -> Self {
         Self {
             cache: Arc::new(RwLock::new(HashMap::new())),
+            cache_versions: Arc::new(RwLock::new(HashMap::new())),
             calendar_cache: Arc::new(RwLock::new(HashMap::new())),
             cache_dir: cache_dir.to_string(),
+            retry_queue: Arc::new(RwLock::new(None)),
+            credentials: CredentialStore::from_env(),
+            clock: Arc::new(SystemClock),
         }
     }
 
-    pub async fn run_with_retry<T: SsufidPostPlugin>(
+    /// Reads the current time from `clock` instead of the system clock, e.g. a [`FixedClock`]
+    /// in tests.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// The directory the core keeps its cache and other state in.
+    pub fn cache_dir(&self) -> &str {
+        &self.cache_dir
+    }
+
+    /// The current time according to the core's [`Clock`].
+    pub fn now(&self) -> time::OffsetDateTime {
+        self.clock.now()
+    }
+
+    /// Uses `credentials` instead of reading them from the environment, see
+    /// [`SsufidPostPlugin::authenticate`].
+    pub fn with_credentials(mut self, credentials: CredentialStore) -> Self {
+        self.credentials = credentials;
+        self
+    }
+
+    pub async fn run_with_retry<T: SsufidPostPlugin + Sync>(
         &self,
         plugin: &T,
-        posts_limit: u32,
+        options: &CrawlOptions,
         retry_count: u32,
     ) -> Result<SsufidSiteData, Error> {
+        let posts_limit = options.posts_limit;
         let mut last_error = None;
 
         for attempt in 1..=retry_count {
             let start = Instant::now();
 
-            match self.run(plugin, posts_limit).await {
+            match self.run(plugin, options).await {
                 Ok(data) => {
                     let elapsed = start.elapsed();
 
@@ -95,15 +159,26 @@ impl SsufidCore {
     #[tracing::instrument(
         name = "run_plugin",
         target = "content_update",
-        skip(self, plugin),
-        fields(plugin = T::IDENTIFIER, posts_limit)
+        skip(self, plugin, options),
+        fields(plugin = T::IDENTIFIER, posts_limit = options.posts_limit)
     )]
-    pub async fn run<T: SsufidPostPlugin>(
+    pub async fn run<T: SsufidPostPlugin + Sync>(
         &self,
         plugin: &T,
-        posts_limit: u32,
+        options: &CrawlOptions,
     ) -> Result<SsufidSiteData, Error> {
-        let new_entries = plugin.crawl(posts_limit).await.inspect_err(|e| {
+        let posts_limit = options.posts_limit;
+        let options = &CrawlOptions {
+            now: Some(options.now.unwrap_or_else(|| self.now())),
+            ..options.clone()
+        };
+        let crawl = async {
+            self.prepare(plugin, options).await?;
+            plugin.crawl_with_options(options).await
+        };
+        let ((crawled, failures), pinned) =
+            takedown::collect_pinned_posts(retry::collect_post_failures(crawl)).await;
+        let mut new_entries = crawled.inspect_err(|e| {
             tracing::error!(
                 target: "content_update",
                 type = "crawl_attempt_failed",
@@ -114,6 +189,33 @@ impl SsufidCore {
                 "Crawl attempt failed"
             )
         })?;
+        let mut coverage = takedown::CrawlCoverage {
+            pinned,
+            failed: failures.iter().map(|f| f.id.clone()).collect(),
+            retried: Default::default(),
+            posts_limit: Some(posts_limit),
+        };
+        let retried = self
+            .retry_failed_posts(plugin, &new_entries, failures)
+            .await?;
+        coverage.retried = retried.iter().map(|post| post.id.clone()).collect();
+        new_entries.extend(retried);
+        new_entries.iter_mut().for_each(|post| {
+            post.normalize();
+            post.mark_inline_attachments();
+        });
+        for post in new_entries.iter().filter(|post| post.looks_mojibake()) {
+            tracing::warn!(
+                plugin = T::IDENTIFIER,
+                id = %post.id,
+                url = %post.url,
+                "Post looks mis-decoded"
+            );
+            crate::metrics::increment_counter(
+                crate::metrics::MOJIBAKE_TOTAL,
+                &[("plugin", T::IDENTIFIER)],
+            );
+        }
         tracing::info!(
             target: "content_update",
             type = "crawl_attempt_success",
@@ -122,18 +224,50 @@ impl SsufidCore {
             posts_limit
         );
         let cache = Arc::clone(&self.cache);
-        let updated_entries = {
+        let (updated_entries, version_current) = {
             let cache = cache.read().await;
-            #[allow(unused_variables)]
-            let old_entries = match cache.get(T::IDENTIFIER) {
+            let mut old_entries = match cache.get(T::IDENTIFIER) {
                 Some(entries) => entries.clone(),
                 None => self.read_cache(T::IDENTIFIER).await?,
             };
-            merge_entries(old_entries, new_entries)
+            // Compare the cache and new posts by the same rules even after normalization changes
+            old_entries.iter_mut().for_each(|post| {
+                post.normalize();
+                migrate_category_id::<T>(post);
+            });
+            let reparsed = self.cached_version(T::IDENTIFIER).await? != Some(T::VERSION);
+            // Record the new version only if this crawl reparsed every cached post, so that
+            // posts outside the range are reparsed by a larger crawl such as a backfill
+            let fully_reparsed = !reparsed || {
+                let crawled_ids = new_entries
+                    .iter()
+                    .map(|post| post.id.as_str())
+                    .collect::<std::collections::HashSet<_>>();
+                old_entries
+                    .iter()
+                    .filter(|post| !post.is_removed())
+                    .all(|post| crawled_ids.contains(post.id.as_str()))
+            };
+            if reparsed {
+                tracing::info!(
+                    plugin = T::IDENTIFIER,
+                    version = T::VERSION,
+                    fully_reparsed,
+                    "Plugin version changed, replacing cached posts"
+                );
+            }
+            let merged = merge_entries(old_entries, new_entries, reparsed, self.now(), &coverage);
+            (merged, fully_reparsed)
         };
+        // Keep every post in the cache, but output only those within max_age
+        let cutoff = options.cutoff(self.now());
         {
             let mut cache = cache.write().await;
             cache.insert(T::IDENTIFIER.to_string(), updated_entries.clone());
+            if version_current {
+                let mut cache_versions = self.cache_versions.write().await;
+                cache_versions.insert(T::IDENTIFIER.to_string(), T::VERSION);
+            }
         }
         Ok(SsufidSiteData {
             title: T::TITLE.to_string(),
@@ -142,11 +276,68 @@ impl SsufidCore {
             items: updated_entries
                 .into_iter()
                 .rev()
+                .filter(|post| cutoff.is_none_or(|cutoff| post.created_at >= cutoff))
                 .take(Self::POST_COUNT_LIMIT as usize)
                 .collect(),
+            plugin_version: Some(T::VERSION),
+            integrity: None,
         })
     }
 
+    /// Readies `plugin` for a crawl with `options`: checks that its site is reachable if
+    /// [`CrawlOptions::preflight`] is set, logs in with its stored credentials and calls
+    /// [`warmup`](SsufidPostPlugin::warmup).
+    async fn prepare<T: SsufidPostPlugin + Sync>(
+        &self,
+        plugin: &T,
+        options: &CrawlOptions,
+    ) -> Result<(), PluginError> {
+        if options.preflight {
+            crate::fetch::check_reachable::<T>(&plugin.http_client(), &plugin.base_url()).await?;
+        }
+        if let Some(credentials) = self.credentials.get(T::IDENTIFIER) {
+            tracing::info!(plugin = T::IDENTIFIER, "Authenticating before the crawl");
+            plugin.authenticate(&credentials).await?;
+        }
+        plugin.warmup().await
+    }
+
+    /// Streams the posts of `plugin` with `options`, readied as for [`run`](Self::run) and
+    /// normalized like its output. Posts come from
+    /// [`crawl_stream`](SsufidPostPlugin::crawl_stream), or from
+    /// [`crawl_with_options`](SsufidPostPlugin::crawl_with_options) when detail pages are off
+    /// for a plugin that [skips](SsufidPostPlugin::SKIPS_DETAIL) them. The cache is left alone.
+    pub fn stream<'a, T: SsufidPostPlugin + Sync>(
+        &'a self,
+        plugin: &'a T,
+        options: &'a CrawlOptions,
+    ) -> impl Stream<Item = Result<SsufidPost, Error>> + Send + 'a {
+        use futures::StreamExt as _;
+
+        let cutoff = options.cutoff(options.now.unwrap_or_else(|| self.now()));
+        stream::once(self.prepare(plugin, options))
+            .map_ok(move |()| {
+                if options.detail || !T::SKIPS_DETAIL {
+                    plugin.crawl_stream(options.posts_limit).left_stream()
+                } else {
+                    stream::once(plugin.crawl_with_options(options))
+                        .map_ok(|posts| stream::iter(posts.into_iter().map(Ok)))
+                        .try_flatten()
+                        .right_stream()
+                }
+            })
+            .try_flatten()
+            .try_filter(move |post| {
+                std::future::ready(cutoff.is_none_or(|cutoff| post.created_at >= cutoff))
+            })
+            .map_ok(|mut post| {
+                post.normalize();
+                post.mark_inline_attachments();
+                post
+            })
+            .map_err(Error::from)
+    }
+
     pub async fn run_calendar_with_retry<T: SsufidCalendarPlugin>(
         &self,
         plugin: &T,
@@ -263,9 +454,149 @@ impl SsufidCore {
                 .into_iter()
                 .rev()
                 .collect(),
+            integrity: None,
         })
     }
 
+    /// Fetches again up to [`RETRY_QUEUE_DRAIN_LIMIT`](Self::RETRY_QUEUE_DRAIN_LIMIT) posts
+    /// that earlier runs of `plugin` failed on, then queues the retryable `failures` of this
+    /// run. Posts that `crawled` already includes leave the queue without a refetch.
+    async fn retry_failed_posts<T: SsufidPostPlugin + Sync>(
+        &self,
+        plugin: &T,
+        crawled: &[SsufidPost],
+        failures: Vec<PostFailure>,
+    ) -> Result<Vec<SsufidPost>, Error> {
+        let due = {
+            let mut queue = self.retry_queue.write().await;
+            let queue = match &mut *queue {
+                Some(queue) => queue,
+                None => queue.insert(RetryQueue::load(Path::new(&self.cache_dir)).await?),
+            };
+            for post in crawled {
+                queue.remove(T::IDENTIFIER, &post.id);
+            }
+            queue.drain(T::IDENTIFIER, Self::RETRY_QUEUE_DRAIN_LIMIT)
+        };
+
+        let mut retried = Vec::new();
+        let mut expired = Vec::new();
+        let mut failed = Vec::new();
+        for entry in due {
+            match plugin.fetch_single(&entry.url).await {
+                Ok(Some(post)) => {
+                    tracing::info!(
+                        target: "content_update",
+                        type = "retry_succeeded",
+                        id = T::IDENTIFIER,
+                        post_id = %entry.id,
+                        attempts = entry.attempts,
+                        "Fetched a previously failed post"
+                    );
+                    retried.push(post);
+                }
+                Ok(None) => {
+                    tracing::warn!(
+                        plugin = T::IDENTIFIER,
+                        post_id = %entry.id,
+                        "Plugin cannot fetch a single post, dropping it from the retry queue"
+                    );
+                }
+                Err(e) if e.is_retryable() => failed.push((entry, e.to_string())),
+                Err(e) => expired.push(RetryEntry {
+                    last_error: e.to_string(),
+                    ..entry
+                }),
+            }
+        }
+
+        {
+            let mut queue = self.retry_queue.write().await;
+            let queue = queue.get_or_insert_default();
+            expired.extend(
+                failed
+                    .into_iter()
+                    .filter_map(|(entry, error)| queue.requeue(entry, error)),
+            );
+            expired.extend(failures.into_iter().filter_map(|f| queue.enqueue(f)));
+        }
+        for entry in expired {
+            tracing::warn!(
+                target: "content_update",
+                type = "retry_dropped",
+                id = T::IDENTIFIER,
+                post_id = %entry.id,
+                url = %entry.url,
+                attempts = entry.attempts,
+                error = %entry.last_error,
+                "Giving up on a post that keeps failing"
+            );
+        }
+        Ok(retried)
+    }
+
+    /// The ids of the posts cached for the plugin `identifier`, e.g. to tell which posts of the
+    /// next crawl are new.
+    pub async fn cached_post_ids(
+        &self,
+        identifier: &str,
+    ) -> Result<std::collections::HashSet<String>, Error> {
+        Ok(self
+            .cached_posts(identifier)
+            .await?
+            .into_iter()
+            .map(|post| post.id)
+            .collect())
+    }
+
+    /// Every post cached for the plugin `identifier`, including those older than what its
+    /// outputs keep, e.g. for statistics over the whole archive.
+    pub async fn cached_posts(&self, identifier: &str) -> Result<Vec<SsufidPost>, Error> {
+        let cached = self.cache.read().await.get(identifier).cloned();
+        match cached {
+            Some(posts) => Ok(posts),
+            None => self.read_cache(identifier).await,
+        }
+    }
+
+    /// Adds the posts of `T` among `posts` that are not cached yet to the cache, leaving the
+    /// cached ones as they are, e.g. after streaming new posts with
+    /// [`crawl_stream`](SsufidPostPlugin::crawl_stream) instead of [`run`](Self::run). Returns
+    /// the number of posts added.
+    pub async fn cache_new_posts<T: SsufidPostPlugin>(
+        &self,
+        posts: Vec<SsufidPost>,
+    ) -> Result<usize, Error> {
+        let mut entries = self.cached_posts(T::IDENTIFIER).await?;
+        let was_empty = entries.is_empty();
+        let mut known_ids = entries
+            .iter()
+            .map(|post| post.id.clone())
+            .collect::<std::collections::HashSet<_>>();
+        let before = entries.len();
+        entries.extend(
+            posts
+                .into_iter()
+                .filter(|post| known_ids.insert(post.id.clone())),
+        );
+        let added = entries.len() - before;
+        if added == 0 {
+            return Ok(0);
+        }
+        entries.sort_by(by_date_then_id);
+        self.cache
+            .write()
+            .await
+            .insert(T::IDENTIFIER.to_string(), entries);
+        if was_empty {
+            self.cache_versions
+                .write()
+                .await
+                .insert(T::IDENTIFIER.to_string(), T::VERSION);
+        }
+        Ok(added)
+    }
+
     pub async fn save_cache(&self) -> Result<(), Error> {
         tokio::fs::create_dir_all(Path::new(&self.cache_dir)).await?;
 
@@ -281,6 +612,14 @@ impl SsufidCore {
                 let mut file = tokio::fs::File::create(path).await?;
                 file.write_all(json.as_bytes()).await?;
             }
+            let cache_versions = self.cache_versions.read().await;
+            for (id, version) in &*cache_versions {
+                tokio::fs::write(self.post_version_path(id), version.to_string()).await?;
+            }
+        }
+
+        if let Some(queue) = &*self.retry_queue.read().await {
+            queue.save(Path::new(&self.cache_dir)).await?;
         }
 
         {
@@ -304,6 +643,23 @@ impl SsufidCore {
         Path::new(&self.cache_dir).join(format!("{id}.json"))
     }
 
+    fn post_version_path(&self, id: &str) -> PathBuf {
+        Path::new(&self.cache_dir).join(format!("{id}.version"))
+    }
+
+    /// The plugin version that produced the cached posts of `id`, or `None` if it cannot be
+    /// told. Caches written before plugins had versions count as version 1.
+    async fn cached_version(&self, id: &str) -> Result<Option<u32>, Error> {
+        if let Some(version) = self.cache_versions.read().await.get(id) {
+            return Ok(Some(*version));
+        }
+        match tokio::fs::read_to_string(self.post_version_path(id)).await {
+            Ok(content) => Ok(content.trim().parse().ok()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Some(1)),
+            Err(e) => Err(Error::Io(e)),
+        }
+    }
+
     fn calendar_cache_path(&self, id: &str) -> PathBuf {
         Path::new(&self.cache_dir)
             .join("calendar")
@@ -333,19 +689,45 @@ impl SsufidCore {
     }
 }
 
+/// Orders cached posts oldest first. Many boards only show dates, so posts of the same time are
+/// ordered by id.
+fn by_date_then_id(a: &SsufidPost, b: &SsufidPost) -> std::cmp::Ordering {
+    a.partial_cmp(b)
+        .unwrap_or(std::cmp::Ordering::Equal)
+        .then_with(|| PostIdOrd(&a.id).cmp(&PostIdOrd(&b.id)))
+}
+
+/// How many changed lines of content a `post_updated` event shows.
+const UPDATE_DIFF_LINES: usize = 6;
+
+/// Merges freshly crawled posts into the cached ones, replacing cached posts even if equal when
+/// `reparsed` is set. Cached posts missing from the crawled range for
+/// [`TAKEDOWN_MISSES`](takedown::CrawlCoverage::TAKEDOWN_MISSES) crawls in a row are
+/// [marked as removed](SsufidPost::mark_removed). Changes are stamped with `current_time`.
 fn merge_entries(
     old_entries: Vec<SsufidPost>,
     mut new_entries: Vec<SsufidPost>,
+    reparsed: bool,
+    current_time: time::OffsetDateTime,
+    coverage: &takedown::CrawlCoverage,
 ) -> Vec<SsufidPost> {
     let mut old_entries_map = old_entries
         .into_iter()
         .map(|post: SsufidPost| (post.id.clone(), post))
         .collect::<IndexMap<String, SsufidPost>>();
-    old_entries_map
-        .sort_by(|_k, v, _k2, v2| v.partial_cmp(v2).unwrap_or(std::cmp::Ordering::Equal));
-    let current_time = time::OffsetDateTime::now_utc();
-    new_entries.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    old_entries_map.sort_by(|_k, v, _k2, v2| by_date_then_id(v, v2));
+    new_entries.sort_by(by_date_then_id);
     let new_entries = new_entries;
+    let oldest_listed = coverage.oldest_listed(&new_entries);
+    let crawled_ids = new_entries
+        .iter()
+        .map(|post| post.id.clone())
+        .collect::<std::collections::HashSet<_>>();
+    for id in &crawled_ids {
+        if let Some(old) = old_entries_map.get_mut(id) {
+            old.clear_missed_crawls();
+        }
+    }
     for post in new_entries {
         let Some(old) = old_entries_map.get(&post.id) else {
             tracing::info!(
@@ -358,17 +740,52 @@ fn merge_entries(
             old_entries_map.insert(post.id.clone(), post);
             continue;
         };
-        if old.contents_eq(&post) {
+        if old.is_removed() {
+            tracing::info!(
+                target: "content_update",
+                type = "post_restored",
+                id = %post.id,
+                title = %post.title,
+                url = %post.url,
+            );
+            old_entries_map.insert(post.id.clone(), post);
             continue;
         }
-        tracing::info!(
-            target: "content_update",
-            type = "post_updated",
-            id = %post.id,
-            title = %post.title,
-            url = %post.url,
-        );
-        if post.updated_at.is_some() {
+        if reparsed {
+            let updated_at = post.updated_at.or(old.updated_at);
+            old_entries_map.insert(post.id.clone(), SsufidPost { updated_at, ..post });
+            continue;
+        }
+        let attachment_changes = AttachmentChanges::between(&old.attachments, &post.attachments);
+        let contents_eq = old.contents_eq(&post);
+        if contents_eq && attachment_changes.is_empty() {
+            continue;
+        }
+        let mut post = post;
+        if !attachment_changes.is_empty() {
+            tracing::info!(
+                target: "content_update",
+                type = "attachments_updated",
+                id = %post.id,
+                title = %post.title,
+                url = %post.url,
+                changes = %attachment_changes,
+            );
+            post.set_attachment_changes(&attachment_changes);
+        }
+        if !contents_eq {
+            let diff = crate::content::text_diff(&old.content, &post.content);
+            tracing::info!(
+                target: "content_update",
+                type = "post_updated",
+                id = %post.id,
+                title = %post.title,
+                url = %post.url,
+                diff = %crate::content::render_diff(&diff, UPDATE_DIFF_LINES),
+            );
+        }
+        // Boards often keep the displayed modification time when only attachments change
+        if post.updated_at.is_some() && (!contents_eq || post.updated_at != old.updated_at) {
             old_entries_map.insert(post.id.clone(), post);
         } else {
             old_entries_map.insert(
@@ -381,9 +798,52 @@ fn merge_entries(
             );
         }
     }
+    if let Some(oldest_listed) = oldest_listed {
+        for post in old_entries_map.values_mut().filter(|post| {
+            post.created_at > oldest_listed
+                && !crawled_ids.contains(&post.id)
+                && !coverage.accounts_for(&post.id)
+                && !post.is_removed()
+        }) {
+            if post.record_missed_crawl() < takedown::CrawlCoverage::TAKEDOWN_MISSES {
+                continue;
+            }
+            post.clear_missed_crawls();
+            tracing::info!(
+                target: "content_update",
+                type = "post_removed",
+                id = %post.id,
+                title = %post.title,
+                url = %post.url,
+            );
+            post.mark_removed(current_time);
+        }
+    }
     old_entries_map.into_values().collect()
 }
 
+/// Prefixes the id of a post cached before its plugin set
+/// [`ID_CATEGORY_PARAM`](SsufidPlugin::ID_CATEGORY_PARAM) with the category in its URL, as
+/// [`query_post_id`](SsufidPlugin::query_post_id) now does, so that the post is matched by its
+/// new id instead of being duplicated under it and taken down under the old one.
+fn migrate_category_id<T: SsufidPlugin>(post: &mut SsufidPost) {
+    let Some(param) = T::ID_CATEGORY_PARAM else {
+        return;
+    };
+    let Some(category) = url::Url::parse(&post.url).ok().and_then(|url| {
+        url.query_pairs()
+            .find(|(key, _)| key == param)
+            .map(|(_, value)| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    }) else {
+        return;
+    };
+    let prefix = format!("{category}-");
+    if !post.id.starts_with(&prefix) {
+        post.id = format!("{prefix}{}", post.id);
+    }
+}
+
 fn filter_calendar_entries_by_range(
     entries: Vec<SsufidCalendar>,
     calendar_range: &CalendarCrawlRange,
@@ -436,18 +896,376 @@ fn merge_calendar_entries(
     old_entries_map.into_values().collect()
 }
 
+/// Describes a crawled site. Boards also implement [`SsufidPostPlugin`], calendars
+/// [`SsufidCalendarPlugin`].
+///
+/// A minimal board whose list page carries everything a post needs:
+///
+/// ```
+/// use ssufid::{
+///     PluginError, SsufidCore,
+///     core::{CrawlOptions, SsufidPlugin, SsufidPost, SsufidPostPlugin, UrlKind},
+///     fetch::{default_client, fetch_text},
+///     html::parse_selector,
+/// };
+/// use wiremock::{Mock, MockServer, ResponseTemplate, matchers::path};
+///
+/// struct ExamplePlugin {
+///     list_url: String,
+/// }
+///
+/// impl SsufidPlugin for ExamplePlugin {
+///     const TITLE: &'static str = "예제 공지사항";
+///     const IDENTIFIER: &'static str = "example.com";
+///     const DESCRIPTION: &'static str = "예제 게시판의 공지사항입니다.";
+///     const BASE_URL: &'static str = "https://example.com/notice";
+/// }
+///
+/// impl SsufidPostPlugin for ExamplePlugin {
+///     async fn crawl(&self, posts_limit: u32) -> Result<Vec<SsufidPost>, PluginError> {
+///         let link = parse_selector::<Self>("li > a")?;
+///         let html = fetch_text::<Self>(&default_client(), &self.list_url).await?;
+///         let document = scraper::Html::parse_document(&html);
+///         Ok(document
+///             .select(&link)
+///             .take(posts_limit as usize)
+///             .map(|a| SsufidPost {
+///                 id: a.value().attr("data-id").unwrap_or_default().to_string(),
+///                 url: format!("{}/{}", Self::BASE_URL, a.value().attr("href").unwrap_or_default()),
+///                 url_kind: UrlKind::Canonical,
+///                 author: None,
+///                 title: a.text().collect(),
+///                 description: None,
+///                 category: vec![],
+///                 created_at: time::macros::datetime!(2025-03-02 00:00 +9),
+///                 updated_at: None,
+///                 thumbnail: None,
+///                 content: String::new(),
+///                 attachments: vec![],
+///                 metadata: None,
+///                 author_detail: None,
+///             })
+///             .collect())
+///     }
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let server = MockServer::start().await;
+/// Mock::given(path("/notice"))
+///     .respond_with(ResponseTemplate::new(200).set_body_string(
+///         r#"<ul><li><a data-id="1" href="1">수강신청 안내</a></li></ul>"#,
+///     ))
+///     .mount(&server)
+///     .await;
+///
+/// let plugin = ExamplePlugin {
+///     list_url: format!("{}/notice", server.uri()),
+/// };
+/// let core = SsufidCore::new(std::env::temp_dir().join("ssufid-doctest").to_str().unwrap());
+/// let site = core.run(&plugin, &CrawlOptions::new(10)).await.unwrap();
+/// assert_eq!(site.items()[0].title, "수강신청 안내");
+/// # }
+/// ```
 pub trait SsufidPlugin {
     const TITLE: &'static str;
     const IDENTIFIER: &'static str;
     const DESCRIPTION: &'static str;
     const BASE_URL: &'static str;
+    /// Bumped when a parsing change may change posts already produced; cached posts from another
+    /// version are re-parsed.
+    const VERSION: u32 = 1;
+    /// How often the board usually gets new posts, from which schedulers derive a default
+    /// crawl interval and how long a quiet board is still normal.
+    const CADENCE: Cadence = Cadence::Weekly;
+    /// How the plugin's list pages look when the board has no posts.
+    const EMPTY_STATE: crate::html::EmptyState = crate::html::EmptyState::DEFAULT;
+    /// Selectors the plugin cannot work without, as `(name, css)` pairs such as
+    /// `("list_item", "table.board > tbody > tr")`, checked by [`crate::html::health_check`].
+    const CRITICAL_SELECTORS: &'static [(&'static str, &'static str)] = &[];
+    /// The encoding the board serves its pages in when they do not declare one, used by
+    /// [`crate::fetch::fetch_text`] to decode them.
+    const ENCODING: &'static encoding_rs::Encoding = encoding_rs::UTF_8;
+    /// Pages larger than this many bytes have their styles and `data:` URIs stripped before
+    /// parsing, see [`crate::fetch::FetchedPage::bounded`].
+    const MAX_PARSE_BYTES: usize = 2 * 1024 * 1024;
+    /// How many posts a list page shows, from which
+    /// [`SsufidPostPlugin::estimate_cost`] derives the number of list requests.
+    const POSTS_PER_PAGE: u32 = 10;
+    /// The URL of a post's page with `{id}` in place of its id, e.g.
+    /// `"https://example.com/board/view.php?idx={id}"`, for boards whose detail URLs are built
+    /// from the id alone. See [`detail_url`](Self::detail_url).
+    const DETAIL_URL_TEMPLATE: Option<&'static str> = None;
+    /// Texts of the stub a board shows instead of a members-only post, checked by
+    /// [`crate::html::is_restricted`].
+    const RESTRICTED_MARKERS: &'static [&'static str] = &[
+        "로그인이 필요합니다",
+        "로그인 후 이용",
+        "회원만 이용",
+        "글을 읽을 권한이 없습니다",
+    ];
+    /// The query parameter naming a post's category, e.g. `"category"`, for boards whose ids
+    /// are only unique within a category. See [`query_post_id`](Self::query_post_id). Posts
+    /// cached before a plugin set it are re-keyed from their URL on its next run.
+    const ID_CATEGORY_PARAM: Option<&'static str> = None;
+    /// Headers sent with every request of the helpers in [`crate::fetch`], e.g.
+    /// `("Referer", "https://example.com/")` for boards that reject requests without one.
+    const EXTRA_HEADERS: &'static [(&'static str, &'static str)] = &[];
+
+    /// The id of the post at `url` from its `id_param` query parameter, e.g. `idx`. When the
+    /// plugin sets [`ID_CATEGORY_PARAM`](Self::ID_CATEGORY_PARAM) and the URL carries a
+    /// non-empty category, the id becomes `{category}-{id}` so that posts of two categories
+    /// sharing a number stay apart.
+    fn query_post_id(url: &url::Url, id_param: &str) -> Option<String> {
+        let query_value = |param: &str| {
+            url.query_pairs()
+                .find(|(key, _)| key == param)
+                .map(|(_, value)| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        let id = query_value(id_param)?;
+        match Self::ID_CATEGORY_PARAM.and_then(query_value) {
+            Some(category) => Some(format!("{category}-{id}")),
+            None => Some(id),
+        }
+    }
+
+    /// The URL of the post `id` from [`DETAIL_URL_TEMPLATE`](Self::DETAIL_URL_TEMPLATE), with
+    /// the id percent-encoded, or `None` when the plugin has no template.
+    fn detail_url(id: &str) -> Option<String> {
+        let id = url::form_urlencoded::byte_serialize(id.as_bytes()).collect::<String>();
+        Self::DETAIL_URL_TEMPLATE.map(|template| template.replace("{id}", &id))
+    }
+
+    /// The URL this instance crawls, which differs from [`BASE_URL`](Self::BASE_URL) for
+    /// instances pointed elsewhere, e.g. at a mock server. The default implementation returns
+    /// `BASE_URL`.
+    fn base_url(&self) -> Cow<'_, str> {
+        Cow::Borrowed(Self::BASE_URL)
+    }
+
+    /// The client this instance crawls with, so that requests made on its behalf, such as the
+    /// [preflight](CrawlOptions::preflight) check, go out with its cookies and settings. The
+    /// default implementation returns the [`shared_client`](crate::fetch::shared_client).
+    fn http_client(&self) -> reqwest::Client {
+        crate::fetch::shared_client().clone()
+    }
+
+    /// Fixes the board's known tag soup in a fetched page before it is parsed, such as tags
+    /// it leaves open, so that html5ever's error recovery has nothing to guess at. Plugins
+    /// call it on their pages before [`Html::parse_document`](scraper::Html::parse_document);
+    /// see [`crate::html::lowercase_tag_names`] and [`crate::html::close_unclosed_in_cells`]
+    /// for common fixes. The default implementation returns the page unchanged.
+    fn normalize_html<'a>(&self, raw: &'a str) -> Cow<'a, str> {
+        Cow::Borrowed(raw)
+    }
 }
 
 pub trait SsufidPostPlugin: SsufidPlugin {
+    /// Whether [`crawl_with_options`](Self::crawl_with_options) skips detail pages when
+    /// [`CrawlOptions::detail`] is off. The default implementation cannot, so plugins that do
+    /// must override both.
+    const SKIPS_DETAIL: bool = false;
+
+    /// Logs in with `credentials` before [`warmup`](Self::warmup), for boards that only list
+    /// posts after a form login. [`SsufidCore::run`] calls it only when credentials for the
+    /// plugin are configured (see [`CredentialStore`]); the plugin keeps the session cookie by
+    /// crawling with a cookie-keeping client such as [`crate::fetch::session_client`]. The
+    /// default implementation does nothing.
+    fn authenticate(
+        &self,
+        _credentials: &Credentials,
+    ) -> impl std::future::Future<Output = Result<(), PluginError>> + Send
+    where
+        Self: Sync,
+    {
+        async { Ok(()) }
+    }
+
+    /// Called by [`SsufidCore::run`] once before every crawl, e.g. to request a page that sets
+    /// the session cookie a board requires. The default implementation does nothing.
+    fn warmup(&self) -> impl std::future::Future<Output = Result<(), PluginError>> + Send
+    where
+        Self: Sync,
+    {
+        async { Ok(()) }
+    }
+
+    /// Returns up to `posts_limit` posts, newest first. The limit counts unique post ids: a
+    /// pinned post repeated at the top of every list page counts once, so a board with enough
+    /// posts yields exactly `posts_limit` of them. [`Paginator`](crate::paginate::Paginator)
+    /// implements this for paginated lists. Plugins that tell pinned notices apart may return
+    /// them on top of `posts_limit` regular posts, so that many notices do not crowd those out.
     fn crawl(
         &self,
         posts_limit: u32,
     ) -> impl std::future::Future<Output = Result<Vec<SsufidPost>, PluginError>> + Send;
+
+    /// Returns the id of the first post on the board, without fetching any detail page
+    /// where the plugin supports it, so that a poller can cheaply detect that nothing changed.
+    ///
+    /// The default implementation falls back to `crawl(1)`.
+    fn latest_post_id(
+        &self,
+    ) -> impl std::future::Future<Output = Result<Option<String>, PluginError>> + Send
+    where
+        Self: Sync,
+    {
+        async { Ok(self.crawl(1).await?.into_iter().next().map(|post| post.id)) }
+    }
+
+    /// Returns the board's categories as the site names them, e.g. from its category tabs or
+    /// selector, so that a UI can offer a filter over [`SsufidPost::category`] before any post
+    /// of a category has been crawled. The default implementation returns no categories.
+    fn categories(
+        &self,
+    ) -> impl std::future::Future<Output = Result<Vec<String>, PluginError>> + Send
+    where
+        Self: Sync,
+    {
+        async { Ok(vec![]) }
+    }
+
+    /// Fetches the post at `id_or_url` alone, for the [`RetryQueue`]: an id for plugins whose
+    /// post URLs can be built from it, otherwise the URL it was reported with. Returns `None` if
+    /// the plugin cannot fetch a single post, which is the default.
+    fn fetch_single(
+        &self,
+        _id_or_url: &str,
+    ) -> impl std::future::Future<Output = Result<Option<SsufidPost>, PluginError>> + Send
+    where
+        Self: Sync,
+    {
+        async { Ok(None) }
+    }
+
+    /// Checks that the first list page still parses, along with any
+    /// [`CRITICAL_SELECTORS`](SsufidPlugin::CRITICAL_SELECTORS) on the list and a post page.
+    fn health_check(
+        &self,
+    ) -> impl std::future::Future<Output = Result<HealthStatus, PluginError>> + Send
+    where
+        Self: Sized + Sync,
+    {
+        async {
+            let started_at = Instant::now();
+            let posts = self
+                .crawl_with_options(&CrawlOptions::new(1).detail(false))
+                .await?;
+            let broken_selectors = if Self::CRITICAL_SELECTORS.is_empty() || posts.is_empty() {
+                Vec::new()
+            } else {
+                let client = self.http_client();
+                let urls = std::iter::once(self.list_page_url())
+                    .chain(posts.iter().map(|post| post.url.clone()));
+                let mut texts = Vec::new();
+                for url in urls {
+                    texts.push(crate::fetch::fetch_text::<Self>(&client, &url).await?);
+                }
+                let pages = texts
+                    .iter()
+                    .map(|text| scraper::Html::parse_document(text))
+                    .collect::<Vec<_>>();
+                crate::html::health_check::<Self>(&pages)
+            };
+            Ok(HealthStatus {
+                ok: !posts.is_empty() && broken_selectors.is_empty(),
+                posts_seen: posts.len(),
+                latency: started_at.elapsed(),
+                broken_selectors,
+            })
+        }
+    }
+
+    /// Estimates the requests a crawl with `options` issues: one list request per
+    /// [`SsufidPlugin::POSTS_PER_PAGE`] posts and one detail request per post. An upper bound when
+    /// [`CrawlOptions::max_age`] is set.
+    fn estimate_cost(&self, options: &CrawlOptions) -> CostEstimate {
+        let list_requests = options.posts_limit.div_ceil(Self::POSTS_PER_PAGE.max(1));
+        let detail_requests = if options.detail || !Self::SKIPS_DETAIL {
+            options.posts_limit
+        } else {
+            0
+        };
+        CostEstimate::new(list_requests, detail_requests)
+    }
+
+    /// The URL of the board's first list page. Defaults to
+    /// [`base_url`](SsufidPlugin::base_url); plugins whose list is elsewhere should override it.
+    fn list_page_url(&self) -> String {
+        self.base_url().into_owned()
+    }
+
+    /// The pages to record as test fixtures with [`crate::fixtures::refresh`]. The default
+    /// implementation returns [`list_page_url`](Self::list_page_url) and the URLs of the first
+    /// `count` posts, crawled without detail pages.
+    fn fixture_urls(
+        &self,
+        count: u32,
+    ) -> impl std::future::Future<Output = Result<Vec<String>, PluginError>> + Send
+    where
+        Self: Sync,
+    {
+        async move {
+            let posts = self
+                .crawl_with_options(&CrawlOptions::new(count).detail(false))
+                .await?;
+            Ok(std::iter::once(self.list_page_url())
+                .chain(posts.into_iter().map(|post| post.url))
+                .collect())
+        }
+    }
+
+    /// Streams up to `posts_limit` posts, so that a large backfill need not hold every post in
+    /// memory at once. The default implementation yields the result of `crawl`; plugins that
+    /// fetch a detail page per post should override it to yield each post as soon as its page
+    /// is parsed, keeping only a bounded number of requests in flight.
+    fn crawl_stream(
+        &self,
+        posts_limit: u32,
+    ) -> impl Stream<Item = Result<SsufidPost, PluginError>> + Send
+    where
+        Self: Sync,
+    {
+        stream::once(self.crawl(posts_limit))
+            .map_ok(|posts| stream::iter(posts.into_iter().map(Ok)))
+            .try_flatten()
+    }
+
+    /// Crawls the posts whose id is greater than `last_id` (by [`PostIdOrd`]), for boards that
+    /// number their posts sequentially. Plugins that can stop paginating once they reach
+    /// `last_id` should override it; the default implementation crawls
+    /// [`SsufidCore::POST_COUNT_LIMIT`] posts and filters them.
+    fn crawl_since_id(
+        &self,
+        last_id: &str,
+    ) -> impl std::future::Future<Output = Result<Vec<SsufidPost>, PluginError>> + Send
+    where
+        Self: Sync,
+    {
+        async move {
+            let mut posts = self.crawl(SsufidCore::POST_COUNT_LIMIT).await?;
+            posts.retain(|post| PostIdOrd(&post.id) > PostIdOrd(last_id));
+            Ok(posts)
+        }
+    }
+
+    /// Crawls with `options`. The default implementation filters the result of `crawl`, so
+    /// paginating plugins should override it to stop at the first page that
+    /// [`is_past`](crate::paginate::ListPage::is_past) the cutoff.
+    fn crawl_with_options(
+        &self,
+        options: &CrawlOptions,
+    ) -> impl std::future::Future<Output = Result<Vec<SsufidPost>, PluginError>> + Send
+    where
+        Self: Sync,
+    {
+        async move {
+            let now = options.current_time();
+            let posts = self.crawl(options.posts_limit).await?;
+            Ok(options.filter(posts, now))
+        }
+    }
 }
 
 pub trait SsufidCalendarPlugin: SsufidPlugin {
@@ -461,14 +1279,17 @@ pub trait SsufidCalendarPlugin: SsufidPlugin {
 mod tests {
     use std::{time::Duration, vec};
 
+    use futures::{StreamExt, TryStreamExt};
     use time::OffsetDateTime;
     use time::macros::datetime;
     use tokio::io::AsyncWriteExt;
 
     use super::{
-        Attachment, CalendarCrawlRange, SsufidCalendar, SsufidCalendarPlugin, SsufidCore,
-        SsufidPlugin, SsufidPost, SsufidPostPlugin, filter_calendar_entries_by_range,
-        merge_calendar_entries, merge_entries,
+        Attachment, AttachmentChanges, AttachmentRename, CalendarCrawlRange, CostEstimate,
+        CrawlOptions, CredentialStore, Credentials, FixedClock, PostField, PostIdOrd,
+        SsufidCalendar, SsufidCalendarPlugin, SsufidCore, SsufidPlugin, SsufidPost,
+        SsufidPostPlugin, UrlKind, filter_calendar_entries_by_range, merge_calendar_entries,
+        merge_entries, normalize_mime_type, post_eq_ignoring, takedown::CrawlCoverage,
     };
     use crate::error::{Error, PluginError};
 
@@ -541,6 +1362,7 @@ mod tests {
             SsufidPost {
                 id: "test-id-1".to_string(),
                 url: "https://example.com/test1".to_string(),
+                url_kind: UrlKind::Canonical,
                 author: Some("Author One".to_string()),
                 title: "Test Title 1".to_string(),
                 description: Some("This is a description for test 1.".to_string()),
@@ -553,6 +1375,7 @@ mod tests {
                     url: "https://example.com/attachment1.pdf".to_string(),
                     name: Some("Attachment 1".to_string()),
                     mime_type: Some("application/pdf".to_string()),
+                    metadata: None,
                 }],
                 metadata: Some(
                     [("key1".to_string(), "value1".to_string())]
@@ -560,10 +1383,12 @@ mod tests {
                         .cloned()
                         .collect(),
                 ),
+                author_detail: None,
             },
             SsufidPost {
                 id: "test-id-2".to_string(),
                 url: "https://example.com/test2".to_string(),
+                url_kind: UrlKind::Canonical,
                 author: None,
                 title: "Test Title 2".to_string(),
                 description: None,
@@ -574,6 +1399,7 @@ mod tests {
                 content: "Test Content 2".to_string(),
                 attachments: vec![],
                 metadata: None,
+                author_detail: None,
             },
         ];
 
@@ -639,11 +1465,12 @@ mod tests {
 
     #[test]
     fn test_merge_entries() {
-        let now = OffsetDateTime::now_utc();
+        let now = datetime!(2024-02-29 12:00 +9);
         let old_entries = vec![
             SsufidPost {
                 id: "1".to_string(),
                 url: "http://example.com/1".to_string(),
+                url_kind: UrlKind::Canonical,
                 author: Some("Author 1".to_string()),
                 title: "Old Title 1".to_string(),
                 description: Some("Description for 1".to_string()),
@@ -656,6 +1483,7 @@ mod tests {
                     url: "http://example.com/attach1.doc".to_string(),
                     name: None,
                     mime_type: None,
+                    metadata: None,
                 }],
                 metadata: Some(
                     [("meta_key_1".to_string(), "meta_value_1".to_string())]
@@ -663,10 +1491,12 @@ mod tests {
                         .cloned()
                         .collect(),
                 ),
+                author_detail: None,
             },
             SsufidPost {
                 id: "2".to_string(),
                 url: "http://example.com/2".to_string(),
+                url_kind: UrlKind::Canonical,
                 author: Some("Author 2".to_string()),
                 title: "Old Title 2".to_string(),
                 description: Some("Description for 2".to_string()),
@@ -677,10 +1507,12 @@ mod tests {
                 content: "Old Content 2".to_string(),
                 attachments: vec![],
                 metadata: None,
+                author_detail: None,
             },
             SsufidPost {
                 id: "0".to_string(),
                 url: "http://example.com/1".to_string(),
+                url_kind: UrlKind::Canonical,
                 author: Some("Author 1".to_string()),
                 title: "Old Title 1".to_string(),
                 description: Some("Description for 1".to_string()),
@@ -693,6 +1525,7 @@ mod tests {
                     url: "http://example.com/attach1.doc".to_string(),
                     name: None,
                     mime_type: None,
+                    metadata: None,
                 }],
                 metadata: Some(
                     [("meta_key_1".to_string(), "meta_value_1".to_string())]
@@ -700,6 +1533,7 @@ mod tests {
                         .cloned()
                         .collect(),
                 ),
+                author_detail: None,
             },
         ];
 
@@ -707,6 +1541,7 @@ mod tests {
             SsufidPost {
                 id: "1".to_string(),
                 url: "http://example.com/1".to_string(),
+                url_kind: UrlKind::Canonical,
                 author: Some("Author 1".to_string()),
                 title: "Old Title 1".to_string(),
                 description: Some("Description for 1".to_string()),
@@ -719,6 +1554,7 @@ mod tests {
                     url: "http://example.com/attach1.doc".to_string(),
                     name: None,
                     mime_type: None,
+                    metadata: None,
                 }],
                 metadata: Some(
                     [("meta_key_1".to_string(), "meta_value_1".to_string())]
@@ -726,10 +1562,12 @@ mod tests {
                         .cloned()
                         .collect(),
                 ),
+                author_detail: None,
             },
             SsufidPost {
                 id: "2".to_string(),
                 url: "http://example.com/2_new".to_string(),
+                url_kind: UrlKind::Canonical,
                 author: Some("Author 2 Updated".to_string()),
                 title: "Updated Title 2".to_string(),
                 description: Some("Description for 2 Updated".to_string()),
@@ -742,6 +1580,7 @@ mod tests {
                     url: "http://example.com/attach2.png".to_string(),
                     name: Some("New Attachment".to_string()),
                     mime_type: Some("image/png".to_string()),
+                    metadata: None,
                 }],
                 metadata: Some(
                     [("meta_key_2".to_string(), "meta_value_2".to_string())]
@@ -749,10 +1588,12 @@ mod tests {
                         .cloned()
                         .collect(),
                 ),
+                author_detail: None,
             },
             SsufidPost {
                 id: "3".to_string(),
                 url: "http://example.com/3".to_string(),
+                url_kind: UrlKind::Canonical,
                 author: Some("New Author 3".to_string()),
                 title: "New Title 3".to_string(),
                 description: Some("Description for 3".to_string()),
@@ -763,10 +1604,12 @@ mod tests {
                 content: "New Content 3".to_string(),
                 attachments: vec![],
                 metadata: None,
+                author_detail: None,
             },
             SsufidPost {
                 id: "4".to_string(),
                 url: "http://example.com/4".to_string(),
+                url_kind: UrlKind::Canonical,
                 author: Some("Author 4".to_string()),
                 title: "Title 4".to_string(),
                 description: Some("Description for 4".to_string()),
@@ -777,15 +1620,22 @@ mod tests {
                 content: "Content 4".to_string(),
                 attachments: vec![],
                 metadata: None,
+                author_detail: None,
             },
         ];
 
-        let result = merge_entries(old_entries, new_entries);
+        let result = merge_entries(
+            old_entries,
+            new_entries,
+            false,
+            now + Duration::from_secs(60),
+            &CrawlCoverage::default(),
+        );
 
         assert_eq!(result[0].id, "0");
         assert!(result[1].updated_at.is_none());
         assert_eq!(result[1].title, "Old Title 1");
-        assert!(result[2].updated_at.is_some());
+        assert_eq!(result[2].updated_at, Some(now + Duration::from_secs(60)));
         assert_eq!(result[2].title, "Updated Title 2");
         assert!(result[3].updated_at.is_none());
         assert_eq!(result[3].title, "New Title 3");
@@ -1005,7 +1855,10 @@ mod tests {
             error_message: "last post failure".to_string(),
         };
 
-        let error = core.run_with_retry(&plugin, 10, 2).await.unwrap_err();
+        let error = core
+            .run_with_retry(&plugin, &CrawlOptions::new(10), 2)
+            .await
+            .unwrap_err();
         match error {
             Error::AttemptsExceeded {
                 plugin,
@@ -1063,6 +1916,1542 @@ mod tests {
             other => panic!("unexpected error: {other:?}"),
         }
     }
+
+    struct MockListPlugin;
+
+    impl SsufidPlugin for MockListPlugin {
+        const TITLE: &'static str = "Mock List";
+        const IDENTIFIER: &'static str = "mock.list";
+        const DESCRIPTION: &'static str = "Mock list plugin for tests";
+        const BASE_URL: &'static str = "https://example.com/list";
+    }
+
+    impl SsufidPostPlugin for MockListPlugin {
+        async fn crawl(&self, posts_limit: u32) -> Result<Vec<SsufidPost>, PluginError> {
+            Ok((0..posts_limit)
+                .map(|i| SsufidPost {
+                    id: format!("post-{i}"),
+                    url: format!("https://example.com/list/{i}"),
+                    url_kind: UrlKind::Canonical,
+                    author: None,
+                    title: format!("Post {i}"),
+                    description: None,
+                    category: vec![],
+                    created_at: datetime!(2025-03-02 00:00 +9),
+                    updated_at: None,
+                    thumbnail: None,
+                    content: String::new(),
+                    attachments: vec![],
+                    metadata: None,
+                    author_detail: None,
+                })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_default_crawl_stream_yields_crawl_result() {
+        let posts: Vec<SsufidPost> = MockListPlugin.crawl_stream(3).try_collect().await.unwrap();
+        assert_eq!(
+            posts.iter().map(|p| p.id.as_str()).collect::<Vec<_>>(),
+            ["post-0", "post-1", "post-2"]
+        );
+
+        let plugin = MockPostPlugin {
+            error_name: "Boom".to_string(),
+            error_message: "list page is down".to_string(),
+        };
+        let results: Vec<_> = plugin.crawl_stream(3).collect().await;
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_err());
+    }
+
+    #[tokio::test]
+    async fn test_author_detail_serialization_is_backward_compatible() {
+        let mut post = MockListPlugin.crawl(1).await.unwrap().remove(0);
+        post.author = Some("학사팀 / 김OO".to_string());
+        let cached = post.clone();
+        let json = serde_json::to_value(&post).unwrap();
+        assert_eq!(json["author"], "학사팀 / 김OO");
+        assert!(json.get("author_detail").is_none());
+
+        // Older caches without author_detail still load
+        assert_eq!(serde_json::from_value::<SsufidPost>(json).unwrap(), post);
+
+        post.author_detail = crate::text::parse_author_kr(post.author.as_deref().unwrap());
+        let json = serde_json::to_value(&post).unwrap();
+        assert_eq!(json["author"], "학사팀 / 김OO");
+        assert_eq!(
+            json["author_detail"],
+            serde_json::json!({ "name": "김OO", "department": "학사팀" })
+        );
+        assert_eq!(serde_json::from_value::<SsufidPost>(json).unwrap(), post);
+        // A newly filled author_detail counts as an update of the cached post
+        assert!(!post.contents_eq(&cached));
+    }
+
+    #[test]
+    fn test_estimate_cost_from_posts_per_page() {
+        // The default page size is 10
+        let estimate = MockListPlugin.estimate_cost(&CrawlOptions::new(25));
+        assert_eq!(estimate, CostEstimate::new(3, 25));
+        assert_eq!(estimate.total_requests(), 28);
+        assert_eq!(
+            estimate.approx_bytes,
+            3 * CostEstimate::LIST_PAGE_BYTES + 25 * CostEstimate::DETAIL_PAGE_BYTES
+        );
+
+        // Plugins that cannot skip the detail page request it even with `detail(false)`
+        assert_eq!(
+            MockListPlugin.estimate_cost(&CrawlOptions::new(30).detail(false)),
+            CostEstimate::new(3, 30)
+        );
+        struct MockListOnlyPlugin;
+        impl SsufidPlugin for MockListOnlyPlugin {
+            const TITLE: &'static str = "Mock List Only";
+            const IDENTIFIER: &'static str = "mock.list-only";
+            const DESCRIPTION: &'static str = "Mock plugin that can skip detail pages";
+            const BASE_URL: &'static str = "https://example.com/list";
+        }
+        impl SsufidPostPlugin for MockListOnlyPlugin {
+            const SKIPS_DETAIL: bool = true;
+
+            async fn crawl(&self, _posts_limit: u32) -> Result<Vec<SsufidPost>, PluginError> {
+                Ok(vec![])
+            }
+        }
+        let list_only = MockListOnlyPlugin.estimate_cost(&CrawlOptions::new(30).detail(false));
+        assert_eq!(list_only, CostEstimate::new(3, 0));
+        assert_eq!(
+            MockListPlugin.estimate_cost(&CrawlOptions::new(0)),
+            CostEstimate::default()
+        );
+        assert_eq!(
+            [estimate, list_only].into_iter().sum::<CostEstimate>(),
+            CostEstimate::new(6, 25)
+        );
+    }
+
+    #[test]
+    fn test_detail_url_from_template() {
+        struct MockTemplatePlugin;
+
+        impl SsufidPlugin for MockTemplatePlugin {
+            const TITLE: &'static str = "Mock Template";
+            const IDENTIFIER: &'static str = "mock.template";
+            const DESCRIPTION: &'static str = "Mock plugin with a detail URL template";
+            const BASE_URL: &'static str = "https://example.com/board/list.php";
+            const DETAIL_URL_TEMPLATE: Option<&'static str> =
+                Some("https://example.com/board/view.php?idx={id}&page=1");
+        }
+
+        assert_eq!(
+            MockTemplatePlugin::detail_url("1234").as_deref(),
+            Some("https://example.com/board/view.php?idx=1234&page=1")
+        );
+        // Characters that would break the query are encoded
+        assert_eq!(
+            MockTemplatePlugin::detail_url("12&page=9").as_deref(),
+            Some("https://example.com/board/view.php?idx=12%26page%3D9&page=1")
+        );
+        assert_eq!(MockListPlugin::detail_url("1234"), None);
+    }
+
+    #[test]
+    fn test_query_post_id_includes_category() {
+        struct MockCategoryPlugin;
+
+        impl SsufidPlugin for MockCategoryPlugin {
+            const TITLE: &'static str = "Mock Category";
+            const IDENTIFIER: &'static str = "mock.category";
+            const DESCRIPTION: &'static str = "Mock plugin numbering posts per category";
+            const BASE_URL: &'static str = "https://example.com/sub/sub03_01.php";
+            const ID_CATEGORY_PARAM: Option<&'static str> = Some("category");
+        }
+
+        let url = |query: &str| {
+            url::Url::parse(&format!("https://example.com/sub/sub03_01.php?{query}")).unwrap()
+        };
+        let academic = url("boardid=notice&mode=view&idx=77&category=1");
+        let scholarship = url("boardid=notice&mode=view&idx=77&category=3");
+
+        // The same idx in another category is a different post
+        assert_eq!(
+            MockCategoryPlugin::query_post_id(&academic, "idx").as_deref(),
+            Some("1-77")
+        );
+        assert_eq!(
+            MockCategoryPlugin::query_post_id(&scholarship, "idx").as_deref(),
+            Some("3-77")
+        );
+        assert_eq!(
+            MockCategoryPlugin::query_post_id(&url("idx=77&category="), "idx").as_deref(),
+            Some("77")
+        );
+        assert_eq!(
+            MockListPlugin::query_post_id(&scholarship, "idx").as_deref(),
+            Some("77")
+        );
+        assert_eq!(
+            MockCategoryPlugin::query_post_id(&url("category=3"), "idx"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_post_id_ord() {
+        let mut ids = vec!["1000", "999", "10", "9"];
+        ids.sort_by_key(|id| PostIdOrd(id));
+        assert_eq!(ids, ["9", "10", "999", "1000"]);
+
+        // Non-numeric ids sort lexicographically after numeric ones
+        let mut ids = vec![
+            "notice_2025.03.02_수강신청 안내",
+            "2",
+            "1a",
+            "10",
+            "notice_2025.02.27_졸업식 안내",
+        ];
+        ids.sort_by_key(|id| PostIdOrd(id));
+        assert_eq!(
+            ids,
+            [
+                "2",
+                "10",
+                "1a",
+                "notice_2025.02.27_졸업식 안내",
+                "notice_2025.03.02_수강신청 안내",
+            ]
+        );
+        assert!(PostIdOrd("999") < PostIdOrd("1000"));
+        assert!(PostIdOrd("999") < PostIdOrd("1000a"));
+        assert_eq!(
+            PostIdOrd("42").cmp(&PostIdOrd("42")),
+            std::cmp::Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn test_merge_entries_orders_same_date_by_id() {
+        let post = |id: &str| SsufidPost {
+            id: id.to_string(),
+            url: format!("https://example.com/{id}"),
+            url_kind: UrlKind::Canonical,
+            author: None,
+            title: id.to_string(),
+            description: None,
+            category: vec![],
+            created_at: datetime!(2025-03-02 00:00 +9),
+            updated_at: None,
+            thumbnail: None,
+            content: String::new(),
+            attachments: vec![],
+            metadata: None,
+            author_detail: None,
+        };
+        let merged = merge_entries(
+            vec![],
+            vec![post("1000"), post("999"), post("1001")],
+            false,
+            OffsetDateTime::now_utc(),
+            &CrawlCoverage::default(),
+        );
+        let ids = merged.iter().map(|p| p.id.as_str()).collect::<Vec<_>>();
+        assert_eq!(ids, ["999", "1000", "1001"]);
+    }
+
+    fn attachment(url: &str, name: &str, size: Option<&str>) -> Attachment {
+        Attachment {
+            url: url.to_string(),
+            name: Some(name.to_string()),
+            mime_type: None,
+            metadata: size.map(|size| {
+                [(Attachment::SIZE_KEY.to_string(), size.to_string())]
+                    .into_iter()
+                    .collect()
+            }),
+        }
+    }
+
+    #[test]
+    fn test_attachment_listed_details() {
+        let manual = Attachment::from_guess(
+            "교내장학금 신청 매뉴얼.pdf (374.8K)".to_string(),
+            "https://example.com/files/1".to_string(),
+        )
+        .with_listed_details("교내장학금 신청 매뉴얼.pdf (374.8K) DATE : 2025-06-04 11:58:11");
+        assert_eq!(manual.name.as_deref(), Some("교내장학금 신청 매뉴얼.pdf"));
+        assert_eq!(manual.mime_type.as_deref(), Some("application/pdf"));
+        assert_eq!(manual.size(), Some(383_795));
+        assert_eq!(
+            manual.posted_at(),
+            Some(time::macros::datetime!(2025-06-04 11:58:11 +9))
+        );
+
+        // Links without a size or date are left as is
+        let plain = attachment("https://example.com/files/2", "안내문.pdf", None);
+        assert_eq!(plain.clone().with_listed_details("안내문.pdf"), plain);
+    }
+
+    #[test]
+    fn test_attachment_changes_between() {
+        let form = attachment("https://example.com/files/1", "신청서.hwp", Some("1024"));
+        let guide = attachment("https://example.com/files/2", "안내문.pdf", None);
+
+        let both = [form.clone(), guide.clone()];
+
+        // A change of order alone is not a change
+        assert!(AttachmentChanges::between(&both, &[guide, form.clone()]).is_empty());
+        assert_eq!(
+            AttachmentChanges::between(&both[..1], &both).added,
+            ["안내문.pdf"]
+        );
+        assert_eq!(
+            AttachmentChanges::between(&both, &both[..1]).removed,
+            ["안내문.pdf"]
+        );
+        let renamed = attachment(
+            "https://example.com/files/1",
+            "신청서(양식).hwp",
+            Some("1024"),
+        );
+        let changes = AttachmentChanges::between(&both[..1], &[renamed]);
+        assert_eq!(
+            changes.renamed,
+            [AttachmentRename {
+                from: "신청서.hwp".to_string(),
+                to: "신청서(양식).hwp".to_string(),
+            }]
+        );
+        assert_eq!(
+            changes.to_string(),
+            "첨부파일 변경: 이름 변경 신청서.hwp → 신청서(양식).hwp"
+        );
+
+        // A file with a different size at the same URL counts as replaced
+        let replaced = attachment("https://example.com/files/1", "신청서.hwp", Some("2048"));
+        let changes = AttachmentChanges::between(&[form], &[replaced]);
+        assert_eq!(changes.added, ["신청서.hwp"]);
+        assert_eq!(changes.removed, ["신청서.hwp"]);
+    }
+
+    #[test]
+    fn test_posts_differing_in_view_count_are_logically_equal() {
+        let post = |views: &str, content: &str| SsufidPost {
+            id: "512".to_string(),
+            url: "https://example.com/view/512".to_string(),
+            url_kind: UrlKind::Canonical,
+            author: Some("학사팀".to_string()),
+            title: "2025학년도 2학기 휴학 신청 안내".to_string(),
+            description: None,
+            category: vec!["학사".to_string()],
+            created_at: datetime!(2025-07-01 09:00 +9),
+            updated_at: None,
+            thumbnail: None,
+            content: content.to_string(),
+            attachments: vec![],
+            metadata: Some(
+                [
+                    ("views".to_string(), views.to_string()),
+                    ("board".to_string(), "notice".to_string()),
+                ]
+                .into(),
+            ),
+            author_detail: None,
+        };
+        let before = post("120", "<p>휴학 신청 기간:\n  7월 1일 ~ 7월 14일</p>");
+        // Only the view count went up and the body's line breaks were re-rendered
+        let after = post("187", "<p>휴학 신청 기간: 7월 1일 ~ 7월 14일</p>");
+
+        assert_ne!(before, after);
+        assert!(before.logical_eq(&after));
+        assert!(post_eq_ignoring(
+            &before,
+            &after,
+            &[PostField::MetadataKey("views")]
+        ));
+        assert!(post_eq_ignoring(&before, &after, &[PostField::Metadata]));
+        assert!(!post_eq_ignoring(&before, &after, &[]));
+
+        let mut retitled = after.clone();
+        retitled.title = "2025학년도 2학기 휴학 신청 기간 연장 안내".to_string();
+        assert!(!before.logical_eq(&retitled));
+        assert!(post_eq_ignoring(
+            &before,
+            &retitled,
+            &[PostField::Title, PostField::MetadataKey("views")]
+        ));
+    }
+
+    #[test]
+    fn test_merge_entries_detects_attachment_changes() {
+        let created_at = datetime!(2024-02-28 12:00 +9);
+        let now = datetime!(2024-02-29 12:00 +9);
+        let post = |attachments: Vec<Attachment>| SsufidPost {
+            id: "1".to_string(),
+            url: "https://example.com/board/view.php?idx=1".to_string(),
+            url_kind: UrlKind::Canonical,
+            author: None,
+            title: "장학금 신청 안내".to_string(),
+            description: None,
+            category: vec![],
+            created_at,
+            updated_at: None,
+            thumbnail: None,
+            content: "<p>첨부파일을 확인하세요.</p>".to_string(),
+            attachments,
+            metadata: None,
+            author_detail: None,
+        };
+        let form = attachment("https://example.com/files/1", "신청서.hwp", None);
+
+        let unchanged = merge_entries(
+            vec![post(vec![form.clone()])],
+            vec![post(vec![form.clone()])],
+            false,
+            now,
+            &CrawlCoverage::default(),
+        );
+        assert_eq!(unchanged[0].updated_at, None);
+        assert_eq!(unchanged[0].attachment_changes(), None);
+
+        let guide = attachment("https://example.com/files/2", "안내문.pdf", None);
+        let merged = merge_entries(
+            vec![post(vec![form])],
+            vec![post(vec![guide])],
+            false,
+            now,
+            &CrawlCoverage::default(),
+        );
+        assert_eq!(merged[0].updated_at, Some(now));
+        assert_eq!(merged[0].created_at, created_at);
+        assert_eq!(
+            merged[0].attachment_changes(),
+            Some(AttachmentChanges {
+                added: vec!["안내문.pdf".to_string()],
+                removed: vec!["신청서.hwp".to_string()],
+                renamed: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn test_mark_inline_attachments() {
+        let mut post = SsufidPost {
+            id: "1".to_string(),
+            url: "https://example.com/board/view.php?idx=1".to_string(),
+            url_kind: UrlKind::Canonical,
+            author: None,
+            title: "포스터 안내".to_string(),
+            description: None,
+            category: vec![],
+            created_at: OffsetDateTime::UNIX_EPOCH,
+            updated_at: None,
+            thumbnail: None,
+            content: r#"<p><img src="/upload/poster.png#view"></p>"#.to_string(),
+            attachments: vec![
+                Attachment::from_guess(
+                    "poster.png".to_string(),
+                    "https://example.com/upload/poster.png".to_string(),
+                ),
+                Attachment::from_guess(
+                    "신청서.hwp".to_string(),
+                    "https://example.com/upload/form.hwp".to_string(),
+                ),
+            ],
+            metadata: None,
+            author_detail: None,
+        };
+
+        post.mark_inline_attachments();
+
+        assert!(post.attachments[0].is_inline());
+        assert_eq!(
+            post.attachments[0].metadata.as_ref().unwrap()["inline"],
+            "true"
+        );
+        assert!(!post.attachments[1].is_inline());
+        assert_eq!(post.attachments[1].metadata, None);
+    }
+
+    #[test]
+    fn test_normalize_mime_type() {
+        assert_eq!(
+            normalize_mime_type("Application/PDF; charset=binary").as_deref(),
+            Some("application/pdf")
+        );
+        assert_eq!(
+            normalize_mime_type(" image/PNG ").as_deref(),
+            Some("image/png")
+        );
+        assert_eq!(normalize_mime_type("pdf"), None);
+        assert_eq!(normalize_mime_type("; charset=utf-8"), None);
+
+        let attachment = Attachment::from_content_type(
+            Some("첨부.pdf".to_string()),
+            "https://example.com/file".to_string(),
+            "",
+        );
+        assert_eq!(attachment.mime_type.as_deref(), Some("application/pdf"));
+    }
+
+    #[test]
+    fn test_normalize_cleans_scraped_title() {
+        let post = |title: &str, author: Option<&str>| SsufidPost {
+            id: "nbsp".to_string(),
+            url: "https://example.com/nbsp".to_string(),
+            url_kind: UrlKind::Canonical,
+            author: author.map(str::to_string),
+            title: title.to_string(),
+            description: None,
+            category: vec![],
+            created_at: datetime!(2025-03-02 00:00 +9),
+            updated_at: None,
+            thumbnail: None,
+            content: String::new(),
+            attachments: vec![],
+            metadata: None,
+            author_detail: None,
+        };
+        // A title as it appears on the infocom list
+        let mut scraped = post(
+            "[학부]\u{a0}2025-2학기\u{a0}\u{a0}캡스톤디자인 최종발표회 안내\u{a0}",
+            Some("\u{a0}"),
+        );
+        let clean = post("[학부] 2025-2학기 캡스톤디자인 최종발표회 안내", None);
+
+        assert!(!scraped.contents_eq(&clean));
+        scraped.normalize();
+        assert_eq!(scraped, clean);
+        assert!(scraped.contents_eq(&clean));
+    }
+
+    #[test]
+    fn test_normalize_resolves_lazy_images_and_thumbnail() {
+        let mut post = SsufidPost {
+            id: "lazy".to_string(),
+            url: "https://example.com/lazy".to_string(),
+            url_kind: UrlKind::Canonical,
+            author: None,
+            title: "행사 안내".to_string(),
+            description: None,
+            category: vec![],
+            created_at: datetime!(2025-03-02 00:00 +9),
+            updated_at: None,
+            thumbnail: Some("/img/loading.gif".to_string()),
+            content: r#"<img src="/img/loading.gif" data-src="/files/poster.jpg" alt="포스터">"#
+                .to_string(),
+            attachments: vec![],
+            metadata: None,
+            author_detail: None,
+        };
+        post.normalize();
+        assert_eq!(
+            post.content,
+            r#"<img src="/files/poster.jpg" alt="포스터">"#
+        );
+        assert_eq!(post.thumbnail.as_deref(), Some("/files/poster.jpg"));
+
+        // Normalizing again is a no-op, so it is not a change against the cached post
+        let normalized = post.clone();
+        post.normalize();
+        assert_eq!(post, normalized);
+    }
+
+    struct MockAgedPlugin;
+
+    impl SsufidPlugin for MockAgedPlugin {
+        const TITLE: &'static str = "Mock Aged";
+        const IDENTIFIER: &'static str = "mock.aged";
+        const DESCRIPTION: &'static str = "Mock plugin with posts of various ages";
+        const BASE_URL: &'static str = "https://example.com/aged";
+    }
+
+    impl SsufidPostPlugin for MockAgedPlugin {
+        async fn crawl(&self, posts_limit: u32) -> Result<Vec<SsufidPost>, PluginError> {
+            let now = OffsetDateTime::now_utc();
+            let mut posts = MockListPlugin.crawl(4).await?;
+            for (post, days) in posts.iter_mut().zip([1, 30, 120, 400]) {
+                post.created_at = now - time::Duration::days(days);
+            }
+            posts.truncate(posts_limit as usize);
+            Ok(posts)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_crawl_with_options_max_age() {
+        let options = CrawlOptions::new(10).max_age(Duration::from_secs(90 * 24 * 60 * 60));
+        let posts = MockAgedPlugin.crawl_with_options(&options).await.unwrap();
+        let ids = posts.into_iter().map(|p| p.id).collect::<Vec<_>>();
+        assert_eq!(ids, vec!["post-0", "post-1"]);
+
+        let posts = MockAgedPlugin
+            .crawl_with_options(&CrawlOptions::new(10))
+            .await
+            .unwrap();
+        assert_eq!(posts.len(), 4);
+
+        // max_age counts from the injected time
+        let options = options.now(OffsetDateTime::now_utc() + time::Duration::days(75));
+        let posts = MockAgedPlugin.crawl_with_options(&options).await.unwrap();
+        let ids = posts.into_iter().map(|p| p.id).collect::<Vec<_>>();
+        assert_eq!(ids, vec!["post-0"]);
+    }
+
+    #[tokio::test]
+    async fn test_run_max_age_applies_to_cached_posts() {
+        let cache_dir = "./max_age_cache_test";
+        let _ = tokio::fs::remove_dir_all(cache_dir).await;
+        let core = SsufidCore::new(cache_dir);
+        core.run(&MockAgedPlugin, &CrawlOptions::new(10))
+            .await
+            .unwrap();
+        core.save_cache().await.unwrap();
+
+        // Old cached posts outside max_age are not output either
+        let core = SsufidCore::new(cache_dir);
+        let options = CrawlOptions::new(10).max_age(Duration::from_secs(90 * 24 * 60 * 60));
+        let site = core.run(&MockAgedPlugin, &options).await.unwrap();
+        let ids = site
+            .items()
+            .iter()
+            .map(|post| post.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, ["post-0", "post-1"]);
+        assert_eq!(
+            core.cached_post_ids(MockAgedPlugin::IDENTIFIER)
+                .await
+                .unwrap()
+                .len(),
+            4
+        );
+        let _ = tokio::fs::remove_dir_all(cache_dir).await;
+    }
+
+    struct MockWarmupPlugin {
+        calls: std::sync::Mutex<Vec<&'static str>>,
+    }
+
+    impl SsufidPlugin for MockWarmupPlugin {
+        const TITLE: &'static str = "Mock Warmup";
+        const IDENTIFIER: &'static str = "mock.warmup";
+        const DESCRIPTION: &'static str = "Mock plugin recording its warmup";
+        const BASE_URL: &'static str = "https://example.com/warmup";
+    }
+
+    impl SsufidPostPlugin for MockWarmupPlugin {
+        async fn warmup(&self) -> Result<(), PluginError> {
+            self.calls.lock().unwrap().push("warmup");
+            Ok(())
+        }
+
+        async fn crawl(&self, posts_limit: u32) -> Result<Vec<SsufidPost>, PluginError> {
+            self.calls.lock().unwrap().push("crawl");
+            MockListPlugin.crawl(posts_limit).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_calls_warmup_before_crawl() {
+        let plugin = MockWarmupPlugin {
+            calls: std::sync::Mutex::new(vec![]),
+        };
+        let core = SsufidCore::new("./warmup_test");
+        let site = core.run(&plugin, &CrawlOptions::new(2)).await.unwrap();
+        assert_eq!(site.items().len(), 2);
+        assert_eq!(*plugin.calls.lock().unwrap(), ["warmup", "crawl"]);
+    }
+
+    struct MockCriticalPlugin;
+
+    impl SsufidPlugin for MockCriticalPlugin {
+        const TITLE: &'static str = "Mock Critical";
+        const IDENTIFIER: &'static str = "mock.critical";
+        const DESCRIPTION: &'static str = "Mock plugin declaring critical selectors";
+        const BASE_URL: &'static str = "https://example.com/critical";
+        const CRITICAL_SELECTORS: &'static [(&'static str, &'static str)] = &[
+            ("list_item", "ul.board > li"),
+            ("title", "div.view h3.title"),
+            ("content", "div.view div.content"),
+        ];
+    }
+
+    impl SsufidPostPlugin for MockCriticalPlugin {
+        async fn crawl(&self, posts_limit: u32) -> Result<Vec<SsufidPost>, PluginError> {
+            let mut posts = MockListPlugin.crawl(posts_limit).await?;
+            for post in &mut posts {
+                post.url = format!("{}/{}", Self::BASE_URL, post.id);
+            }
+            Ok(posts)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_health_check_names_broken_selectors() {
+        use wiremock::{Mock, MockServer, ResponseTemplate, matchers::path};
+
+        let mock_server = MockServer::start().await;
+        Mock::given(path("/critical"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(r#"<ul class="board"><li>공지</li></ul>"#),
+            )
+            .mount(&mock_server)
+            .await;
+        // The content area's class changed, so only the content selector fails to match
+        Mock::given(path("/critical/post-0"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<div class="view"><h3 class="title">공지</h3><div class="body">본문</div></div>"#,
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let status = crate::fetch::with_origin_override(
+            MockCriticalPlugin::BASE_URL,
+            &mock_server.uri(),
+            MockCriticalPlugin.health_check(),
+        )
+        .await
+        .unwrap();
+        assert!(!status.ok);
+        assert_eq!(status.posts_seen, 1);
+        assert_eq!(status.broken_selectors, ["content"]);
+    }
+
+    /// Like [`MockCriticalPlugin`], served from `base_url` and counting its crawls.
+    struct MockCountingPlugin {
+        base_url: String,
+        crawls: std::sync::atomic::AtomicU32,
+    }
+
+    impl SsufidPlugin for MockCountingPlugin {
+        const TITLE: &'static str = "Mock Counting";
+        const IDENTIFIER: &'static str = "mock.counting";
+        const DESCRIPTION: &'static str = "Mock plugin counting its crawls";
+        const BASE_URL: &'static str = "https://example.com/counting";
+        const CRITICAL_SELECTORS: &'static [(&'static str, &'static str)] =
+            MockCriticalPlugin::CRITICAL_SELECTORS;
+
+        fn base_url(&self) -> std::borrow::Cow<'_, str> {
+            std::borrow::Cow::Borrowed(&self.base_url)
+        }
+    }
+
+    impl SsufidPostPlugin for MockCountingPlugin {
+        async fn crawl(&self, posts_limit: u32) -> Result<Vec<SsufidPost>, PluginError> {
+            self.crawls
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            let mut posts = MockListPlugin.crawl(posts_limit).await?;
+            for post in &mut posts {
+                post.url = format!("{}/{}", self.base_url, post.id);
+            }
+            Ok(posts)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_health_check_uses_base_url_and_crawls_once() {
+        use wiremock::{Mock, MockServer, ResponseTemplate, matchers::path};
+
+        let mock_server = MockServer::start().await;
+        Mock::given(path("/board"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(r#"<ul class="board"><li>공지</li></ul>"#),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(path("/board/post-0"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<div class="view"><h3 class="title">공지</h3><div class="content">본문</div></div>"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let plugin = MockCountingPlugin {
+            base_url: format!("{}/board", mock_server.uri()),
+            crawls: Default::default(),
+        };
+        let status = plugin.health_check().await.unwrap();
+        assert!(status.ok, "{:?}", status.broken_selectors);
+        assert_eq!(plugin.crawls.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_preflight_fails_fast_on_unreachable_host() {
+        let mock_server = wiremock::MockServer::start().await;
+        // Falls back to GET when HEAD is rejected; an error status still crawls
+        wiremock::Mock::given(wiremock::matchers::method("HEAD"))
+            .respond_with(wiremock::ResponseTemplate::new(405))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        wiremock::Mock::given(wiremock::matchers::method("GET"))
+            .respond_with(wiremock::ResponseTemplate::new(503))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let plugin = MockWarmupPlugin {
+            calls: std::sync::Mutex::new(vec![]),
+        };
+        let core = SsufidCore::new("./preflight_test");
+        let options = CrawlOptions::new(2).preflight(true);
+
+        crate::fetch::with_origin_override(
+            MockWarmupPlugin::BASE_URL,
+            &mock_server.uri(),
+            core.run(&plugin, &options),
+        )
+        .await
+        .unwrap();
+        assert_eq!(*plugin.calls.lock().unwrap(), ["warmup", "crawl"]);
+        plugin.calls.lock().unwrap().clear();
+
+        // A host that does not even accept connections
+        let closed = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let closed_uri = format!("http://{}", closed.local_addr().unwrap());
+        drop(closed);
+        let error = crate::fetch::with_origin_override(
+            MockWarmupPlugin::BASE_URL,
+            &closed_uri,
+            core.run(&plugin, &options),
+        )
+        .await
+        .unwrap_err();
+        assert!(
+            error
+                .plugin_error()
+                .unwrap()
+                .message()
+                .starts_with("Host unreachable: example.com did not answer HEAD")
+        );
+        assert!(plugin.calls.lock().unwrap().is_empty());
+    }
+
+    /// Crawls with a client that sends its own default headers.
+    struct MockClientPlugin {
+        client: reqwest::Client,
+    }
+
+    impl SsufidPlugin for MockClientPlugin {
+        const TITLE: &'static str = "Mock Client";
+        const IDENTIFIER: &'static str = "mock.client";
+        const DESCRIPTION: &'static str = "Mock plugin with its own client";
+        const BASE_URL: &'static str = "https://example.com/client";
+
+        fn http_client(&self) -> reqwest::Client {
+            self.client.clone()
+        }
+    }
+
+    impl SsufidPostPlugin for MockClientPlugin {
+        async fn crawl(&self, posts_limit: u32) -> Result<Vec<SsufidPost>, PluginError> {
+            MockListPlugin.crawl(posts_limit).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_preflight_uses_plugin_client() {
+        use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+
+        let mock_server = MockServer::start().await;
+        Mock::given(matchers::method("HEAD"))
+            .and(matchers::header("x-plugin", "mock"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(
+            "x-plugin",
+            reqwest::header::HeaderValue::from_static("mock"),
+        );
+        let plugin = MockClientPlugin {
+            client: reqwest::Client::builder()
+                .default_headers(headers)
+                .build()
+                .unwrap(),
+        };
+        let core = SsufidCore::new("./preflight_test");
+        let options = CrawlOptions::new(2).preflight(true);
+
+        let site = crate::fetch::with_origin_override(
+            MockClientPlugin::BASE_URL,
+            &mock_server.uri(),
+            core.run(&plugin, &options),
+        )
+        .await
+        .unwrap();
+        assert_eq!(site.items.len(), 2);
+    }
+
+    /// Lists posts only for a session started by a form login at `/login`.
+    struct MockLoginPlugin {
+        base_url: String,
+        client: reqwest::Client,
+        calls: std::sync::Mutex<Vec<&'static str>>,
+    }
+
+    impl SsufidPlugin for MockLoginPlugin {
+        const TITLE: &'static str = "Mock Login";
+        const IDENTIFIER: &'static str = "mock.login";
+        const DESCRIPTION: &'static str = "Mock plugin behind a login form";
+        const BASE_URL: &'static str = "https://example.com/login";
+    }
+
+    impl SsufidPostPlugin for MockLoginPlugin {
+        async fn authenticate(&self, credentials: &Credentials) -> Result<(), PluginError> {
+            self.calls.lock().unwrap().push("authenticate");
+            let form = url::form_urlencoded::Serializer::new(String::new())
+                .append_pair("username", credentials.username())
+                .append_pair("password", credentials.password())
+                .finish();
+            self.client
+                .post(format!("{}/login", self.base_url))
+                .header("content-type", "application/x-www-form-urlencoded")
+                .body(form)
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status)
+                .map_err(|e| PluginError::request::<Self>(e.to_string()))?;
+            Ok(())
+        }
+
+        async fn warmup(&self) -> Result<(), PluginError> {
+            self.calls.lock().unwrap().push("warmup");
+            Ok(())
+        }
+
+        async fn crawl(&self, posts_limit: u32) -> Result<Vec<SsufidPost>, PluginError> {
+            self.calls.lock().unwrap().push("crawl");
+            self.client
+                .get(format!("{}/list", self.base_url))
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status)
+                .map_err(|e| PluginError::request::<Self>(e.to_string()))?;
+            MockListPlugin.crawl(posts_limit).await
+        }
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_run_authenticates_before_warmup_without_logging_credentials() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{body_string_contains, header, method, path},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/login"))
+            .and(body_string_contains("username=20250001"))
+            .and(body_string_contains("password=s3cret"))
+            .respond_with(
+                ResponseTemplate::new(200).insert_header("set-cookie", "session=ok; Path=/"),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/list"))
+            .and(header("cookie", "session=ok"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/list"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+        let plugin = || MockLoginPlugin {
+            base_url: server.uri(),
+            client: crate::fetch::session_client(),
+            calls: std::sync::Mutex::new(vec![]),
+        };
+
+        let core = SsufidCore::new("./login_test").with_credentials(CredentialStore::new().insert(
+            MockLoginPlugin::IDENTIFIER,
+            Credentials::new("20250001", "s3cret"),
+        ));
+        let logged_in = plugin();
+        let site = core.run(&logged_in, &CrawlOptions::new(2)).await.unwrap();
+        assert_eq!(site.items().len(), 2);
+        assert_eq!(
+            *logged_in.calls.lock().unwrap(),
+            ["authenticate", "warmup", "crawl"]
+        );
+        assert!(logs_contain("Authenticating before the crawl"));
+        assert!(!logs_contain("20250001"));
+        assert!(!logs_contain("s3cret"));
+
+        // Without credentials there is no login, and the list request is rejected
+        let core = SsufidCore::new("./login_test").with_credentials(CredentialStore::new());
+        let anonymous = plugin();
+        assert!(core.run(&anonymous, &CrawlOptions::new(2)).await.is_err());
+        assert_eq!(*anonymous.calls.lock().unwrap(), ["warmup", "crawl"]);
+    }
+
+    /// Serves posts `1..` dated one day apart, with `ids` being the ones still on the board.
+    struct MockTakedownPlugin {
+        ids: &'static [u8],
+    }
+
+    impl SsufidPlugin for MockTakedownPlugin {
+        const TITLE: &'static str = "Mock Takedown";
+        const IDENTIFIER: &'static str = "mock.takedown";
+        const DESCRIPTION: &'static str = "Mock plugin whose posts get taken down";
+        const BASE_URL: &'static str = "https://example.com/takedown";
+    }
+
+    impl SsufidPostPlugin for MockTakedownPlugin {
+        async fn crawl(&self, _posts_limit: u32) -> Result<Vec<SsufidPost>, PluginError> {
+            let posts = MockListPlugin.crawl(1).await?;
+            Ok(self
+                .ids
+                .iter()
+                .map(|&id| SsufidPost {
+                    id: id.to_string(),
+                    url: format!("https://example.com/takedown/{id}"),
+                    created_at: datetime!(2025-03-01 00:00 +9)
+                        + Duration::from_secs(86400 * id as u64),
+                    ..posts[0].clone()
+                })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_vanished_post_becomes_tombstone() {
+        let cache_dir = "./takedown_cache_test";
+        let _ = tokio::fs::remove_dir_all(cache_dir).await;
+        let options = CrawlOptions::new(3);
+        let leap_day = datetime!(2024-02-29 12:00 +9);
+        let core = SsufidCore::new(cache_dir).with_clock(FixedClock(leap_day));
+        core.run(&MockTakedownPlugin { ids: &[1, 2, 3] }, &options)
+            .await
+            .unwrap();
+
+        // Posts 1 and 2 only fell out of the crawl range; they were not taken down
+        let site = core
+            .run(&MockTakedownPlugin { ids: &[3, 4] }, &options)
+            .await
+            .unwrap();
+        assert_eq!(site.items().len(), 4);
+        assert!(site.items().iter().all(|post| !post.is_removed()));
+
+        // Post 3 was in range, but must be missing on consecutive runs to be marked deleted
+        for _ in 1..CrawlCoverage::TAKEDOWN_MISSES {
+            let site = core
+                .run(&MockTakedownPlugin { ids: &[2, 4] }, &options)
+                .await
+                .unwrap();
+            assert!(site.items().iter().all(|post| !post.is_removed()));
+        }
+        let site = core
+            .run(&MockTakedownPlugin { ids: &[2, 4] }, &options)
+            .await
+            .unwrap();
+        let tombstone = site.items().iter().find(|post| post.id == "3").unwrap();
+        assert_eq!(tombstone.removed_at(), Some(leap_day));
+
+        #[cfg(feature = "rss")]
+        {
+            let now = core.now();
+            let feed_ids = |options: &super::rss::FeedOptions| {
+                options
+                    .apply(site.items().to_vec(), now)
+                    .into_iter()
+                    .map(|post| post.id)
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(
+                feed_ids(&super::rss::FeedOptions::default()),
+                ["4", "2", "1"]
+            );
+            assert_eq!(
+                feed_ids(&super::rss::FeedOptions::default().include_removed(true)),
+                ["4", "3", "2", "1"]
+            );
+        }
+
+        // The mark goes away once it is back
+        let site = core
+            .run(&MockTakedownPlugin { ids: &[2, 3, 4] }, &options)
+            .await
+            .unwrap();
+        assert!(site.items().iter().all(|post| !post.is_removed()));
+        let _ = tokio::fs::remove_dir_all(cache_dir).await;
+    }
+
+    #[tokio::test]
+    async fn test_pinned_and_retried_posts_do_not_widen_takedown_window() {
+        let now = datetime!(2025-04-01 00:00 +9);
+        let cached = MockTakedownPlugin {
+            ids: &[1, 2, 3, 4, 5],
+        }
+        .crawl(5)
+        .await
+        .unwrap();
+        // Old post 1 is pinned at the top of the list or refetched from the retry queue
+        let crawled = MockTakedownPlugin { ids: &[1, 4, 5] }
+            .crawl(2)
+            .await
+            .unwrap();
+        let pinned = CrawlCoverage {
+            pinned: ["1".to_string()].into(),
+            ..Default::default()
+        };
+        let retried = CrawlCoverage {
+            retried: ["1".to_string()].into(),
+            ..Default::default()
+        };
+        for coverage in [pinned, retried] {
+            let mut merged = cached.clone();
+            for _ in 0..CrawlCoverage::TAKEDOWN_MISSES {
+                merged = merge_entries(merged, crawled.clone(), false, now, &coverage);
+            }
+            assert!(
+                merged
+                    .iter()
+                    .all(|post| !post.is_removed() && post.missed_crawls() == 0),
+                "{coverage:?}"
+            );
+        }
+
+        // Without the pinned flag, posts 2 and 3 would count as missing from the range
+        let missed = |coverage: &CrawlCoverage| {
+            merge_entries(cached.clone(), crawled.clone(), false, now, coverage)
+                .into_iter()
+                .filter(|post| post.missed_crawls() == 1)
+                .map(|post| post.id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(missed(&CrawlCoverage::default()), ["2", "3"]);
+        // Posts fetched beyond the limit do not widen the range
+        let limited = CrawlCoverage {
+            posts_limit: Some(2),
+            ..Default::default()
+        };
+        assert!(missed(&limited).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_post_with_failed_detail_is_not_taken_down() {
+        let now = datetime!(2025-04-01 00:00 +9);
+        let mut merged = MockTakedownPlugin {
+            ids: &[1, 2, 3, 4, 5],
+        }
+        .crawl(5)
+        .await
+        .unwrap();
+        // Post 4 is on the list but its detail page failed
+        let crawled = MockTakedownPlugin { ids: &[1, 2, 3, 5] }
+            .crawl(5)
+            .await
+            .unwrap();
+        let failed = CrawlCoverage {
+            failed: ["4".to_string()].into(),
+            ..Default::default()
+        };
+        for _ in 0..CrawlCoverage::TAKEDOWN_MISSES {
+            merged = merge_entries(merged, crawled.clone(), false, now, &failed);
+        }
+        assert!(merged.iter().all(|post| !post.is_removed()));
+
+        // Once gone from the list too, it is marked deleted after consecutive misses
+        let missing = CrawlCoverage::default();
+        for missed in 1..CrawlCoverage::TAKEDOWN_MISSES {
+            merged = merge_entries(merged, crawled.clone(), false, now, &missing);
+            let post = merged.iter().find(|post| post.id == "4").unwrap();
+            assert_eq!(post.missed_crawls(), missed);
+            assert!(!post.is_removed());
+        }
+        merged = merge_entries(merged, crawled, false, now, &missing);
+        let post = merged.iter().find(|post| post.id == "4").unwrap();
+        assert_eq!(post.removed_at(), Some(now));
+        assert_eq!(post.missed_crawls(), 0);
+    }
+
+    /// Fails on the detail page of post 2 until `recovered` is set.
+    struct MockRetryPlugin {
+        recovered: std::sync::atomic::AtomicBool,
+    }
+
+    impl SsufidPlugin for MockRetryPlugin {
+        const TITLE: &'static str = "Mock Retry";
+        const IDENTIFIER: &'static str = "mock.retry";
+        const DESCRIPTION: &'static str = "Mock plugin whose detail page times out";
+        const BASE_URL: &'static str = "https://example.com/retry";
+    }
+
+    impl MockRetryPlugin {
+        fn post(id: &str) -> SsufidPost {
+            SsufidPost {
+                id: id.to_string(),
+                url: format!("https://example.com/retry/{id}"),
+                url_kind: UrlKind::Canonical,
+                author: None,
+                title: format!("Post {id}"),
+                description: None,
+                category: vec![],
+                created_at: datetime!(2025-03-02 00:00 +9),
+                updated_at: None,
+                thumbnail: None,
+                content: String::new(),
+                attachments: vec![],
+                metadata: None,
+                author_detail: None,
+            }
+        }
+    }
+
+    impl SsufidPostPlugin for MockRetryPlugin {
+        async fn crawl(&self, _posts_limit: u32) -> Result<Vec<SsufidPost>, PluginError> {
+            // Posts 1 and 2 are listed, but post 2 is dropped because its detail page failed
+            let error = PluginError::request::<Self>("detail page timed out".to_string());
+            super::report_post_failure::<Self>("2", "https://example.com/retry/2", &error);
+            Ok(vec![Self::post("1")])
+        }
+
+        async fn fetch_single(&self, id_or_url: &str) -> Result<Option<SsufidPost>, PluginError> {
+            if !self.recovered.load(std::sync::atomic::Ordering::SeqCst) {
+                return Err(PluginError::request::<Self>("status 503".to_string()));
+            }
+            let id = id_or_url.rsplit('/').next().unwrap_or(id_or_url);
+            Ok(Some(Self::post(id)))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_post_is_fetched_on_next_run() {
+        let cache_dir = "./retry_queue_cache_test";
+        let _ = tokio::fs::remove_dir_all(cache_dir).await;
+        let plugin = MockRetryPlugin {
+            recovered: false.into(),
+        };
+        let options = CrawlOptions::new(2);
+        let ids = |site: &super::SsufidSiteData| {
+            site.items()
+                .iter()
+                .map(|post| post.id.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let core = SsufidCore::new(cache_dir);
+        let site = core.run(&plugin, &options).await.unwrap();
+        assert_eq!(ids(&site), ["1"]);
+        core.save_cache().await.unwrap();
+        let queue = super::RetryQueue::load(std::path::Path::new(cache_dir))
+            .await
+            .unwrap();
+        assert_eq!(queue.entries().len(), 1);
+        assert_eq!(queue.entries()[0].id, "2");
+
+        // The next run refetches the queued post
+        plugin
+            .recovered
+            .store(true, std::sync::atomic::Ordering::SeqCst);
+        let core = SsufidCore::new(cache_dir);
+        let site = core.run(&plugin, &options).await.unwrap();
+        assert!(ids(&site).contains(&"2".to_string()));
+        core.save_cache().await.unwrap();
+        let queue = super::RetryQueue::load(std::path::Path::new(cache_dir))
+            .await
+            .unwrap();
+        // Only post 2, which failed again in this run, stays queued
+        assert_eq!(queue.entries().len(), 1);
+        assert_eq!(queue.entries()[0].attempts, 1);
+        let _ = tokio::fs::remove_dir_all(cache_dir).await;
+    }
+
+    struct MockCategoryIdPlugin;
+
+    impl SsufidPlugin for MockCategoryIdPlugin {
+        const TITLE: &'static str = "Mock Category Id";
+        const IDENTIFIER: &'static str = "mock.category_id";
+        const DESCRIPTION: &'static str = "Mock plugin that started numbering posts per category";
+        const BASE_URL: &'static str = "https://example.com/sub/sub03_01.php";
+        const ID_CATEGORY_PARAM: Option<&'static str> = Some("category");
+    }
+
+    impl SsufidPostPlugin for MockCategoryIdPlugin {
+        async fn crawl(&self, _posts_limit: u32) -> Result<Vec<SsufidPost>, PluginError> {
+            Ok(vec![SsufidPost {
+                id: "1-77".to_string(),
+                url: "https://example.com/sub/sub03_01.php?mode=view&idx=77&category=1".to_string(),
+                url_kind: UrlKind::Canonical,
+                author: None,
+                title: "수강신청 안내".to_string(),
+                description: None,
+                category: vec![],
+                created_at: datetime!(2025-03-02 00:00 +9),
+                updated_at: None,
+                thumbnail: None,
+                content: "<p>본문</p>".to_string(),
+                attachments: vec![],
+                metadata: None,
+                author_detail: None,
+            }])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cached_ids_gain_category_prefix() {
+        let cache_dir = "./category_id_cache_test";
+        let _ = tokio::fs::remove_dir_all(cache_dir).await;
+        let core = SsufidCore::new(cache_dir);
+        // Before ID_CATEGORY_PARAM was set, the same post was stored by idx alone
+        let mut cached = MockCategoryIdPlugin.crawl(1).await.unwrap();
+        cached[0].id = "77".to_string();
+        core.cache_new_posts::<MockCategoryIdPlugin>(cached)
+            .await
+            .unwrap();
+
+        let site = core
+            .run(&MockCategoryIdPlugin, &CrawlOptions::new(1))
+            .await
+            .unwrap();
+        let _ = tokio::fs::remove_dir_all(cache_dir).await;
+        assert_eq!(
+            site.items()
+                .iter()
+                .map(|post| (post.id.as_str(), post.is_removed()))
+                .collect::<Vec<_>>(),
+            [("1-77", false)]
+        );
+    }
+
+    struct MockVersionedPlugin<const V: u32> {
+        author: &'static str,
+    }
+
+    impl<const V: u32> SsufidPlugin for MockVersionedPlugin<V> {
+        const TITLE: &'static str = "Mock Versioned";
+        const IDENTIFIER: &'static str = "mock.versioned";
+        const DESCRIPTION: &'static str = "Mock plugin for version tests";
+        const BASE_URL: &'static str = "https://example.com/versioned";
+        const VERSION: u32 = V;
+    }
+
+    impl<const V: u32> SsufidPostPlugin for MockVersionedPlugin<V> {
+        async fn crawl(&self, _posts_limit: u32) -> Result<Vec<SsufidPost>, PluginError> {
+            Ok(vec![SsufidPost {
+                id: "1".to_string(),
+                url: "https://example.com/versioned/1".to_string(),
+                url_kind: UrlKind::Canonical,
+                author: Some(self.author.to_string()),
+                title: "수강신청 안내".to_string(),
+                description: None,
+                category: vec![],
+                created_at: datetime!(2025-03-02 00:00 +9),
+                updated_at: None,
+                thumbnail: None,
+                content: "<p>본문</p>".to_string(),
+                attachments: vec![],
+                metadata: None,
+                author_detail: None,
+            }])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_version_change_replaces_cached_posts() {
+        let cache_dir = "./versioned_cache_test";
+        let _ = tokio::fs::remove_dir_all(cache_dir).await;
+        let options = CrawlOptions::new(1);
+
+        let core = SsufidCore::new(cache_dir);
+        core.run(
+            &MockVersionedPlugin::<1> {
+                author: "학사팀"
+            },
+            &options,
+        )
+        .await
+        .unwrap();
+        core.save_cache().await.unwrap();
+        assert_eq!(
+            tokio::fs::read_to_string(format!("{cache_dir}/mock.versioned.version"))
+                .await
+                .unwrap(),
+            "1"
+        );
+
+        // The same version trusts the cache with the same content
+        let core = SsufidCore::new(cache_dir);
+        let site = core
+            .run(
+                &MockVersionedPlugin::<1> {
+                    author: "교务처"
+                },
+                &options,
+            )
+            .await
+            .unwrap();
+        assert_eq!(site.plugin_version(), Some(1));
+        assert_eq!(site.items()[0].author.as_deref(), Some("학사팀"));
+
+        // A new version replaces it with the reparsed post
+        let site = core
+            .run(
+                &MockVersionedPlugin::<2> {
+                    author: "교무처"
+                },
+                &options,
+            )
+            .await
+            .unwrap();
+        assert_eq!(site.plugin_version(), Some(2));
+        assert_eq!(site.items()[0].author.as_deref(), Some("교무처"));
+        assert_eq!(site.items()[0].updated_at, None);
+        core.save_cache().await.unwrap();
+        assert_eq!(
+            tokio::fs::read_to_string(format!("{cache_dir}/mock.versioned.version"))
+                .await
+                .unwrap(),
+            "2"
+        );
+
+        tokio::fs::remove_dir_all(cache_dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_partial_reparse_keeps_cached_version() {
+        let cache_dir = "./partial_reparse_cache_test";
+        let _ = tokio::fs::remove_dir_all(cache_dir).await;
+        tokio::fs::create_dir_all(cache_dir).await.unwrap();
+        // Post 0 fell out of the crawl range and is cached at the old version
+        let mut cached = MockVersionedPlugin::<1> {
+            author: "학사팀"
+        }
+        .crawl(1)
+        .await
+        .unwrap();
+        cached.push(SsufidPost {
+            id: "0".to_string(),
+            url: "https://example.com/versioned/0".to_string(),
+            created_at: datetime!(2025-02-01 00:00 +9),
+            ..cached[0].clone()
+        });
+        tokio::fs::write(
+            format!("{cache_dir}/mock.versioned.json"),
+            serde_json::to_string(&cached).unwrap(),
+        )
+        .await
+        .unwrap();
+
+        let core = SsufidCore::new(cache_dir);
+        let site = core
+            .run(
+                &MockVersionedPlugin::<2> {
+                    author: "교무처"
+                },
+                &CrawlOptions::new(1),
+            )
+            .await
+            .unwrap();
+        assert_eq!(site.items().len(), 2);
+        core.save_cache().await.unwrap();
+
+        // Post 0 was not reparsed, so the new version is not recorded
+        let version_path = format!("{cache_dir}/mock.versioned.version");
+        assert!(!std::path::Path::new(&version_path).exists());
+        tokio::fs::remove_dir_all(cache_dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_latest_post_id_default() {
+        assert_eq!(
+            MockListPlugin.latest_post_id().await.unwrap(),
+            Some("post-0".to_string())
+        );
+    }
+
+    struct MockBoardPlugin {
+        list_url: String,
+    }
+
+    impl SsufidPlugin for MockBoardPlugin {
+        const TITLE: &'static str = "Mock Board";
+        const IDENTIFIER: &'static str = "mock.board";
+        const DESCRIPTION: &'static str = "Mock board plugin for health check tests";
+        const BASE_URL: &'static str = "https://example.com/board";
+    }
+
+    impl SsufidPostPlugin for MockBoardPlugin {
+        async fn crawl(&self, posts_limit: u32) -> Result<Vec<SsufidPost>, PluginError> {
+            let selector = scraper::Selector::parse("li.item a").unwrap();
+            let html =
+                crate::fetch::fetch_text::<Self>(&crate::fetch::default_client(), &self.list_url)
+                    .await?;
+            let document = scraper::Html::parse_document(&html);
+            Ok(document
+                .select(&selector)
+                .take(posts_limit as usize)
+                .map(|link| SsufidPost {
+                    id: link.value().attr("href").unwrap_or_default().to_string(),
+                    url: format!(
+                        "{}{}",
+                        Self::BASE_URL,
+                        link.value().attr("href").unwrap_or_default()
+                    ),
+                    url_kind: UrlKind::Canonical,
+                    author: None,
+                    title: link.text().collect(),
+                    description: None,
+                    category: vec![],
+                    created_at: datetime!(2025-03-02 00:00 +9),
+                    updated_at: None,
+                    thumbnail: None,
+                    content: String::new(),
+                    attachments: vec![],
+                    metadata: None,
+                    author_detail: None,
+                })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_health_check_parses_first_list_page() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/ok"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ul><li class="item"><a href="/view?idx=1">수강신청 안내</a></li></ul>"#,
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/renamed"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ul><li class="row"><a href="/view?idx=1">수강신청 안내</a></li></ul>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let healthy = MockBoardPlugin {
+            list_url: format!("{}/ok", server.uri()),
+        };
+        let status = healthy.health_check().await.unwrap();
+        assert!(status.ok);
+        assert_eq!(status.posts_seen, 1);
+
+        let broken = MockBoardPlugin {
+            list_url: format!("{}/renamed", server.uri()),
+        };
+        let status = broken.health_check().await.unwrap();
+        assert!(!status.ok);
+        assert_eq!(status.posts_seen, 0);
+    }
 }
 
 #[cfg(feature = "rss")]
@@ -0,0 +1,94 @@
+use std::time::Duration;
+
+use time::OffsetDateTime;
+
+use super::SsufidPost;
+
+/// Options for a single crawl, see [`SsufidPostPlugin::crawl_with_options`](super::SsufidPostPlugin::crawl_with_options).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrawlOptions {
+    pub posts_limit: u32,
+    /// Drops posts created more than `max_age` ago, even within `posts_limit`.
+    pub max_age: Option<Duration>,
+    /// Whether to fetch detail pages. Without them posts only carry what the list page shows
+    /// (id, url, title, created_at) and have empty content. Plugins that cannot skip them (see
+    /// [`SKIPS_DETAIL`](super::SsufidPostPlugin::SKIPS_DETAIL)) fetch them anyway and return
+    /// full posts.
+    pub detail: bool,
+    /// How many consecutive empty list pages to skip past before pagination gives up, for
+    /// boards with gaps such as a first page holding only pinned posts.
+    pub empty_page_tolerance: u32,
+    /// Whether [`SsufidCore::run`](crate::SsufidCore::run) first checks that the plugin's
+    /// [`base_url`](super::SsufidPlugin::base_url) is reachable with
+    /// [`check_reachable`](crate::fetch::check_reachable), so that
+    /// an unreachable host fails the crawl at once rather than deep in pagination. Off by
+    /// default, since it costs a request.
+    pub preflight: bool,
+    /// The time the crawl runs at, which [`max_age`](Self::max_age) counts back from. `None`
+    /// reads the system clock; [`SsufidCore::run`](crate::SsufidCore::run) sets it from the
+    /// core's [`Clock`](super::Clock).
+    pub now: Option<OffsetDateTime>,
+}
+
+impl CrawlOptions {
+    pub fn new(posts_limit: u32) -> Self {
+        Self {
+            posts_limit,
+            max_age: None,
+            detail: true,
+            empty_page_tolerance: 1,
+            preflight: false,
+            now: None,
+        }
+    }
+
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    pub fn detail(mut self, detail: bool) -> Self {
+        self.detail = detail;
+        self
+    }
+
+    pub fn empty_page_tolerance(mut self, empty_page_tolerance: u32) -> Self {
+        self.empty_page_tolerance = empty_page_tolerance;
+        self
+    }
+
+    pub fn preflight(mut self, preflight: bool) -> Self {
+        self.preflight = preflight;
+        self
+    }
+
+    pub fn now(mut self, now: OffsetDateTime) -> Self {
+        self.now = Some(now);
+        self
+    }
+
+    /// The time the crawl runs at: [`now`](Self::now) if set, or the system clock.
+    pub fn current_time(&self) -> OffsetDateTime {
+        self.now.unwrap_or_else(OffsetDateTime::now_utc)
+    }
+
+    /// The oldest `created_at` accepted at `now`, if any.
+    pub fn cutoff(&self, now: OffsetDateTime) -> Option<OffsetDateTime> {
+        let max_age = time::Duration::try_from(self.max_age?).ok()?;
+        now.checked_sub(max_age)
+    }
+
+    /// Keeps the posts accepted at `now`.
+    pub fn filter(&self, mut posts: Vec<SsufidPost>, now: OffsetDateTime) -> Vec<SsufidPost> {
+        if let Some(cutoff) = self.cutoff(now) {
+            posts.retain(|post| post.created_at >= cutoff);
+        }
+        posts
+    }
+}
+
+impl From<u32> for CrawlOptions {
+    fn from(posts_limit: u32) -> Self {
+        Self::new(posts_limit)
+    }
+}
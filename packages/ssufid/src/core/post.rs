@@ -1,43 +1,324 @@
 use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Attachment {
     pub url: String,
     pub name: Option<String>,
     pub mime_type: Option<String>,
+    /// Extra flags, e.g. `inline = "true"` for files that are also shown in the content.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<BTreeMap<String, String>>,
 }
 
 impl Attachment {
+    /// The `metadata` key set on attachments that the content also shows as an `<img>`.
+    pub const INLINE_KEY: &'static str = "inline";
+
+    /// Whether the content also shows this attachment, so renderers may skip it.
+    pub fn is_inline(&self) -> bool {
+        self.metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get(Self::INLINE_KEY))
+            .is_some_and(|value| value == "true")
+    }
+
     pub fn from_guess(name: String, url: String) -> Self {
         let mime = mime_guess::from_path(&name).first().map(|m| m.to_string());
         Self {
             url,
             name: Some(name),
             mime_type: mime,
+            metadata: None,
+        }
+    }
+
+    /// Builds an attachment from a `Content-Type` value, e.g. the header of a HEAD response.
+    /// Falls back to guessing from `name` when the value is not a usable MIME type.
+    pub fn from_content_type(name: Option<String>, url: String, content_type: &str) -> Self {
+        let mime_type = normalize_mime_type(content_type).or_else(|| {
+            name.as_deref()
+                .and_then(|name| mime_guess::from_path(name).first())
+                .map(|m| m.to_string())
+        });
+        Self {
+            url,
+            name,
+            mime_type,
+            metadata: None,
+        }
+    }
+}
+
+impl Attachment {
+    /// The `metadata` key holding the file size in bytes, when the board shows it.
+    pub const SIZE_KEY: &'static str = "size";
+
+    /// The `metadata` key holding when the file was uploaded, in RFC 3339, when the board
+    /// shows it.
+    pub const POSTED_AT_KEY: &'static str = "posted_at";
+
+    /// Labels boards put in front of an attachment's upload date, e.g. `DATE : 2025-06-04`.
+    const POSTED_AT_LABELS: &'static [&'static str] = &["DATE", "등록일", "작성일"];
+
+    /// Fills in the [size](Self::SIZE_KEY) and [upload date](Self::POSTED_AT_KEY) that `text`,
+    /// the link text or the list item around it, shows for the file, e.g.
+    /// `교내장학금 신청 매뉴얼.pdf (374.8K) DATE : 2025-06-04 11:58:11`, so that they are known
+    /// without downloading it. A size shown at the end of the name is removed from the name.
+    pub fn with_listed_details(mut self, text: &str) -> Self {
+        if let Some((_, size)) = crate::text::split_file_size(text) {
+            self.metadata
+                .get_or_insert_default()
+                .insert(Self::SIZE_KEY.to_string(), size.to_string());
+        }
+        let posted_at = Self::POSTED_AT_LABELS.iter().find_map(|label| {
+            let start = text.to_ascii_uppercase().find(label)?;
+            crate::datetime::parse_kst_labeled(&text[start..])
+        });
+        if let Some(posted_at) = posted_at.and_then(|at| {
+            at.format(&time::format_description::well_known::Rfc3339)
+                .ok()
+        }) {
+            self.metadata
+                .get_or_insert_default()
+                .insert(Self::POSTED_AT_KEY.to_string(), posted_at);
+        }
+        if let Some(name) = &mut self.name
+            && let Some((stripped, _)) = crate::text::split_file_size(name)
+                .filter(|(stripped, _)| !stripped.is_empty())
+                .filter(|_| name.trim_end().ends_with(')'))
+        {
+            *name = stripped.to_string();
+            if self.mime_type.is_none() {
+                self.mime_type = mime_guess::from_path(&*name).first().map(|m| m.to_string());
+            }
+        }
+        self
+    }
+
+    /// The file size in bytes, see [`SIZE_KEY`](Self::SIZE_KEY).
+    pub fn size(&self) -> Option<u64> {
+        self.metadata.as_ref()?.get(Self::SIZE_KEY)?.parse().ok()
+    }
+
+    /// When the file was uploaded, see [`POSTED_AT_KEY`](Self::POSTED_AT_KEY).
+    pub fn posted_at(&self) -> Option<time::OffsetDateTime> {
+        time::OffsetDateTime::parse(
+            self.metadata.as_ref()?.get(Self::POSTED_AT_KEY)?,
+            &time::format_description::well_known::Rfc3339,
+        )
+        .ok()
+    }
+
+    /// What identifies the file itself regardless of its name: its URL and, when known, its
+    /// size, so that a corrected file uploaded under the same URL still counts as a new file.
+    fn identity(&self) -> (&str, Option<&str>) {
+        (
+            self.url.trim(),
+            self.metadata
+                .as_ref()
+                .and_then(|metadata| metadata.get(Self::SIZE_KEY))
+                .map(String::as_str),
+        )
+    }
+
+    /// The name shown for the attachment in change summaries: its name, or its URL.
+    fn label(&self) -> &str {
+        self.name
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .unwrap_or(self.url.trim())
+    }
+}
+
+/// An attachment whose file stayed the same under a new name.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct AttachmentRename {
+    pub from: String,
+    pub to: String,
+}
+
+/// How a post's attachments changed between two crawls, stored as JSON under
+/// [`SsufidPost::ATTACHMENT_CHANGES_KEY`]. Entries are attachment names, or URLs for unnamed
+/// attachments.
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub struct AttachmentChanges {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub renamed: Vec<AttachmentRename>,
+}
+
+impl AttachmentChanges {
+    /// Compares attachments by URL and, when known, [size](Attachment::SIZE_KEY); the same file
+    /// under another name is a rename, anything else is added or removed.
+    pub fn between(old: &[Attachment], new: &[Attachment]) -> Self {
+        let old_by_identity = old
+            .iter()
+            .map(|attachment| (attachment.identity(), attachment))
+            .collect::<BTreeMap<_, _>>();
+        let new_by_identity = new
+            .iter()
+            .map(|attachment| (attachment.identity(), attachment))
+            .collect::<BTreeMap<_, _>>();
+        let mut changes = Self::default();
+        for (identity, attachment) in &new_by_identity {
+            match old_by_identity.get(identity) {
+                None => changes.added.push(attachment.label().to_string()),
+                Some(old) if old.label() != attachment.label() => {
+                    changes.renamed.push(AttachmentRename {
+                        from: old.label().to_string(),
+                        to: attachment.label().to_string(),
+                    })
+                }
+                Some(_) => {}
+            }
         }
+        changes.removed = old_by_identity
+            .iter()
+            .filter(|(identity, _)| !new_by_identity.contains_key(*identity))
+            .map(|(_, attachment)| attachment.label().to_string())
+            .collect();
+        changes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.renamed.is_empty()
+    }
+}
+
+impl std::fmt::Display for AttachmentChanges {
+    /// A summary for update notices, e.g. `첨부파일 변경: 추가 신청서(수정).hwp, 삭제 신청서.hwp`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::new();
+        if !self.added.is_empty() {
+            parts.push(format!("추가 {}", self.added.join(", ")));
+        }
+        if !self.removed.is_empty() {
+            parts.push(format!("삭제 {}", self.removed.join(", ")));
+        }
+        if !self.renamed.is_empty() {
+            let renamed = self
+                .renamed
+                .iter()
+                .map(|rename| format!("{} → {}", rename.from, rename.to))
+                .collect::<Vec<_>>();
+            parts.push(format!("이름 변경 {}", renamed.join(", ")));
+        }
+        write!(f, "첨부파일 변경: {}", parts.join(", "))
+    }
+}
+
+/// Normalizes a `Content-Type` value into a bare MIME type: parameters such as `; charset=`
+/// are dropped and the result is lowercased, so `"Application/PDF; charset=binary"` becomes
+/// `"application/pdf"`. Returns `None` for values that are not `type/subtype`.
+pub fn normalize_mime_type(content_type: &str) -> Option<String> {
+    let essence = content_type.split(';').next()?.trim();
+    let (kind, subtype) = essence.split_once('/')?;
+    if kind.trim().is_empty() || subtype.trim().is_empty() {
+        return None;
+    }
+    Some(format!("{}/{}", kind.trim(), subtype.trim()).to_ascii_lowercase())
+}
+
+/// A post's author split into a person and the department they write for, e.g. `학사팀 / 김OO`.
+/// See [`parse_author_kr`](crate::text::parse_author_kr).
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Author {
+    /// The person, or the department itself when the board shows no person.
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub department: Option<String>,
+}
+
+/// What [`SsufidPost::url`] points at.
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum UrlKind {
+    /// The post's own detail page.
+    #[default]
+    Canonical,
+    /// The post has no detail page but is shown on the list page, under `#fragment` if any.
+    ListAnchor { fragment: Option<String> },
+    /// The post has no page at all; the URL only identifies it.
+    Synthetic,
+}
+
+impl UrlKind {
+    pub fn is_canonical(&self) -> bool {
+        matches!(self, UrlKind::Canonical)
     }
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct SsufidPost {
     pub id: String,
     pub url: String,
+    #[serde(default, skip_serializing_if = "UrlKind::is_canonical")]
+    pub url_kind: UrlKind,
     pub author: Option<String>,
     pub title: String,
     pub description: Option<String>,
     #[serde(default)]
     pub category: Vec<String>,
     #[serde(with = "time::serde::rfc3339")]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(with = "String", extend("format" = "date-time"))
+    )]
     pub created_at: time::OffsetDateTime,
     #[serde(with = "time::serde::rfc3339::option")]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(with = "Option<String>", extend("format" = "date-time"))
+    )]
     pub updated_at: Option<time::OffsetDateTime>,
     pub thumbnail: Option<String>,
     pub content: String,
     #[serde(default)]
     pub attachments: Vec<Attachment>,
     pub metadata: Option<BTreeMap<String, String>>,
+    /// [`author`](Self::author) split into a name and a department, where the board shows
+    /// both. `author` keeps the plugin's original string for compatibility.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author_detail: Option<Author>,
+}
+
+/// Orders post ids numerically when both are integers, so that `"999" < "1000"`.
+///
+/// Non-numeric ids (e.g. chemeng's `notice_<date>_<title>` pseudo-ids) compare
+/// lexicographically and sort after all numeric ids, which keeps the order total.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PostIdOrd<'a>(pub &'a str);
+
+impl PostIdOrd<'_> {
+    fn numeric(&self) -> Option<u64> {
+        self.0.trim().parse().ok()
+    }
+}
+
+impl Ord for PostIdOrd<'_> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        use std::cmp::Ordering;
+        match (self.numeric(), other.numeric()) {
+            (Some(a), Some(b)) => a.cmp(&b).then_with(|| self.0.cmp(other.0)),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => self.0.cmp(other.0),
+        }
+    }
+}
+
+impl PartialOrd for PostIdOrd<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 impl PartialOrd for SsufidPost {
@@ -47,12 +328,289 @@ impl PartialOrd for SsufidPost {
 }
 
 impl SsufidPost {
+    /// The URL readers should follow: `url` for canonical posts, otherwise the site's
+    /// `base_url` with the post's list anchor, if any.
+    pub fn link(&self, base_url: &str) -> String {
+        let base_url = base_url.split('#').next().unwrap_or(base_url);
+        match &self.url_kind {
+            UrlKind::Canonical => self.url.clone(),
+            UrlKind::ListAnchor {
+                fragment: Some(fragment),
+            } => format!("{base_url}#{fragment}"),
+            UrlKind::ListAnchor { fragment: None } | UrlKind::Synthetic => base_url.to_string(),
+        }
+    }
+
+    /// Whether the title or the text of the content [looks mis-decoded](crate::text::looks_mojibake).
+    pub fn looks_mojibake(&self) -> bool {
+        crate::text::looks_mojibake(&self.title)
+            || crate::text::looks_mojibake(&crate::content::plain_text(&self.content))
+    }
+
+    /// Cleans the title and author, resolves lazily loaded images before sanitizing and replaces a
+    /// placeholder thumbnail with the first image of the content.
+    pub fn normalize(&mut self) {
+        self.title = crate::text::clean_text(&self.title);
+        self.author = self
+            .author
+            .take()
+            .and_then(|author| crate::text::clean_author(&author));
+        if let Ok(content) = crate::content::resolve_lazy_images(&self.content) {
+            self.content = content;
+        }
+        if self
+            .thumbnail
+            .as_deref()
+            .is_some_and(crate::content::is_placeholder_src)
+        {
+            self.thumbnail = crate::content::image_sources(&self.content)
+                .into_iter()
+                .find(|src| !crate::content::is_placeholder_src(src));
+        }
+    }
+
+    /// Marks attachments whose URL also appears as an `<img src>` in the content with
+    /// `metadata["inline"] = "true"`. Both URLs are resolved against the post URL and
+    /// compared without their fragments.
+    pub fn mark_inline_attachments(&mut self) {
+        if self.attachments.is_empty() {
+            return;
+        }
+        let base = url::Url::parse(&self.url).ok();
+        let canonicalize = |src: &str| {
+            let mut url = match &base {
+                Some(base) => base.join(src).ok()?,
+                None => url::Url::parse(src).ok()?,
+            };
+            url.set_fragment(None);
+            Some(url)
+        };
+        let inline = crate::content::image_sources(&self.content)
+            .iter()
+            .filter_map(|src| canonicalize(src))
+            .collect::<std::collections::HashSet<_>>();
+        for attachment in &mut self.attachments {
+            if canonicalize(&attachment.url).is_some_and(|url| inline.contains(&url)) {
+                attachment
+                    .metadata
+                    .get_or_insert_default()
+                    .insert(Attachment::INLINE_KEY.to_string(), "true".to_string());
+            }
+        }
+    }
+
+    /// The `metadata` key holding when a post disappeared from its board, as RFC 3339.
+    pub const REMOVED_AT_KEY: &'static str = "removed_at";
+
+    /// When the post disappeared from its board, if it did. Such a tombstone is kept in the
+    /// cache for audits but left out of feeds by default.
+    pub fn removed_at(&self) -> Option<time::OffsetDateTime> {
+        self.metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get(Self::REMOVED_AT_KEY))
+            .and_then(|value| {
+                time::OffsetDateTime::parse(value, &time::format_description::well_known::Rfc3339)
+                    .ok()
+            })
+    }
+
+    pub fn is_removed(&self) -> bool {
+        self.removed_at().is_some()
+    }
+
+    /// Records that the post disappeared from its board at `at`.
+    pub fn mark_removed(&mut self, at: time::OffsetDateTime) {
+        if let Ok(at) = at.format(&time::format_description::well_known::Rfc3339) {
+            self.metadata
+                .get_or_insert_with(BTreeMap::new)
+                .insert(Self::REMOVED_AT_KEY.to_string(), at);
+        }
+    }
+
+    /// The `metadata` key counting the consecutive crawls that should have returned the post
+    /// but did not, before it is [marked as removed](Self::mark_removed).
+    pub const MISSED_CRAWLS_KEY: &'static str = "missed_crawls";
+
+    /// How many consecutive crawls the post has been missing from.
+    pub fn missed_crawls(&self) -> u32 {
+        self.metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get(Self::MISSED_CRAWLS_KEY))
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(0)
+    }
+
+    /// Records that a crawl missed the post, returning how many consecutive crawls have.
+    pub fn record_missed_crawl(&mut self) -> u32 {
+        let missed = self.missed_crawls() + 1;
+        self.metadata
+            .get_or_insert_with(BTreeMap::new)
+            .insert(Self::MISSED_CRAWLS_KEY.to_string(), missed.to_string());
+        missed
+    }
+
+    /// Forgets the crawls the post was missing from, e.g. once it is seen again.
+    pub fn clear_missed_crawls(&mut self) {
+        if let Some(metadata) = &mut self.metadata {
+            metadata.remove(Self::MISSED_CRAWLS_KEY);
+            if metadata.is_empty() {
+                self.metadata = None;
+            }
+        }
+    }
+
+    /// The `metadata` key set to `"true"` when the post's page was too large to parse as is and
+    /// had its styles and `data:` URIs stripped first, so inline images are missing from it.
+    pub const CONTENT_STRIPPED_KEY: &'static str = "content_stripped";
+
+    /// Records that the post's page was stripped before parsing, see
+    /// [`crate::fetch::FetchedPage`].
+    pub fn mark_content_stripped(&mut self) {
+        self.metadata
+            .get_or_insert_with(BTreeMap::new)
+            .insert(Self::CONTENT_STRIPPED_KEY.to_string(), "true".to_string());
+    }
+
+    /// The `metadata` key holding the [`AttachmentChanges`] of the post's last update, as JSON.
+    pub const ATTACHMENT_CHANGES_KEY: &'static str = "attachment_changes";
+
+    /// Records how the post's attachments changed since the previous crawl.
+    pub fn set_attachment_changes(&mut self, changes: &AttachmentChanges) {
+        if let Ok(json) = serde_json::to_string(changes) {
+            self.metadata
+                .get_or_insert_with(BTreeMap::new)
+                .insert(Self::ATTACHMENT_CHANGES_KEY.to_string(), json);
+        }
+    }
+
+    pub fn attachment_changes(&self) -> Option<AttachmentChanges> {
+        self.metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get(Self::ATTACHMENT_CHANGES_KEY))
+            .and_then(|json| serde_json::from_str(json).ok())
+    }
+
+    /// The `metadata` key set to `"fallback"` when the post's content was found by a generic
+    /// fallback rather than the plugin's own selector, see [`crate::html::select_content`].
+    pub const CONTENT_SOURCE_KEY: &'static str = "content_source";
+
+    /// Records that the post's content came from a fallback selector.
+    pub fn mark_content_fallback(&mut self) {
+        self.metadata
+            .get_or_insert_with(BTreeMap::new)
+            .insert(Self::CONTENT_SOURCE_KEY.to_string(), "fallback".to_string());
+    }
+
+    /// The `metadata` key set to `"true"` when the post is listed but its page is members-only,
+    /// so it carries only what the list shows and no content.
+    pub const RESTRICTED_KEY: &'static str = "restricted";
+
+    /// Records that the post's page is members-only, see [`crate::html::is_restricted`].
+    pub fn mark_restricted(&mut self) {
+        self.metadata
+            .get_or_insert_with(BTreeMap::new)
+            .insert(Self::RESTRICTED_KEY.to_string(), "true".to_string());
+    }
+
+    pub fn is_restricted(&self) -> bool {
+        self.metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get(Self::RESTRICTED_KEY))
+            .is_some_and(|value| value == "true")
+    }
+
     pub fn contents_eq(&self, other: &SsufidPost) -> bool {
         self.id.trim() == other.id.trim()
             && self.title.trim() == other.title.trim()
             && self.category == other.category
-            && self.content.trim() == other.content.trim()
+            && self.author_detail == other.author_detail
+            && self.content_hash() == other.content_hash()
+    }
+
+    /// A hash of the content with whitespace collapsed, so re-rendered markup that only
+    /// differs in indentation or line breaks hashes the same.
+    pub fn content_hash(&self) -> String {
+        let content = self
+            .content
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        Sha256::digest(content.as_bytes())
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect()
     }
+
+    /// Whether both posts are the same notice: the same id, title, creation date and
+    /// [content hash](Self::content_hash), whatever their volatile fields such as view counts
+    /// in `metadata` say.
+    pub fn logical_eq(&self, other: &SsufidPost) -> bool {
+        self.id == other.id
+            && self.title == other.title
+            && self.created_at == other.created_at
+            && self.content_hash() == other.content_hash()
+    }
+}
+
+/// A field of [`SsufidPost`], for comparisons that leave some out with [`post_eq_ignoring`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PostField {
+    Id,
+    Url,
+    UrlKind,
+    Author,
+    Title,
+    Description,
+    Category,
+    CreatedAt,
+    UpdatedAt,
+    Thumbnail,
+    /// Compared by [content hash](SsufidPost::content_hash), so whitespace never counts.
+    Content,
+    Attachments,
+    /// All of `metadata`.
+    Metadata,
+    /// One key of `metadata`, e.g. a board's view count.
+    MetadataKey(&'static str),
+    AuthorDetail,
+}
+
+/// Whether `a` and `b` are equal in every field except the `ignored` ones.
+pub fn post_eq_ignoring(a: &SsufidPost, b: &SsufidPost, ignored: &[PostField]) -> bool {
+    let ignores = |field: PostField| ignored.contains(&field);
+    let metadata_eq = || metadata_without(a, ignored) == metadata_without(b, ignored);
+    (ignores(PostField::Id) || a.id == b.id)
+        && (ignores(PostField::Url) || a.url == b.url)
+        && (ignores(PostField::UrlKind) || a.url_kind == b.url_kind)
+        && (ignores(PostField::Author) || a.author == b.author)
+        && (ignores(PostField::Title) || a.title == b.title)
+        && (ignores(PostField::Description) || a.description == b.description)
+        && (ignores(PostField::Category) || a.category == b.category)
+        && (ignores(PostField::CreatedAt) || a.created_at == b.created_at)
+        && (ignores(PostField::UpdatedAt) || a.updated_at == b.updated_at)
+        && (ignores(PostField::Thumbnail) || a.thumbnail == b.thumbnail)
+        && (ignores(PostField::Content) || a.content_hash() == b.content_hash())
+        && (ignores(PostField::Attachments) || a.attachments == b.attachments)
+        && (ignores(PostField::Metadata) || metadata_eq())
+        && (ignores(PostField::AuthorDetail) || a.author_detail == b.author_detail)
+}
+
+/// The metadata of `post` without the keys named by [`PostField::MetadataKey`] in `ignored`.
+fn metadata_without<'a>(
+    post: &'a SsufidPost,
+    ignored: &[PostField],
+) -> Option<Vec<(&'a String, &'a String)>> {
+    let is_ignored = |key: &str| {
+        ignored
+            .iter()
+            .any(|field| matches!(field, PostField::MetadataKey(ignored) if *ignored == key))
+    };
+    post.metadata.as_ref().map(|metadata| {
+        metadata
+            .iter()
+            .filter(|(key, _)| !is_ignored(key))
+            .collect()
+    })
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
@@ -61,6 +619,52 @@ pub struct SsufidSiteData {
     pub(crate) source: String,
     pub(crate) description: String,
     pub(crate) items: Vec<SsufidPost>,
+    /// The [`SsufidPlugin::VERSION`](super::SsufidPlugin::VERSION) that parsed `items`, if the
+    /// data comes from a single plugin.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) plugin_version: Option<u32>,
+    /// The checksum of the site, set by [`seal`](Self::seal).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) integrity: Option<crate::output::Integrity>,
+}
+
+impl SsufidSiteData {
+    pub fn new(
+        title: impl Into<String>,
+        source: impl Into<String>,
+        description: impl Into<String>,
+        items: Vec<SsufidPost>,
+    ) -> Self {
+        Self {
+            title: title.into(),
+            source: source.into(),
+            description: description.into(),
+            items,
+            plugin_version: None,
+            integrity: None,
+        }
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn plugin_version(&self) -> Option<u32> {
+        self.plugin_version
+    }
+
+    pub fn items(&self) -> &[SsufidPost] {
+        &self.items
+    }
+
+    pub fn items_mut(&mut self) -> &mut [SsufidPost] {
+        &mut self.items
+    }
+
+    /// Drops the posts [marked as removed](SsufidPost::is_removed) from their board.
+    pub fn drop_removed(&mut self) {
+        self.items.retain(|post| !post.is_removed());
+    }
 }
 
 #[cfg(feature = "rss")]
@@ -68,4 +672,9 @@ impl SsufidSiteData {
     pub fn to_rss(self) -> ::rss::Channel {
         self.into()
     }
+
+    /// Like [`to_rss`](Self::to_rss), but keeps only the items allowed by `options`.
+    pub fn to_rss_with(self, options: &super::rss::FeedOptions) -> ::rss::Channel {
+        super::rss::to_channel(self, options, time::OffsetDateTime::now_utc())
+    }
 }
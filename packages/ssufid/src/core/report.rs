@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Cadence, SsufidPlugin};
+
+/// Static description of a plugin, as declared by its [`SsufidPlugin`] constants.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PluginInfo {
+    pub identifier: String,
+    pub title: String,
+    pub description: String,
+    pub base_url: String,
+    /// See [`SsufidPlugin::VERSION`].
+    #[serde(default = "first_version")]
+    pub version: u32,
+    /// See [`SsufidPlugin::CADENCE`].
+    #[serde(default)]
+    pub cadence: Cadence,
+}
+
+fn first_version() -> u32 {
+    1
+}
+
+impl PluginInfo {
+    pub fn of<T: SsufidPlugin>() -> Self {
+        Self {
+            identifier: T::IDENTIFIER.to_string(),
+            title: T::TITLE.to_string(),
+            description: T::DESCRIPTION.to_string(),
+            base_url: T::BASE_URL.to_string(),
+            version: T::VERSION,
+            cadence: T::CADENCE,
+        }
+    }
+}
+
+/// Outcome of a single plugin run.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CrawlReport {
+    pub plugin: PluginInfo,
+    #[serde(with = "time::serde::rfc3339")]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(with = "String", extend("format" = "date-time"))
+    )]
+    pub started_at: time::OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(with = "String", extend("format" = "date-time"))
+    )]
+    pub finished_at: time::OffsetDateTime,
+    /// Number of items written, or `None` when the run failed.
+    pub items: Option<usize>,
+    /// Number of crawled posts that [look mis-decoded](crate::text::looks_mojibake).
+    #[serde(default)]
+    pub mojibake: usize,
+    pub error: Option<String>,
+    /// Debugging context of the error, e.g. the HTML a selector failed on. See
+    /// [`PluginError::detail`](crate::PluginError::detail).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_detail: Option<String>,
+}
+
+impl CrawlReport {
+    pub fn success<T: SsufidPlugin>(
+        started_at: time::OffsetDateTime,
+        finished_at: time::OffsetDateTime,
+        items: usize,
+    ) -> Self {
+        Self {
+            plugin: PluginInfo::of::<T>(),
+            started_at,
+            finished_at,
+            items: Some(items),
+            mojibake: 0,
+            error: None,
+            error_detail: None,
+        }
+    }
+
+    pub fn failure<T: SsufidPlugin>(
+        started_at: time::OffsetDateTime,
+        finished_at: time::OffsetDateTime,
+        error: String,
+    ) -> Self {
+        Self {
+            plugin: PluginInfo::of::<T>(),
+            started_at,
+            finished_at,
+            items: None,
+            mojibake: 0,
+            error: Some(error),
+            error_detail: None,
+        }
+    }
+
+    pub fn with_mojibake(self, mojibake: usize) -> Self {
+        Self { mojibake, ..self }
+    }
+
+    pub fn with_error_detail(self, error_detail: Option<String>) -> Self {
+        Self {
+            error_detail,
+            ..self
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Result of a plugin health check, see [`SsufidPostPlugin::health_check`](super::SsufidPostPlugin::health_check).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HealthStatus {
+    pub ok: bool,
+    /// Number of posts parsed from the list page, `0` for a bare reachability check.
+    pub posts_seen: usize,
+    pub latency: std::time::Duration,
+    /// Names of the plugin's [`CRITICAL_SELECTORS`](super::SsufidPlugin::CRITICAL_SELECTORS)
+    /// that matched nothing, see [`crate::html::health_check`].
+    pub broken_selectors: Vec<&'static str>,
+}
@@ -0,0 +1,247 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::SsufidPlugin;
+use crate::error::{Error, PluginError};
+
+/// A post whose detail page failed during a crawl, reported with [`report_post_failure`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PostFailure {
+    pub plugin: String,
+    pub id: String,
+    pub url: String,
+    pub error: String,
+    /// See [`PluginError::is_retryable`].
+    pub retryable: bool,
+}
+
+tokio::task_local! {
+    static POST_FAILURES: Arc<Mutex<Vec<PostFailure>>>;
+}
+
+/// Records that the plugin `T` skipped the post `id` at `url` because of `error`, for
+/// plugins that leave failed posts out of a crawl instead of failing it. When the crawl runs
+/// under [`SsufidCore::run`](crate::SsufidCore::run), retryable failures are queued in the
+/// [`RetryQueue`] and fetched again on the next run; otherwise this does nothing.
+pub fn report_post_failure<T: SsufidPlugin>(id: &str, url: &str, error: &PluginError) {
+    let _ = POST_FAILURES.try_with(|failures| {
+        failures
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(PostFailure {
+                plugin: T::IDENTIFIER.to_string(),
+                id: id.to_string(),
+                url: url.to_string(),
+                error: error.to_string(),
+                retryable: error.is_retryable(),
+            })
+    });
+}
+
+/// Runs `future`, collecting the failures reported on the task polling it.
+pub(crate) async fn collect_post_failures<F: std::future::Future>(
+    future: F,
+) -> (F::Output, Vec<PostFailure>) {
+    let failures = Arc::new(Mutex::new(vec![]));
+    let output = POST_FAILURES.scope(Arc::clone(&failures), future).await;
+    let failures = std::mem::take(&mut *failures.lock().unwrap_or_else(|e| e.into_inner()));
+    (output, failures)
+}
+
+/// A post waiting in the [`RetryQueue`].
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct RetryEntry {
+    pub plugin: String,
+    pub id: String,
+    pub url: String,
+    /// How many times fetching the post has failed so far.
+    pub attempts: u32,
+    pub last_error: String,
+}
+
+/// Posts that failed with a retryable error, persisted in the state directory as
+/// `retry_queue.json` so that they are fetched again on the next run instead of waiting for a
+/// full crawl to include them. Entries are kept oldest first.
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub struct RetryQueue {
+    entries: Vec<RetryEntry>,
+}
+
+impl RetryQueue {
+    pub const FILE: &'static str = "retry_queue.json";
+    /// Failures after which a post is dropped from the queue.
+    pub const MAX_ATTEMPTS: u32 = 5;
+    /// Entries kept per plugin; the oldest are dropped beyond it.
+    pub const MAX_ENTRIES_PER_PLUGIN: usize = 100;
+
+    pub fn path(state_dir: &Path) -> PathBuf {
+        state_dir.join(Self::FILE)
+    }
+
+    /// Reads the queue in `state_dir`, which is empty if it was never saved.
+    pub async fn load(state_dir: &Path) -> Result<Self, Error> {
+        match tokio::fs::read_to_string(Self::path(state_dir)).await {
+            Ok(json) => Ok(serde_json::from_str(&json)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Writes the queue to `state_dir` through a temporary file.
+    pub async fn save(&self, state_dir: &Path) -> Result<(), Error> {
+        tokio::fs::create_dir_all(state_dir).await?;
+        let path = Self::path(state_dir);
+        let tmp = path.with_extension("json.tmp");
+        tokio::fs::write(&tmp, serde_json::to_string_pretty(self)?).await?;
+        tokio::fs::rename(&tmp, &path).await?;
+        Ok(())
+    }
+
+    pub fn entries(&self) -> &[RetryEntry] {
+        &self.entries
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Queues a retryable `failure`, counting it as another attempt if the post is already
+    /// queued. Returns the entry if this failure used up its attempts and it was dropped
+    /// instead; non-retryable failures are not queued.
+    pub fn enqueue(&mut self, failure: PostFailure) -> Option<RetryEntry> {
+        if !failure.retryable {
+            return None;
+        }
+        let attempts = match self
+            .entries
+            .iter()
+            .position(|entry| entry.plugin == failure.plugin && entry.id == failure.id)
+        {
+            Some(index) => self.entries.remove(index).attempts,
+            None => 0,
+        };
+        self.requeue(
+            RetryEntry {
+                plugin: failure.plugin,
+                id: failure.id,
+                url: failure.url,
+                attempts,
+                last_error: String::new(),
+            },
+            failure.error,
+        )
+    }
+
+    /// Puts a [drained](Self::drain) `entry` back after it failed again with `error`. Returns
+    /// the entry if it used up its attempts and was dropped instead.
+    pub fn requeue(&mut self, mut entry: RetryEntry, error: String) -> Option<RetryEntry> {
+        entry.attempts += 1;
+        entry.last_error = error;
+        if entry.attempts >= Self::MAX_ATTEMPTS {
+            return Some(entry);
+        }
+        let plugin = entry.plugin.clone();
+        self.entries.push(entry);
+        let queued = self.entries.iter().filter(|e| e.plugin == plugin).count();
+        if queued > Self::MAX_ENTRIES_PER_PLUGIN {
+            let oldest = self.entries.iter().position(|e| e.plugin == plugin)?;
+            let dropped = self.entries.remove(oldest);
+            tracing::warn!(
+                plugin = %dropped.plugin,
+                id = %dropped.id,
+                "Retry queue is full, dropping the oldest entry"
+            );
+        }
+        None
+    }
+
+    /// Removes and returns up to `limit` of the oldest entries of `plugin`.
+    pub fn drain(&mut self, plugin: &str, limit: usize) -> Vec<RetryEntry> {
+        let mut drained = Vec::new();
+        self.entries.retain(|entry| {
+            if entry.plugin == plugin && drained.len() < limit {
+                drained.push(entry.clone());
+                false
+            } else {
+                true
+            }
+        });
+        drained
+    }
+
+    /// Drops the entry of `plugin` with `id`, e.g. because a crawl fetched the post.
+    pub fn remove(&mut self, plugin: &str, id: &str) {
+        self.entries
+            .retain(|entry| !(entry.plugin == plugin && entry.id == id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failure(id: &str, retryable: bool) -> PostFailure {
+        PostFailure {
+            plugin: "mock.retry".to_string(),
+            id: id.to_string(),
+            url: format!("https://example.com/view?idx={id}"),
+            error: "timed out".to_string(),
+            retryable,
+        }
+    }
+
+    #[test]
+    fn test_enqueue_counts_attempts() {
+        let mut queue = RetryQueue::default();
+        assert_eq!(queue.enqueue(failure("1", true)), None);
+        assert_eq!(queue.enqueue(failure("2", true)), None);
+        // Parse failures would fail again, so they are not queued
+        assert_eq!(queue.enqueue(failure("3", false)), None);
+        assert_eq!(queue.enqueue(failure("1", true)), None);
+
+        let attempts = queue
+            .entries()
+            .iter()
+            .map(|entry| (entry.id.as_str(), entry.attempts))
+            .collect::<Vec<_>>();
+        assert_eq!(attempts, [("2", 1), ("1", 2)]);
+
+        assert_eq!(queue.drain("other.plugin", 10), []);
+        let drained = queue.drain("mock.retry", 1);
+        assert_eq!(drained.len(), 1);
+        assert_eq!(drained[0].id, "2");
+        assert_eq!(queue.entries().len(), 1);
+    }
+
+    #[test]
+    fn test_entry_is_dropped_after_max_attempts() {
+        let mut queue = RetryQueue::default();
+        for _ in 1..RetryQueue::MAX_ATTEMPTS {
+            assert_eq!(queue.enqueue(failure("1", true)), None);
+        }
+        let entry = queue.drain("mock.retry", 10).remove(0);
+        assert_eq!(entry.attempts, RetryQueue::MAX_ATTEMPTS - 1);
+
+        let dropped = queue.requeue(entry, "status 503".to_string()).unwrap();
+        assert_eq!(dropped.attempts, RetryQueue::MAX_ATTEMPTS);
+        assert_eq!(dropped.last_error, "status 503");
+        assert!(queue.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_queue_persists_in_state_dir() {
+        let dir = std::env::temp_dir().join(format!("ssufid-retry-queue-{}", std::process::id()));
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+        assert!(RetryQueue::load(&dir).await.unwrap().is_empty());
+
+        let mut queue = RetryQueue::default();
+        queue.enqueue(failure("1", true));
+        queue.save(&dir).await.unwrap();
+        assert_eq!(RetryQueue::load(&dir).await.unwrap(), queue);
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+}
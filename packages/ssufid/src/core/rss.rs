@@ -1,14 +1,91 @@
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, time::Duration};
 
+use super::{SsufidPost, SsufidSiteData};
+use crate::datetime::{to_rfc822, to_rfc3339};
 use rss::{
     Category, ChannelBuilder, Enclosure, ItemBuilder,
     extension::{Extension, ExtensionBuilder},
 };
-use time::format_description::well_known::{Rfc2822, Rfc3339};
+use time::OffsetDateTime;
 
-use super::{SsufidPost, SsufidSiteData};
+pub(crate) const ATOM_NAMESPACE: &str = "http://www.w3.org/2005/Atom";
+
+/// Limits applied to the items of a serialized feed, regardless of how many posts the site data
+/// holds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedOptions {
+    pub max_items: usize,
+    /// Drops items created more than `max_age` ago.
+    pub max_age: Option<Duration>,
+    /// Keeps items [removed from their board](SsufidPost::is_removed), e.g. for audits.
+    pub include_removed: bool,
+}
+
+impl Default for FeedOptions {
+    fn default() -> Self {
+        Self {
+            max_items: Self::DEFAULT_MAX_ITEMS,
+            max_age: None,
+            include_removed: false,
+        }
+    }
+}
 
-const ATOM_NAMESPACE: &str = "http://www.w3.org/2005/Atom";
+impl FeedOptions {
+    pub const DEFAULT_MAX_ITEMS: usize = 50;
+
+    pub fn max_items(mut self, max_items: usize) -> Self {
+        self.max_items = max_items;
+        self
+    }
+
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    pub fn include_removed(mut self, include_removed: bool) -> Self {
+        self.include_removed = include_removed;
+        self
+    }
+
+    /// The creation time before which items are dropped at `now`, if `max_age` is set.
+    pub fn cutoff(&self, now: OffsetDateTime) -> Option<OffsetDateTime> {
+        self.max_age
+            .and_then(|max_age| time::Duration::try_from(max_age).ok())
+            .and_then(|max_age| now.checked_sub(max_age))
+    }
+
+    /// Sorts `items` newest-first, then drops removed items unless `include_removed` is set and
+    /// items older than `max_age` at `now`, and keeps at most `max_items` of the rest.
+    pub fn apply(&self, mut items: Vec<SsufidPost>, now: OffsetDateTime) -> Vec<SsufidPost> {
+        items.sort_by_key(|post| std::cmp::Reverse(post.created_at));
+        if !self.include_removed {
+            items.retain(|post| !post.is_removed());
+        }
+        if let Some(cutoff) = self.cutoff(now) {
+            items.retain(|post| post.created_at >= cutoff);
+        }
+        items.truncate(self.max_items);
+        items
+    }
+}
+
+/// Builds a channel from `site` with `options` applied at `now`. The channel's `lastBuildDate`
+/// is the creation time of the newest included item.
+pub fn to_channel(
+    site: SsufidSiteData,
+    options: &FeedOptions,
+    now: OffsetDateTime,
+) -> rss::Channel {
+    let items = options.apply(site.items, now);
+    let last_build_date = items
+        .first()
+        .and_then(|post| to_rfc822(post.created_at).ok());
+    let mut channel: rss::Channel = SsufidSiteData { items, ..site }.into();
+    channel.set_last_build_date(last_build_date);
+    channel
+}
 
 impl From<SsufidPost> for rss::Item {
     fn from(post: SsufidPost) -> Self {
@@ -24,7 +101,7 @@ impl From<SsufidPost> for rss::Item {
         builder
             .title(post.title)
             .link(post.url.clone())
-            .pub_date(post.created_at.format(&Rfc2822).unwrap())
+            .pub_date(to_rfc822(post.created_at).unwrap())
             .guid::<rss::Guid>(rss::Guid {
                 value: post.id,
                 permalink: false,
@@ -66,7 +143,7 @@ impl From<SsufidPost> for rss::Item {
         if let Some(updated_at) = post.updated_at {
             let extension = ExtensionBuilder::default()
                 .name("atom:updated")
-                .value(updated_at.format(&Rfc3339).unwrap())
+                .value(to_rfc3339(updated_at).unwrap())
                 .build();
             builder.extension((
                 ATOM_NAMESPACE.into(),
@@ -81,16 +158,21 @@ impl From<SsufidPost> for rss::Item {
 
 impl From<SsufidSiteData> for rss::Channel {
     fn from(site: SsufidSiteData) -> Self {
+        let items = site
+            .items
+            .into_iter()
+            .map(|post| {
+                let link = post.link(&site.source);
+                let mut item: rss::Item = post.into();
+                item.set_link(link);
+                item
+            })
+            .collect::<Vec<rss::Item>>();
         ChannelBuilder::default()
             .title(site.title)
             .link(site.source)
             .description(site.description)
-            .items(
-                site.items
-                    .into_iter()
-                    .map(SsufidPost::into)
-                    .collect::<Vec<rss::Item>>(),
-            )
+            .items(items)
             .namespace(("atom".to_string(), ATOM_NAMESPACE.to_string()))
             .namespace((
                 "content".to_string(),
@@ -106,12 +188,14 @@ mod tests {
 
     use super::*;
     use crate::core::Attachment; // Import Attachment
+    use crate::core::UrlKind;
 
     #[test]
     fn test_ssufid_post_to_rss_item_basic() {
         let post = SsufidPost {
             id: "test-id-basic".to_string(),
             url: "https://example.com/basic".to_string(),
+            url_kind: UrlKind::Canonical,
             author: Some("Basic Author".to_string()),
             title: "Basic Title".to_string(),
             description: None, // Description is None, should fallback to content
@@ -122,6 +206,7 @@ mod tests {
             content: "Basic Content".to_string(),
             attachments: vec![], // No attachments
             metadata: None,
+            author_detail: None,
         };
 
         let rss_item: rss::Item = post.into();
@@ -163,6 +248,7 @@ mod tests {
         let post = SsufidPost {
             id: "test-id-full".to_string(),
             url: "https://example.com/full".to_string(),
+            url_kind: UrlKind::Canonical,
             author: None, // Test None author
             title: "Full Title".to_string(),
             description: Some("This is a specific description.".to_string()), // Specific description
@@ -177,11 +263,13 @@ mod tests {
                     url: "https://example.com/attachment1.pdf".to_string(),
                     name: Some("Document 1".to_string()), // Name is not used in RSS enclosure
                     mime_type: Some("application/pdf".to_string()),
+                    metadata: None,
                 },
                 Attachment {
                     url: "https://example.com/attachment2.zip".to_string(),
                     name: None,
                     mime_type: None, // Test None mime_type, should default
+                    metadata: None,
                 },
             ],
             metadata: Some(
@@ -191,6 +279,7 @@ mod tests {
                     .cloned()
                     .collect(),
             ),
+            author_detail: None,
         };
 
         let rss_item: rss::Item = post.into();
@@ -219,12 +308,164 @@ mod tests {
         );
     }
 
+    fn aged_post(id: &str, created_at: OffsetDateTime) -> SsufidPost {
+        SsufidPost {
+            id: id.to_string(),
+            url: format!("https://example.com/{id}"),
+            url_kind: UrlKind::Canonical,
+            author: None,
+            title: id.to_string(),
+            description: None,
+            category: vec![],
+            created_at,
+            updated_at: None,
+            thumbnail: None,
+            content: String::new(),
+            attachments: vec![],
+            metadata: None,
+            author_detail: None,
+        }
+    }
+
+    #[test]
+    fn test_url_kind_serialization() {
+        let mut post = aged_post("post-1", datetime!(2024-01-01 00:00:00 UTC));
+        let json = serde_json::to_value(&post).unwrap();
+        assert!(json.get("url_kind").is_none());
+
+        post.url_kind = UrlKind::ListAnchor {
+            fragment: Some("row-1".to_string()),
+        };
+        let json = serde_json::to_value(&post).unwrap();
+        assert_eq!(
+            json["url_kind"],
+            serde_json::json!({ "kind": "list_anchor", "fragment": "row-1" })
+        );
+        assert_eq!(serde_json::from_value::<SsufidPost>(json).unwrap(), post);
+
+        post.url_kind = UrlKind::Synthetic;
+        let json = serde_json::to_value(&post).unwrap();
+        assert_eq!(json["url_kind"], serde_json::json!({ "kind": "synthetic" }));
+        assert_eq!(serde_json::from_value::<SsufidPost>(json).unwrap(), post);
+    }
+
+    #[test]
+    fn test_channel_links_for_list_only_posts() {
+        let created_at = datetime!(2024-01-01 00:00:00 UTC);
+        let mut anchored = aged_post("anchored", created_at);
+        anchored.url_kind = UrlKind::ListAnchor {
+            fragment: Some("row-7".to_string()),
+        };
+        let mut bare = aged_post("bare", created_at);
+        bare.url_kind = UrlKind::ListAnchor { fragment: None };
+        let mut synthetic = aged_post("synthetic", created_at);
+        synthetic.url_kind = UrlKind::Synthetic;
+        let site = SsufidSiteData {
+            title: "List Site".to_string(),
+            source: "https://example.com/notice".to_string(),
+            description: "Posts without detail pages".to_string(),
+            items: vec![
+                aged_post("canonical", created_at),
+                anchored,
+                bare,
+                synthetic,
+            ],
+            plugin_version: None,
+            integrity: None,
+        };
+
+        let channel: rss::Channel = site.into();
+        let links = channel
+            .items()
+            .iter()
+            .map(|item| item.link().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            links,
+            [
+                "https://example.com/canonical",
+                "https://example.com/notice#row-7",
+                "https://example.com/notice",
+                "https://example.com/notice",
+            ]
+        );
+    }
+
+    fn aged_site(count: i64) -> SsufidSiteData {
+        let start = datetime!(2024-01-01 00:00:00 UTC);
+        SsufidSiteData {
+            title: "Aged Site".to_string(),
+            source: "https://example.com".to_string(),
+            description: "Posts one day apart".to_string(),
+            // Mimics a cache stored oldest first
+            items: (0..count)
+                .map(|i| aged_post(&format!("post-{i}"), start + time::Duration::days(i)))
+                .collect(),
+            plugin_version: None,
+            integrity: None,
+        }
+    }
+
+    fn item_ids(channel: &rss::Channel) -> Vec<&str> {
+        channel
+            .items()
+            .iter()
+            .map(|item| item.guid().unwrap().value())
+            .collect()
+    }
+
+    #[test]
+    fn test_feed_max_items_keeps_newest() {
+        let now = datetime!(2024-12-31 00:00:00 UTC);
+        let channel = to_channel(aged_site(120), &FeedOptions::default(), now);
+        assert_eq!(channel.items().len(), FeedOptions::DEFAULT_MAX_ITEMS);
+        assert_eq!(item_ids(&channel)[..2], ["post-119", "post-118"]);
+        assert_eq!(
+            channel.last_build_date(),
+            Some("Mon, 29 Apr 2024 00:00:00 +0000")
+        );
+    }
+
+    #[test]
+    fn test_feed_max_age_drops_old_items() {
+        let now = datetime!(2024-01-10 12:00:00 UTC);
+        let options = FeedOptions::default().max_age(Duration::from_secs(3 * 86400));
+        let channel = to_channel(aged_site(10), &options, now);
+        assert_eq!(item_ids(&channel), ["post-9", "post-8", "post-7"]);
+    }
+
+    #[test]
+    fn test_feed_max_items_and_max_age_together() {
+        let now = datetime!(2024-01-10 12:00:00 UTC);
+
+        // The age bound is stricter
+        let options = FeedOptions::default()
+            .max_items(5)
+            .max_age(Duration::from_secs(2 * 86400));
+        let channel = to_channel(aged_site(10), &options, now);
+        assert_eq!(item_ids(&channel), ["post-9", "post-8"]);
+
+        // The count bound is stricter
+        let options = FeedOptions::default()
+            .max_items(2)
+            .max_age(Duration::from_secs(5 * 86400));
+        let channel = to_channel(aged_site(10), &options, now);
+        assert_eq!(item_ids(&channel), ["post-9", "post-8"]);
+
+        // No lastBuildDate when everything is filtered out
+        let options = FeedOptions::default().max_age(Duration::from_secs(86400));
+        let channel = to_channel(aged_site(5), &options, now);
+        assert!(channel.items().is_empty());
+        assert!(channel.last_build_date().is_none());
+    }
+
     #[test]
     fn test_ssufid_site_data_to_rss_channel() {
         let post1 = SsufidPost {
             // Post with full details
             id: "site-post-1".to_string(),
             url: "https://example.com/post1".to_string(),
+            url_kind: UrlKind::Canonical,
             author: Some("Site Author 1".to_string()),
             title: "Site Post 1".to_string(),
             description: Some("Site Post Description 1".to_string()),
@@ -237,13 +478,16 @@ mod tests {
                 url: "https://example.com/site_attach1.txt".to_string(),
                 name: None,
                 mime_type: Some("text/plain".to_string()),
+                metadata: None,
             }],
             metadata: None,
+            author_detail: None,
         };
         let post2 = SsufidPost {
             // Post with minimal details
             id: "site-post-2".to_string(),
             url: "https://example.com/post2".to_string(),
+            url_kind: UrlKind::Canonical,
             author: None,
             title: "Site Post 2".to_string(),
             description: None,
@@ -254,6 +498,7 @@ mod tests {
             content: "Site Content 2".to_string(),
             attachments: vec![],
             metadata: None,
+            author_detail: None,
         };
 
         let site_data = SsufidSiteData {
@@ -261,6 +506,8 @@ mod tests {
             source: "https://example.com".to_string(),
             description: "Test Site Description".to_string(),
             items: vec![post1, post2], // Include both posts
+            plugin_version: None,
+            integrity: None,
         };
 
         let rss_channel: rss::Channel = site_data.into();
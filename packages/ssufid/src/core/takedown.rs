@@ -0,0 +1,78 @@
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+};
+
+use super::{PostIdOrd, SsufidPost};
+
+tokio::task_local! {
+    static PINNED_POSTS: Arc<Mutex<HashSet<String>>>;
+}
+
+/// Records that the post `id` is pinned to the top of its board's list, for plugins that can
+/// tell. A pinned post keeps its place whatever its date, so it says nothing about which dates
+/// a crawl covered, and [`SsufidCore::run`](crate::SsufidCore::run) leaves it out when telling
+/// which cached posts were taken down. Outside of a run this does nothing.
+pub fn report_pinned_post(id: &str) {
+    let _ = PINNED_POSTS.try_with(|pinned| {
+        pinned
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(id.to_string())
+    });
+}
+
+/// Runs `future`, collecting the pinned posts reported on the task polling it.
+pub(crate) async fn collect_pinned_posts<F: std::future::Future>(
+    future: F,
+) -> (F::Output, HashSet<String>) {
+    let pinned = Arc::new(Mutex::new(HashSet::new()));
+    let output = PINNED_POSTS.scope(Arc::clone(&pinned), future).await;
+    let pinned = std::mem::take(&mut *pinned.lock().unwrap_or_else(|e| e.into_inner()));
+    (output, pinned)
+}
+
+/// What a crawl saw of its board, for telling the cached posts that were taken down from those
+/// the crawl merely did not reach.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct CrawlCoverage {
+    /// Posts [reported as pinned](report_pinned_post).
+    pub pinned: HashSet<String>,
+    /// Posts the crawl listed but could not fetch.
+    pub failed: HashSet<String>,
+    /// Posts fetched from the [`RetryQueue`](super::RetryQueue) rather than the list, which may
+    /// be far older than the rest.
+    pub retried: HashSet<String>,
+    /// The `posts_limit` of the crawl, if it had one.
+    pub posts_limit: Option<u32>,
+}
+
+impl CrawlCoverage {
+    /// Consecutive crawls a post must be missing from before it is marked as removed, so that
+    /// a board briefly hiding a post (e.g. while it is being edited) leaves no tombstone.
+    pub const TAKEDOWN_MISSES: u32 = 3;
+
+    /// The creation time of the oldest post the crawl listed in order: the oldest of the
+    /// newest `posts_limit` posts of `crawled` that were neither pinned nor retried. Every
+    /// post newer than it should have been crawled.
+    pub fn oldest_listed(&self, crawled: &[SsufidPost]) -> Option<time::OffsetDateTime> {
+        let mut listed = crawled
+            .iter()
+            .filter(|post| !self.pinned.contains(&post.id) && !self.retried.contains(&post.id))
+            .collect::<Vec<_>>();
+        listed.sort_by(|a, b| {
+            b.created_at
+                .cmp(&a.created_at)
+                .then_with(|| PostIdOrd(&b.id).cmp(&PostIdOrd(&a.id)))
+        });
+        if let Some(posts_limit) = self.posts_limit {
+            listed.truncate(posts_limit as usize);
+        }
+        listed.last().map(|post| post.created_at)
+    }
+
+    /// Whether the crawl accounted for the post `id`, even without returning it.
+    pub fn accounts_for(&self, id: &str) -> bool {
+        self.failed.contains(id)
+    }
+}
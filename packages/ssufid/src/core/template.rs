@@ -0,0 +1,327 @@
+use std::{fmt::Write as _, str::FromStr};
+
+use thiserror::Error;
+use time::OffsetDateTime;
+
+use super::SsufidPost;
+use crate::datetime::{KST, format_relative_kr};
+
+/// Why a [`MessageTemplate`] could not be parsed.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TemplateError {
+    #[error("unknown placeholder {{{0}}}, expected one of {fields}", fields = Field::NAMES.join(", "))]
+    UnknownPlaceholder(String),
+    #[error("unclosed placeholder starting at byte {0}")]
+    Unclosed(usize),
+    #[error("unmatched `}}` at byte {0}, write `}}}}` for a literal brace")]
+    UnmatchedBrace(usize),
+    #[error("{{{0}}} is not a date and takes no format")]
+    FormatOnText(String),
+    #[error("unknown date format directive %{0}, expected one of %Y %y %m %d %H %M %S %%")]
+    UnknownDirective(char),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Plugin,
+    Id,
+    Title,
+    Url,
+    Author,
+    Description,
+    Category,
+    CreatedAt,
+    CreatedAgo,
+    UpdatedAt,
+}
+
+impl Field {
+    const NAMES: &[&str] = &[
+        "plugin",
+        "id",
+        "title",
+        "url",
+        "author",
+        "description",
+        "category",
+        "created_at",
+        "created_ago",
+        "updated_at",
+    ];
+
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "plugin" => Self::Plugin,
+            "id" => Self::Id,
+            "title" => Self::Title,
+            "url" => Self::Url,
+            "author" => Self::Author,
+            "description" => Self::Description,
+            "category" => Self::Category,
+            "created_at" => Self::CreatedAt,
+            "created_ago" => Self::CreatedAgo,
+            "updated_at" => Self::UpdatedAt,
+            _ => return None,
+        })
+    }
+
+    fn is_date(self) -> bool {
+        matches!(self, Self::CreatedAt | Self::UpdatedAt)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Text(String),
+    Field {
+        field: Field,
+        format: Option<String>,
+    },
+}
+
+/// A notification message with placeholders for a post's fields, e.g.
+/// `"[{plugin}] {title} ({created_at:%m/%d}) {url}"`.
+///
+/// The placeholders are `plugin`, `id`, `title`, `url`, `author`, `description`, `category`,
+/// `created_at`, `created_ago` ([`format_relative_kr`]) and `updated_at`. Dates render in KST with
+/// an optional strftime-like format after a colon. Literal braces are written `{{` and `}}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageTemplate {
+    segments: Vec<Segment>,
+}
+
+impl MessageTemplate {
+    /// The date format of placeholders without one.
+    pub const DEFAULT_DATE_FORMAT: &'static str = "%Y-%m-%d %H:%M";
+
+    /// Parses `template`, rejecting unknown placeholders and date formats up front so a typo
+    /// fails when the configuration is loaded rather than on the first new post.
+    pub fn parse(template: &str) -> Result<Self, TemplateError> {
+        let mut segments = Vec::new();
+        let mut text = String::new();
+        let mut chars = template.char_indices().peekable();
+        while let Some((start, c)) = chars.next() {
+            match c {
+                '{' if chars.next_if(|&(_, c)| c == '{').is_some() => text.push('{'),
+                '}' if chars.next_if(|&(_, c)| c == '}').is_some() => text.push('}'),
+                '}' => return Err(TemplateError::UnmatchedBrace(start)),
+                '{' => {
+                    let rest = &template[start + 1..];
+                    let end = rest.find('}').ok_or(TemplateError::Unclosed(start))?;
+                    let placeholder = &rest[..end];
+                    while chars.next_if(|&(i, _)| i <= start + 1 + end).is_some() {}
+
+                    let (name, format) = match placeholder.split_once(':') {
+                        Some((name, format)) => (name.trim(), Some(format)),
+                        None => (placeholder.trim(), None),
+                    };
+                    let field = Field::parse(name)
+                        .ok_or_else(|| TemplateError::UnknownPlaceholder(name.to_string()))?;
+                    if let Some(format) = format {
+                        if !field.is_date() {
+                            return Err(TemplateError::FormatOnText(name.to_string()));
+                        }
+                        validate_date_format(format)?;
+                    }
+                    if !text.is_empty() {
+                        segments.push(Segment::Text(std::mem::take(&mut text)));
+                    }
+                    segments.push(Segment::Field {
+                        field,
+                        format: format.map(str::to_string),
+                    });
+                }
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            segments.push(Segment::Text(text));
+        }
+        Ok(Self { segments })
+    }
+
+    /// Renders the message for `post` of the plugin `plugin`, sent at `now`.
+    pub fn render(&self, plugin: &str, post: &SsufidPost, now: OffsetDateTime) -> String {
+        let mut message = String::new();
+        for segment in &self.segments {
+            let (field, format) = match segment {
+                Segment::Text(text) => {
+                    message.push_str(text);
+                    continue;
+                }
+                Segment::Field { field, format } => (*field, format.as_deref()),
+            };
+            let format = format.unwrap_or(Self::DEFAULT_DATE_FORMAT);
+            match field {
+                Field::Plugin => message.push_str(plugin),
+                Field::Id => message.push_str(&post.id),
+                Field::Title => message.push_str(&post.title),
+                Field::Url => message.push_str(&post.url),
+                Field::Author => message.push_str(post.author.as_deref().unwrap_or_default()),
+                Field::Description => {
+                    message.push_str(post.description.as_deref().unwrap_or_default())
+                }
+                Field::Category => message.push_str(&post.category.join(", ")),
+                Field::CreatedAt => format_date(&mut message, post.created_at, format),
+                Field::CreatedAgo => message.push_str(&format_relative_kr(post.created_at, now)),
+                Field::UpdatedAt => {
+                    if let Some(updated_at) = post.updated_at {
+                        format_date(&mut message, updated_at, format);
+                    }
+                }
+            }
+        }
+        message
+    }
+}
+
+impl FromStr for MessageTemplate {
+    type Err = TemplateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+fn validate_date_format(format: &str) -> Result<(), TemplateError> {
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        if c == '%' {
+            match chars.next() {
+                Some('Y' | 'y' | 'm' | 'd' | 'H' | 'M' | 'S' | '%') => {}
+                Some(c) => return Err(TemplateError::UnknownDirective(c)),
+                // A lone trailing % is printed literally
+                None => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Writes `dt` in KST with a format already checked by [`validate_date_format`].
+fn format_date(out: &mut String, dt: OffsetDateTime, format: &str) {
+    let dt = dt.to_offset(KST);
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+        let _ = match chars.next() {
+            Some('Y') => write!(out, "{}", dt.year()),
+            Some('y') => write!(out, "{:02}", dt.year().rem_euclid(100)),
+            Some('m') => write!(out, "{:02}", u8::from(dt.month())),
+            Some('d') => write!(out, "{:02}", dt.day()),
+            Some('H') => write!(out, "{:02}", dt.hour()),
+            Some('M') => write!(out, "{:02}", dt.minute()),
+            Some('S') => write!(out, "{:02}", dt.second()),
+            _ => write!(out, "%"),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+    use crate::core::UrlKind;
+
+    const NOW: OffsetDateTime = datetime!(2025-03-10 03:30:00 +9);
+
+    fn post() -> SsufidPost {
+        SsufidPost {
+            id: "1234".to_string(),
+            url: "https://example.com/board/view.php?idx=1234".to_string(),
+            url_kind: UrlKind::Canonical,
+            author: None,
+            title: "2025학년도 1학기 수강신청 안내".to_string(),
+            description: None,
+            category: vec!["학사".to_string(), "수업".to_string()],
+            // Early morning of March 10 in KST
+            created_at: datetime!(2025-03-09 16:30:00 UTC),
+            updated_at: None,
+            thumbnail: None,
+            content: String::new(),
+            attachments: vec![],
+            metadata: None,
+            author_detail: None,
+        }
+    }
+
+    #[test]
+    fn test_placeholders_are_substituted() {
+        let template = MessageTemplate::parse("[{plugin}] {title} / {category} {url}").unwrap();
+        assert_eq!(
+            template.render("sw.ssu.ac.kr", &post(), NOW),
+            "[sw.ssu.ac.kr] 2025학년도 1학기 수강신청 안내 / 학사, 수업 https://example.com/board/view.php?idx=1234"
+        );
+
+        // Unknown fields are empty, and doubled braces are printed literally
+        let template = MessageTemplate::parse("{{{id}}} 작성자: {author}{updated_at}").unwrap();
+        assert_eq!(
+            template.render("sw.ssu.ac.kr", &post(), NOW),
+            "{1234} 작성자: "
+        );
+    }
+
+    #[test]
+    fn test_dates_are_formatted_in_kst() {
+        let template =
+            MessageTemplate::parse("{title} ({created_at:%m/%d}) {created_at:%y.%m.%d %H:%M:%S}")
+                .unwrap();
+        assert_eq!(
+            template.render("sw.ssu.ac.kr", &post(), NOW),
+            "2025학년도 1학기 수강신청 안내 (03/10) 25.03.10 01:30:00"
+        );
+
+        let template = MessageTemplate::parse("{created_at} 100%").unwrap();
+        assert_eq!(
+            template.render("sw.ssu.ac.kr", &post(), NOW),
+            "2025-03-10 01:30 100%"
+        );
+    }
+
+    #[test]
+    fn test_created_ago_is_relative_to_now() {
+        let template = MessageTemplate::parse("{title} ({created_ago})").unwrap();
+        assert_eq!(
+            template.render("sw.ssu.ac.kr", &post(), NOW),
+            "2025학년도 1학기 수강신청 안내 (2시간 전)"
+        );
+        assert_eq!(
+            MessageTemplate::parse("{created_ago:%m}"),
+            Err(TemplateError::FormatOnText("created_ago".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_invalid_templates_are_rejected() {
+        assert_eq!(
+            MessageTemplate::parse("{title} {writer}"),
+            Err(TemplateError::UnknownPlaceholder("writer".to_string()))
+        );
+        assert_eq!(
+            MessageTemplate::parse("{title:%m}"),
+            Err(TemplateError::FormatOnText("title".to_string()))
+        );
+        assert_eq!(
+            MessageTemplate::parse("{created_at:%B %d}"),
+            Err(TemplateError::UnknownDirective('B'))
+        );
+        assert_eq!(
+            MessageTemplate::parse("[{plugin] {title}"),
+            Err(TemplateError::UnknownPlaceholder(
+                "plugin] {title".to_string()
+            ))
+        );
+        assert_eq!(
+            MessageTemplate::parse("{title"),
+            Err(TemplateError::Unclosed(0))
+        );
+        assert_eq!(
+            MessageTemplate::parse("title}"),
+            Err(TemplateError::UnmatchedBrace(5))
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use std::{collections::BTreeMap, fmt};
+
+use time::OffsetDateTime;
+
+use super::{SsufidPlugin, SsufidPost};
+
+/// A sign that a plugin's selectors drifted, found by [`validate_posts`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationWarning {
+    /// A post has an empty `id`, `title` or `url`.
+    EmptyField { id: String, field: &'static str },
+    /// A post links to a host other than the plugin's [`BASE_URL`](SsufidPlugin::BASE_URL).
+    WrongHost { id: String, url: String },
+    /// A post was created more than a day in the future.
+    FutureDate {
+        id: String,
+        created_at: OffsetDateTime,
+    },
+    /// Several posts share a URL, e.g. because the link selector picks up a "more" button.
+    DuplicateUrl { url: String, ids: Vec<String> },
+    /// An attachment of a post could not be downloaded, e.g. because the board wants a header
+    /// the plugin does not send.
+    BrokenAttachment {
+        id: String,
+        url: String,
+        error: String,
+    },
+}
+
+impl fmt::Display for ValidationWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyField { id, field } => write!(f, "post {id:?} has an empty {field}"),
+            Self::WrongHost { id, url } => {
+                write!(f, "post {id:?} links to another host: {url}")
+            }
+            Self::FutureDate { id, created_at } => {
+                write!(f, "post {id:?} is dated in the future: {created_at}")
+            }
+            Self::DuplicateUrl { url, ids } => {
+                write!(f, "posts {} share the url {url}", ids.join(", "))
+            }
+            Self::BrokenAttachment { id, url, error } => {
+                write!(
+                    f,
+                    "post {id:?} has an attachment that cannot be downloaded: {url}: {error}"
+                )
+            }
+        }
+    }
+}
+
+/// Checks a sample of posts crawled by `T` for empty fields, links to another host, dates in
+/// the future and duplicate URLs at `now`.
+///
+/// Only [canonical](super::UrlKind::Canonical) URLs are checked for their host and uniqueness,
+/// since list anchors and synthetic URLs point at the list page by design.
+pub fn validate_posts<T: SsufidPlugin>(
+    posts: &[SsufidPost],
+    now: OffsetDateTime,
+) -> Vec<ValidationWarning> {
+    let base_host = url::Url::parse(T::BASE_URL)
+        .ok()
+        .and_then(|url| url.host_str().map(normalize_host));
+    let mut warnings = Vec::new();
+    let mut by_url = BTreeMap::<&str, Vec<String>>::new();
+    for post in posts {
+        for (field, value) in [("id", &post.id), ("title", &post.title), ("url", &post.url)] {
+            if value.trim().is_empty() {
+                warnings.push(ValidationWarning::EmptyField {
+                    id: post.id.clone(),
+                    field,
+                });
+            }
+        }
+        if post.created_at > now + time::Duration::days(1) {
+            warnings.push(ValidationWarning::FutureDate {
+                id: post.id.clone(),
+                created_at: post.created_at,
+            });
+        }
+        if !post.url_kind.is_canonical() || post.url.trim().is_empty() {
+            continue;
+        }
+        let host = url::Url::parse(&post.url)
+            .ok()
+            .and_then(|url| url.host_str().map(normalize_host));
+        if base_host.is_some() && host != base_host {
+            warnings.push(ValidationWarning::WrongHost {
+                id: post.id.clone(),
+                url: post.url.clone(),
+            });
+        }
+        by_url.entry(&post.url).or_default().push(post.id.clone());
+    }
+    warnings.extend(
+        by_url
+            .into_iter()
+            .filter(|(_, ids)| ids.len() > 1)
+            .map(|(url, ids)| ValidationWarning::DuplicateUrl {
+                url: url.to_string(),
+                ids,
+            }),
+    );
+    warnings
+}
+
+/// Downloads the first attachment of each of `posts` the way a reader's browser would, with
+/// [`fetch_attachment`](crate::fetch::fetch_attachment), and warns about those that fail.
+pub async fn validate_attachments<T: SsufidPlugin>(
+    client: &reqwest::Client,
+    posts: &[SsufidPost],
+) -> Vec<ValidationWarning> {
+    let mut warnings = Vec::new();
+    for post in posts {
+        let Some(attachment) = post.attachments.first() else {
+            continue;
+        };
+        if let Err(e) = crate::fetch::fetch_attachment::<T>(
+            client,
+            attachment,
+            &post.url,
+            crate::fetch::MAX_ATTACHMENT_BYTES,
+        )
+        .await
+        {
+            warnings.push(ValidationWarning::BrokenAttachment {
+                id: post.id.clone(),
+                url: attachment.url.clone(),
+                error: e.to_string(),
+            });
+        }
+    }
+    warnings
+}
+
+fn normalize_host(host: &str) -> String {
+    host.trim_start_matches("www.").to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+    use crate::core::UrlKind;
+
+    struct MockValidatePlugin;
+
+    impl SsufidPlugin for MockValidatePlugin {
+        const TITLE: &'static str = "Mock Validate";
+        const IDENTIFIER: &'static str = "mock.validate";
+        const DESCRIPTION: &'static str = "Mock plugin for validation tests";
+        const BASE_URL: &'static str = "https://www.example.com/board/list.php";
+    }
+
+    fn post(id: &str, title: &str, url: &str, created_at: OffsetDateTime) -> SsufidPost {
+        SsufidPost {
+            id: id.to_string(),
+            url: url.to_string(),
+            url_kind: UrlKind::Canonical,
+            author: None,
+            title: title.to_string(),
+            description: None,
+            category: vec![],
+            created_at,
+            updated_at: None,
+            thumbnail: None,
+            content: String::new(),
+            attachments: vec![],
+            metadata: None,
+            author_detail: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_posts() {
+        let now = datetime!(2025-03-10 12:00 +9);
+        let posts = [
+            post(
+                "1",
+                "수강신청 안내",
+                "https://example.com/board/view.php?idx=1",
+                now,
+            ),
+            post("2", " ", "https://example.com/board/view.php?idx=2", now),
+            post("3", "외부 링크", "https://evil.example.org/view?idx=3", now),
+            post(
+                "4",
+                "미래 공지",
+                "https://example.com/board/view.php?idx=4",
+                datetime!(2026-03-10 12:00 +9),
+            ),
+            post(
+                "5",
+                "더보기",
+                "https://example.com/board/view.php?idx=1",
+                now,
+            ),
+        ];
+
+        assert_eq!(
+            validate_posts::<MockValidatePlugin>(&posts, now),
+            [
+                ValidationWarning::EmptyField {
+                    id: "2".to_string(),
+                    field: "title"
+                },
+                ValidationWarning::WrongHost {
+                    id: "3".to_string(),
+                    url: "https://evil.example.org/view?idx=3".to_string()
+                },
+                ValidationWarning::FutureDate {
+                    id: "4".to_string(),
+                    created_at: datetime!(2026-03-10 12:00 +9)
+                },
+                ValidationWarning::DuplicateUrl {
+                    url: "https://example.com/board/view.php?idx=1".to_string(),
+                    ids: vec!["1".to_string(), "5".to_string()]
+                },
+            ]
+        );
+        assert!(validate_posts::<MockValidatePlugin>(&posts[..1], now).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_validate_attachments() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/download/ok.pdf"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"%PDF-1.4".to_vec()))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+
+        let now = datetime!(2025-03-02 12:00 +9);
+        let with_attachment = |id: &str, file: &str| {
+            let mut post = post(id, "공지", &format!("{}/view?idx={id}", server.uri()), now);
+            post.attachments = vec![crate::core::Attachment {
+                url: format!("{}/download/{file}", server.uri()),
+                name: Some(file.to_string()),
+                mime_type: None,
+                metadata: None,
+            }];
+            post
+        };
+        let posts = [
+            with_attachment("1", "ok.pdf"),
+            with_attachment("2", "protected.pdf"),
+            post("3", "공지", "https://example.com/board/view.php?idx=3", now),
+        ];
+
+        let warnings =
+            validate_attachments::<MockValidatePlugin>(&crate::fetch::default_client(), &posts)
+                .await;
+        assert_eq!(warnings.len(), 1);
+        assert!(matches!(
+            &warnings[0],
+            ValidationWarning::BrokenAttachment { id, .. } if id == "2"
+        ));
+    }
+}
@@ -0,0 +1,194 @@
+//! Timestamp formatting shared by the feed serializers and notification messages, and lenient
+//! parsing of the dates boards show.
+
+use std::sync::LazyLock;
+
+use time::{
+    OffsetDateTime, UtcOffset,
+    format_description::well_known::{Rfc2822, Rfc3339},
+    macros::offset,
+};
+
+/// Korea Standard Time, the offset every SSU board publishes in.
+pub const KST: UtcOffset = offset!(+9);
+
+/// Formats `dt` as an RFC 822 (RFC 2822) date for RSS `pubDate`, keeping its offset,
+/// e.g. `Mon, 03 Mar 2025 09:30:00 +0900`.
+///
+/// Fails for years before 1900, which RFC 2822 cannot represent.
+pub fn to_rfc822(dt: OffsetDateTime) -> Result<String, time::error::Format> {
+    dt.format(&Rfc2822)
+}
+
+/// Formats `dt` as an RFC 3339 timestamp for Atom and JSON Feed, keeping its offset,
+/// e.g. `2025-03-03T09:30:00+09:00`.
+///
+/// Fails for offsets with a seconds component, which RFC 3339 cannot represent.
+pub fn to_rfc3339(dt: OffsetDateTime) -> Result<String, time::error::Format> {
+    dt.format(&Rfc3339)
+}
+
+/// Formats how long ago `then` was at `now` in Korean, e.g. `3시간 전`, `어제` or `2025년 3월 2일`,
+/// counting days on the KST calendar.
+pub fn format_relative_kr(then: OffsetDateTime, now: OffsetDateTime) -> String {
+    let (then, now) = (then.to_offset(KST), now.to_offset(KST));
+    let elapsed = now - then;
+    if elapsed < time::Duration::MINUTE {
+        return "방금 전".to_string();
+    }
+    if elapsed < time::Duration::HOUR {
+        return format!("{}분 전", elapsed.whole_minutes());
+    }
+    match (now.date() - then.date()).whole_days() {
+        0 => format!("{}시간 전", elapsed.whole_hours()),
+        1 => "어제".to_string(),
+        days @ 2..7 => format!("{days}일 전"),
+        _ => format!(
+            "{}년 {}월 {}일",
+            then.year(),
+            u8::from(then.month()),
+            then.day()
+        ),
+    }
+}
+
+static KST_DATE_TIME: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(
+        r"^(\d{4})\s*[-./년]\s*(\d{1,2})\s*[-./월]\s*(\d{1,2})\s*(?:일|\.)?(?:\s*(?:\([^)]*\))?\s*(\d{1,2}):(\d{2})(?::(\d{2}))?)?",
+    )
+    .unwrap()
+});
+
+/// Parses a date as boards show it in KST, such as `2025.03.12 (수) 11:58` or `2025년 3월 12일`,
+/// ignoring anything after it. Dates without a time are taken at midnight.
+pub fn parse_kst(s: &str) -> Option<OffsetDateTime> {
+    let caps = KST_DATE_TIME.captures(s.trim())?;
+    let number = |i: usize| caps.get(i).and_then(|m| m.as_str().parse::<u8>().ok());
+    let date = time::Date::from_calendar_date(
+        caps[1].parse().ok()?,
+        time::Month::try_from(number(2)?).ok()?,
+        number(3)?,
+    )
+    .ok()?;
+    let time = match number(4) {
+        Some(hour) => time::Time::from_hms(hour, number(5)?, number(6).unwrap_or(0)).ok()?,
+        None => time::Time::MIDNIGHT,
+    };
+    Some(date.with_time(time).assume_offset(KST))
+}
+
+/// Like [`parse_kst`], after stripping a label before the date, such as `작성일 : 2025.03.12`,
+/// `DATE : 2025-06-04 11:58:11` or `[등록일] 2025-03-02`: everything up to the first digit, as
+/// long as it contains no digit itself.
+pub fn parse_kst_labeled(s: &str) -> Option<OffsetDateTime> {
+    let start = s.find(|c: char| c.is_ascii_digit())?;
+    parse_kst(&s[start..])
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    #[test]
+    fn test_to_rfc822_kst() {
+        let dt = datetime!(2025-03-03 09:30:05 +9);
+        assert_eq!(to_rfc822(dt).unwrap(), "Mon, 03 Mar 2025 09:30:05 +0900");
+    }
+
+    #[test]
+    fn test_to_rfc3339_kst() {
+        let dt = datetime!(2025-03-03 09:30:05 +9);
+        assert_eq!(to_rfc3339(dt).unwrap(), "2025-03-03T09:30:05+09:00");
+    }
+
+    #[test]
+    fn test_parse_kst_labeled() {
+        assert_eq!(
+            parse_kst_labeled("작성일 : 2025.03.12"),
+            Some(datetime!(2025-03-12 00:00 +9))
+        );
+        assert_eq!(
+            parse_kst_labeled("DATE : 2025-06-04 11:58:11"),
+            Some(datetime!(2025-06-04 11:58:11 +9))
+        );
+        assert_eq!(
+            parse_kst_labeled("  [등록일]\n\t2025/3/2 09:05 | 조회 120"),
+            Some(datetime!(2025-03-02 09:05 +9))
+        );
+        assert_eq!(
+            parse_kst_labeled("게시일: 2025년 3월 2일"),
+            Some(datetime!(2025-03-02 00:00 +9))
+        );
+        assert_eq!(
+            parse_kst_labeled("2025.03.12 (수) 14:30"),
+            Some(datetime!(2025-03-12 14:30 +9))
+        );
+        // A date that does not exist and a string without a date
+        assert_eq!(parse_kst_labeled("작성일 : 2025.02.30"), None);
+        assert_eq!(parse_kst_labeled("작성일 : 미정"), None);
+        assert_eq!(parse_kst("작성일 : 2025.03.12"), None);
+    }
+
+    #[test]
+    fn test_format_relative_kr_buckets() {
+        let now = datetime!(2025-03-10 15:00:00 +9);
+        let ago = |duration: time::Duration| format_relative_kr(now - duration, now);
+        assert_eq!(format_relative_kr(now, now), "방금 전");
+        assert_eq!(
+            format_relative_kr(now + time::Duration::HOUR, now),
+            "방금 전"
+        );
+        assert_eq!(ago(time::Duration::seconds(59)), "방금 전");
+        assert_eq!(ago(time::Duration::MINUTE), "1분 전");
+        assert_eq!(ago(time::Duration::seconds(3599)), "59분 전");
+        assert_eq!(ago(time::Duration::HOUR), "1시간 전");
+        assert_eq!(ago(time::Duration::hours(15)), "15시간 전");
+        assert_eq!(
+            ago(time::Duration::hours(15) + time::Duration::SECOND),
+            "어제"
+        );
+        assert_eq!(ago(time::Duration::hours(39)), "어제");
+        assert_eq!(
+            ago(time::Duration::hours(39) + time::Duration::SECOND),
+            "2일 전"
+        );
+        assert_eq!(ago(time::Duration::days(6)), "6일 전");
+        assert_eq!(ago(time::Duration::days(7)), "2025년 3월 3일");
+    }
+
+    #[test]
+    fn test_format_relative_kr_yesterday_at_kst_midnight() {
+        // The same day in UTC, but past midnight in KST
+        let now = datetime!(2025-03-09 15:10:00 UTC);
+        assert_eq!(
+            format_relative_kr(datetime!(2025-03-09 14:50:00 UTC), now),
+            "20분 전"
+        );
+        assert_eq!(
+            format_relative_kr(datetime!(2025-03-09 14:00:00 UTC), now),
+            "어제"
+        );
+        assert_eq!(
+            format_relative_kr(datetime!(2025-03-09 15:00:00 UTC), now),
+            "10분 전"
+        );
+        assert_eq!(
+            format_relative_kr(datetime!(2025-03-08 15:00:00 UTC), now),
+            "어제"
+        );
+        assert_eq!(
+            format_relative_kr(datetime!(2025-03-08 14:59:59 UTC), now),
+            "2일 전"
+        );
+    }
+
+    #[test]
+    fn test_utc_converted_to_kst() {
+        // The date must follow KST across midnight too
+        let dt = datetime!(2024-12-31 15:00:00 UTC).to_offset(KST);
+        assert_eq!(to_rfc822(dt).unwrap(), "Wed, 01 Jan 2025 00:00:00 +0900");
+        assert_eq!(to_rfc3339(dt).unwrap(), "2025-01-01T00:00:00+09:00");
+    }
+}
@@ -0,0 +1,11 @@
+//! Optional post-processing that adds derived data to crawled posts.
+//!
+//! Enrichers only ever add entries to [`SsufidPost::metadata`](crate::core::SsufidPost); the
+//! crawled fields are left untouched, and a failing enricher never drops a post.
+
+mod translate;
+
+pub use translate::{
+    HttpTranslator, SUMMARY_EN_KEY, TITLE_EN_KEY, TranslateError, TranslationCache, Translator,
+    translate_posts,
+};
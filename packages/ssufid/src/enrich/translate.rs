@@ -0,0 +1,454 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    future::Future,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use tokio::{sync::Mutex, time::Instant};
+
+use crate::{core::SsufidPost, fetch::RetryPolicy};
+
+pub const TITLE_EN_KEY: &str = "title_en";
+pub const SUMMARY_EN_KEY: &str = "summary_en";
+
+#[derive(Debug, Error)]
+pub enum TranslateError {
+    #[error("Translation request failed: {0}")]
+    Request(#[from] reqwest::Error),
+
+    #[error("Translation endpoint answered HTTP {0}")]
+    Status(reqwest::StatusCode),
+
+    #[error("Translation endpoint kept rate limiting after {0} attempts")]
+    RateLimited(u32),
+
+    #[error("Unexpected translation response: {0}")]
+    Parse(String),
+}
+
+/// A machine translation backend, e.g. DeepL or Papago.
+pub trait Translator {
+    /// Translates `text` from language `from` to `to`, both given as ISO 639-1 codes.
+    fn translate(
+        &self,
+        text: &str,
+        from: &str,
+        to: &str,
+    ) -> impl Future<Output = Result<String, TranslateError>> + Send;
+}
+
+/// Translator for an HTTP endpoint speaking the DeepL `v2/translate` protocol.
+///
+/// Requests are spaced at least `min_interval` apart, and `429` responses are retried following
+/// `Retry-After` or the [`RetryPolicy`] backoff.
+pub struct HttpTranslator {
+    client: reqwest::Client,
+    endpoint: String,
+    api_key: String,
+    min_interval: Duration,
+    retry: RetryPolicy,
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl fmt::Debug for HttpTranslator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HttpTranslator")
+            .field("endpoint", &self.endpoint)
+            .field("min_interval", &self.min_interval)
+            .field("retry", &self.retry)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Deserialize)]
+struct TranslateResponse {
+    translations: Vec<Translation>,
+}
+
+#[derive(Deserialize)]
+struct Translation {
+    text: String,
+}
+
+impl HttpTranslator {
+    pub const ENDPOINT_ENV: &'static str = "SSUFID_TRANSLATE_ENDPOINT";
+    pub const API_KEY_ENV: &'static str = "SSUFID_TRANSLATE_API_KEY";
+    pub const DEFAULT_ENDPOINT: &'static str = "https://api-free.deepl.com/v2/translate";
+    pub const DEFAULT_MIN_INTERVAL: Duration = Duration::from_millis(200);
+
+    pub fn new(endpoint: impl Into<String>, api_key: impl Into<String>) -> Self {
+        Self {
+            client: crate::fetch::default_client(),
+            endpoint: endpoint.into(),
+            api_key: api_key.into(),
+            min_interval: Self::DEFAULT_MIN_INTERVAL,
+            retry: RetryPolicy::default(),
+            last_request: Mutex::new(None),
+        }
+    }
+
+    /// Reads the API key from `SSUFID_TRANSLATE_API_KEY` and the endpoint from
+    /// `SSUFID_TRANSLATE_ENDPOINT`, defaulting to the DeepL free API.
+    pub fn from_env() -> Option<Self> {
+        let api_key = std::env::var(Self::API_KEY_ENV).ok()?;
+        let endpoint = std::env::var(Self::ENDPOINT_ENV)
+            .unwrap_or_else(|_| Self::DEFAULT_ENDPOINT.to_string());
+        Some(Self::new(endpoint, api_key))
+    }
+
+    pub fn min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    pub fn retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    async fn throttle(&self) {
+        let mut last_request = self.last_request.lock().await;
+        if let Some(last) = *last_request {
+            tokio::time::sleep_until(last + self.min_interval).await;
+        }
+        *last_request = Some(Instant::now());
+    }
+}
+
+impl Translator for HttpTranslator {
+    async fn translate(&self, text: &str, from: &str, to: &str) -> Result<String, TranslateError> {
+        let params = [
+            ("text", text),
+            ("source_lang", &from.to_ascii_uppercase()),
+            ("target_lang", &to.to_ascii_uppercase()),
+        ];
+        let mut delay = self.retry.base_delay;
+        for attempt in 1..=self.retry.attempts {
+            self.throttle().await;
+            let response = self
+                .client
+                .post(&self.endpoint)
+                .header(
+                    reqwest::header::AUTHORIZATION,
+                    format!("DeepL-Auth-Key {}", self.api_key),
+                )
+                .form(&params)
+                .send()
+                .await?;
+            let status = response.status();
+            if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                if attempt < self.retry.attempts {
+                    let retry_after = response
+                        .headers()
+                        .get(reqwest::header::RETRY_AFTER)
+                        .and_then(|v| v.to_str().ok())
+                        .and_then(|v| v.trim().parse::<u64>().ok())
+                        .map(Duration::from_secs);
+                    tracing::warn!(attempt, "Translation rate limited, retrying");
+                    tokio::time::sleep(retry_after.unwrap_or(delay)).await;
+                    delay *= 2;
+                }
+                continue;
+            }
+            if !status.is_success() {
+                return Err(TranslateError::Status(status));
+            }
+            let body = response.text().await?;
+            let response: TranslateResponse =
+                serde_json::from_str(&body).map_err(|e| TranslateError::Parse(e.to_string()))?;
+            return response
+                .translations
+                .into_iter()
+                .next()
+                .map(|t| t.text)
+                .ok_or_else(|| TranslateError::Parse("no translations".to_string()));
+        }
+        Err(TranslateError::RateLimited(self.retry.attempts))
+    }
+}
+
+/// Translations made by earlier runs, so that a post goes to the translator again only once
+/// its title or description changes. Kept per plugin in the state directory as
+/// `translations/{identifier}.json`.
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub struct TranslationCache {
+    /// Keyed by post id.
+    entries: BTreeMap<String, CachedTranslation>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+struct CachedTranslation {
+    /// The [`source_hash`] of the post when it was translated.
+    source_hash: String,
+    translated: BTreeMap<String, String>,
+}
+
+impl TranslationCache {
+    pub const DIR: &'static str = "translations";
+
+    pub fn path(state_dir: &Path, identifier: &str) -> PathBuf {
+        state_dir.join(Self::DIR).join(format!("{identifier}.json"))
+    }
+
+    /// Reads the cache of the plugin `identifier` in `state_dir`, which is empty if it was
+    /// never saved.
+    pub async fn load(state_dir: &Path, identifier: &str) -> Result<Self, crate::Error> {
+        match tokio::fs::read_to_string(Self::path(state_dir, identifier)).await {
+            Ok(json) => Ok(serde_json::from_str(&json)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Writes the cache of the plugin `identifier` to `state_dir` through a temporary file.
+    pub async fn save(&self, state_dir: &Path, identifier: &str) -> Result<(), crate::Error> {
+        let path = Self::path(state_dir, identifier);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        tokio::fs::write(&tmp, serde_json::to_string_pretty(self)?).await?;
+        tokio::fs::rename(&tmp, &path).await?;
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The translations of `post`, if it was translated with the same title and description.
+    fn get(&self, post: &SsufidPost) -> Option<&BTreeMap<String, String>> {
+        self.entries
+            .get(&post.id)
+            .filter(|entry| entry.source_hash == source_hash(post))
+            .map(|entry| &entry.translated)
+    }
+}
+
+/// A hash of what gets translated: the post's title and description.
+fn source_hash(post: &SsufidPost) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(post.title.as_bytes());
+    hasher.update([0]);
+    hasher.update(post.description.as_deref().unwrap_or_default().as_bytes());
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Stores English translations of each post's title and description in `metadata["title_en"]`
+/// and `metadata["summary_en"]`, reusing those in `cache` while the post is unchanged.
+pub async fn translate_posts<Tr: Translator>(
+    translator: &Tr,
+    posts: &mut [SsufidPost],
+    cache: &mut TranslationCache,
+) {
+    cache
+        .entries
+        .retain(|id, _| posts.iter().any(|post| &post.id == id));
+    for post in posts {
+        if post
+            .metadata
+            .as_ref()
+            .is_some_and(|m| m.contains_key(TITLE_EN_KEY))
+        {
+            continue;
+        }
+        if let Some(translated) = cache.get(post) {
+            let translated = translated.clone();
+            post.metadata.get_or_insert_default().extend(translated);
+            continue;
+        }
+        match translate_post(translator, post).await {
+            Ok(translated) => {
+                let translated = translated.into_iter().collect::<BTreeMap<_, _>>();
+                cache.entries.insert(
+                    post.id.clone(),
+                    CachedTranslation {
+                        source_hash: source_hash(post),
+                        translated: translated.clone(),
+                    },
+                );
+                post.metadata.get_or_insert_default().extend(translated);
+            }
+            Err(e) => tracing::warn!(id = %post.id, error = %e, "Failed to translate post"),
+        }
+    }
+}
+
+async fn translate_post<Tr: Translator>(
+    translator: &Tr,
+    post: &SsufidPost,
+) -> Result<Vec<(String, String)>, TranslateError> {
+    let mut translated = vec![(
+        TITLE_EN_KEY.to_string(),
+        translator.translate(&post.title, "ko", "en").await?,
+    )];
+    if let Some(description) = post.description.as_deref().filter(|d| !d.trim().is_empty()) {
+        translated.push((
+            SUMMARY_EN_KEY.to_string(),
+            translator.translate(description, "ko", "en").await?,
+        ));
+    }
+    Ok(translated)
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{body_string_contains, header, method},
+    };
+
+    use super::*;
+    use crate::core::UrlKind;
+
+    fn post(title: &str, description: Option<&str>) -> SsufidPost {
+        SsufidPost {
+            id: "1".to_string(),
+            url: "https://example.com/1".to_string(),
+            url_kind: UrlKind::Canonical,
+            author: None,
+            title: title.to_string(),
+            description: description.map(str::to_string),
+            category: vec![],
+            created_at: datetime!(2025-03-02 00:00 +9),
+            updated_at: None,
+            thumbnail: None,
+            content: "본문".to_string(),
+            attachments: vec![],
+            metadata: None,
+            author_detail: None,
+        }
+    }
+
+    fn translator(server: &MockServer) -> HttpTranslator {
+        HttpTranslator::new(server.uri(), "test-key")
+            .min_interval(Duration::from_millis(1))
+            .retry_policy(RetryPolicy {
+                attempts: 2,
+                base_delay: Duration::from_millis(1),
+            })
+    }
+
+    fn translated(text: &str) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_string(format!(
+            r#"{{"translations":[{{"detected_source_language":"KO","text":"{text}"}}]}}"#
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_translate_posts_stores_metadata() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(header("authorization", "DeepL-Auth-Key test-key"))
+            .and(body_string_contains("target_lang=EN"))
+            .and(body_string_contains("%EC%9E%A5%ED%95%99")) // 장학
+            .respond_with(translated("Scholarship notice"))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(translated("Applications are open"))
+            .mount(&server)
+            .await;
+
+        let mut posts = vec![post("장학 공지", Some("신청을 받습니다"))];
+        translate_posts(
+            &translator(&server),
+            &mut posts,
+            &mut TranslationCache::default(),
+        )
+        .await;
+
+        let metadata = posts[0].metadata.as_ref().unwrap();
+        assert_eq!(metadata[TITLE_EN_KEY], "Scholarship notice");
+        assert_eq!(metadata[SUMMARY_EN_KEY], "Applications are open");
+        assert_eq!(posts[0].title, "장학 공지");
+        assert_eq!(posts[0].description.as_deref(), Some("신청을 받습니다"));
+    }
+
+    #[tokio::test]
+    async fn test_translate_retries_rate_limit() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "0"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(translated("Notice"))
+            .mount(&server)
+            .await;
+
+        let text = translator(&server)
+            .translate("공지", "ko", "en")
+            .await
+            .unwrap();
+        assert_eq!(text, "Notice");
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_translation_keeps_post() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(429))
+            .mount(&server)
+            .await;
+
+        let original = post("공지", None);
+        let mut posts = vec![original.clone()];
+        translate_posts(
+            &translator(&server),
+            &mut posts,
+            &mut TranslationCache::default(),
+        )
+        .await;
+
+        assert_eq!(posts, vec![original]);
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_translation_cache_skips_unchanged_posts() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(translated("Notice"))
+            .mount(&server)
+            .await;
+        let state_dir =
+            std::env::temp_dir().join(format!("ssufid-translate-{}", std::process::id()));
+        let translator = translator(&server);
+
+        let mut cache = TranslationCache::default();
+        translate_posts(&translator, &mut [post("공지", None)], &mut cache).await;
+        cache.save(&state_dir, "example.com/notice").await.unwrap();
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+
+        // The next run does not ask again even though the cached original has no translation
+        let mut cache = TranslationCache::load(&state_dir, "example.com/notice")
+            .await
+            .unwrap();
+        let mut posts = vec![post("공지", None)];
+        translate_posts(&translator, &mut posts, &mut cache).await;
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+        assert_eq!(posts[0].metadata.as_ref().unwrap()[TITLE_EN_KEY], "Notice");
+
+        // A changed title is translated again
+        translate_posts(&translator, &mut [post("수정된 공지", None)], &mut cache).await;
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+        let _ = std::fs::remove_dir_all(&state_dir);
+    }
+}
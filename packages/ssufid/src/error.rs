@@ -10,6 +10,10 @@ pub enum Error {
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
 
+    /// The invocation is invalid, e.g. conflicting flags or an unknown plugin.
+    #[error("Configuration error: {0}")]
+    Config(String),
+
     #[error("Attempts exceeded for plugin {plugin} after {attempts} tries")]
     AttemptsExceeded {
         plugin: &'static str,
@@ -22,6 +26,48 @@ pub enum Error {
     Plugin(Box<PluginError>),
 }
 
+impl Error {
+    /// Exit code of an invalid configuration, `EX_CONFIG` in `sysexits.h`.
+    pub const EXIT_CONFIG: u8 = 78;
+    /// Exit code of a failure to read or write local files, `EX_IOERR`.
+    pub const EXIT_IO: u8 = 74;
+    /// Exit code of unreadable local data such as a corrupt cache, `EX_DATAERR`.
+    pub const EXIT_DATA: u8 = 65;
+    /// Exit code of a site that could not be reached, `EX_UNAVAILABLE`.
+    pub const EXIT_UNAVAILABLE: u8 = 69;
+    /// Exit code of a plugin that reached its site but failed otherwise, e.g. on a page whose
+    /// layout changed, `EX_SOFTWARE`.
+    pub const EXIT_PLUGIN: u8 = 70;
+
+    /// The process exit code for this error, so that scripts around the CLI can tell a bad
+    /// invocation from a site that is down. Retries report the code of their last error.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Error::Config(_) => Self::EXIT_CONFIG,
+            Error::Io(_) => Self::EXIT_IO,
+            Error::Serialization(_) => Self::EXIT_DATA,
+            Error::AttemptsExceeded {
+                source: Some(source),
+                ..
+            } => source.exit_code(),
+            Error::AttemptsExceeded { source: None, .. } => Self::EXIT_UNAVAILABLE,
+            Error::Plugin(error) => error.exit_code(),
+        }
+    }
+
+    /// The plugin error behind this error, looking through retries.
+    pub fn plugin_error(&self) -> Option<&PluginError> {
+        match self {
+            Error::Plugin(error) => Some(error),
+            Error::AttemptsExceeded {
+                source: Some(source),
+                ..
+            } => source.plugin_error(),
+            _ => None,
+        }
+    }
+}
+
 impl From<PluginError> for Error {
     fn from(err: PluginError) -> Self {
         Error::Plugin(Box::new(err))
@@ -34,12 +80,19 @@ pub struct PluginError {
     kind: PluginErrorKind,
     plugin: &'static str,
     message: String,
+    /// Debugging context kept out of the message, e.g. the HTML a selector failed on.
+    detail: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PluginErrorKind {
     Request,
     Parse,
+    /// A page matched, but an element expected inside the match is missing.
+    Structure,
+    /// The host answered with an anti-bot interstitial (a JavaScript challenge or redirect)
+    /// instead of the page, so the board is blocked rather than empty.
+    BotChallenge,
     Custom(Box<str>),
     Unknown,
 }
@@ -50,6 +103,7 @@ impl PluginError {
             kind: PluginErrorKind::Request,
             plugin: T::IDENTIFIER,
             message,
+            detail: None,
         }
     }
 
@@ -58,6 +112,28 @@ impl PluginError {
             kind: PluginErrorKind::Parse,
             plugin: T::IDENTIFIER,
             message,
+            detail: None,
+        }
+    }
+
+    /// The page matched, but `expected` (e.g. the title link of a row) is missing from
+    /// `context` (e.g. which row of which page).
+    pub fn structure<T: SsufidPlugin>(expected: &str, context: impl std::fmt::Display) -> Self {
+        Self {
+            kind: PluginErrorKind::Structure,
+            plugin: T::IDENTIFIER,
+            message: format!("expected {expected} in {context}"),
+            detail: None,
+        }
+    }
+
+    /// `url` answered with an anti-bot interstitial, see [`PluginErrorKind::BotChallenge`].
+    pub fn bot_challenge<T: SsufidPlugin>(url: &str) -> Self {
+        Self {
+            kind: PluginErrorKind::BotChallenge,
+            plugin: T::IDENTIFIER,
+            message: format!("{url} answered with an anti-bot interstitial instead of the page"),
+            detail: None,
         }
     }
 
@@ -66,6 +142,7 @@ impl PluginError {
             kind: PluginErrorKind::Custom(name.into()),
             plugin: T::IDENTIFIER,
             message,
+            detail: None,
         }
     }
 
@@ -73,6 +150,20 @@ impl PluginError {
         &self.kind
     }
 
+    /// Whether trying again later may succeed: request errors such as timeouts and 5xx
+    /// responses, as opposed to pages that fetched but did not parse.
+    pub fn is_retryable(&self) -> bool {
+        self.kind == PluginErrorKind::Request
+    }
+
+    /// See [`Error::exit_code`].
+    pub fn exit_code(&self) -> u8 {
+        match self.kind {
+            PluginErrorKind::Request | PluginErrorKind::BotChallenge => Error::EXIT_UNAVAILABLE,
+            _ => Error::EXIT_PLUGIN,
+        }
+    }
+
     pub fn plugin(&self) -> &str {
         self.plugin
     }
@@ -80,4 +171,110 @@ impl PluginError {
     pub fn message(&self) -> &str {
         &self.message
     }
+
+    pub fn detail(&self) -> Option<&str> {
+        self.detail.as_deref()
+    }
+
+    pub fn with_detail(self, detail: impl Into<String>) -> Self {
+        Self {
+            detail: Some(detail.into()),
+            ..self
+        }
+    }
+
+    /// Attaches the selector that failed and a [snippet](crate::html::html_snippet) of the
+    /// page it was applied to.
+    pub fn with_snippet(self, selector: &scraper::Selector, html: &str) -> Self {
+        use scraper::selector::ToCss;
+        self.with_detail(format!(
+            "selector: {}\n{}",
+            selector.to_css_string(),
+            crate::html::html_snippet(html)
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::html::SNIPPET_MAX_BYTES;
+
+    struct MockErrorPlugin;
+
+    impl SsufidPlugin for MockErrorPlugin {
+        const TITLE: &'static str = "Mock Error";
+        const IDENTIFIER: &'static str = "mock.error";
+        const DESCRIPTION: &'static str = "Mock plugin for error tests";
+        const BASE_URL: &'static str = "https://example.com/error";
+    }
+
+    #[test]
+    fn test_with_snippet_captures_truncated_body() {
+        let html = format!(
+            "<html><head><title>공지</title></head><body>\
+             <script>track();</script><div class=\"view\">{}</div></body></html>",
+            "본문 ".repeat(1000)
+        );
+        let error = PluginError::parse::<MockErrorPlugin>("Content not found".to_string())
+            .with_snippet(
+                &scraper::Selector::parse("div.view > div.con").unwrap(),
+                &html,
+            );
+
+        assert_eq!(
+            error.to_string(),
+            "Error from plugin mock.error: Parse - Content not found"
+        );
+        let detail = error.detail().unwrap().to_string();
+        let (selector, snippet) = detail.split_once('\n').unwrap();
+        assert_eq!(selector, "selector: div.view > div.con");
+        assert!(snippet.starts_with("<body><div class=\"view\">본문 본문"));
+        assert!(!snippet.contains("track()"));
+        assert!(snippet.ends_with('…'));
+        assert!(snippet.len() <= SNIPPET_MAX_BYTES + '…'.len_utf8());
+
+        let error = Error::AttemptsExceeded {
+            plugin: MockErrorPlugin::IDENTIFIER,
+            attempts: 3,
+            source: Some(Box::new(error.into())),
+        };
+        assert_eq!(
+            error.plugin_error().and_then(PluginError::detail),
+            Some(detail.as_str())
+        );
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        let io = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "cache");
+        let error = Error::from(io);
+        assert!(matches!(error, Error::Io(_)));
+        assert_eq!(error.exit_code(), Error::EXIT_IO);
+
+        let error = Error::from(serde_json::from_str::<Vec<u32>>("[1,").unwrap_err());
+        assert!(matches!(error, Error::Serialization(_)));
+        assert_eq!(error.exit_code(), Error::EXIT_DATA);
+
+        let error = Error::Config("--include and --exclude conflict".to_string());
+        assert_eq!(error.exit_code(), Error::EXIT_CONFIG);
+
+        let error = Error::from(PluginError::request::<MockErrorPlugin>(
+            "timed out".to_string(),
+        ));
+        assert!(matches!(error, Error::Plugin(_)));
+        assert_eq!(error.exit_code(), Error::EXIT_UNAVAILABLE);
+        let error = Error::from(PluginError::parse::<MockErrorPlugin>(
+            "title not found".to_string(),
+        ));
+        assert_eq!(error.exit_code(), Error::EXIT_PLUGIN);
+
+        // Failing after retries uses the last error's exit code
+        let error = Error::AttemptsExceeded {
+            plugin: MockErrorPlugin::IDENTIFIER,
+            attempts: 3,
+            source: Some(Box::new(error)),
+        };
+        assert_eq!(error.exit_code(), Error::EXIT_PLUGIN);
+    }
 }
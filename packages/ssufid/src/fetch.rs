@@ -0,0 +1,2071 @@
+//! Shared HTTP client configuration and request helpers for plugins.
+
+use std::{
+    collections::HashMap,
+    io::Write as _,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, OnceLock},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use time::OffsetDateTime;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore, SemaphorePermit};
+
+use crate::{
+    PluginError,
+    core::{Attachment, HealthStatus, SsufidPlugin, normalize_mime_type},
+};
+
+pub const DEFAULT_USER_AGENT: &str = concat!(
+    "Mozilla/5.0 (compatible; ssufid/",
+    env!("CARGO_PKG_VERSION"),
+    "; +https://github.com/yourssu/ssufid)"
+);
+
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+pub const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Timeout of the HEAD request sent by [`check_reachable`].
+pub const PREFLIGHT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Connection reuse settings of a client, see [`client_builder_with`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionOptions {
+    /// Speaks HTTP/2 right away instead of negotiating it. Requests fail against hosts that
+    /// only speak HTTP/1.1.
+    pub http2_prior_knowledge: bool,
+    /// How long an idle pooled connection is kept open for reuse.
+    pub pool_idle_timeout: Duration,
+    /// How many idle connections are kept per host.
+    pub pool_max_idle_per_host: usize,
+    /// Interval of TCP keep-alive probes, if any.
+    pub tcp_keepalive: Option<Duration>,
+    /// Interval of HTTP/2 pings keeping idle HTTP/2 connections open, if any.
+    pub http2_keep_alive_interval: Option<Duration>,
+}
+
+impl Default for ConnectionOptions {
+    fn default() -> Self {
+        Self {
+            http2_prior_knowledge: false,
+            pool_idle_timeout: Duration::from_secs(90),
+            pool_max_idle_per_host: 16,
+            tcp_keepalive: Some(Duration::from_secs(60)),
+            http2_keep_alive_interval: Some(Duration::from_secs(30)),
+        }
+    }
+}
+
+impl ConnectionOptions {
+    pub fn http2_prior_knowledge(mut self, http2_prior_knowledge: bool) -> Self {
+        self.http2_prior_knowledge = http2_prior_knowledge;
+        self
+    }
+
+    pub fn pool_idle_timeout(mut self, pool_idle_timeout: Duration) -> Self {
+        self.pool_idle_timeout = pool_idle_timeout;
+        self
+    }
+
+    pub fn pool_max_idle_per_host(mut self, pool_max_idle_per_host: usize) -> Self {
+        self.pool_max_idle_per_host = pool_max_idle_per_host;
+        self
+    }
+
+    pub fn tcp_keepalive(mut self, tcp_keepalive: Option<Duration>) -> Self {
+        self.tcp_keepalive = tcp_keepalive;
+        self
+    }
+
+    pub fn http2_keep_alive_interval(
+        mut self,
+        http2_keep_alive_interval: Option<Duration>,
+    ) -> Self {
+        self.http2_keep_alive_interval = http2_keep_alive_interval;
+        self
+    }
+}
+
+/// A client builder with the standard user agent and timeouts, for plugins that need to
+/// customize it further (cookies, certificates, ...).
+pub fn client_builder() -> reqwest::ClientBuilder {
+    client_builder_with(&ConnectionOptions::default())
+}
+
+/// Like [`client_builder`], with the given connection reuse settings.
+pub fn client_builder_with(options: &ConnectionOptions) -> reqwest::ClientBuilder {
+    let builder = reqwest::Client::builder()
+        .user_agent(DEFAULT_USER_AGENT)
+        .timeout(REQUEST_TIMEOUT)
+        .connect_timeout(CONNECT_TIMEOUT)
+        .pool_idle_timeout(options.pool_idle_timeout)
+        .pool_max_idle_per_host(options.pool_max_idle_per_host)
+        .tcp_keepalive(options.tcp_keepalive)
+        .http2_keep_alive_interval(options.http2_keep_alive_interval)
+        .http2_keep_alive_while_idle(options.http2_keep_alive_interval.is_some());
+    if options.http2_prior_knowledge {
+        builder.http2_prior_knowledge()
+    } else {
+        builder
+    }
+}
+
+/// A client with the standard user agent and timeouts.
+/// Build it once per plugin and reuse it, so connections are pooled.
+pub fn default_client() -> reqwest::Client {
+    client_builder()
+        .build()
+        .expect("Failed to build default HTTP client")
+}
+
+/// A [`default_client`] shared by the whole process, for plugins without state of their own
+/// to keep a client in.
+pub fn shared_client() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(default_client)
+}
+
+/// A client like [`default_client`] that also keeps the cookies it receives, so that a
+/// session started by a login (see
+/// [`SsufidPostPlugin::authenticate`](crate::core::SsufidPostPlugin::authenticate)) lasts for
+/// the whole crawl.
+pub fn session_client() -> reqwest::Client {
+    client_builder()
+        .cookie_store(true)
+        .build()
+        .expect("Failed to build session HTTP client")
+}
+
+/// Caps the number of requests in flight, overall and per host, across every plugin and client.
+#[derive(Debug, Clone)]
+pub struct RequestLimiter {
+    total: Arc<Semaphore>,
+    max_per_host: usize,
+    hosts: Arc<Mutex<HashMap<String, Arc<Semaphore>>>>,
+}
+
+/// A slot in a [`RequestLimiter`], released when dropped.
+#[derive(Debug)]
+pub struct RequestPermit<'a> {
+    _total: SemaphorePermit<'a>,
+    host: Option<(String, OwnedSemaphorePermit)>,
+}
+
+impl Drop for RequestPermit<'_> {
+    fn drop(&mut self) {
+        if let Some((host, _)) = &self.host {
+            crate::metrics::add_to_gauge(crate::metrics::IN_FLIGHT_REQUESTS, &[("host", host)], -1);
+        }
+    }
+}
+
+static GLOBAL_LIMITER: OnceLock<RequestLimiter> = OnceLock::new();
+
+impl RequestLimiter {
+    pub const DEFAULT_MAX_IN_FLIGHT: usize = 64;
+    pub const DEFAULT_MAX_PER_HOST: usize = 4;
+
+    pub fn new(max_in_flight: usize) -> Self {
+        Self {
+            total: Arc::new(Semaphore::new(max_in_flight.max(1))),
+            max_per_host: Self::DEFAULT_MAX_PER_HOST,
+            hosts: Arc::default(),
+        }
+    }
+
+    /// Allows at most `max_per_host` requests in flight to the same host and port.
+    pub fn max_per_host(self, max_per_host: usize) -> Self {
+        Self {
+            max_per_host: max_per_host.max(1),
+            ..self
+        }
+    }
+
+    /// The limiter shared by the fetch helpers, allowing
+    /// [`DEFAULT_MAX_IN_FLIGHT`](Self::DEFAULT_MAX_IN_FLIGHT) requests unless
+    /// [`init_global`](Self::init_global) ran first.
+    pub fn global() -> &'static RequestLimiter {
+        GLOBAL_LIMITER.get_or_init(|| Self::new(Self::DEFAULT_MAX_IN_FLIGHT))
+    }
+
+    /// Sets the global limit. Only the first call before any request takes effect; returns
+    /// `false` if the global limiter was already in use.
+    pub fn init_global(max_in_flight: usize) -> bool {
+        Self::init_global_with(Self::new(max_in_flight))
+    }
+
+    /// Like [`init_global`](Self::init_global), with a configured limiter.
+    pub fn init_global_with(limiter: RequestLimiter) -> bool {
+        GLOBAL_LIMITER.set(limiter).is_ok()
+    }
+
+    /// Waits until fewer than the maximum number of requests are in flight.
+    pub async fn acquire(&self) -> SemaphorePermit<'_> {
+        self.total
+            .acquire()
+            .await
+            .expect("request limiter semaphore is never closed")
+    }
+
+    /// Waits until fewer than the maximum number of requests are in flight, both in total and
+    /// to the host of `url`. URLs without a host only count towards the total.
+    pub async fn acquire_for(&self, url: &str) -> RequestPermit<'_> {
+        let host = url::Url::parse(url).ok().and_then(|url| {
+            let host = url.host_str()?.to_ascii_lowercase();
+            Some(match url.port_or_known_default() {
+                Some(port) => format!("{host}:{port}"),
+                None => host,
+            })
+        });
+        // Take the host slot first so requests to other hosts can use the global slots
+        let host = match host {
+            Some(host) => {
+                let semaphore = self
+                    .hosts
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .entry(host.clone())
+                    .or_insert_with(|| Arc::new(Semaphore::new(self.max_per_host)))
+                    .clone();
+                let permit = semaphore
+                    .acquire_owned()
+                    .await
+                    .expect("request limiter semaphore is never closed");
+                Some((host, permit))
+            }
+            None => None,
+        };
+        let total = self.acquire().await;
+        if let Some((host, _)) = &host {
+            crate::metrics::add_to_gauge(crate::metrics::IN_FLIGHT_REQUESTS, &[("host", host)], 1);
+        }
+        RequestPermit {
+            _total: total,
+            host,
+        }
+    }
+}
+
+/// Where [`request_url`] sends requests instead of their own origin.
+#[derive(Debug, Clone)]
+enum OriginOverride {
+    /// Requests to the origin `from` go to the origin `to`.
+    One { from: String, to: String },
+    /// Requests to any origin go to `to`, under a first path segment naming their host.
+    All { to: String },
+}
+
+tokio::task_local! {
+    static ORIGIN_OVERRIDE: OriginOverride;
+}
+
+fn origin_of(url: &str) -> String {
+    url::Url::parse(url)
+        .map(|url| url.origin().ascii_serialization())
+        .unwrap_or_else(|_| url.trim_end_matches('/').to_string())
+}
+
+/// Runs `future` with the requests the helpers in this module make to the origin of `from`
+/// sent to the origin of `to` instead, keeping their path and query, e.g. to crawl a plugin
+/// against a mock server of recorded pages. Only requests made on the task polling `future`
+/// are redirected.
+pub async fn with_origin_override<F: std::future::Future>(
+    from: &str,
+    to: &str,
+    future: F,
+) -> F::Output {
+    let origin_override = OriginOverride::One {
+        from: origin_of(from),
+        to: origin_of(to),
+    };
+    ORIGIN_OVERRIDE.scope(origin_override, future).await
+}
+
+/// Like [`with_origin_override`], but sends the requests to every origin to `to`, with the
+/// host (and port, if not the default) of their original URL as the first path segment:
+/// `https://board.example.com/list?page=2` is requested as
+/// `{to}/board.example.com/list?page=2`. No request made on the task reaches a real host, even
+/// for plugins that call APIs on other hosts than their [`BASE_URL`](SsufidPlugin::BASE_URL).
+pub async fn with_all_origins_routed_to<F: std::future::Future>(to: &str, future: F) -> F::Output {
+    let origin_override = OriginOverride::All { to: origin_of(to) };
+    ORIGIN_OVERRIDE.scope(origin_override, future).await
+}
+
+/// `url` as it is actually requested, see [`with_origin_override`].
+fn request_url(url: &str) -> std::borrow::Cow<'_, str> {
+    ORIGIN_OVERRIDE
+        .try_with(|origin_override| match origin_override {
+            OriginOverride::One { from, to } => url
+                .strip_prefix(from.as_str())
+                .filter(|rest| rest.is_empty() || rest.starts_with(['/', '?', '#']))
+                .map(|rest| format!("{to}{rest}")),
+            OriginOverride::All { to } => {
+                let parsed = url::Url::parse(url).ok()?;
+                let host = parsed.host_str()?;
+                let authority = match parsed.port() {
+                    Some(port) => format!("{host}:{port}"),
+                    None => host.to_string(),
+                };
+                let query = parsed.query().map(|query| format!("?{query}"));
+                Some(format!(
+                    "{to}/{authority}{}{}",
+                    parsed.path(),
+                    query.unwrap_or_default()
+                ))
+            }
+        })
+        .ok()
+        .flatten()
+        .map_or(std::borrow::Cow::Borrowed(url), std::borrow::Cow::Owned)
+}
+
+/// How often and how patiently a request is retried.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub attempts: u32,
+    /// Delay before the second attempt, doubled for every further attempt.
+    pub base_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            base_delay: Duration::from_millis(500),
+        }
+    }
+}
+
+/// One request in a [`RequestLog`]: what was asked of which server and what came back, without
+/// bodies or headers other than the content type.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RequestLogEntry {
+    #[serde(with = "time::serde::rfc3339")]
+    pub started_at: OffsetDateTime,
+    pub plugin: String,
+    pub method: String,
+    pub url: String,
+    /// `None` when no response arrived, e.g. on a timeout.
+    pub status: Option<u16>,
+    pub duration_ms: u64,
+    /// The size of the response body, when it was read.
+    pub bytes: Option<u64>,
+    pub content_type: Option<String>,
+}
+
+/// A JSONL file of every request the fetch helpers in this module send during one run, one
+/// [`RequestLogEntry`] per line, kept as evidence of a crawl's actual request pattern.
+///
+/// Requests are logged to the log [`scope`](Self::scope)d to the current task, or else to the
+/// one set with [`init_global`](Self::init_global); without either nothing is recorded.
+#[derive(Debug, Clone)]
+pub struct RequestLog {
+    path: PathBuf,
+    file: Arc<Mutex<std::fs::File>>,
+}
+
+static GLOBAL_REQUEST_LOG: OnceLock<RequestLog> = OnceLock::new();
+
+tokio::task_local! {
+    static SCOPED_REQUEST_LOG: RequestLog;
+}
+
+impl RequestLog {
+    /// The directory of the state directory holding one log per run.
+    pub const DIR: &'static str = "requests";
+    /// How many runs' logs are kept by default, see [`prune`](Self::prune).
+    pub const DEFAULT_RETENTION: usize = 30;
+
+    /// Opens `requests/<run_id>.jsonl` in `state_dir` for appending, creating it if needed.
+    pub fn create(state_dir: &Path, run_id: &str) -> std::io::Result<Self> {
+        let dir = state_dir.join(Self::DIR);
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join(format!("{run_id}.jsonl"));
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)?;
+        Ok(Self {
+            path,
+            file: Arc::new(Mutex::new(file)),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Logs the requests of the whole process from now on. Only the first call takes effect;
+    /// returns `false` if a log was already set.
+    pub fn init_global(log: RequestLog) -> bool {
+        GLOBAL_REQUEST_LOG.set(log).is_ok()
+    }
+
+    /// Runs `future` with the requests made on the task polling it logged here instead.
+    pub async fn scope<F: std::future::Future>(self, future: F) -> F::Output {
+        SCOPED_REQUEST_LOG.scope(self, future).await
+    }
+
+    fn with_current(f: impl FnOnce(&RequestLog)) {
+        match SCOPED_REQUEST_LOG.try_with(RequestLog::clone) {
+            Ok(log) => f(&log),
+            Err(_) => {
+                if let Some(log) = GLOBAL_REQUEST_LOG.get() {
+                    f(log);
+                }
+            }
+        }
+    }
+
+    pub fn append(&self, entry: &RequestLogEntry) -> std::io::Result<()> {
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+        self.file
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .write_all(line.as_bytes())
+    }
+
+    /// Deletes all but the `keep` most recent logs in `state_dir`. Run ids sort in the order
+    /// the runs started, see [`RunManifest::run_id_of`](crate::core::RunManifest::run_id_of).
+    pub fn prune(state_dir: &Path, keep: usize) -> std::io::Result<()> {
+        let mut paths = match std::fs::read_dir(state_dir.join(Self::DIR)) {
+            Ok(entries) => entries
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<Result<Vec<_>, _>>()?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        paths.retain(|path| path.extension().is_some_and(|ext| ext == "jsonl"));
+        paths.sort();
+        let stale = paths.len().saturating_sub(keep);
+        for path in &paths[..stale] {
+            std::fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+/// An on-disk cache of fetched pages for developing plugins, keyed by URL and never expiring.
+/// Never enable it for a production crawl.
+#[derive(Debug, Clone)]
+pub struct DevCache {
+    dir: PathBuf,
+}
+
+static GLOBAL_DEV_CACHE: OnceLock<Option<DevCache>> = OnceLock::new();
+
+tokio::task_local! {
+    static SCOPED_DEV_CACHE: DevCache;
+}
+
+impl DevCache {
+    /// The environment variable naming the cache directory.
+    pub const ENV: &'static str = "SSUFID_DEV_CACHE";
+
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The cache in the directory named by [`ENV`](Self::ENV), if set.
+    pub fn from_env() -> Option<Self> {
+        std::env::var_os(Self::ENV)
+            .filter(|dir| !dir.is_empty())
+            .map(Self::new)
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Caches the pages of the whole process from now on. Only the first call takes effect;
+    /// returns `false` if the global cache was already set or read.
+    pub fn init_global(cache: DevCache) -> bool {
+        GLOBAL_DEV_CACHE.set(Some(cache)).is_ok()
+    }
+
+    /// Runs `future` with the pages fetched on the task polling it cached here instead.
+    pub async fn scope<F: std::future::Future>(self, future: F) -> F::Output {
+        SCOPED_DEV_CACHE.scope(self, future).await
+    }
+
+    /// The cache of the whole process, if any. The first call reads [`ENV`](Self::ENV) unless
+    /// [`init_global`](Self::init_global) ran first, warning when it enables the cache, so
+    /// call it at startup to have a forgotten variable show up in the logs.
+    pub fn global() -> Option<&'static DevCache> {
+        GLOBAL_DEV_CACHE
+            .get_or_init(|| {
+                let cache = Self::from_env();
+                if let Some(cache) = &cache {
+                    tracing::warn!(
+                        dir = %cache.dir().display(),
+                        "{} is set: pages are served from the dev cache and never refreshed",
+                        Self::ENV
+                    );
+                }
+                cache
+            })
+            .as_ref()
+    }
+
+    fn current() -> Option<DevCache> {
+        SCOPED_DEV_CACHE
+            .try_with(DevCache::clone)
+            .ok()
+            .or_else(|| Self::global().cloned())
+    }
+
+    fn key(input: &[u8]) -> String {
+        Sha256::digest(input)
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect()
+    }
+
+    fn path(&self, url: &str) -> PathBuf {
+        self.dir.join(format!("{}.html", Self::key(url.as_bytes())))
+    }
+
+    /// The key of a request for [`send`]: its method, URL and body.
+    fn request_key(request: &reqwest::Request) -> String {
+        let mut input = format!("{} {}\n", request.method(), request.url()).into_bytes();
+        if let Some(body) = request.body().and_then(reqwest::Body::as_bytes) {
+            input.extend_from_slice(body);
+        }
+        Self::key(&input)
+    }
+
+    fn get_response(&self, key: &str) -> Option<reqwest::Response> {
+        let meta = std::fs::read_to_string(self.dir.join(format!("{key}.json"))).ok()?;
+        let meta = serde_json::from_str::<CachedResponse>(&meta).ok()?;
+        let body = std::fs::read(self.dir.join(format!("{key}.body"))).ok()?;
+        let mut headers = reqwest::header::HeaderMap::new();
+        if let Some(content_type) = meta
+            .content_type
+            .and_then(|value| reqwest::header::HeaderValue::from_str(&value).ok())
+        {
+            headers.insert(reqwest::header::CONTENT_TYPE, content_type);
+        }
+        Some(buffered_response(
+            reqwest::StatusCode::from_u16(meta.status).ok()?,
+            reqwest::Version::HTTP_11,
+            url::Url::parse(&meta.url).ok()?,
+            headers,
+            body,
+        ))
+    }
+
+    fn put_response(&self, key: &str, meta: &CachedResponse, body: &[u8]) -> std::io::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::write(self.dir.join(format!("{key}.body")), body)?;
+        std::fs::write(
+            self.dir.join(format!("{key}.json")),
+            serde_json::to_string(meta)?,
+        )
+    }
+
+    /// The cached page of `url`, if any.
+    pub fn get(&self, url: &str) -> Option<String> {
+        std::fs::read_to_string(self.path(url)).ok()
+    }
+
+    pub fn put(&self, url: &str, text: &str) -> std::io::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::write(self.path(url), text)
+    }
+}
+
+/// What [`DevCache`] keeps of a response to [`send`] besides its body.
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedResponse {
+    status: u16,
+    url: String,
+    content_type: Option<String>,
+}
+
+/// A request being timed for the [`RequestLog`].
+struct LoggedRequest<'a> {
+    method: &'a str,
+    url: &'a str,
+    started_at: OffsetDateTime,
+    started: std::time::Instant,
+}
+
+impl<'a> LoggedRequest<'a> {
+    fn start(method: &'a str, url: &'a str) -> Self {
+        Self {
+            method,
+            url,
+            started_at: OffsetDateTime::now_utc(),
+            started: std::time::Instant::now(),
+        }
+    }
+
+    fn finish<T: SsufidPlugin>(
+        self,
+        status: Option<reqwest::StatusCode>,
+        bytes: Option<usize>,
+        content_type: Option<&str>,
+    ) {
+        RequestLog::with_current(|log| {
+            let entry = RequestLogEntry {
+                started_at: self.started_at,
+                plugin: T::IDENTIFIER.to_string(),
+                method: self.method.to_string(),
+                url: self.url.to_string(),
+                status: status.map(|status| status.as_u16()),
+                duration_ms: self.started.elapsed().as_millis() as u64,
+                bytes: bytes.map(|bytes| bytes as u64),
+                content_type: content_type.map(str::to_string),
+            };
+            if let Err(e) = log.append(&entry) {
+                tracing::warn!(path = %log.path().display(), "Failed to write the request log: {e}");
+            }
+        });
+    }
+}
+
+/// Adds the plugin's [`EXTRA_HEADERS`](SsufidPlugin::EXTRA_HEADERS) to a request.
+fn with_plugin_headers<T: SsufidPlugin>(
+    request: reqwest::RequestBuilder,
+) -> reqwest::RequestBuilder {
+    T::EXTRA_HEADERS
+        .iter()
+        .fold(request, |request, (name, value)| {
+            request.header(*name, *value)
+        })
+}
+
+/// Adds the plugin's [`EXTRA_HEADERS`](SsufidPlugin::EXTRA_HEADERS) to `request`, with
+/// `referer` in place of any `Referer` among them.
+fn with_referer<T: SsufidPlugin>(
+    request: reqwest::RequestBuilder,
+    referer: &str,
+) -> reqwest::RequestBuilder {
+    T::EXTRA_HEADERS
+        .iter()
+        .filter(|(name, _)| !name.eq_ignore_ascii_case(reqwest::header::REFERER.as_str()))
+        .fold(request, |request, (name, value)| {
+            request.header(*name, *value)
+        })
+        .header(reqwest::header::REFERER, referer)
+}
+
+fn content_type_of(response: &reqwest::Response) -> Option<String> {
+    response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+/// Fetches `url` as text with the default [`RetryPolicy`].
+pub async fn fetch_text<T: SsufidPlugin>(
+    client: &reqwest::Client,
+    url: &str,
+) -> Result<String, PluginError> {
+    fetch_text_with_policy::<T>(client, url, RetryPolicy::default()).await
+}
+
+/// Fetches `url` as text, retrying connection failures, timeouts, `429` and `5xx` responses.
+/// Other error statuses are returned as pages, but an anti-bot interstitial is a
+/// [`BotChallenge`](crate::PluginErrorKind::BotChallenge) error.
+pub async fn fetch_text_with_policy<T: SsufidPlugin>(
+    client: &reqwest::Client,
+    url: &str,
+    policy: RetryPolicy,
+) -> Result<String, PluginError> {
+    fetch_text_limited::<T>(client, url, policy, RequestLimiter::global()).await
+}
+
+/// Fetches `url` like [`fetch_text_with_policy`], counting the request against `limiter`
+/// instead of the global limiter.
+pub async fn fetch_text_limited<T: SsufidPlugin>(
+    client: &reqwest::Client,
+    url: &str,
+    policy: RetryPolicy,
+    limiter: &RequestLimiter,
+) -> Result<String, PluginError> {
+    let mut delay = policy.base_delay;
+    let mut attempt = 1;
+    let request_url = request_url(url);
+    let dev_cache = DevCache::current();
+    if let Some(text) = dev_cache.as_ref().and_then(|cache| cache.get(&request_url)) {
+        tracing::debug!(
+            plugin = T::IDENTIFIER,
+            url,
+            "Serving page from the dev cache"
+        );
+        return Ok(text);
+    }
+    loop {
+        let permit = limiter.acquire_for(&request_url).await;
+        let logged = LoggedRequest::start("GET", &request_url);
+        let request = with_plugin_headers::<T>(client.get(request_url.as_ref()));
+        let error = match request.send().await {
+            Ok(response) if !is_retryable_status(response.status()) => {
+                let status = response.status();
+                let content_type = content_type_of(&response);
+                let bytes = match response.bytes().await {
+                    Ok(bytes) => bytes,
+                    Err(e) => {
+                        logged.finish::<T>(Some(status), None, content_type.as_deref());
+                        return Err(PluginError::request::<T>(format!(
+                            "Failed to read body of {url}: {e}"
+                        )));
+                    }
+                };
+                logged.finish::<T>(Some(status), Some(bytes.len()), content_type.as_deref());
+                let text = decode_html(&bytes, content_type.as_deref(), T::ENCODING);
+                // Login redirect pages are tiny script-only pages too, so filter them first
+                if !crate::html::has_restricted_marker::<T>(&text)
+                    && looks_like_bot_challenge(&text)
+                {
+                    // The script shows how the block works, so keep the whole body
+                    return Err(PluginError::bot_challenge::<T>(url).with_detail(text));
+                }
+                if let Some(cache) = &dev_cache
+                    && let Err(e) = cache.put(&request_url, &text)
+                {
+                    tracing::warn!(dir = %cache.dir().display(), "Failed to write the dev cache: {e}");
+                }
+                return Ok(text);
+            }
+            Ok(response) => {
+                let content_type = content_type_of(&response);
+                logged.finish::<T>(Some(response.status()), None, content_type.as_deref());
+                format!("HTTP {}", response.status())
+            }
+            Err(e) if e.is_connect() || e.is_timeout() || e.is_request() => {
+                logged.finish::<T>(e.status(), None, None);
+                e.to_string()
+            }
+            Err(e) => {
+                logged.finish::<T>(e.status(), None, None);
+                return Err(PluginError::request::<T>(format!(
+                    "Failed to fetch {url}: {e}"
+                )));
+            }
+        };
+        drop(permit);
+        if attempt >= policy.attempts {
+            return Err(PluginError::request::<T>(format!(
+                "Failed to fetch {url} after {attempt} attempts: {error}"
+            )));
+        }
+        tracing::warn!(
+            plugin = T::IDENTIFIER,
+            url,
+            attempt,
+            error,
+            "Request failed, retrying"
+        );
+        tokio::time::sleep(delay).await;
+        delay *= 2;
+        attempt += 1;
+    }
+}
+
+/// Sends a request the plugin built itself the way the other helpers in this module do, with
+/// [`EXTRA_HEADERS`](SsufidPlugin::EXTRA_HEADERS), the [`RequestLimiter`] and the [`RequestLog`].
+/// Nothing is retried.
+pub async fn send<T: SsufidPlugin>(
+    request: reqwest::RequestBuilder,
+) -> Result<reqwest::Response, reqwest::Error> {
+    let (client, request) = with_plugin_headers::<T>(request).build_split();
+    let mut request = request?;
+    if let std::borrow::Cow::Owned(url) = request_url(request.url().as_str())
+        && let Ok(url) = url::Url::parse(&url)
+    {
+        *request.url_mut() = url;
+    }
+    let method = request.method().to_string();
+    let url = request.url().to_string();
+    let dev_cache = DevCache::current().map(|cache| {
+        let key = DevCache::request_key(&request);
+        (cache, key)
+    });
+    if let Some(response) = dev_cache
+        .as_ref()
+        .and_then(|(cache, key)| cache.get_response(key))
+    {
+        tracing::debug!(
+            plugin = T::IDENTIFIER,
+            url,
+            "Serving response from the dev cache"
+        );
+        return Ok(response);
+    }
+
+    let _permit = RequestLimiter::global().acquire_for(&url).await;
+    let logged = LoggedRequest::start(&method, &url);
+    let response = match client.execute(request).await {
+        Ok(response) => response,
+        Err(e) => {
+            logged.finish::<T>(e.status(), None, None);
+            return Err(e);
+        }
+    };
+    let status = response.status();
+    let version = response.version();
+    let final_url = response.url().clone();
+    let headers = response.headers().clone();
+    let content_type = content_type_of(&response);
+    let bytes = match response.bytes().await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            logged.finish::<T>(Some(status), None, content_type.as_deref());
+            return Err(e);
+        }
+    };
+    logged.finish::<T>(Some(status), Some(bytes.len()), content_type.as_deref());
+    if let Some((cache, key)) = &dev_cache
+        && status.is_success()
+    {
+        let meta = CachedResponse {
+            status: status.as_u16(),
+            url: final_url.to_string(),
+            content_type,
+        };
+        if let Err(e) = cache.put_response(key, &meta, &bytes) {
+            tracing::warn!(dir = %cache.dir().display(), "Failed to write the dev cache: {e}");
+        }
+    }
+    Ok(buffered_response(
+        status, version, final_url, headers, bytes,
+    ))
+}
+
+/// A response with an already read body, as [`send`] returns.
+fn buffered_response(
+    status: reqwest::StatusCode,
+    version: reqwest::Version,
+    url: url::Url,
+    headers: reqwest::header::HeaderMap,
+    body: impl Into<reqwest::Body>,
+) -> reqwest::Response {
+    use reqwest::ResponseBuilderExt as _;
+    let mut builder = http::Response::builder()
+        .status(status)
+        .version(version)
+        .url(url);
+    if let Some(map) = builder.headers_mut() {
+        *map = headers;
+    }
+    reqwest::Response::from(
+        builder
+            .body(body.into())
+            .expect("status, version and headers come from a valid response"),
+    )
+}
+
+/// A fetched page, with its heavy content stripped if it was too large to parse safely.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchedPage {
+    pub text: String,
+    /// Whether [`crate::html::strip_heavy_content`] was applied, which plugins record with
+    /// [`SsufidPost::mark_content_stripped`](crate::core::SsufidPost::mark_content_stripped).
+    pub stripped: bool,
+}
+
+impl FetchedPage {
+    /// Wraps `text`, stripping it when it is larger than [`SsufidPlugin::MAX_PARSE_BYTES`]:
+    /// `scraper` allocates several times the size of a page to parse it, so a detail page with
+    /// megabytes of inline base64 images can exhaust a small container.
+    pub fn bounded<T: SsufidPlugin>(text: String) -> Self {
+        if text.len() <= T::MAX_PARSE_BYTES {
+            return Self {
+                text,
+                stripped: false,
+            };
+        }
+        let stripped = crate::html::strip_heavy_content(&text);
+        tracing::warn!(
+            plugin = T::IDENTIFIER,
+            bytes = text.len(),
+            stripped_bytes = stripped.len(),
+            "Stripped styles and data URIs from a large page before parsing"
+        );
+        Self {
+            text: stripped,
+            stripped: true,
+        }
+    }
+}
+
+/// Fetches `url` like [`fetch_text`], guarding its parse with [`FetchedPage::bounded`].
+pub async fn fetch_page<T: SsufidPlugin>(
+    client: &reqwest::Client,
+    url: &str,
+) -> Result<FetchedPage, PluginError> {
+    Ok(FetchedPage::bounded::<T>(
+        fetch_text::<T>(client, url).await?,
+    ))
+}
+
+/// Pages larger than this are never taken for an anti-bot interstitial.
+const BOT_CHALLENGE_MAX_BYTES: usize = 16 * 1024;
+/// Markers of common anti-bot services, e.g. Cloudflare's `/cdn-cgi/challenge-platform/`.
+const BOT_CHALLENGE_MARKERS: &[&str] = &[
+    "challenge-platform",
+    "cf-chl",
+    "_Incapsula_Resource",
+    "captcha",
+];
+
+/// Guesses whether `html` is an anti-bot interstitial: a small page without links whose script
+/// sets a cookie or redirects.
+pub fn looks_like_bot_challenge(html: &str) -> bool {
+    if html.len() > BOT_CHALLENGE_MAX_BYTES {
+        return false;
+    }
+    let document = scraper::Html::parse_document(html);
+    let select = |css: &str| {
+        let selector = scraper::Selector::parse(css).expect("valid selector");
+        document.select(&selector).collect::<Vec<_>>()
+    };
+
+    let scripts = select("script")
+        .into_iter()
+        .map(|script| script.text().collect::<String>())
+        .collect::<Vec<_>>();
+    let has_refresh = !select(r#"meta[http-equiv="refresh" i]"#).is_empty();
+    if scripts.is_empty() && !has_refresh {
+        return false;
+    }
+    let redirects = has_refresh
+        || scripts
+            .iter()
+            .any(|script| script.contains("location") || script.contains("document.cookie"));
+    let marked = BOT_CHALLENGE_MARKERS
+        .iter()
+        .any(|marker| html.contains(marker));
+    if !redirects && !marked {
+        return false;
+    }
+    let login_stub = html.to_ascii_lowercase().contains("login")
+        || scripts.iter().any(|script| script.contains("alert("));
+    if login_stub && !marked {
+        return false;
+    }
+
+    let visible_text = select("body")
+        .first()
+        .map(|body| {
+            body.descendants()
+                .filter_map(|node| node.value().as_text().map(|text| (node, text)))
+                .filter(|(node, _)| {
+                    node.ancestors().all(|ancestor| {
+                        ancestor.value().as_element().is_none_or(|element| {
+                            !matches!(element.name(), "script" | "style" | "noscript")
+                        })
+                    })
+                })
+                .map(|(_, text)| text.trim().chars().count())
+                .sum::<usize>()
+        })
+        .unwrap_or_default();
+    visible_text < 200 && select("a[href]").is_empty()
+}
+
+/// How many nested frames [`fetch_text_following_frames`] follows at most.
+pub const MAX_FRAME_DEPTH: usize = 3;
+
+/// Fetches `url` like [`fetch_text`], but when the page only wraps its content in frames or a
+/// single iframe, follows the dominant frame's `src` and returns that document instead.
+///
+/// Returns the URL the text was finally fetched from, so that relative links can be resolved
+/// against the right document.
+pub async fn fetch_text_following_frames<T: SsufidPlugin>(
+    client: &reqwest::Client,
+    url: &str,
+) -> Result<(url::Url, String), PluginError> {
+    let mut url = url::Url::parse(url)
+        .map_err(|e| PluginError::request::<T>(format!("Invalid URL {url}: {e}")))?;
+    let mut text = fetch_text::<T>(client, url.as_str()).await?;
+    for _ in 0..MAX_FRAME_DEPTH {
+        let Some(src) = dominant_frame_src(&text) else {
+            break;
+        };
+        let frame_url = url.join(&src).map_err(|e| {
+            PluginError::parse::<T>(format!("Invalid frame src '{src}' in {url}: {e}"))
+        })?;
+        tracing::debug!(plugin = T::IDENTIFIER, %url, frame = %frame_url, "Following frame");
+        text = fetch_text::<T>(client, frame_url.as_str()).await?;
+        url = frame_url;
+    }
+    Ok((url, text))
+}
+
+/// Names commonly given to the content frame of a frameset.
+const CONTENT_FRAME_NAMES: &[&str] = &["main", "content", "body", "contents"];
+
+/// Finds the frame holding the actual content of a frame-based page.
+///
+/// A `<frameset>` page picks the frame named like a content frame, or its only frame. Any other
+/// page is only considered a wrapper when it has no text of its own besides a single iframe.
+fn dominant_frame_src(html: &str) -> Option<String> {
+    let document = scraper::Html::parse_document(html);
+    let src_of = |el: scraper::ElementRef<'_>| {
+        el.value()
+            .attr("src")
+            .map(str::trim)
+            .filter(|src| !src.is_empty() && !src.starts_with("about:"))
+            .map(str::to_string)
+    };
+
+    let frame = scraper::Selector::parse("frameset frame[src]").ok()?;
+    let frames = document.select(&frame).collect::<Vec<_>>();
+    if !frames.is_empty() {
+        let named = frames.iter().find(|el| {
+            el.value().attr("name").is_some_and(|name| {
+                CONTENT_FRAME_NAMES.contains(&name.trim().to_ascii_lowercase().as_str())
+            })
+        });
+        return match (named, frames.as_slice()) {
+            (Some(el), _) | (None, [el]) => src_of(*el),
+            _ => None,
+        };
+    }
+
+    let iframe = scraper::Selector::parse("body iframe[src]").ok()?;
+    let body = scraper::Selector::parse("body").ok()?;
+    let [iframe] = document.select(&iframe).collect::<Vec<_>>()[..] else {
+        return None;
+    };
+    let has_text = document
+        .select(&body)
+        .flat_map(|body| body.text())
+        .any(|text| !text.trim().is_empty());
+    if has_text {
+        return None;
+    }
+    src_of(iframe)
+}
+
+/// Detects the encoding of an HTML body from, in order, the `charset` of its `Content-Type`, a
+/// byte order mark, or a `<meta>` charset declaration near the top of the document. A body
+/// without any of these that is valid UTF-8 and not plain ASCII is taken as UTF-8.
+///
+/// Returns `None` when the encoding cannot be told.
+pub fn detect_html_encoding(
+    bytes: &[u8],
+    content_type: Option<&str>,
+) -> Option<&'static encoding_rs::Encoding> {
+    let declared = content_type
+        .and_then(charset_param)
+        .and_then(|label| encoding_rs::Encoding::for_label(label.as_bytes()));
+    if let Some(encoding) = declared {
+        return Some(encoding);
+    }
+    if let Some((encoding, _)) = encoding_rs::Encoding::for_bom(bytes) {
+        return Some(encoding);
+    }
+    let head = String::from_utf8_lossy(&bytes[..bytes.len().min(1024)]).to_ascii_lowercase();
+    let meta = head
+        .match_indices("<meta")
+        .filter_map(|(start, _)| {
+            let tag = &head[start..];
+            charset_param(&tag[..tag.find('>').unwrap_or(tag.len())])
+        })
+        .find_map(|label| encoding_rs::Encoding::for_label(label.as_bytes()));
+    if let Some(encoding) = meta {
+        return Some(encoding);
+    }
+    (!bytes.is_ascii() && std::str::from_utf8(bytes).is_ok()).then_some(encoding_rs::UTF_8)
+}
+
+fn charset_param(s: &str) -> Option<String> {
+    let lower = s.to_ascii_lowercase();
+    let start = lower.find("charset")? + "charset".len();
+    let value = lower[start..].trim_start().strip_prefix('=')?.trim_start();
+    let label = value
+        .trim_start_matches(['"', '\''])
+        .split(|c: char| c == '"' || c == '\'' || c == ';' || c == '/' || c.is_whitespace())
+        .next()?;
+    (!label.is_empty()).then(|| label.to_string())
+}
+
+/// Decodes an HTML body with [`detect_html_encoding`], using `fallback` when the encoding
+/// cannot be detected.
+pub fn decode_html(
+    bytes: &[u8],
+    content_type: Option<&str>,
+    fallback: &'static encoding_rs::Encoding,
+) -> String {
+    let encoding = detect_html_encoding(bytes, content_type).unwrap_or(fallback);
+    encoding.decode(bytes).0.into_owned()
+}
+
+/// Sends a HEAD request to `url` and returns its normalized `Content-Type`, if any.
+/// See [`normalize_mime_type`]. For an attachment, pass the URL of its post as `post_url` to
+/// send it as the `Referer`, as [`fetch_attachment`] does.
+pub async fn fetch_content_type<T: SsufidPlugin>(
+    client: &reqwest::Client,
+    url: &str,
+    post_url: Option<&str>,
+) -> Result<Option<String>, PluginError> {
+    let request_url = request_url(url);
+    let _permit = RequestLimiter::global().acquire_for(&request_url).await;
+    let logged = LoggedRequest::start("HEAD", &request_url);
+    let request = client.head(request_url.as_ref());
+    let response = match post_url {
+        Some(post_url) => with_referer::<T>(request, post_url),
+        None => with_plugin_headers::<T>(request),
+    }
+    .send()
+    .await;
+    let content_type = response.as_ref().ok().and_then(content_type_of);
+    logged.finish::<T>(
+        response
+            .as_ref()
+            .map_or_else(|e| e.status(), |r| Some(r.status())),
+        None,
+        content_type.as_deref(),
+    );
+    response
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| PluginError::request::<T>(format!("Failed to fetch {url}: {e}")))?;
+    Ok(content_type.as_deref().and_then(normalize_mime_type))
+}
+
+/// Attachments larger than this many bytes are not downloaded by [`fetch_attachment`] unless
+/// the caller allows more.
+pub const MAX_ATTACHMENT_BYTES: usize = 32 * 1024 * 1024;
+
+/// Downloads `attachment`, sending `post_url` as the `Referer` and failing once the file is
+/// larger than `max_bytes`.
+pub async fn fetch_attachment<T: SsufidPlugin>(
+    client: &reqwest::Client,
+    attachment: &Attachment,
+    post_url: &str,
+    max_bytes: usize,
+) -> Result<Vec<u8>, PluginError> {
+    let url = attachment.url.as_str();
+    let request_url = request_url(url);
+    let _permit = RequestLimiter::global().acquire_for(&request_url).await;
+    let logged = LoggedRequest::start("GET", &request_url);
+    let response = with_referer::<T>(client.get(request_url.as_ref()), post_url)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status());
+    let mut response = match response {
+        Ok(response) => response,
+        Err(e) => {
+            logged.finish::<T>(e.status(), None, None);
+            return Err(PluginError::request::<T>(format!(
+                "Failed to download {url}: {e}"
+            )));
+        }
+    };
+    let status = response.status();
+    let content_type = content_type_of(&response);
+    let too_large = || {
+        PluginError::request::<T>(format!(
+            "Failed to download {url}: larger than {max_bytes} bytes"
+        ))
+    };
+    if response
+        .content_length()
+        .is_some_and(|length| length > max_bytes as u64)
+    {
+        logged.finish::<T>(Some(status), None, content_type.as_deref());
+        return Err(too_large());
+    }
+    let mut bytes = Vec::new();
+    loop {
+        match response.chunk().await {
+            Ok(Some(chunk)) if bytes.len() + chunk.len() > max_bytes => {
+                logged.finish::<T>(Some(status), None, content_type.as_deref());
+                return Err(too_large());
+            }
+            Ok(Some(chunk)) => bytes.extend_from_slice(&chunk),
+            Ok(None) => break,
+            Err(e) => {
+                logged.finish::<T>(Some(status), None, content_type.as_deref());
+                return Err(PluginError::request::<T>(format!(
+                    "Failed to read body of {url}: {e}"
+                )));
+            }
+        }
+    }
+    logged.finish::<T>(Some(status), Some(bytes.len()), content_type.as_deref());
+    Ok(bytes)
+}
+
+/// Checks that `T::BASE_URL` answers with a success status, without parsing anything.
+pub async fn ping<T: SsufidPlugin>(client: &reqwest::Client) -> Result<HealthStatus, PluginError> {
+    let request_url = request_url(T::BASE_URL);
+    let _permit = RequestLimiter::global().acquire_for(&request_url).await;
+    let started_at = std::time::Instant::now();
+    let logged = LoggedRequest::start("GET", &request_url);
+    let response = with_plugin_headers::<T>(client.get(request_url.as_ref()))
+        .send()
+        .await;
+    logged.finish::<T>(
+        response
+            .as_ref()
+            .map_or_else(|e| e.status(), |r| Some(r.status())),
+        None,
+        response.as_ref().ok().and_then(content_type_of).as_deref(),
+    );
+    let response = response
+        .map_err(|e| PluginError::request::<T>(format!("Failed to fetch {}: {e}", T::BASE_URL)))?;
+    Ok(HealthStatus {
+        ok: response.status().is_success(),
+        posts_seen: 0,
+        latency: started_at.elapsed(),
+        broken_selectors: Vec::new(),
+    })
+}
+
+/// Sends a HEAD request to `url`, failing only when the host cannot be connected to or does
+/// not answer in time. Any status counts as reachable; boards that reject HEAD with `405` or
+/// `501` are asked again with GET, so that the request shows what the board really serves.
+pub async fn check_reachable<T: SsufidPlugin>(
+    client: &reqwest::Client,
+    url: &str,
+) -> Result<(), PluginError> {
+    let host = url::Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_else(|| url.to_string());
+    let mut method = reqwest::Method::HEAD;
+    let mut response = send_preflight::<T>(client, &method, url).await;
+    if response.as_ref().is_ok_and(|response| {
+        matches!(
+            response.status(),
+            reqwest::StatusCode::METHOD_NOT_ALLOWED | reqwest::StatusCode::NOT_IMPLEMENTED
+        )
+    }) {
+        method = reqwest::Method::GET;
+        response = send_preflight::<T>(client, &method, url).await;
+    }
+    match response {
+        Err(e) if e.is_connect() || e.is_timeout() => Err(PluginError::request::<T>(format!(
+            "Host unreachable: {host} did not answer {method} {url}: {e}"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+async fn send_preflight<T: SsufidPlugin>(
+    client: &reqwest::Client,
+    method: &reqwest::Method,
+    url: &str,
+) -> Result<reqwest::Response, reqwest::Error> {
+    let request_url = request_url(url);
+    let _permit = RequestLimiter::global().acquire_for(&request_url).await;
+    let logged = LoggedRequest::start(method.as_str(), &request_url);
+    let response = with_plugin_headers::<T>(client.request(method.clone(), request_url.as_ref()))
+        .timeout(PREFLIGHT_TIMEOUT)
+        .send()
+        .await;
+    logged.finish::<T>(
+        response
+            .as_ref()
+            .map_or_else(|e| e.status(), |r| Some(r.status())),
+        None,
+        None,
+    );
+    response
+}
+
+fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+    status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{header, method, path, query_param},
+    };
+
+    use super::*;
+
+    struct MockFetchPlugin;
+
+    impl SsufidPlugin for MockFetchPlugin {
+        const TITLE: &'static str = "Mock Fetch";
+        const IDENTIFIER: &'static str = "mock.fetch";
+        const DESCRIPTION: &'static str = "Mock plugin for fetch helper tests";
+        const BASE_URL: &'static str = "https://example.com/fetch";
+    }
+
+    const FAST_RETRY: RetryPolicy = RetryPolicy {
+        attempts: 3,
+        base_delay: Duration::from_millis(1),
+    };
+
+    #[tokio::test]
+    async fn test_check_reachable_falls_back_to_get() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/board"))
+            .respond_with(ResponseTemplate::new(501))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/board"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = default_client();
+        check_reachable::<MockFetchPlugin>(&client, &format!("{}/board", server.uri()))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_client_speaks_http2_with_prior_knowledge() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/list"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+            .mount(&server)
+            .await;
+
+        let client = client_builder_with(&ConnectionOptions::default().http2_prior_knowledge(true))
+            .build()
+            .unwrap();
+        let response = client
+            .get(format!("{}/list", server.uri()))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.version(), reqwest::Version::HTTP_2);
+        assert_eq!(response.text().await.unwrap(), "ok");
+    }
+
+    #[tokio::test]
+    async fn test_request_log_records_each_request() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/list"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw("목록", "text/html"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/gone"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let dir = std::env::temp_dir().join(format!("ssufid-request-log-{}", std::process::id()));
+        let log = RequestLog::create(&dir, "20250310T090000Z").unwrap();
+        let path = log.path().to_path_buf();
+        let client = default_client();
+        log.scope(async {
+            fetch_text::<MockFetchPlugin>(&client, &format!("{}/list", server.uri()))
+                .await
+                .unwrap();
+            fetch_text::<MockFetchPlugin>(&client, &format!("{}/gone", server.uri()))
+                .await
+                .unwrap();
+        })
+        .await;
+
+        let entries = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<RequestLogEntry>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].plugin, "mock.fetch");
+        assert_eq!(entries[0].method, "GET");
+        assert_eq!(entries[0].url, format!("{}/list", server.uri()));
+        assert_eq!(entries[0].status, Some(200));
+        assert_eq!(entries[0].bytes, Some("목록".len() as u64));
+        assert_eq!(entries[0].content_type.as_deref(), Some("text/html"));
+        // Error responses are logged with their status too
+        assert_eq!(entries[1].status, Some(404));
+
+        // Requests outside the log scope are not recorded
+        fetch_text::<MockFetchPlugin>(&client, &format!("{}/list", server.uri()))
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 2);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_dev_cache_serves_second_fetch_from_disk() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/list"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw("목록", "text/html"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/busy"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let dir = std::env::temp_dir().join(format!("ssufid-dev-cache-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let client = default_client();
+        let url = format!("{}/list", server.uri());
+        let busy = format!("{}/busy", server.uri());
+        let policy = RetryPolicy {
+            attempts: 1,
+            ..FAST_RETRY
+        };
+        DevCache::new(&dir)
+            .scope(async {
+                for _ in 0..2 {
+                    let text = fetch_text::<MockFetchPlugin>(&client, &url).await.unwrap();
+                    assert_eq!(text, "목록");
+                    // Failed responses are not cached, so each call requests again
+                    fetch_text_with_policy::<MockFetchPlugin>(&client, &busy, policy)
+                        .await
+                        .unwrap_err();
+                }
+            })
+            .await;
+        assert_eq!(DevCache::new(&dir).get(&url).as_deref(), Some("목록"));
+
+        server.verify().await;
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_send_goes_through_override_and_log() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/list"))
+            .and(header("x-requested-with", "XMLHttpRequest"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_raw(r#"{"ok":true}"#, "application/json"),
+            )
+            .mount(&server)
+            .await;
+
+        struct HeaderPlugin;
+        impl SsufidPlugin for HeaderPlugin {
+            const TITLE: &'static str = "Header";
+            const IDENTIFIER: &'static str = "mock.header";
+            const DESCRIPTION: &'static str = "Mock plugin sending extra headers";
+            const BASE_URL: &'static str = "https://api.example.com";
+            const EXTRA_HEADERS: &'static [(&'static str, &'static str)] =
+                &[("x-requested-with", "XMLHttpRequest")];
+        }
+
+        let dir = std::env::temp_dir().join(format!("ssufid-send-log-{}", std::process::id()));
+        let log = RequestLog::create(&dir, "20250310T090000Z").unwrap();
+        let path = log.path().to_path_buf();
+        let response = log
+            .scope(with_origin_override(
+                "https://api.example.com",
+                &server.uri(),
+                send::<HeaderPlugin>(
+                    default_client()
+                        .post("https://api.example.com/api/list")
+                        .body("page=1"),
+                ),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(
+            response.url().as_str(),
+            format!("{}/api/list", server.uri())
+        );
+        assert_eq!(response.text().await.unwrap(), r#"{"ok":true}"#);
+
+        let entries = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<RequestLogEntry>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].plugin, "mock.header");
+        assert_eq!(entries[0].method, "POST");
+        assert_eq!(entries[0].bytes, Some(11));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_dev_cache_covers_sent_requests() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/list"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw("[1]", "application/json"))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let dir =
+            std::env::temp_dir().join(format!("ssufid-dev-cache-send-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let client = default_client();
+        let url = format!("{}/api/list", server.uri());
+        DevCache::new(&dir)
+            .scope(async {
+                for _ in 0..2 {
+                    // Requests with different bodies are cached separately
+                    for page in ["page=1", "page=2"] {
+                        let response = send::<MockFetchPlugin>(client.post(&url).body(page))
+                            .await
+                            .unwrap();
+                        assert_eq!(
+                            response.headers()[reqwest::header::CONTENT_TYPE],
+                            "application/json"
+                        );
+                        assert_eq!(response.text().await.unwrap(), "[1]");
+                    }
+                }
+            })
+            .await;
+
+        server.verify().await;
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_request_log_prune_keeps_latest_runs() {
+        let dir = std::env::temp_dir().join(format!("ssufid-request-prune-{}", std::process::id()));
+        for run_id in ["20250301T000000Z", "20250302T000000Z", "20250303T000000Z"] {
+            RequestLog::create(&dir, run_id).unwrap();
+        }
+        RequestLog::prune(&dir, 2).unwrap();
+        let mut names = std::fs::read_dir(dir.join(RequestLog::DIR))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, ["20250302T000000Z.jsonl", "20250303T000000Z.jsonl"]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_origin_override_keeps_path_and_query() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/board/list.php"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("2쪽"))
+            .mount(&server)
+            .await;
+
+        let text = with_origin_override(
+            "https://board.example.com",
+            &server.uri(),
+            fetch_text::<MockFetchPlugin>(
+                &default_client(),
+                "https://board.example.com/board/list.php?page=2",
+            ),
+        )
+        .await
+        .unwrap();
+        assert_eq!(text, "2쪽");
+
+        // URLs on another host are left alone
+        let url = "https://board.example.com.evil.org/list";
+        let unchanged = with_origin_override("https://board.example.com", &server.uri(), async {
+            request_url(url).into_owned()
+        })
+        .await;
+        assert_eq!(unchanged, url);
+    }
+
+    #[tokio::test]
+    async fn test_all_origins_routed_under_their_host() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api.example.com/v1/board"))
+            .and(query_param("page", "0"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("[]"))
+            .mount(&server)
+            .await;
+
+        // An API on a host other than BASE_URL's does not reach the real host either
+        let response = with_all_origins_routed_to(
+            &server.uri(),
+            send::<MockFetchPlugin>(
+                default_client().get("https://api.example.com/v1/board?page=0"),
+            ),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.text().await.unwrap(), "[]");
+    }
+
+    #[tokio::test]
+    async fn test_retries_server_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(header("user-agent", DEFAULT_USER_AGENT))
+            .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+            .mount(&server)
+            .await;
+
+        let text =
+            fetch_text_with_policy::<MockFetchPlugin>(&default_client(), &server.uri(), FAST_RETRY)
+                .await
+                .unwrap();
+        assert_eq!(text, "ok");
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_attempts() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let error =
+            fetch_text_with_policy::<MockFetchPlugin>(&default_client(), &server.uri(), FAST_RETRY)
+                .await
+                .unwrap_err();
+        assert_eq!(error.kind(), &crate::PluginErrorKind::Request);
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_not_found_is_returned_as_page() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404).set_body_string("게시물이 없습니다"))
+            .mount(&server)
+            .await;
+
+        let text =
+            fetch_text_with_policy::<MockFetchPlugin>(&default_client(), &server.uri(), FAST_RETRY)
+                .await
+                .unwrap();
+        assert_eq!(text, "게시물이 없습니다");
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_bot_challenge_is_a_distinct_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<html><head><title>Just a moment...</title></head>
+                <body>
+                    <noscript>Please enable JavaScript to continue.</noscript>
+                    <script>document.cookie = "wsid=3f2a; path=/"; location.reload();</script>
+                </body></html>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let error = fetch_text::<MockFetchPlugin>(&default_client(), &server.uri())
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), &crate::PluginErrorKind::BotChallenge);
+        assert!(error.detail().unwrap().contains("location.reload()"));
+        // Interstitials are not retried
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_empty_board_is_not_a_bot_challenge() {
+        let empty_board = r#"<html><body>
+            <nav><a href="/">홈</a><a href="/notice">공지사항</a></nav>
+            <table class="board"><tr><td>등록된 게시물이 없습니다.</td></tr></table>
+            <script>window.location.hash = "";</script>
+        </body></html>"#;
+        assert!(!looks_like_bot_challenge(empty_board));
+
+        let app_shell =
+            r#"<html><body><div id="root"></div><script src="/app.js"></script></body></html>"#;
+        assert!(!looks_like_bot_challenge(app_shell));
+
+        let meta_refresh = r#"<html><head><meta http-equiv="Refresh" content="0; url=/challenge"></head><body></body></html>"#;
+        assert!(looks_like_bot_challenge(meta_refresh));
+    }
+
+    #[test]
+    fn test_login_stub_is_not_a_bot_challenge() {
+        let alert_redirect = r#"<html><body><script>alert("권한이 없습니다."); location.href = "/member/enter.php";</script></body></html>"#;
+        assert!(!looks_like_bot_challenge(alert_redirect));
+
+        let login_refresh = r#"<html><head><meta http-equiv="refresh" content="0; url=/bbs/login.php?url=%2Fbbs%2Fboard.php"></head><body></body></html>"#;
+        assert!(!looks_like_bot_challenge(login_refresh));
+    }
+
+    #[tokio::test]
+    async fn test_restricted_stub_is_returned_as_text() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<html><body><script>document.cookie = "ret=1"; window.location.replace("/sso");</script><noscript>로그인이 필요합니다</noscript></body></html>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let text = fetch_text::<MockFetchPlugin>(&default_client(), &server.uri())
+            .await
+            .unwrap();
+        assert!(text.contains("로그인이 필요합니다"));
+    }
+
+    #[test]
+    fn test_detect_html_encoding() {
+        let (euc_kr, _, _) = encoding_rs::EUC_KR.encode("<p>기숙사 공지</p>");
+        assert_eq!(detect_html_encoding(&euc_kr, None), None);
+        assert_eq!(
+            detect_html_encoding(&euc_kr, Some("text/html; charset=EUC-KR")),
+            Some(encoding_rs::EUC_KR)
+        );
+
+        let meta = br#"<html><head><meta http-equiv="Content-Type" content="text/html; charset=euc-kr"></head>"#;
+        assert_eq!(
+            detect_html_encoding(meta, Some("text/html")),
+            Some(encoding_rs::EUC_KR)
+        );
+        let meta = br#"<html><head><meta charset='utf-8'/></head>"#;
+        assert_eq!(detect_html_encoding(meta, None), Some(encoding_rs::UTF_8));
+
+        assert_eq!(
+            detect_html_encoding("<p>기숙사 공지</p>".as_bytes(), None),
+            Some(encoding_rs::UTF_8)
+        );
+        assert_eq!(detect_html_encoding(b"<p>ascii</p>", None), None);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_page_strips_very_large_page() {
+        let server = MockServer::start().await;
+        let image = "A".repeat(5 * 1024 * 1024);
+        let html = format!(
+            r#"<html><head><style>.view {{ color: red; }}</style></head><body><div class="con"><p>공지 본문</p><img src="data:image/png;base64,{image}"></div></body></html>"#
+        );
+        Mock::given(method("GET"))
+            .and(path("/large"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(html))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/small"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(r#"<img src="data:image/png;base64,AAAA">"#),
+            )
+            .mount(&server)
+            .await;
+
+        let client = reqwest::Client::new();
+        let page = fetch_page::<MockFetchPlugin>(&client, &format!("{}/large", server.uri()))
+            .await
+            .unwrap();
+        assert!(page.stripped);
+        assert!(page.text.len() < 1024);
+        let document = scraper::Html::parse_document(&page.text);
+        let content = scraper::Selector::parse("div.con").unwrap();
+        let content = document.select(&content).next().unwrap();
+        assert_eq!(content.text().collect::<String>(), "공지 본문");
+        assert_eq!(content.inner_html(), r#"<p>공지 본문</p><img src="">"#);
+
+        let page = fetch_page::<MockFetchPlugin>(&client, &format!("{}/small", server.uri()))
+            .await
+            .unwrap();
+        assert!(!page.stripped);
+        assert!(page.text.contains("base64,AAAA"));
+    }
+
+    struct MockEucKrPlugin;
+
+    impl SsufidPlugin for MockEucKrPlugin {
+        const TITLE: &'static str = "Mock EUC-KR";
+        const IDENTIFIER: &'static str = "mock.euc-kr";
+        const DESCRIPTION: &'static str = "Mock plugin serving EUC-KR pages";
+        const BASE_URL: &'static str = "https://example.com/euc-kr";
+        const ENCODING: &'static encoding_rs::Encoding = encoding_rs::EUC_KR;
+    }
+
+    #[tokio::test]
+    async fn test_fetch_text_decodes_with_declared_encoding() {
+        let server = MockServer::start().await;
+        let html = "<p>2025학년도 1학기 기숙사 입사 안내</p>";
+        let (euc_kr, _, _) = encoding_rs::EUC_KR.encode(html);
+        Mock::given(method("GET"))
+            .and(path("/list"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(euc_kr.into_owned(), "text/html"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/utf8"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw(html.as_bytes().to_vec(), "text/html; charset=UTF-8"),
+            )
+            .mount(&server)
+            .await;
+
+        let client = reqwest::Client::new();
+        let text = fetch_text::<MockEucKrPlugin>(&client, &format!("{}/list", server.uri()))
+            .await
+            .unwrap();
+        assert_eq!(text, html);
+
+        // The encoding a page declares wins over the plugin's
+        let text = fetch_text::<MockEucKrPlugin>(&client, &format!("{}/utf8", server.uri()))
+            .await
+            .unwrap();
+        assert_eq!(text, html);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_content_type_is_normalized() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "Application/PDF; charset=binary"),
+            )
+            .mount(&server)
+            .await;
+
+        let mime_type =
+            fetch_content_type::<MockFetchPlugin>(&default_client(), &server.uri(), None)
+                .await
+                .unwrap();
+        assert_eq!(mime_type.as_deref(), Some("application/pdf"));
+    }
+
+    #[tokio::test]
+    async fn test_attachment_download_sends_post_as_referer() {
+        struct ProtectedPlugin;
+
+        impl SsufidPlugin for ProtectedPlugin {
+            const TITLE: &'static str = "Protected";
+            const IDENTIFIER: &'static str = "protected.example.com";
+            const DESCRIPTION: &'static str = "Mock plugin with hotlink-protected files";
+            const BASE_URL: &'static str = "https://protected.example.com";
+            const EXTRA_HEADERS: &'static [(&'static str, &'static str)] = &[
+                ("X-Requested-With", "XMLHttpRequest"),
+                ("Referer", "https://protected.example.com/"),
+            ];
+        }
+
+        let server = MockServer::start().await;
+        let post_url = format!("{}/board/view.php?idx=7", server.uri());
+        Mock::given(method("GET"))
+            .and(path("/board/download.php"))
+            .and(header("referer", post_url.as_str()))
+            .and(header("x-requested-with", "XMLHttpRequest"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"%PDF-1.4".to_vec()))
+            .mount(&server)
+            .await;
+        // A board that refuses downloads opened from elsewhere
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+
+        let attachment = Attachment {
+            url: format!("{}/board/download.php?idx=7&no=1", server.uri()),
+            name: Some("신청서.pdf".to_string()),
+            mime_type: None,
+            metadata: None,
+        };
+        let client = default_client();
+        let bytes = fetch_attachment::<ProtectedPlugin>(
+            &client,
+            &attachment,
+            &post_url,
+            MAX_ATTACHMENT_BYTES,
+        )
+        .await
+        .unwrap();
+        assert_eq!(bytes, b"%PDF-1.4");
+        // Sends only the post URL instead of the plugin's Referer
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests[0].headers.get_all("referer").iter().count(), 1);
+
+        let other_post = format!("{}/board/view.php?idx=8", server.uri());
+        assert!(
+            fetch_attachment::<ProtectedPlugin>(
+                &client,
+                &attachment,
+                &other_post,
+                MAX_ATTACHMENT_BYTES
+            )
+            .await
+            .is_err()
+        );
+
+        // Files larger than allowed are not downloaded
+        assert!(
+            fetch_attachment::<ProtectedPlugin>(&client, &attachment, &post_url, 4)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_follows_frameset_content_frame() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<html><frameset rows="80,*">
+                    <frame name="top" src="/menu.html">
+                    <frame name="main" src="board/list.html">
+                </frameset></html>"#,
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/board/list.html"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<html><body><iframe src="notice.html"></iframe></body></html>"#,
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/board/notice.html"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<html><body><ul class="notice"><li>학사 공지</li></ul></body></html>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let (url, text) =
+            fetch_text_following_frames::<MockFetchPlugin>(&default_client(), &server.uri())
+                .await
+                .unwrap();
+        assert_eq!(url.path(), "/board/notice.html");
+        assert!(text.contains("학사 공지"));
+    }
+
+    #[tokio::test]
+    async fn test_does_not_follow_embedded_iframe() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<html><body><p>본문</p><iframe src="https://www.youtube.com/embed/x"></iframe></body></html>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let (url, text) =
+            fetch_text_following_frames::<MockFetchPlugin>(&default_client(), &server.uri())
+                .await
+                .unwrap();
+        assert_eq!(url.as_str().trim_end_matches('/'), server.uri());
+        assert!(text.contains("본문"));
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    struct MockOtherFetchPlugin;
+
+    impl SsufidPlugin for MockOtherFetchPlugin {
+        const TITLE: &'static str = "Mock Other Fetch";
+        const IDENTIFIER: &'static str = "mock.fetch.other";
+        const DESCRIPTION: &'static str = "Second mock plugin for fetch helper tests";
+        const BASE_URL: &'static str = "https://example.com/fetch/other";
+    }
+
+    /// Serves every request after a short delay, recording the most requests ever open at once.
+    async fn slow_server() -> (String, Arc<std::sync::atomic::AtomicUsize>) {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let open = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let peak_clone = peak.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let (open, peak) = (open.clone(), peak_clone.clone());
+                tokio::spawn(async move {
+                    let mut buf = [0; 1024];
+                    let _ = stream.read(&mut buf).await;
+                    let now_open = open.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now_open, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(30)).await;
+                    open.fetch_sub(1, Ordering::SeqCst);
+                    let _ = stream
+                        .write_all(
+                            b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok",
+                        )
+                        .await;
+                });
+            }
+        });
+        (format!("http://{addr}"), peak)
+    }
+
+    #[tokio::test]
+    async fn test_request_limiter_caps_in_flight_requests_across_plugins() {
+        let (uri, peak) = slow_server().await;
+        let limiter = RequestLimiter::new(3);
+        let client = default_client();
+        let urls = (0..8).map(|i| format!("{uri}/{i}")).collect::<Vec<_>>();
+
+        let first =
+            futures::future::join_all(urls.iter().map(|url| {
+                fetch_text_limited::<MockFetchPlugin>(&client, url, FAST_RETRY, &limiter)
+            }));
+        let second = futures::future::join_all(urls.iter().map(|url| {
+            fetch_text_limited::<MockOtherFetchPlugin>(&client, url, FAST_RETRY, &limiter)
+        }));
+        let (first, second) = tokio::join!(first, second);
+
+        assert!(first.into_iter().chain(second).all(|r| r.unwrap() == "ok"));
+        let peak = peak.load(std::sync::atomic::Ordering::SeqCst);
+        assert!((1..=3).contains(&peak), "peak of {peak} requests in flight");
+    }
+
+    #[tokio::test]
+    async fn test_request_limiter_caps_in_flight_requests_per_host() {
+        let (uri, peak) = slow_server().await;
+        let host = uri.trim_start_matches("http://").to_string();
+        let limiter = RequestLimiter::new(64).max_per_host(2);
+        let client = default_client();
+        let urls = (0..8).map(|i| format!("{uri}/{i}")).collect::<Vec<_>>();
+
+        let first =
+            futures::future::join_all(urls.iter().map(|url| {
+                fetch_text_limited::<MockFetchPlugin>(&client, url, FAST_RETRY, &limiter)
+            }));
+        let second = futures::future::join_all(urls.iter().map(|url| {
+            fetch_text_limited::<MockOtherFetchPlugin>(&client, url, FAST_RETRY, &limiter)
+        }));
+        let gauge_peak = async {
+            let mut gauge_peak = 0;
+            for _ in 0..20 {
+                let in_flight =
+                    crate::metrics::gauge(crate::metrics::IN_FLIGHT_REQUESTS, &[("host", &host)]);
+                gauge_peak = gauge_peak.max(in_flight);
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+            gauge_peak
+        };
+        let (first, second, gauge_peak) = tokio::join!(first, second, gauge_peak);
+
+        assert!(first.into_iter().chain(second).all(|r| r.unwrap() == "ok"));
+        // The global limit is generous, but only two go to the same host
+        let peak = peak.load(std::sync::atomic::Ordering::SeqCst);
+        assert!((1..=2).contains(&peak), "peak of {peak} requests in flight");
+        assert!(
+            (1..=2).contains(&gauge_peak),
+            "gauge peaked at {gauge_peak}"
+        );
+        assert_eq!(
+            crate::metrics::gauge(crate::metrics::IN_FLIGHT_REQUESTS, &[("host", &host)]),
+            0
+        );
+    }
+}
@@ -0,0 +1,262 @@
+//! Recorded copies of a plugin's pages for offline tests, kept up to date with
+//! `ssufid fixtures refresh`.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    path::Path,
+    sync::LazyLock,
+};
+
+use crate::{Error, core::SsufidPlugin, fetch::fetch_text};
+
+/// What replaces a scrubbed value, so that a refresh only shows changes to the page itself.
+pub const SCRUBBED: &str = "SCRUBBED";
+
+static COMMENT: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r"(?s)<!--.*?-->").unwrap());
+static DATE_TIME: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(r"\d{4}[-./]\d{1,2}[-./]\d{1,2}(?:[ T]\d{1,2}:\d{2}(?::\d{2})?)?").unwrap()
+});
+static CSRF_TAG: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(
+        r#"(?i)<(?:input|meta)\b[^>]*\b(?:name|id)\s*=\s*["']?[\w-]*(?:csrf|xsrf|authenticity_token|requestverificationtoken|_token)[\w-]*["']?[^>]*>"#,
+    )
+    .unwrap()
+});
+static TOKEN_VALUE: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(r#"(?i)\b(value|content)\s*=\s*(?:"[^"]*"|'[^']*'|[^\s>]+)"#).unwrap()
+});
+
+/// Replaces the parts of a page that change on every request with [`SCRUBBED`]: dates and
+/// times inside HTML comments (e.g. a render timestamp) and the values of CSRF token fields
+/// and `<meta name="csrf-token">` tags.
+pub fn scrub(html: &str) -> String {
+    let html = COMMENT.replace_all(html, |caps: &regex::Captures<'_>| {
+        DATE_TIME.replace_all(&caps[0], SCRUBBED).into_owned()
+    });
+    CSRF_TAG
+        .replace_all(&html, |caps: &regex::Captures<'_>| {
+            TOKEN_VALUE
+                .replace_all(&caps[0], format!(r#"$1="{SCRUBBED}""#))
+                .into_owned()
+        })
+        .into_owned()
+}
+
+/// The file a fixture of `url` is stored in: the URL percent-encoded and without its scheme
+/// or fragment, with every character other than ASCII letters, digits, `-` and `.` replaced by
+/// `_`, so that a page keeps its file across refreshes whether its URL was encoded or not.
+pub fn fixture_file_name(url: &str) -> String {
+    let normalized = url::Url::parse(url).map(|mut url| {
+        url.set_fragment(None);
+        url.to_string()
+    });
+    let url = normalized.as_deref().unwrap_or(url);
+    let url = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let stem = url
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    format!("{}.html", stem.trim_matches('_'))
+}
+
+/// How a fixture differs from the one it replaced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixtureChange {
+    Added,
+    Unchanged,
+    /// The number of lines only in the new fixture and only in the old one.
+    Changed {
+        added: usize,
+        removed: usize,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixtureDiff {
+    pub file: String,
+    pub change: FixtureChange,
+}
+
+impl fmt::Display for FixtureDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.change {
+            FixtureChange::Added => write!(f, "added\t{}", self.file),
+            FixtureChange::Unchanged => write!(f, "unchanged\t{}", self.file),
+            FixtureChange::Changed { added, removed } => {
+                write!(f, "changed\t{}\t+{added} -{removed}", self.file)
+            }
+        }
+    }
+}
+
+/// Downloads `urls` into `dir` with [`fetch_text`], [scrubbed](scrub), replacing their
+/// fixtures there. Other files in `dir` are left alone, since fixtures of the pages a test
+/// serves by hand (e.g. a later list page) are not among `urls`.
+///
+/// Returns how each downloaded fixture changed, sorted by file name.
+pub async fn refresh<T: SsufidPlugin>(
+    client: &reqwest::Client,
+    urls: &[String],
+    dir: &Path,
+) -> Result<Vec<FixtureDiff>, Error> {
+    let mut pages = BTreeMap::new();
+    for url in urls {
+        let html = scrub(&fetch_text::<T>(client, url).await?);
+        pages.insert(fixture_file_name(url), html);
+    }
+
+    std::fs::create_dir_all(dir)?;
+    let mut diffs = Vec::new();
+    for (file, html) in pages {
+        let path = dir.join(&file);
+        let change = match std::fs::read_to_string(&path) {
+            Ok(old) if old == html => FixtureChange::Unchanged,
+            Ok(old) => {
+                let (added, removed) = line_changes(&old, &html);
+                FixtureChange::Changed { added, removed }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => FixtureChange::Added,
+            Err(e) => return Err(e.into()),
+        };
+        if change != FixtureChange::Unchanged {
+            std::fs::write(&path, html)?;
+        }
+        diffs.push(FixtureDiff { file, change });
+    }
+    Ok(diffs)
+}
+
+/// Counts the lines only in `new` and only in `old`, ignoring their order.
+fn line_changes(old: &str, new: &str) -> (usize, usize) {
+    let mut counts = HashMap::<&str, isize>::new();
+    for line in old.lines() {
+        *counts.entry(line).or_default() -= 1;
+    }
+    for line in new.lines() {
+        *counts.entry(line).or_default() += 1;
+    }
+    counts
+        .values()
+        .fold((0, 0), |(added, removed), &count| match count {
+            1.. => (added + count.unsigned_abs(), removed),
+            ..0 => (added, removed + count.unsigned_abs()),
+            0 => (added, removed),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path},
+    };
+
+    use super::*;
+
+    struct MockFixturePlugin;
+
+    impl SsufidPlugin for MockFixturePlugin {
+        const TITLE: &'static str = "Mock Fixture";
+        const IDENTIFIER: &'static str = "mock.fixture";
+        const DESCRIPTION: &'static str = "Mock plugin for fixture tests";
+        const BASE_URL: &'static str = "https://example.com/fixture";
+    }
+
+    #[test]
+    fn test_scrub_volatile_parts() {
+        let html = r#"<!-- generated 2025-03-10 12:34:56 --><form>
+<input type="hidden" name="_csrf" value="a1b2c3"><input name="title" value="2025-03-10">
+<meta name="csrf-token" content='d4e5f6'></form><p>2025-03-10 공지</p>"#;
+        assert_eq!(
+            scrub(html),
+            r#"<!-- generated SCRUBBED --><form>
+<input type="hidden" name="_csrf" value="SCRUBBED"><input name="title" value="2025-03-10">
+<meta name="csrf-token" content="SCRUBBED"></form><p>2025-03-10 공지</p>"#
+        );
+    }
+
+    #[test]
+    fn test_fixture_file_name_is_stable() {
+        assert_eq!(
+            fixture_file_name("https://example.com/board/view.php?idx=12&page=1"),
+            "example.com_board_view.php_idx_12_page_1.html"
+        );
+        assert_eq!(
+            fixture_file_name("https://example.com/공지"),
+            fixture_file_name("https://example.com/%EA%B3%B5%EC%A7%80")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_refresh_scrubs_and_summarizes_changes() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/list"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "<!-- 2025-03-10 09:00 -->\n<input name=\"csrf_token\" value=\"x9y8\">\n<ul><li>새 공지</li></ul>",
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/view/1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<p>본문</p>"))
+            .mount(&server)
+            .await;
+
+        let dir = std::env::temp_dir().join(format!("ssufid-fixtures-{}", std::process::id()));
+        let list_url = format!("{}/list", server.uri());
+        let view_url = format!("{}/view/1", server.uri());
+        let list_file = fixture_file_name(&list_url);
+        let view_file = fixture_file_name(&view_url);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join(&list_file),
+            "<!-- SCRUBBED -->\n<input name=\"csrf_token\" value=\"SCRUBBED\">\n<ul><li>옛 공지</li></ul>",
+        )
+        .unwrap();
+        std::fs::write(dir.join(&view_file), "<p>본문</p>").unwrap();
+        std::fs::write(dir.join("page_2.html"), "<p>2페이지</p>").unwrap();
+
+        let diffs = refresh::<MockFixturePlugin>(
+            &reqwest::Client::new(),
+            &[list_url.clone(), view_url],
+            &dir,
+        )
+        .await
+        .unwrap();
+        let list = std::fs::read_to_string(dir.join(&list_file)).unwrap();
+        let other = std::fs::read_to_string(dir.join("page_2.html")).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        // Lines where only dates and tokens changed are not reported
+        assert_eq!(
+            list,
+            "<!-- SCRUBBED -->\n<input name=\"csrf_token\" value=\"SCRUBBED\">\n<ul><li>새 공지</li></ul>"
+        );
+        // Fixtures that were not fetched are kept
+        assert_eq!(other, "<p>2페이지</p>");
+        let mut expected = vec![
+            FixtureDiff {
+                file: list_file,
+                change: FixtureChange::Changed {
+                    added: 1,
+                    removed: 1,
+                },
+            },
+            FixtureDiff {
+                file: view_file,
+                change: FixtureChange::Unchanged,
+            },
+        ];
+        expected.sort_by(|a, b| a.file.cmp(&b.file));
+        assert_eq!(diffs, expected);
+    }
+}
@@ -0,0 +1,1430 @@
+use std::{borrow::Cow, sync::LazyLock};
+
+use scraper::{ElementRef, Html, Node, Selector, selector::ToCss};
+
+use crate::{PluginError, core::SsufidPlugin, metrics};
+
+/// Parses `css` into a [`Selector`], reporting an invalid selector as a plugin error instead of
+/// panicking.
+pub fn parse_selector<T: SsufidPlugin>(css: &str) -> Result<Selector, PluginError> {
+    Selector::parse(css).map_err(|e| {
+        PluginError::custom::<T>(
+            "SelectorParse".to_string(),
+            format!("Failed to parse selector '{css}': {e}"),
+        )
+    })
+}
+
+/// Selects `selector` in a document that is expected to match it, warning and counting
+/// `selector_miss_total` when nothing does.
+pub fn select_expecting<'a, T: SsufidPlugin>(
+    document: &'a Html,
+    selector: &Selector,
+    page_url: &str,
+) -> impl Iterator<Item = ElementRef<'a>> {
+    let mut elements = document.select(selector).peekable();
+    if elements.peek().is_none() {
+        let selector = selector.to_css_string();
+        tracing::warn!(
+            plugin = T::IDENTIFIER,
+            selector = %selector,
+            url = page_url,
+            "Selector matched zero elements"
+        );
+        metrics::increment_counter(metrics::SELECTOR_MISS_TOTAL, &[("plugin", T::IDENTIFIER)]);
+    }
+    elements
+}
+
+/// Where [`select_content`] found a post's content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentSource {
+    /// The plugin's own content selector matched.
+    Primary,
+    /// One of the generic fallbacks matched, so the content may include surrounding chrome.
+    Fallback,
+}
+
+/// Generic selectors tried in order when a plugin's content selector misses. Among several
+/// matches, the one with the most text wins.
+const CONTENT_FALLBACKS: &[&str] = &["article", r#"div[class*="content"]"#];
+
+/// Blocks considered by the text-density fallback of [`select_content`].
+const TEXT_BLOCKS: &str = "div, td, section, main";
+
+/// A text-dense block needs at least this many characters of its own text to count as content.
+const MIN_TEXT_BLOCK_CHARS: usize = 50;
+
+/// Finds a post's content with the `primary` selector, falling back to an `<article>`, a
+/// `content` class and then the block with the most text.
+pub fn select_content<'a, T: SsufidPlugin>(
+    document: &'a Html,
+    primary: &Selector,
+    page_url: &str,
+) -> Option<(ElementRef<'a>, ContentSource)> {
+    if let Some(content) = select_expecting::<T>(document, primary, page_url).next() {
+        return Some((content, ContentSource::Primary));
+    }
+    let text_len = |element: &ElementRef<'_>| {
+        element
+            .text()
+            .map(|text| text.trim().chars().count())
+            .sum::<usize>()
+    };
+    let fallback = CONTENT_FALLBACKS
+        .iter()
+        .filter_map(|css| Selector::parse(css).ok())
+        .find_map(|selector| {
+            document
+                .select(&selector)
+                .filter(|element| text_len(element) > 0)
+                .max_by_key(text_len)
+        })
+        .or_else(|| densest_text_block(document));
+    if let Some(content) = &fallback {
+        tracing::warn!(
+            plugin = T::IDENTIFIER,
+            url = page_url,
+            element = content.value().name(),
+            "Using fallback content"
+        );
+    }
+    fallback.map(|content| (content, ContentSource::Fallback))
+}
+
+/// The block with the most text of its own, see [`select_content`].
+fn densest_text_block(document: &Html) -> Option<ElementRef<'_>> {
+    static BLOCKS: LazyLock<Selector> = LazyLock::new(|| Selector::parse(TEXT_BLOCKS).unwrap());
+    static NESTED: LazyLock<Selector> =
+        LazyLock::new(|| Selector::parse(&format!("{TEXT_BLOCKS}, ul, ol, table, nav")).unwrap());
+    static LINKS: LazyLock<Selector> = LazyLock::new(|| Selector::parse("a").unwrap());
+    let own_text = |block: &ElementRef<'_>| {
+        block
+            .children()
+            .map(|child| match (child.value(), ElementRef::wrap(child)) {
+                (Node::Text(text), _) => text.trim().chars().count(),
+                (Node::Element(_), Some(element))
+                    if element.value().name() != "a" && !NESTED.matches(&element) =>
+                {
+                    let text = element.text().collect::<String>();
+                    let links = element
+                        .select(&LINKS)
+                        .map(|a| a.text().collect::<String>().trim().chars().count())
+                        .sum::<usize>();
+                    text.trim().chars().count().saturating_sub(links)
+                }
+                _ => 0,
+            })
+            .sum::<usize>()
+    };
+    document
+        .select(&BLOCKS)
+        .map(|block| (own_text(&block), block))
+        .filter(|(score, _)| *score >= MIN_TEXT_BLOCK_CHARS)
+        .max_by_key(|(score, _)| *score)
+        .map(|(_, block)| block)
+}
+
+/// The maximum length of an [`html_snippet`], in bytes.
+pub const SNIPPET_MAX_BYTES: usize = 2048;
+
+/// A short excerpt of a page for error reports: the page from `<body>` on, if present, with
+/// `<script>` and `<style>` elements removed and whitespace collapsed, cut to
+/// [`SNIPPET_MAX_BYTES`].
+pub fn html_snippet(html: &str) -> String {
+    let start = find_ignore_case(html, "<body").unwrap_or(0);
+    let mut rest = &html[start..];
+    let mut stripped = String::new();
+    while let Some(open) = ["<script", "<style"]
+        .iter()
+        .filter_map(|tag| find_ignore_case(rest, tag).map(|i| (i, &tag[1..])))
+        .min()
+    {
+        let (i, tag) = open;
+        stripped.push_str(&rest[..i]);
+        let close = format!("</{tag}>");
+        rest =
+            find_ignore_case(&rest[i..], &close).map_or("", |end| &rest[i + end + close.len()..]);
+    }
+    stripped.push_str(rest);
+
+    let collapsed = stripped.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.len() <= SNIPPET_MAX_BYTES {
+        return collapsed;
+    }
+    let end = (0..=SNIPPET_MAX_BYTES)
+        .rev()
+        .find(|&i| collapsed.is_char_boundary(i))
+        .unwrap_or(0);
+    format!("{}…", &collapsed[..end])
+}
+
+fn find_ignore_case(haystack: &str, needle: &str) -> Option<usize> {
+    haystack
+        .as_bytes()
+        .windows(needle.len())
+        .position(|window| window.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+/// Returns the names of the plugin's [`SsufidPlugin::CRITICAL_SELECTORS`] that match zero
+/// elements in every one of `pages`, e.g. `["content"]` when the detail page markup changed.
+///
+/// Pass both list and detail pages, since list selectors only match the former and post
+/// selectors only the latter. Invalid selectors are reported as broken as well.
+pub fn health_check<T: SsufidPlugin>(pages: &[Html]) -> Vec<&'static str> {
+    T::CRITICAL_SELECTORS
+        .iter()
+        .filter(|(name, css)| {
+            let matched = Selector::parse(css).is_ok_and(|selector| {
+                pages
+                    .iter()
+                    .any(|page| page.select(&selector).next().is_some())
+            });
+            if !matched {
+                tracing::warn!(
+                    plugin = T::IDENTIFIER,
+                    name,
+                    selector = css,
+                    "Critical selector matched zero elements"
+                );
+            }
+            !matched
+        })
+        .map(|(name, _)| *name)
+        .collect()
+}
+
+/// Extracts the highest page number linked from a pagination widget matched by `selector`.
+pub fn last_page(document: &Html, selector: &Selector) -> Option<u32> {
+    document
+        .select(selector)
+        .filter_map(|el| {
+            let el = el.value();
+            el.attr("href")
+                .and_then(page_from_href)
+                .or_else(|| el.attr("onclick").and_then(page_from_call))
+        })
+        .max()
+}
+
+fn page_from_href(href: &str) -> Option<u32> {
+    let href = href.trim();
+    if href.get(..11)?.eq_ignore_ascii_case("javascript:") {
+        return page_from_call(href);
+    }
+    let url = url::Url::parse("http://localhost/").ok()?.join(href).ok()?;
+    let from_query = url.query_pairs().find_map(|(key, value)| {
+        let key = key.to_ascii_lowercase();
+        (key.contains("page") || key == "pno" || key == "pg")
+            .then(|| value.parse().ok())
+            .flatten()
+    });
+    from_query.or_else(|| {
+        url.path_segments()?
+            .skip_while(|segment| *segment != "page")
+            .nth(1)?
+            .parse()
+            .ok()
+    })
+}
+
+/// Reads the first numeric argument of a call such as `fnGoPage(12); return false;`.
+fn page_from_call(script: &str) -> Option<u32> {
+    let args = &script[script.find('(')? + 1..];
+    args[..args.find(')')?]
+        .split(',')
+        .find_map(|arg| arg.trim().trim_matches(['\'', '"']).parse().ok())
+}
+
+/// Metadata key under which [`store_tables_in_metadata`] saves the extracted tables.
+pub const TABLES_METADATA_KEY: &str = "tables";
+
+/// Converts every `<table>` in an HTML fragment into a JSON array of row objects keyed by the
+/// table's header cells.
+///
+/// The header is the first row of `<thead>`, or else the first row of the table. Header cells
+/// that are empty or repeated are keyed by their 1-based column number instead.
+pub fn tables_to_json(content: &str) -> Vec<serde_json::Value> {
+    static TABLE: LazyLock<Selector> = LazyLock::new(|| Selector::parse("table").unwrap());
+    static HEAD_ROW: LazyLock<Selector> = LazyLock::new(|| Selector::parse("thead > tr").unwrap());
+    static ROW: LazyLock<Selector> = LazyLock::new(|| Selector::parse("tr").unwrap());
+    static CELL: LazyLock<Selector> = LazyLock::new(|| Selector::parse("th, td").unwrap());
+
+    let cells = |row: ElementRef<'_>| {
+        row.select(&CELL)
+            .map(|cell| crate::text::clean_text(&cell.text().collect::<Vec<_>>().join(" ")))
+            .collect::<Vec<_>>()
+    };
+
+    let fragment = Html::parse_fragment(content);
+    fragment
+        .select(&TABLE)
+        .map(|table| {
+            // Rows of a nested table are not part of the outer table
+            let rows = table
+                .select(&ROW)
+                .filter(|row| {
+                    row.ancestors()
+                        .filter_map(ElementRef::wrap)
+                        .find(|el| el.value().name() == "table")
+                        == Some(table)
+                })
+                .collect::<Vec<_>>();
+            let header_row = table
+                .select(&HEAD_ROW)
+                .next()
+                .or_else(|| rows.first().copied());
+            let Some(header_row) = header_row else {
+                return serde_json::Value::Array(vec![]);
+            };
+            let mut keys = Vec::<String>::new();
+            for (i, header) in cells(header_row).into_iter().enumerate() {
+                let key = if header.is_empty() || keys.contains(&header) {
+                    (i + 1).to_string()
+                } else {
+                    header
+                };
+                keys.push(key);
+            }
+            rows.into_iter()
+                .filter(|row| *row != header_row)
+                .map(cells)
+                .filter(|values| values.iter().any(|v| !v.is_empty()))
+                .map(|values| {
+                    let row = values
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, value)| {
+                            let key = keys.get(i).cloned().unwrap_or_else(|| (i + 1).to_string());
+                            (key, serde_json::Value::String(value))
+                        })
+                        .collect::<serde_json::Map<_, _>>();
+                    serde_json::Value::Object(row)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Stores the tables of `post`'s content, if any, as a JSON string under
+/// [`TABLES_METADATA_KEY`] in its metadata.
+pub fn store_tables_in_metadata(post: &mut crate::core::SsufidPost) {
+    let tables = tables_to_json(&post.content);
+    if tables.is_empty() {
+        return;
+    }
+    post.metadata.get_or_insert_default().insert(
+        TABLES_METADATA_KEY.to_string(),
+        serde_json::Value::Array(tables).to_string(),
+    );
+}
+
+/// Elements that carry content even without any text.
+const EMBEDDED_ELEMENTS: &str = "img, iframe, video, audio, embed, object, table, hr, input";
+
+fn is_blank_text(text: &str) -> bool {
+    text.chars().all(|c| c.is_whitespace() || c == '\u{a0}')
+}
+
+/// Whether a node renders as nothing but blank space: whitespace or `&nbsp;` text, `<br>`,
+/// comments, or an element containing only those.
+fn is_blank_node(node: &Node, element: Option<ElementRef<'_>>) -> bool {
+    static EMBEDDED: LazyLock<Selector> =
+        LazyLock::new(|| Selector::parse(EMBEDDED_ELEMENTS).unwrap());
+    match (node, element) {
+        (Node::Text(text), _) => is_blank_text(text),
+        (Node::Comment(_), _) => true,
+        (Node::Element(_), Some(element)) => {
+            element.value().name() == "br"
+                || (!EMBEDDED.matches(&element)
+                    && element.select(&EMBEDDED).next().is_none()
+                    && element.text().all(is_blank_text))
+        }
+        _ => false,
+    }
+}
+
+/// Removes blank nodes from the start and end of an HTML fragment, such as a leading
+/// `<p>&nbsp;</p>` or a trailing `<br>`. Nodes in between, and the inside of the nodes that
+/// are kept, are left untouched.
+pub fn trim_empty_edges(html: &str) -> String {
+    let fragment = Html::parse_fragment(html);
+    let nodes = fragment.root_element().children().collect::<Vec<_>>();
+    let Some(start) = nodes
+        .iter()
+        .position(|node| !is_blank_node(node.value(), ElementRef::wrap(*node)))
+    else {
+        return String::new();
+    };
+    let end = nodes
+        .iter()
+        .rposition(|node| !is_blank_node(node.value(), ElementRef::wrap(*node)))
+        .unwrap_or(start);
+    nodes[start..=end]
+        .iter()
+        .map(|node| render_node(node.value(), ElementRef::wrap(*node)))
+        .collect()
+}
+
+fn render_node(node: &Node, element: Option<ElementRef<'_>>) -> String {
+    match node {
+        Node::Text(text) => escape_text(text),
+        Node::Comment(comment) => format!("<!--{}-->", &**comment),
+        _ => element.map(|element| element.html()).unwrap_or_default(),
+    }
+}
+
+const HEADING_ELEMENTS: &str = "h1, h2, h3, h4, h5, h6, strong, b";
+
+/// Removes the first non-blank node of an HTML fragment if it is a heading repeating
+/// `title`, such as `<h3>제목</h3>` or `<p><strong>제목</strong></p>`, so that feeds do not show
+/// the title twice. Wrappers such as `<div id="bo_v_con">` are descended through their first
+/// non-blank element. Texts are compared after [`clean_text`](crate::text::clean_text); anything
+/// else is returned unchanged.
+pub fn dedupe_leading_title(html: &str, title: &str) -> String {
+    static HEADING: LazyLock<Selector> =
+        LazyLock::new(|| Selector::parse(HEADING_ELEMENTS).unwrap());
+    let title = crate::text::clean_text(title);
+    if title.is_empty() {
+        return html.to_string();
+    }
+    let is_title = |element: ElementRef<'_>| {
+        crate::text::clean_text(&element.text().collect::<String>()) == title
+    };
+    let mut fragment = Html::parse_fragment(html);
+    let mut parent = fragment.root_element();
+    // The heading and the blank nodes before it
+    let removed = loop {
+        let nodes = parent.children().collect::<Vec<_>>();
+        let Some(first) = nodes
+            .iter()
+            .position(|node| !is_blank_node(node.value(), ElementRef::wrap(*node)))
+        else {
+            return html.to_string();
+        };
+        let Some(element) = ElementRef::wrap(nodes[first]) else {
+            return html.to_string();
+        };
+        if is_title(element)
+            && (HEADING.matches(&element) || element.select(&HEADING).any(is_title))
+        {
+            break nodes[..=first]
+                .iter()
+                .map(|node| node.id())
+                .collect::<Vec<_>>();
+        }
+        if HEADING.matches(&element) {
+            return html.to_string();
+        }
+        parent = element;
+    };
+    for id in removed {
+        if let Some(mut node) = fragment.tree.get_mut(id) {
+            node.detach();
+        }
+    }
+    fragment.root_element().inner_html()
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('\u{a0}', "&nbsp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+static EMAIL: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}").unwrap()
+});
+static PHONE: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r"0\d{1,2}[-. )]?\d{3,4}[-. ]?\d{4}").unwrap());
+
+/// Masks contact details in plain text: the local part of email addresses
+/// (`***@ssu.ac.kr`) and all but the area code of Korean phone numbers (`02-***-****`).
+fn redact_contacts_in_text(text: &str) -> String {
+    let text = EMAIL.replace_all(text, |caps: &regex::Captures<'_>| {
+        let email = &caps[0];
+        let domain = email.rsplit_once('@').map_or("", |(_, domain)| domain);
+        format!("***@{domain}")
+    });
+    let mut redacted = String::with_capacity(text.len());
+    let mut last = 0;
+    for phone in PHONE.find_iter(&text) {
+        // Part of a longer run of digits is not a phone number
+        let digit_before = text[..phone.start()]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_ascii_digit());
+        let digit_after = text[phone.end()..]
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_digit());
+        if digit_before || digit_after {
+            continue;
+        }
+        let area_len = phone
+            .as_str()
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(if phone.as_str().starts_with("02") {
+                2
+            } else {
+                3
+            });
+        redacted.push_str(&text[last..phone.start()]);
+        redacted.push_str(&phone.as_str()[..area_len]);
+        redacted.extend(
+            phone.as_str()[area_len..]
+                .chars()
+                .map(|c| if c.is_ascii_digit() { '*' } else { c }),
+        );
+        last = phone.end();
+    }
+    redacted.push_str(&text[last..]);
+    redacted
+}
+
+/// Masks email addresses and Korean phone numbers in the text of an HTML fragment, e.g. for a
+/// public mirror. Markup, including attributes such as `mailto:` links, is left as is.
+pub fn redact_contacts(html: &str) -> Result<String, lol_html::errors::RewritingError> {
+    use lol_html::{RewriteStrSettings, html_content::ContentType, rewrite_str, text};
+
+    let mut buffer = String::new();
+    rewrite_str(
+        html,
+        RewriteStrSettings {
+            element_content_handlers: vec![text!("*", |chunk| {
+                // A text node may arrive in several chunks, so replace once on the last chunk
+                buffer.push_str(chunk.as_str());
+                if chunk.last_in_text_node() {
+                    chunk.replace(
+                        &redact_contacts_in_text(&std::mem::take(&mut buffer)),
+                        ContentType::Html,
+                    );
+                } else {
+                    chunk.remove();
+                }
+                Ok(())
+            })],
+            ..RewriteStrSettings::new()
+        },
+    )
+}
+
+/// Unwraps links in an HTML fragment that point to one of `attachments`, for boards that show
+/// their file list both in the content and as separate attachments. The link text and any
+/// images inside are kept. Relative `href`s are resolved against `base_url`, the URL of the post.
+pub fn dedupe_attachment_links(
+    html: &str,
+    base_url: &str,
+    attachments: &[crate::core::Attachment],
+) -> Result<String, lol_html::errors::RewritingError> {
+    use lol_html::{RewriteStrSettings, element, rewrite_str};
+
+    let base_url = url::Url::parse(base_url).ok();
+    let resolve = |href: &str| {
+        let href = href.trim();
+        match &base_url {
+            Some(base_url) => base_url
+                .join(href)
+                .map_or_else(|_| href.to_string(), String::from),
+            None => href.to_string(),
+        }
+    };
+    let attachment_urls = attachments
+        .iter()
+        .map(|attachment| resolve(&attachment.url))
+        .collect::<std::collections::HashSet<_>>();
+    if attachment_urls.is_empty() {
+        return Ok(html.to_string());
+    }
+    rewrite_str(
+        html,
+        RewriteStrSettings {
+            element_content_handlers: vec![element!("a[href]", |anchor| {
+                if anchor
+                    .get_attribute("href")
+                    // lol_html does not decode entities in attribute values
+                    .is_some_and(|href| {
+                        attachment_urls.contains(&resolve(&href.replace("&amp;", "&")))
+                    })
+                {
+                    anchor.remove_and_keep_content();
+                }
+                Ok(())
+            })],
+            ..RewriteStrSettings::new()
+        },
+    )
+}
+
+/// The class of the `<div>` that [`wrap_tables`] puts around tables.
+pub const TABLE_SCROLL_CLASS: &str = "table-scroll";
+
+/// Wraps each `<table>` of an HTML fragment in a `<div class="table-scroll">`, so that a
+/// frontend can let wide tables scroll sideways on narrow screens instead of overflowing.
+/// Tables nested in another table scroll with it and are not wrapped again, and tables
+/// without a closing tag are left alone since their wrapper could not be closed.
+pub fn wrap_tables(html: &str) -> Result<String, lol_html::errors::RewritingError> {
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    };
+
+    use lol_html::{
+        EndTagHandler, RewriteStrSettings, element, html_content::ContentType, rewrite_str,
+    };
+
+    // First pass: record in document order whether each table has a closing tag
+    let closed = Rc::new(RefCell::new(Vec::new()));
+    rewrite_str(
+        html,
+        RewriteStrSettings {
+            element_content_handlers: vec![element!("table", |table| {
+                let index = closed.borrow().len();
+                closed.borrow_mut().push(false);
+                if let Some(handlers) = table.end_tag_handlers() {
+                    let closed = Rc::clone(&closed);
+                    let handler: EndTagHandler<'static> = Box::new(move |_| {
+                        closed.borrow_mut()[index] = true;
+                        Ok(())
+                    });
+                    handlers.push(handler);
+                }
+                Ok(())
+            })],
+            ..RewriteStrSettings::new()
+        },
+    )?;
+    let closed = closed.take();
+
+    let index = Cell::new(0usize);
+    let depth = Rc::new(Cell::new(0usize));
+    rewrite_str(
+        html,
+        RewriteStrSettings {
+            element_content_handlers: vec![element!("table", |table| {
+                let is_closed = closed.get(index.get()).copied().unwrap_or(false);
+                index.set(index.get() + 1);
+                if depth.get() == 0 && is_closed {
+                    table.before(
+                        &format!(r#"<div class="{TABLE_SCROLL_CLASS}">"#),
+                        ContentType::Html,
+                    );
+                    table.after("</div>", ContentType::Html);
+                }
+                // A table without a closing tag runs to the end, so the depth is not restored
+                if let Some(handlers) = table.end_tag_handlers() {
+                    depth.set(depth.get() + 1);
+                    let depth = Rc::clone(&depth);
+                    let handler: EndTagHandler<'static> = Box::new(move |_| {
+                        depth.set(depth.get().saturating_sub(1));
+                        Ok(())
+                    });
+                    handlers.push(handler);
+                }
+                Ok(())
+            })],
+            ..RewriteStrSettings::new()
+        },
+    )
+}
+
+/// A redirect or click tracker whose links carry their destination in a query parameter, e.g.
+/// `https://www.google.com/url?q=<destination>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RedirectPattern<'a> {
+    /// The start of the tracker's URLs, up to the query string.
+    pub prefix: &'a str,
+    /// The query parameter holding the destination.
+    pub param: &'a str,
+}
+
+impl RedirectPattern<'static> {
+    /// Trackers commonly found in links pasted into notices.
+    pub const DEFAULTS: &'static [Self] = &[
+        Self {
+            prefix: "https://www.google.com/url",
+            param: "q",
+        },
+        Self {
+            prefix: "https://l.facebook.com/l.php",
+            param: "u",
+        },
+        Self {
+            prefix: "https://l.instagram.com/",
+            param: "u",
+        },
+        Self {
+            prefix: "https://out.reddit.com/",
+            param: "url",
+        },
+    ];
+}
+
+/// Rewrites links in an HTML fragment that go through one of `patterns` to point at their
+/// destination directly. Links whose destination is missing or not an absolute `http(s)` URL are
+/// left as they are.
+pub fn unwrap_redirect_links(
+    html: &str,
+    patterns: &[RedirectPattern<'_>],
+) -> Result<String, lol_html::errors::RewritingError> {
+    use lol_html::{RewriteStrSettings, element, rewrite_str};
+
+    let destination = |href: &str| {
+        let pattern = patterns
+            .iter()
+            .find(|pattern| href.starts_with(pattern.prefix))?;
+        let href = url::Url::parse(href).ok()?;
+        let (_, target) = href.query_pairs().find(|(key, _)| key == pattern.param)?;
+        let target = url::Url::parse(&target).ok()?;
+        matches!(target.scheme(), "http" | "https").then(|| target.to_string())
+    };
+    rewrite_str(
+        html,
+        RewriteStrSettings {
+            element_content_handlers: vec![element!("a[href]", |anchor| {
+                // lol_html does not decode entities in attribute values
+                let href = anchor
+                    .get_attribute("href")
+                    .map(|href| href.trim().replace("&amp;", "&"));
+                if let Some(target) = href.as_deref().and_then(destination) {
+                    anchor.set_attribute("href", &target.replace('&', "&amp;"))?;
+                }
+                Ok(())
+            })],
+            ..RewriteStrSettings::new()
+        },
+    )
+}
+
+/// Whether a list row is a pinned notice rather than a regular post: it or one of its cells has
+/// a `notice` or `is-notice` class, its first cell reads `공지` or `Notice` in place of the post
+/// number, or it shows a notice icon (an image whose `alt` reads `공지`). Classes are matched as
+/// whole tokens, since boards also use names like `notice-list` for every row.
+pub fn is_notice_row(row: ElementRef<'_>) -> bool {
+    static NOTICE_CLASS: LazyLock<Selector> =
+        LazyLock::new(|| Selector::parse(".notice, .is-notice").unwrap());
+    static NOTICE_ICON: LazyLock<Selector> =
+        LazyLock::new(|| Selector::parse(r#"img[alt*="공지"]"#).unwrap());
+
+    let first_cell_is_marker = row.child_elements().next().is_some_and(|cell| {
+        let text = cell.text().collect::<String>();
+        let text = text.trim().trim_matches(['[', ']']).trim();
+        text == "공지" || text.eq_ignore_ascii_case("notice")
+    });
+    NOTICE_CLASS.matches(&row)
+        || first_cell_is_marker
+        || row.select(&NOTICE_CLASS).next().is_some()
+        || row.select(&NOTICE_ICON).next().is_some()
+}
+
+static STYLE_BLOCK: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r"(?is)<style\b[^>]*>.*?</style\s*>").unwrap());
+static DATA_URI: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r#"(?i)\bdata:[a-z-]+/[^"'\s)>]*"#).unwrap());
+
+/// Removes `<style>` blocks and `data:` URIs, e.g. inline base64 images, from a page in one
+/// pass over the text, without parsing it. Such content can make up almost all of a page while
+/// no plugin reads it. See [`crate::fetch::FetchedPage::bounded`].
+pub fn strip_heavy_content(html: &str) -> String {
+    let html = STYLE_BLOCK.replace_all(html, "");
+    DATA_URI.replace_all(&html, "").into_owned()
+}
+
+/// A start or end tag, capturing the slash, the name and the attributes. Quoted attribute
+/// values may contain `>`.
+static TAG: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(r#"<(/?)([A-Za-z][A-Za-z0-9]*)((?:[^>"']|"[^"]*"|'[^']*')*)>"#).unwrap()
+});
+
+/// Lowercases the names of tags and of their attributes in a page, leaving attribute values
+/// and text alone, for pre-HTML5 markup such as `<TD BGCOLOR="#EDF8FC">`. For a
+/// [`normalize_html`](crate::core::SsufidPlugin::normalize_html) step that compares raw tags.
+pub fn lowercase_tag_names(html: &str) -> Cow<'_, str> {
+    let lowercase = TAG.captures_iter(html).all(|caps| {
+        !caps[2].bytes().any(|b| b.is_ascii_uppercase())
+            && lowercase_attribute_names(&caps[3]) == caps[3]
+    });
+    if lowercase {
+        return Cow::Borrowed(html);
+    }
+    TAG.replace_all(html, |caps: &regex::Captures<'_>| {
+        format!(
+            "<{}{}{}>",
+            &caps[1],
+            caps[2].to_ascii_lowercase(),
+            lowercase_attribute_names(&caps[3])
+        )
+    })
+}
+
+fn lowercase_attribute_names(attributes: &str) -> String {
+    #[derive(Clone, Copy, PartialEq)]
+    enum State {
+        Between,
+        Name,
+        BeforeValue,
+        Unquoted,
+        Quoted(char),
+    }
+    let mut state = State::Between;
+    let mut out = String::with_capacity(attributes.len());
+    for c in attributes.chars() {
+        state = match (state, c) {
+            (State::Quoted(quote), c) if c == quote => State::Between,
+            (State::Quoted(_), _) => state,
+            (State::Between | State::Name, '=') => State::BeforeValue,
+            (State::BeforeValue, c) if c.is_ascii_whitespace() => State::BeforeValue,
+            (State::BeforeValue, '"' | '\'') => State::Quoted(c),
+            (State::BeforeValue, _) => State::Unquoted,
+            (_, c) if c.is_ascii_whitespace() => State::Between,
+            (State::Unquoted, _) => State::Unquoted,
+            (State::Between | State::Name, _) => State::Name,
+        };
+        out.push(if state == State::Name {
+            c.to_ascii_lowercase()
+        } else {
+            c
+        });
+    }
+    out
+}
+
+/// Closes the `tag` elements a table cell leaves open at its `</td>` or `</th>`, and drops
+/// `</tag>` end tags with nothing to close, for boards whose editors leave `<font>` tags
+/// open. Left to html5ever, such tags are reconstructed around whatever follows, nesting the
+/// content differently from what the board shows.
+pub fn close_unclosed_in_cells<'a>(html: &'a str, tag: &str) -> Cow<'a, str> {
+    let is_cell = |name: &str| name.eq_ignore_ascii_case("td") || name.eq_ignore_ascii_case("th");
+    // Count of unclosed `tag`s outside tables and in each open cell
+    let mut open = vec![0usize];
+    let mut out = String::new();
+    let mut copied = 0;
+    let mut changed = false;
+    for caps in TAG.captures_iter(html) {
+        let whole = caps.get(0).unwrap();
+        let closing = !caps[1].is_empty();
+        let name = &caps[2];
+        let depth = open.last_mut().unwrap();
+        if name.eq_ignore_ascii_case(tag) {
+            if !closing {
+                *depth += 1;
+            } else if *depth > 0 {
+                *depth -= 1;
+            } else {
+                out.push_str(&html[copied..whole.start()]);
+                copied = whole.end();
+                changed = true;
+            }
+        } else if is_cell(name) && !closing {
+            open.push(0);
+        } else if is_cell(name) && open.len() > 1 {
+            let unclosed = open.pop().unwrap();
+            if unclosed > 0 {
+                out.push_str(&html[copied..whole.start()]);
+                out.push_str(&format!("</{tag}>").repeat(unclosed));
+                copied = whole.start();
+                changed = true;
+            }
+        }
+    }
+    if !changed {
+        return Cow::Borrowed(html);
+    }
+    out.push_str(&html[copied..]);
+    Cow::Owned(out)
+}
+
+/// Describes how a board renders its "no posts" page.
+///
+/// Boards commonly answer an empty list with a 200 response and a friendly message instead of
+/// an empty table. `selector` narrows down where the message lives; when `texts` is empty the
+/// presence of `selector` alone marks the page as empty.
+#[derive(Debug, Clone, Copy)]
+pub struct EmptyState {
+    pub selector: Option<&'static str>,
+    pub texts: &'static [&'static str],
+}
+
+impl EmptyState {
+    pub const DEFAULT_TEXTS: &'static [&'static str] = &[
+        "게시물이 없습니다",
+        "게시글이 없습니다",
+        "등록된 글이 없습니다",
+        "검색된 결과가 없습니다",
+    ];
+
+    pub const DEFAULT: Self = Self {
+        selector: None,
+        texts: Self::DEFAULT_TEXTS,
+    };
+
+    pub fn matches(&self, document: &Html) -> bool {
+        let texts = match self.selector {
+            Some(selector) => {
+                let Ok(selector) = Selector::parse(selector) else {
+                    tracing::warn!(selector, "Invalid empty-state selector");
+                    return false;
+                };
+                if self.texts.is_empty() {
+                    return document.select(&selector).next().is_some();
+                }
+                document
+                    .select(&selector)
+                    .map(|el| el.text().collect::<String>())
+                    .collect::<Vec<_>>()
+            }
+            None => vec![document.root_element().text().collect::<String>()],
+        };
+        texts.iter().any(|text| {
+            let text = strip_whitespace(text);
+            self.texts
+                .iter()
+                .any(|marker| text.contains(&strip_whitespace(marker)))
+        })
+    }
+}
+
+/// Content shorter than this many characters may be a login stub, see [`is_restricted`].
+const RESTRICTED_STUB_MAX_CHARS: usize = 200;
+
+/// Whether a post page is a members-only stub reading one of
+/// [`SsufidPlugin::RESTRICTED_MARKERS`].
+pub fn is_restricted<T: SsufidPlugin>(document: &Html, content: &Selector) -> bool {
+    let text = match document.select(content).next() {
+        Some(content) => {
+            let text = strip_whitespace(&content.text().collect::<String>());
+            if text.chars().count() > RESTRICTED_STUB_MAX_CHARS {
+                return false;
+            }
+            text
+        }
+        None => strip_whitespace(&document.root_element().text().collect::<String>()),
+    };
+    has_restricted_marker::<T>(&text)
+}
+
+/// Whether `text` reads one of [`SsufidPlugin::RESTRICTED_MARKERS`], ignoring whitespace.
+pub(crate) fn has_restricted_marker<T: SsufidPlugin>(text: &str) -> bool {
+    let text = strip_whitespace(text);
+    T::RESTRICTED_MARKERS
+        .iter()
+        .any(|marker| text.contains(&strip_whitespace(marker)))
+}
+
+fn strip_whitespace(s: &str) -> String {
+    s.split_whitespace().collect()
+}
+
+/// Result of parsing a list page.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ListPage<T> {
+    Items(Vec<T>),
+    /// The board explicitly reported that it has no posts on this page.
+    Empty,
+}
+
+impl<T> ListPage<T> {
+    pub fn is_empty(&self) -> bool {
+        match self {
+            ListPage::Items(items) => items.is_empty(),
+            ListPage::Empty => true,
+        }
+    }
+
+    pub fn into_items(self) -> Vec<T> {
+        match self {
+            ListPage::Items(items) => items,
+            ListPage::Empty => vec![],
+        }
+    }
+}
+
+/// Classifies the parsed items of a list page.
+///
+/// A page without items is only accepted as empty when it shows the plugin's
+/// [`SsufidPlugin::EMPTY_STATE`] marker; otherwise the markup most likely changed and a parse
+/// error is returned.
+pub fn classify_list_page<T: SsufidPlugin, I>(
+    document: &Html,
+    items: Vec<I>,
+    page_url: &str,
+) -> Result<ListPage<I>, PluginError> {
+    if !items.is_empty() {
+        return Ok(ListPage::Items(items));
+    }
+    if T::EMPTY_STATE.matches(document) {
+        tracing::debug!(
+            plugin = T::IDENTIFIER,
+            url = page_url,
+            "Board reported no posts"
+        );
+        return Ok(ListPage::Empty);
+    }
+    Err(PluginError::parse::<T>(format!(
+        "No posts parsed and no empty-state marker found: {page_url}"
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing_test::traced_test;
+
+    use super::*;
+
+    struct MockHtmlPlugin;
+
+    impl SsufidPlugin for MockHtmlPlugin {
+        const TITLE: &'static str = "Mock Html";
+        const IDENTIFIER: &'static str = "mock.html";
+        const DESCRIPTION: &'static str = "Mock plugin for html helper tests";
+        const BASE_URL: &'static str = "https://example.com/html";
+    }
+
+    const RENAMED_LIST_HTML: &str = r#"
+    <ul class="board-list">
+        <li class="item"><a href="/view?idx=1">첫 번째 공지</a></li>
+        <li class="item"><a href="/view?idx=2">두 번째 공지</a></li>
+    </ul>
+    "#;
+
+    struct MockCriticalPlugin;
+
+    impl SsufidPlugin for MockCriticalPlugin {
+        const TITLE: &'static str = "Mock Critical";
+        const IDENTIFIER: &'static str = "mock.html.critical";
+        const DESCRIPTION: &'static str = "Mock plugin declaring critical selectors";
+        const BASE_URL: &'static str = "https://example.com/html/critical";
+        const CRITICAL_SELECTORS: &'static [(&'static str, &'static str)] = &[
+            ("list_item", "ul.board-list > li.item"),
+            ("title", "div.view h3.title"),
+            ("content", "div.view div.content"),
+        ];
+    }
+
+    #[test]
+    fn test_health_check_flags_broken_selector() {
+        let list = Html::parse_document(RENAMED_LIST_HTML);
+        // The content area's class changed, so only the content selector fails to match
+        let detail = Html::parse_document(
+            r#"<div class="view"><h3 class="title">공지</h3><div class="body">본문</div></div>"#,
+        );
+        assert_eq!(
+            health_check::<MockCriticalPlugin>(&[list, detail]),
+            ["content"]
+        );
+    }
+
+    #[test]
+    fn test_health_check_without_critical_selectors() {
+        let document = Html::parse_document(RENAMED_LIST_HTML);
+        assert!(health_check::<MockHtmlPlugin>(&[document]).is_empty());
+    }
+
+    struct MockSelectorEmptyPlugin;
+
+    impl SsufidPlugin for MockSelectorEmptyPlugin {
+        const TITLE: &'static str = "Mock Selector Empty";
+        const IDENTIFIER: &'static str = "mock.html.empty";
+        const DESCRIPTION: &'static str = "Mock plugin with a selector-based empty state";
+        const BASE_URL: &'static str = "https://example.com/html/empty";
+        const EMPTY_STATE: EmptyState = EmptyState {
+            selector: Some("td.empty_table"),
+            texts: &[],
+        };
+    }
+
+    const EMPTY_BOARD_HTML: &str = r#"
+    <div id="bo_list">
+        <table>
+            <tbody>
+                <tr><td colspan="5" class="empty_table">게시물이 없습니다.</td></tr>
+            </tbody>
+        </table>
+    </div>
+    "#;
+
+    #[test]
+    fn test_classify_list_page_detects_empty_state() {
+        let document = Html::parse_document(EMPTY_BOARD_HTML);
+        let page =
+            classify_list_page::<MockHtmlPlugin, ()>(&document, vec![], "https://example.com")
+                .unwrap();
+        assert_eq!(page, ListPage::Empty);
+
+        let page = classify_list_page::<MockSelectorEmptyPlugin, ()>(
+            &document,
+            vec![],
+            "https://example.com",
+        )
+        .unwrap();
+        assert_eq!(page, ListPage::Empty);
+    }
+
+    #[test]
+    fn test_classify_list_page_rejects_unrecognized_empty_page() {
+        let document = Html::parse_document(RENAMED_LIST_HTML);
+        let error =
+            classify_list_page::<MockHtmlPlugin, ()>(&document, vec![], "https://example.com")
+                .unwrap_err();
+        assert_eq!(error.kind(), &crate::PluginErrorKind::Parse);
+
+        let document = Html::parse_document(EMPTY_BOARD_HTML);
+        let page =
+            classify_list_page::<MockHtmlPlugin, u32>(&document, vec![1], "https://example.com")
+                .unwrap();
+        assert_eq!(page, ListPage::Items(vec![1]));
+    }
+
+    #[test]
+    fn test_last_page_from_href() {
+        let document = Html::parse_document(
+            r#"<div class="paging">
+                <a href="?page=1&amp;category=1">1</a>
+                <a href="?page=2&amp;category=1">2</a>
+                <a href="?page=3&amp;category=1">3</a>
+                <a href="/notice?page=47&amp;category=1">»</a>
+            </div>
+            <div class="other"><a href="?page=99">99</a></div>"#,
+        );
+        let selector = Selector::parse("div.paging a").unwrap();
+        assert_eq!(last_page(&document, &selector), Some(47));
+
+        let document = Html::parse_document(
+            r#"<div class="paging">
+                <a class="page" href="https://example.com/notice/page/2">2</a>
+                <a class="next-btn-last" href="https://example.com/notice/page/120?f=all">마지막</a>
+            </div>"#,
+        );
+        assert_eq!(last_page(&document, &selector), Some(120));
+
+        let document = Html::parse_document(r##"<div class="paging"><a href="#">1</a></div>"##);
+        assert_eq!(last_page(&document, &selector), None);
+    }
+
+    #[test]
+    fn test_last_page_from_onclick() {
+        let document = Html::parse_document(
+            r##"<ul class="pagination">
+                <li><a href="javascript:void(0)" onclick="fnGoPage(1); return false;">1</a></li>
+                <li><a href="javascript:void(0)" onclick="fnGoPage(2); return false;">2</a></li>
+                <li><a href="#" onclick="fnGoPage('31')"><img src="last_arrow.png" alt="마지막"></a></li>
+            </ul>"##,
+        );
+        let selector = Selector::parse("ul.pagination a").unwrap();
+        assert_eq!(last_page(&document, &selector), Some(31));
+
+        let document = Html::parse_document(
+            r#"<div class="paging"><a href="javascript:goPage(8, 'notice')">»</a></div>"#,
+        );
+        let selector = Selector::parse("div.paging a").unwrap();
+        assert_eq!(last_page(&document, &selector), Some(8));
+    }
+
+    #[test]
+    fn test_tables_to_json() {
+        let content = r#"
+            <p>2025학년도 1학기 주요 일정입니다.</p>
+            <table>
+                <tr><th>일정</th><th>날짜</th></tr>
+                <tr><td>개강</td><td>3월 4일</td></tr>
+                <tr><td>중간고사</td><td>4월 22일 ~ 4월 28일</td></tr>
+            </table>"#;
+        assert_eq!(
+            tables_to_json(content),
+            vec![serde_json::json!([
+                { "일정": "개강", "날짜": "3월 4일" },
+                { "일정": "중간고사", "날짜": "4월 22일 ~ 4월 28일" },
+            ])]
+        );
+        assert!(tables_to_json("<p>표 없음</p>").is_empty());
+    }
+
+    #[test]
+    fn test_parse_selector_error() {
+        assert!(parse_selector::<MockHtmlPlugin>("ul > li.item").is_ok());
+        let error = parse_selector::<MockHtmlPlugin>("td:nth-child(").unwrap_err();
+        assert_eq!(
+            error.kind(),
+            &crate::PluginErrorKind::Custom("SelectorParse".into())
+        );
+        assert!(error.message().contains("td:nth-child("));
+    }
+
+    #[test]
+    fn test_select_expecting_returns_matches() {
+        let document = Html::parse_document(RENAMED_LIST_HTML);
+        let selector = Selector::parse("ul.board-list > li.item").unwrap();
+        let items = select_expecting::<MockHtmlPlugin>(&document, &selector, "https://example.com")
+            .collect::<Vec<_>>();
+        assert_eq!(items.len(), 2);
+    }
+
+    #[traced_test]
+    #[test]
+    fn test_select_expecting_warns_and_counts_on_miss() {
+        let labels = [("plugin", MockHtmlPlugin::IDENTIFIER)];
+        let before = metrics::counter(metrics::SELECTOR_MISS_TOTAL, &labels);
+
+        let document = Html::parse_document(RENAMED_LIST_HTML);
+        let selector = Selector::parse("a.con_box").unwrap();
+        let items = select_expecting::<MockHtmlPlugin>(
+            &document,
+            &selector,
+            "https://example.com/list?page=1",
+        )
+        .collect::<Vec<_>>();
+
+        assert!(items.is_empty());
+        assert!(logs_contain("Selector matched zero elements"));
+        assert!(logs_contain("a.con_box"));
+        assert!(logs_contain("https://example.com/list?page=1"));
+        assert_eq!(
+            metrics::counter(metrics::SELECTOR_MISS_TOTAL, &labels),
+            before + 1
+        );
+    }
+
+    #[test]
+    fn test_trim_empty_edges() {
+        let content = "<p>&nbsp;</p>\n<p>수강신청 일정을 <b>안내</b>합니다.</p><p>&nbsp;</p><p>문의: 학과 사무실</p><br>\n";
+        assert_eq!(
+            trim_empty_edges(content),
+            "<p>수강신청 일정을 <b>안내</b>합니다.</p><p>&nbsp;</p><p>문의: 학과 사무실</p>"
+        );
+        assert_eq!(
+            trim_empty_edges("<div><br></div>본문 <img src=\"a.png\"><br>"),
+            "본문 <img src=\"a.png\">"
+        );
+        assert_eq!(trim_empty_edges("<p> </p><br>"), "");
+    }
+
+    #[test]
+    fn test_dedupe_leading_title() {
+        let title = "2025학년도 1학기 수강신청 안내";
+        assert_eq!(
+            dedupe_leading_title(
+                "<p>&nbsp;</p><h3>2025학년도 1학기 수강신청 안내</h3><p>일정을 안내합니다.</p>",
+                title
+            ),
+            "<p>일정을 안내합니다.</p>"
+        );
+        assert_eq!(
+            dedupe_leading_title(
+                "<p><strong>2025학년도\u{a0}1학기 수강신청 안내</strong></p><p>본문</p>",
+                title
+            ),
+            "<p>본문</p>"
+        );
+        // A heading inside the content wrapper the plugin passes is removed too
+        assert_eq!(
+            dedupe_leading_title(
+                "<div id=\"bo_v_con\"><div><h3>2025학년도 1학기 수강신청 안내</h3><p>본문</p></div></div>",
+                title
+            ),
+            "<div id=\"bo_v_con\"><div><p>본문</p></div></div>"
+        );
+        // Headings that differ from the title or sit mid-content are kept
+        for content in [
+            "<h3>수강신청 일정</h3><p>본문</p>",
+            "<p>본문</p><h3>2025학년도 1학기 수강신청 안내</h3>",
+            "<p>2025학년도 1학기 수강신청 안내</p><p>본문</p>",
+            "<div><p>본문</p><h3>2025학년도 1학기 수강신청 안내</h3></div>",
+        ] {
+            assert_eq!(dedupe_leading_title(content, title), content);
+        }
+    }
+
+    #[test]
+    fn test_select_content_falls_back() {
+        let primary = Selector::parse("td.descript").unwrap();
+        let select = |html: &str| {
+            let document = Html::parse_document(html);
+            select_content::<MockHtmlPlugin>(&document, &primary, "https://example.com/view/1")
+                .map(|(content, source)| (content.text().collect::<String>(), source))
+        };
+
+        assert_eq!(
+            select(r#"<table><tr><td class="descript">본문</td></tr></table>"#),
+            Some(("본문".to_string(), ContentSource::Primary))
+        );
+        assert_eq!(
+            select(
+                r#"<div class="header-content">메뉴</div><div class="view_content">바뀐 클래스의 본문</div>"#
+            ),
+            Some(("바뀐 클래스의 본문".to_string(), ContentSource::Fallback))
+        );
+
+        // Falls back to the block with the most text when no class name matches
+        let body = "기숙사 입사 신청 일정과 제출 서류를 안내합니다. 기한 내에 제출하지 않으면 입사가 취소됩니다.";
+        let html = format!(
+            r#"<div id="wrap"><div class="gnb"><a href="/">홈</a><a href="/notice">공지사항</a></div>
+            <div class="v2"><div class="x1"><p>{body}</p><p>문의: 행정실</p></div></div>
+            <div class="footer">숭실대학교</div></div>"#
+        );
+        let (text, source) = select(&html).unwrap();
+        assert_eq!(source, ContentSource::Fallback);
+        assert!(text.starts_with(body));
+        assert!(!text.contains("공지사항"));
+
+        assert_eq!(select("<div><a href=\"/\">홈</a></div>"), None);
+    }
+
+    #[test]
+    fn test_is_restricted() {
+        let content = Selector::parse("div.td_box").unwrap();
+        let stub = Html::parse_document(
+            r#"<script>alert("로그인이 필요합니다.");</script><p>로그인이  필요합니다.</p>"#,
+        );
+        assert!(is_restricted::<MockHtmlPlugin>(&stub, &content));
+        let stub_in_template =
+            Html::parse_document(r#"<div class="td_box"><p>회원만 이용 가능합니다.</p></div>"#);
+        assert!(is_restricted::<MockHtmlPlugin>(&stub_in_template, &content));
+
+        // A long body is a regular post even if it mentions logging in
+        let post = Html::parse_document(&format!(
+            r#"<div class="td_box"><p>수강신청 시스템은 로그인이 필요합니다.</p><p>{}</p></div>"#,
+            "수강신청 일정 안내 ".repeat(30)
+        ));
+        assert!(!is_restricted::<MockHtmlPlugin>(&post, &content));
+    }
+
+    #[test]
+    fn test_is_notice_row() {
+        let html = Html::parse_document(
+            r#"<table><tbody>
+            <tr><td>공지</td><td><a href="/view?idx=1">2020학년도 학칙 안내</a></td></tr>
+            <tr><td><img src="/icon.gif" alt="공지사항"></td><td><a href="/view?idx=2">장학 안내</a></td></tr>
+            <tr class="notice"><td>3</td><td><a href="/view?idx=3">휴강 안내</a></td></tr>
+            <tr><td>[Notice]</td><td><a href="/view?idx=4">Exchange program</a></td></tr>
+            <tr><td>5</td><td><a href="/view?idx=5">공지사항 게시판 이용 안내</a></td></tr>
+            <tr class="row is-notice"><td>6</td><td><a href="/view?idx=6">시험 일정</a></td></tr>
+            <tr class="notice-list"><td class="notice_col3">7</td><td><a href="/view?idx=7">특강 안내</a></td></tr>
+            </tbody></table>"#,
+        );
+        let row = Selector::parse("tr").unwrap();
+        let notices = html.select(&row).map(is_notice_row).collect::<Vec<_>>();
+        // Other classes containing notice, such as notice-list, are not notices
+        assert_eq!(notices, [true, true, true, true, false, true, false]);
+    }
+
+    #[test]
+    fn test_wrap_tables() {
+        let content = r#"<p>일정</p><table border="1"><tr><td>3월</td><td><table><tr><td>개강</td></tr></table></td></tr></table><p>문의</p><table><tr><td>학사팀</td></tr></table>"#;
+        // Nested tables are not wrapped again
+        assert_eq!(
+            wrap_tables(content).unwrap(),
+            r#"<p>일정</p><div class="table-scroll"><table border="1"><tr><td>3월</td><td><table><tr><td>개강</td></tr></table></td></tr></table></div><p>문의</p><div class="table-scroll"><table><tr><td>학사팀</td></tr></table></div>"#
+        );
+        let plain = "<p>2025학년도 1학기 수강신청 안내</p>";
+        assert_eq!(wrap_tables(plain).unwrap(), plain);
+    }
+
+    #[test]
+    fn test_wrap_tables_skips_unclosed_table() {
+        // Wrapping a table without a closing tag would leave the div open, so it is left alone
+        let content =
+            r#"<table><tr><td>3월</td></tr></table><p>문의</p><table><tr><td>학사팀</td>"#;
+        assert_eq!(
+            wrap_tables(content).unwrap(),
+            r#"<div class="table-scroll"><table><tr><td>3월</td></tr></table></div><p>문의</p><table><tr><td>학사팀</td>"#
+        );
+    }
+
+    #[test]
+    fn test_redact_contacts() {
+        let content = r#"<p>문의: <b>학사팀</b> hakSa.team@ssu.ac.kr, 02-820-0114 (내선 3)</p><p>휴대전화 010 1234 5678로 연락 바랍니다. 학번 20251234567</p>"#;
+        assert_eq!(
+            redact_contacts(content).unwrap(),
+            r#"<p>문의: <b>학사팀</b> ***@ssu.ac.kr, 02-***-**** (내선 3)</p><p>휴대전화 010 **** ****로 연락 바랍니다. 학번 20251234567</p>"#
+        );
+        let plain = "<p>2025학년도 1학기 수강신청 안내</p>";
+        assert_eq!(redact_contacts(plain).unwrap(), plain);
+    }
+
+    #[test]
+    fn test_unwrap_redirect_links() {
+        let patterns = [RedirectPattern {
+            prefix: "https://link.example.com/redirect",
+            param: "url",
+        }];
+        let content = r#"<p><a href="https://link.example.com/redirect?url=https%3A%2F%2Fapply.ssu.ac.kr%2Fform%3Fid%3D3%26lang%3Dko&amp;track=n12">신청하기</a> <a href="https://link.example.com/redirect?url=javascript%3Aalert(1)">이상한 링크</a> <a href="https://ssu.ac.kr/?url=https%3A%2F%2Fexample.com">학교</a></p>"#;
+
+        // Only registered tracking URLs are unwrapped, and non-http(s) targets are kept
+        assert_eq!(
+            unwrap_redirect_links(content, &patterns).unwrap(),
+            r#"<p><a href="https://apply.ssu.ac.kr/form?id=3&amp;lang=ko">신청하기</a> <a href="https://link.example.com/redirect?url=javascript%3Aalert(1)">이상한 링크</a> <a href="https://ssu.ac.kr/?url=https%3A%2F%2Fexample.com">학교</a></p>"#
+        );
+        assert_eq!(unwrap_redirect_links(content, &[]).unwrap(), content);
+    }
+
+    #[test]
+    fn test_dedupe_attachment_links() {
+        let content = r#"<p>신청서를 제출하세요.</p><div class="file_box"><ul><li><a href="/module/board/download.php?idx=7&amp;no=1">신청서.hwp</a></li></ul></div><p><a href="https://example.com/guide">안내 페이지</a></p>"#;
+        let attachments = [crate::core::Attachment {
+            url: "https://materials.ssu.ac.kr/module/board/download.php?idx=7&no=1".to_string(),
+            name: Some("신청서.hwp".to_string()),
+            mime_type: None,
+            metadata: None,
+        }];
+
+        // Only body links to an attachment's URL are unwrapped, keeping their text
+        assert_eq!(
+            dedupe_attachment_links(
+                content,
+                "https://materials.ssu.ac.kr/bbs/board.php?bo_table=notice&idx=7",
+                &attachments
+            )
+            .unwrap(),
+            r#"<p>신청서를 제출하세요.</p><div class="file_box"><ul><li>신청서.hwp</li></ul></div><p><a href="https://example.com/guide">안내 페이지</a></p>"#
+        );
+        // Images inside the link are kept too
+        assert_eq!(
+            dedupe_attachment_links(
+                r#"<a href="/module/board/download.php?idx=7&amp;no=1"><img src="/poster.png"></a>"#,
+                "https://materials.ssu.ac.kr/",
+                &attachments
+            )
+            .unwrap(),
+            r#"<img src="/poster.png">"#
+        );
+        assert_eq!(
+            dedupe_attachment_links(content, "https://materials.ssu.ac.kr/", &[]).unwrap(),
+            content
+        );
+    }
+
+    #[test]
+    fn test_lowercase_tag_names() {
+        assert_eq!(
+            lowercase_tag_names(
+                r##"<TD BGCOLOR="#EDF8FC" Title='A > B' CLASS=Descript>본문 TEXT</TD><BR/>"##
+            ),
+            r##"<td bgcolor="#EDF8FC" title='A > B' class=Descript>본문 TEXT</td><br/>"##
+        );
+        assert_eq!(
+            lowercase_tag_names(r#"<a HREF = "/View?IDX=1">"#),
+            r#"<a href = "/View?IDX=1">"#
+        );
+        assert!(matches!(
+            lowercase_tag_names("<p>이미 소문자</p>"),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn test_close_unclosed_in_cells() {
+        assert_eq!(
+            close_unclosed_in_cells(
+                r#"<table><tr><td><font color="red">빨강<font size=2>작게</td><td>기본</font></td></tr></table>"#,
+                "font"
+            ),
+            r#"<table><tr><td><font color="red">빨강<font size=2>작게</font></font></td><td>기본</td></tr></table>"#
+        );
+        let balanced = "<td><FONT>강조</FONT></td>";
+        assert!(matches!(
+            close_unclosed_in_cells(balanced, "font"),
+            Cow::Borrowed(_)
+        ));
+    }
+}
@@ -1,5 +1,19 @@
+pub mod content;
 pub mod core;
+pub mod datetime;
+pub mod enrich;
 pub mod error;
+pub mod fetch;
+pub mod fixtures;
+pub mod html;
+pub mod metrics;
+pub mod output;
+pub mod paginate;
+#[cfg(feature = "schema")]
+pub mod schema;
+pub mod stats;
+pub mod text;
+pub mod url;
 
 pub use core::SsufidCore;
 
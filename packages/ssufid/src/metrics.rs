@@ -0,0 +1,81 @@
+use std::{
+    collections::BTreeMap,
+    sync::{LazyLock, Mutex},
+};
+
+/// Process-wide counters, keyed by `name{label="value",...}`.
+static COUNTERS: LazyLock<Mutex<BTreeMap<String, u64>>> =
+    LazyLock::new(|| Mutex::new(BTreeMap::new()));
+/// Process-wide gauges, keyed like [`COUNTERS`].
+static GAUGES: LazyLock<Mutex<BTreeMap<String, i64>>> =
+    LazyLock::new(|| Mutex::new(BTreeMap::new()));
+
+pub const SELECTOR_MISS_TOTAL: &str = "selector_miss_total";
+/// Posts that look mis-decoded, labelled by `plugin`. The count accumulates over the process,
+/// so a per-run figure is the difference across the run.
+pub const MOJIBAKE_TOTAL: &str = "mojibake_total";
+/// Requests currently in flight, labelled by `host`. See [`crate::fetch::RequestLimiter`].
+pub const IN_FLIGHT_REQUESTS: &str = "in_flight_requests";
+
+fn key(name: &str, labels: &[(&str, &str)]) -> String {
+    if labels.is_empty() {
+        return name.to_string();
+    }
+    let labels = labels
+        .iter()
+        .map(|(k, v)| format!("{k}=\"{v}\""))
+        .collect::<Vec<_>>()
+        .join(",");
+    format!("{name}{{{labels}}}")
+}
+
+pub fn increment_counter(name: &str, labels: &[(&str, &str)]) {
+    let mut counters = COUNTERS.lock().unwrap_or_else(|e| e.into_inner());
+    *counters.entry(key(name, labels)).or_default() += 1;
+}
+
+pub fn counter(name: &str, labels: &[(&str, &str)]) -> u64 {
+    let counters = COUNTERS.lock().unwrap_or_else(|e| e.into_inner());
+    counters
+        .get(&key(name, labels))
+        .copied()
+        .unwrap_or_default()
+}
+
+/// Returns every counter recorded so far.
+pub fn snapshot() -> BTreeMap<String, u64> {
+    COUNTERS.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+pub fn add_to_gauge(name: &str, labels: &[(&str, &str)], delta: i64) {
+    let mut gauges = GAUGES.lock().unwrap_or_else(|e| e.into_inner());
+    *gauges.entry(key(name, labels)).or_default() += delta;
+}
+
+pub fn gauge(name: &str, labels: &[(&str, &str)]) -> i64 {
+    let gauges = GAUGES.lock().unwrap_or_else(|e| e.into_inner());
+    gauges.get(&key(name, labels)).copied().unwrap_or_default()
+}
+
+/// Returns the current value of every gauge.
+pub fn gauge_snapshot() -> BTreeMap<String, i64> {
+    GAUGES.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counter_with_labels() {
+        let labels = [("plugin", "metrics.test")];
+        assert_eq!(counter("test_total", &labels), 0);
+        increment_counter("test_total", &labels);
+        increment_counter("test_total", &labels);
+        assert_eq!(counter("test_total", &labels), 2);
+        assert_eq!(
+            snapshot().get("test_total{plugin=\"metrics.test\"}"),
+            Some(&2)
+        );
+    }
+}
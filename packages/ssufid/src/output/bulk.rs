@@ -0,0 +1,258 @@
+use serde::Deserialize;
+use serde_json::{Value, json};
+use thiserror::Error;
+
+use crate::{content::plain_text, core::SsufidPost};
+
+/// Deterministic document id for `post`: a UUIDv5 of its URL, so that re-indexing a post
+/// overwrites the previous document instead of duplicating it.
+pub fn bulk_document_id(post: &SsufidPost) -> uuid::Uuid {
+    uuid::Uuid::new_v5(&uuid::Uuid::NAMESPACE_URL, post.url.as_bytes())
+}
+
+/// Builds an Elasticsearch/OpenSearch `_bulk` request body indexing `posts` into `index_name`.
+///
+/// Every post becomes an `index` action line followed by the post's serde representation, with
+/// the text of its HTML content added as `content_text` for analyzers.
+pub fn bulk_ndjson<'a>(
+    posts: impl IntoIterator<Item = &'a SsufidPost>,
+    index_name: &str,
+) -> Result<String, serde_json::Error> {
+    let mut body = String::new();
+    for post in posts {
+        let action = json!({
+            "index": { "_index": index_name, "_id": bulk_document_id(post).to_string() }
+        });
+        let mut document = serde_json::to_value(post)?;
+        if let Value::Object(fields) = &mut document {
+            fields.insert(
+                "content_text".to_string(),
+                Value::String(plain_text(&post.content)),
+            );
+        }
+        body.push_str(&serde_json::to_string(&action)?);
+        body.push('\n');
+        body.push_str(&serde_json::to_string(&document)?);
+        body.push('\n');
+    }
+    Ok(body)
+}
+
+#[derive(Debug, Error)]
+pub enum BulkError {
+    #[error("Bulk request failed: {0}")]
+    Request(#[from] reqwest::Error),
+
+    #[error("Bulk endpoint answered HTTP {status}: {body}")]
+    Status {
+        status: reqwest::StatusCode,
+        body: String,
+    },
+
+    #[error("Unexpected bulk response: {0}")]
+    Parse(#[from] serde_json::Error),
+}
+
+/// Outcome of a `_bulk` request that was accepted as a whole.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BulkReport {
+    pub indexed: usize,
+    pub rejected: Vec<BulkRejection>,
+}
+
+/// A document the cluster refused, e.g. because of a mapping conflict.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BulkRejection {
+    pub id: String,
+    pub status: u16,
+    pub reason: String,
+}
+
+#[derive(Deserialize)]
+struct BulkResponse {
+    items: Vec<serde_json::Map<String, Value>>,
+}
+
+#[derive(Deserialize)]
+struct BulkItem {
+    #[serde(rename = "_id", default)]
+    id: String,
+    status: u16,
+    error: Option<Value>,
+}
+
+/// Parses a `_bulk` response, collecting the items that failed.
+pub fn parse_bulk_response(body: &str) -> Result<BulkReport, serde_json::Error> {
+    let response: BulkResponse = serde_json::from_str(body)?;
+    let mut report = BulkReport::default();
+    for item in response.items {
+        // Each item is keyed by a single action name, as in {"index": {...}}
+        let Some(result) = item.into_iter().next().map(|(_, v)| v) else {
+            continue;
+        };
+        let item: BulkItem = serde_json::from_value(result)?;
+        match item.error {
+            Some(error) if item.status >= 300 => report.rejected.push(BulkRejection {
+                id: item.id,
+                status: item.status,
+                reason: error
+                    .get("reason")
+                    .and_then(Value::as_str)
+                    .map_or_else(|| error.to_string(), str::to_string),
+            }),
+            _ => report.indexed += 1,
+        }
+    }
+    Ok(report)
+}
+
+/// Sends bulk bodies to a `_bulk` endpoint, optionally with basic auth.
+#[derive(Debug, Clone)]
+pub struct BulkPusher {
+    client: reqwest::Client,
+    url: String,
+    credentials: Option<(String, String)>,
+}
+
+impl BulkPusher {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            client: crate::fetch::default_client(),
+            url: url.into(),
+            credentials: None,
+        }
+    }
+
+    pub fn basic_auth(mut self, username: impl Into<String>, password: impl Into<String>) -> Self {
+        self.credentials = Some((username.into(), password.into()));
+        self
+    }
+
+    /// Posts `body` and reports which documents were rejected. A request that is refused as a
+    /// whole is returned as an error instead.
+    pub async fn push(&self, body: String) -> Result<BulkReport, BulkError> {
+        let mut request = self
+            .client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/x-ndjson")
+            .body(body);
+        if let Some((username, password)) = &self.credentials {
+            request = request.basic_auth(username, Some(password));
+        }
+        let response = request.send().await?;
+        let status = response.status();
+        let body = response.text().await?;
+        if !status.is_success() {
+            return Err(BulkError::Status { status, body });
+        }
+        let report = parse_bulk_response(&body)?;
+        for rejection in &report.rejected {
+            tracing::warn!(
+                id = %rejection.id,
+                status = rejection.status,
+                reason = %rejection.reason,
+                "Bulk indexing rejected a document"
+            );
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{header, method, path},
+    };
+
+    use super::*;
+    use crate::core::UrlKind;
+
+    fn post(id: &str) -> SsufidPost {
+        SsufidPost {
+            id: id.to_string(),
+            url: format!("https://example.com/{id}"),
+            url_kind: UrlKind::Canonical,
+            author: Some("학과사무실".to_string()),
+            title: format!("공지 {id}"),
+            description: None,
+            category: vec!["학사".to_string()],
+            created_at: datetime!(2025-03-02 09:00 +9),
+            updated_at: None,
+            thumbnail: None,
+            content: "<p>수강신청은<br><b>3월 4일</b>까지입니다.</p>".to_string(),
+            attachments: vec![],
+            metadata: None,
+            author_detail: None,
+        }
+    }
+
+    const PARTIAL_FAILURE: &str = r#"{
+        "took": 12,
+        "errors": true,
+        "items": [
+            {"index": {"_index": "notices", "_id": "a", "_version": 1, "result": "created", "status": 201}},
+            {"index": {"_index": "notices", "_id": "b", "status": 400,
+                "error": {"type": "mapper_parsing_exception", "reason": "failed to parse field [created_at]"}}}
+        ]
+    }"#;
+
+    #[test]
+    fn test_bulk_ndjson_structure() {
+        let posts = [post("1"), post("2")];
+        let body = bulk_ndjson(&posts, "notices").unwrap();
+        assert!(body.ends_with('\n'));
+
+        let lines = body.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        for (pair, post) in lines.chunks(2).zip(&posts) {
+            let action: Value = serde_json::from_str(pair[0]).unwrap();
+            assert_eq!(action["index"]["_index"], "notices");
+            assert_eq!(
+                action["index"]["_id"],
+                bulk_document_id(post).to_string().as_str()
+            );
+
+            let document: Value = serde_json::from_str(pair[1]).unwrap();
+            assert_eq!(document["id"], post.id.as_str());
+            assert_eq!(document["created_at"], "2025-03-02T09:00:00+09:00");
+            assert_eq!(document["content"], post.content.as_str());
+            assert_eq!(document["content_text"], "수강신청은 3월 4일 까지입니다.");
+        }
+        assert_ne!(bulk_document_id(&posts[0]), bulk_document_id(&posts[1]));
+        assert_eq!(bulk_document_id(&posts[0]), bulk_document_id(&post("1")));
+    }
+
+    #[test]
+    fn test_parse_bulk_response_partial_failure() {
+        let report = parse_bulk_response(PARTIAL_FAILURE).unwrap();
+        assert_eq!(report.indexed, 1);
+        assert_eq!(
+            report.rejected,
+            vec![BulkRejection {
+                id: "b".to_string(),
+                status: 400,
+                reason: "failed to parse field [created_at]".to_string(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_push_reports_rejected_ids() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/_bulk"))
+            .and(header("content-type", "application/x-ndjson"))
+            .and(header("authorization", "Basic dXNlcjpwYXNz"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(PARTIAL_FAILURE))
+            .mount(&server)
+            .await;
+
+        let pusher = BulkPusher::new(format!("{}/_bulk", server.uri())).basic_auth("user", "pass");
+        let body = bulk_ndjson(&[post("1")], "notices").unwrap();
+        let report = pusher.push(body).await.unwrap();
+        assert_eq!(report.rejected.len(), 1);
+        assert_eq!(report.rejected[0].id, "b");
+    }
+}
@@ -0,0 +1,519 @@
+use std::{fmt, path::Path};
+
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    Error,
+    core::{SsufidCalendarSiteData, SsufidPost, SsufidSiteData},
+};
+
+/// The checksum, and optionally the signature, stored in `data.json` as `integrity` so that
+/// a mirrored archive can be checked against tampering with [`verify`].
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct Integrity {
+    /// The SHA-256 of the [canonical JSON](canonical_json) of the whole output but `integrity`,
+    /// with `sealed_at` added, in lowercase hex. Outputs sealed before `sealed_at` existed
+    /// only hash their `items`.
+    pub sha256: String,
+    /// The Ed25519 signature of the UTF-8 bytes of `sha256`, in standard base64.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// When the output was sealed.
+    #[serde(
+        default,
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub sealed_at: Option<time::OffsetDateTime>,
+}
+
+impl Integrity {
+    /// Seals `output`, a site or calendar whose own `integrity` is ignored, at `sealed_at`.
+    fn seal(
+        output: &impl Serialize,
+        key: Option<&SigningKey>,
+        sealed_at: time::OffsetDateTime,
+    ) -> Result<Self, serde_json::Error> {
+        let sha256 = envelope_checksum(
+            serde_json::to_value(output)?,
+            Self::sealed_at_value(sealed_at)?,
+        )?;
+        let signature = key.map(|key| key.sign(sha256.as_bytes()));
+        Ok(Self {
+            sha256,
+            signature,
+            sealed_at: Some(sealed_at),
+        })
+    }
+
+    fn sealed_at_value(sealed_at: time::OffsetDateTime) -> Result<Value, serde_json::Error> {
+        #[derive(Serialize)]
+        struct SealedAt(#[serde(with = "time::serde::rfc3339")] time::OffsetDateTime);
+        serde_json::to_value(SealedAt(sealed_at))
+    }
+}
+
+/// The canonical JSON of `value`: compact, with the keys of every object sorted by their UTF-8
+/// bytes and strings escaped as `serde_json` does. Two serializations of the same posts give
+/// the same canonical JSON whatever order their fields were written in.
+pub fn canonical_json(value: &impl Serialize) -> Result<String, serde_json::Error> {
+    let mut value = serde_json::to_value(value)?;
+    sort_keys(&mut value);
+    serde_json::to_string(&value)
+}
+
+fn sort_keys(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.sort_keys();
+            map.values_mut().for_each(sort_keys);
+        }
+        Value::Array(values) => values.iter_mut().for_each(sort_keys),
+        _ => {}
+    }
+}
+
+fn sha256_hex(value: &impl Serialize) -> Result<String, serde_json::Error> {
+    Ok(Sha256::digest(canonical_json(value)?.as_bytes())
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect())
+}
+
+/// The checksum of `posts` alone, which outputs sealed before [`Integrity::sealed_at`] existed
+/// store in [`Integrity::sha256`].
+pub fn posts_checksum(posts: &[SsufidPost]) -> Result<String, serde_json::Error> {
+    sha256_hex(&posts)
+}
+
+/// The checksum of `output` without its `integrity` and with `sealed_at`, see
+/// [`Integrity::sha256`].
+fn envelope_checksum(mut output: Value, sealed_at: Value) -> Result<String, serde_json::Error> {
+    if let Value::Object(map) = &mut output {
+        map.remove("integrity");
+        map.insert("sealed_at".to_string(), sealed_at);
+    }
+    sha256_hex(&output)
+}
+
+/// An Ed25519 key for signing outputs, read from [`SIGNING_KEY_ENV`](Self::SIGNING_KEY_ENV).
+/// Only the `signing` feature can build one.
+#[derive(Clone)]
+pub struct SigningKey {
+    #[cfg(feature = "signing")]
+    key: ed25519_dalek::SigningKey,
+}
+
+/// The public half of a [`SigningKey`], for [`verify`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifyingKey {
+    #[cfg(feature = "signing")]
+    key: ed25519_dalek::VerifyingKey,
+}
+
+#[cfg(not(feature = "signing"))]
+fn signing_disabled() -> Error {
+    Error::Config("Signing outputs requires the `signing` feature".to_string())
+}
+
+impl SigningKey {
+    /// The environment variable holding the 32-byte secret key in standard base64.
+    pub const SIGNING_KEY_ENV: &'static str = "SSUFID_SIGNING_KEY";
+
+    /// Parses a 32-byte secret key in standard base64.
+    pub fn from_base64(key: &str) -> Result<Self, Error> {
+        let bytes = decode_key::<32>(key, "signing key")?;
+        #[cfg(feature = "signing")]
+        {
+            Ok(Self {
+                key: ed25519_dalek::SigningKey::from_bytes(&bytes),
+            })
+        }
+        #[cfg(not(feature = "signing"))]
+        {
+            let _ = bytes;
+            Err(signing_disabled())
+        }
+    }
+
+    /// The key in [`SIGNING_KEY_ENV`](Self::SIGNING_KEY_ENV).
+    pub fn from_env() -> Result<Self, Error> {
+        let key = std::env::var(Self::SIGNING_KEY_ENV).map_err(|_| {
+            Error::Config(format!(
+                "Signing requires the {} environment variable",
+                Self::SIGNING_KEY_ENV
+            ))
+        })?;
+        Self::from_base64(&key)
+    }
+
+    pub fn verifying_key(&self) -> VerifyingKey {
+        #[cfg(feature = "signing")]
+        {
+            VerifyingKey {
+                key: self.key.verifying_key(),
+            }
+        }
+        #[cfg(not(feature = "signing"))]
+        unreachable!("a signing key cannot be built without the `signing` feature")
+    }
+
+    fn sign(&self, message: &[u8]) -> String {
+        #[cfg(feature = "signing")]
+        {
+            use ed25519_dalek::Signer as _;
+            STANDARD.encode(self.key.sign(message).to_bytes())
+        }
+        #[cfg(not(feature = "signing"))]
+        {
+            let _ = message;
+            unreachable!("a signing key cannot be built without the `signing` feature")
+        }
+    }
+}
+
+impl fmt::Debug for SigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SigningKey").finish_non_exhaustive()
+    }
+}
+
+impl VerifyingKey {
+    /// Parses a 32-byte public key in standard base64.
+    pub fn from_base64(key: &str) -> Result<Self, Error> {
+        let bytes = decode_key::<32>(key, "public key")?;
+        #[cfg(feature = "signing")]
+        {
+            ed25519_dalek::VerifyingKey::from_bytes(&bytes)
+                .map(|key| Self { key })
+                .map_err(|e| Error::Config(format!("Invalid public key: {e}")))
+        }
+        #[cfg(not(feature = "signing"))]
+        {
+            let _ = bytes;
+            Err(signing_disabled())
+        }
+    }
+
+    pub fn to_base64(&self) -> String {
+        #[cfg(feature = "signing")]
+        {
+            STANDARD.encode(self.key.as_bytes())
+        }
+        #[cfg(not(feature = "signing"))]
+        unreachable!("a public key cannot be built without the `signing` feature")
+    }
+
+    fn verifies(&self, message: &[u8], signature: &str) -> bool {
+        #[cfg(feature = "signing")]
+        {
+            use ed25519_dalek::Verifier as _;
+            STANDARD
+                .decode(signature.trim())
+                .ok()
+                .and_then(|bytes| <[u8; 64]>::try_from(bytes).ok())
+                .is_some_and(|bytes| {
+                    let signature = ed25519_dalek::Signature::from_bytes(&bytes);
+                    self.key.verify(message, &signature).is_ok()
+                })
+        }
+        #[cfg(not(feature = "signing"))]
+        {
+            let _ = (message, signature);
+            unreachable!("a public key cannot be built without the `signing` feature")
+        }
+    }
+}
+
+fn decode_key<const N: usize>(key: &str, name: &str) -> Result<[u8; N], Error> {
+    STANDARD
+        .decode(key.trim())
+        .ok()
+        .and_then(|bytes| <[u8; N]>::try_from(bytes).ok())
+        .ok_or_else(|| {
+            Error::Config(format!(
+                "Invalid {name}: expected {N} bytes in standard base64"
+            ))
+        })
+}
+
+impl SsufidSiteData {
+    /// Stores the checksum of the site and its posts as of `sealed_at`, signed with `key` if
+    /// given. Call it last, as any later change to the site invalidates it.
+    pub fn seal(
+        &mut self,
+        key: Option<&SigningKey>,
+        sealed_at: time::OffsetDateTime,
+    ) -> Result<(), serde_json::Error> {
+        self.integrity = Some(Integrity::seal(self, key, sealed_at)?);
+        Ok(())
+    }
+
+    pub fn integrity(&self) -> Option<&Integrity> {
+        self.integrity.as_ref()
+    }
+}
+
+impl SsufidCalendarSiteData {
+    /// Like [`SsufidSiteData::seal`], for a calendar.
+    pub fn seal(
+        &mut self,
+        key: Option<&SigningKey>,
+        sealed_at: time::OffsetDateTime,
+    ) -> Result<(), serde_json::Error> {
+        self.integrity = Some(Integrity::seal(self, key, sealed_at)?);
+        Ok(())
+    }
+
+    pub fn integrity(&self) -> Option<&Integrity> {
+        self.integrity.as_ref()
+    }
+}
+
+/// The outcome of [`verify`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VerifyResult {
+    /// The posts match the checksum, and the signature matches the public key if one was
+    /// given.
+    Valid { signed: bool },
+    /// The output has no checksum, e.g. because it predates them.
+    Unsealed,
+    /// The posts were changed after the checksum was computed.
+    ChecksumMismatch { expected: String, actual: String },
+    /// A public key was given but the output is not signed.
+    Unsigned,
+    /// The signature does not match the checksum and public key.
+    InvalidSignature,
+}
+
+impl VerifyResult {
+    pub fn is_valid(&self) -> bool {
+        matches!(self, Self::Valid { .. })
+    }
+}
+
+impl fmt::Display for VerifyResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Valid { signed: true } => write!(f, "checksum and signature are valid"),
+            Self::Valid { signed: false } => write!(f, "checksum is valid"),
+            Self::Unsealed => write!(f, "no checksum"),
+            Self::ChecksumMismatch { expected, actual } => {
+                write!(f, "checksum mismatch: expected {expected}, got {actual}")
+            }
+            Self::Unsigned => write!(f, "no signature"),
+            Self::InvalidSignature => write!(f, "invalid signature"),
+        }
+    }
+}
+
+/// Checks the `data.json` at `path` against its stored [`Integrity`], and its signature against
+/// `public_key` if given.
+///
+/// The checksum is computed over the file as read, not as parsed into a site, so that fields
+/// added outside of ssufid also count as tampering.
+pub fn verify(path: &Path, public_key: Option<&VerifyingKey>) -> Result<VerifyResult, Error> {
+    let json = std::fs::read_to_string(path)?;
+    let site = serde_json::from_str::<Value>(&json)?;
+    let Some(integrity) = site.get("integrity").filter(|value| !value.is_null()) else {
+        return Ok(VerifyResult::Unsealed);
+    };
+    let integrity = serde_json::from_value::<Integrity>(integrity.clone())?;
+    let actual = match integrity.sealed_at {
+        Some(sealed_at) => envelope_checksum(site, Integrity::sealed_at_value(sealed_at)?)?,
+        None => sha256_hex(site.get("items").unwrap_or(&Value::Null))?,
+    };
+    if actual != integrity.sha256 {
+        return Ok(VerifyResult::ChecksumMismatch {
+            expected: integrity.sha256,
+            actual,
+        });
+    }
+    let Some(public_key) = public_key else {
+        return Ok(VerifyResult::Valid {
+            signed: integrity.signature.is_some(),
+        });
+    };
+    Ok(match &integrity.signature {
+        None => VerifyResult::Unsigned,
+        Some(signature) if public_key.verifies(integrity.sha256.as_bytes(), signature) => {
+            VerifyResult::Valid { signed: true }
+        }
+        Some(_) => VerifyResult::InvalidSignature,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+    use crate::core::UrlKind;
+
+    const SEALED_AT: time::OffsetDateTime = datetime!(2025-03-10 12:00:00 +9);
+
+    fn site() -> SsufidSiteData {
+        SsufidSiteData::new(
+            "예시학과",
+            "https://example.com",
+            "예시학과 공지사항",
+            vec![SsufidPost {
+                id: "1".to_string(),
+                url: "https://example.com/board/view.php?idx=1".to_string(),
+                url_kind: UrlKind::Canonical,
+                author: Some("학과 사무실".to_string()),
+                title: "수강신청 안내".to_string(),
+                description: None,
+                category: vec!["학사".to_string()],
+                created_at: datetime!(2025-03-10 09:00:00 +9),
+                updated_at: None,
+                thumbnail: None,
+                content: "<p>수강신청 기간은 3월 4일까지입니다.</p>".to_string(),
+                attachments: vec![],
+                metadata: None,
+                author_detail: None,
+            }],
+        )
+    }
+
+    fn write(name: &str, json: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "ssufid-integrity-{name}-{}.json",
+            std::process::id()
+        ));
+        std::fs::write(&path, json).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_canonical_json_sorts_keys() {
+        let a = serde_json::json!({"b": 1, "a": [{"d": null, "c": "가"}]});
+        let b = serde_json::json!({"a": [{"c": "가", "d": null}], "b": 1});
+        assert_eq!(
+            canonical_json(&a).unwrap(),
+            r#"{"a":[{"c":"가","d":null}],"b":1}"#
+        );
+        assert_eq!(canonical_json(&a).unwrap(), canonical_json(&b).unwrap());
+    }
+
+    #[test]
+    fn test_tampered_content_is_detected() {
+        let mut site = site();
+        site.seal(None, SEALED_AT).unwrap();
+        let json = serde_json::to_string_pretty(&site).unwrap();
+        let path = write("tampered", &json);
+        assert_eq!(
+            verify(&path, None).unwrap(),
+            VerifyResult::Valid { signed: false }
+        );
+
+        // Changing a single character of the body changes the checksum
+        std::fs::write(&path, json.replace("3월 4일", "3월 5일")).unwrap();
+        assert!(matches!(
+            verify(&path, None).unwrap(),
+            VerifyResult::ChecksumMismatch { .. }
+        ));
+
+        // So does changing the site info or the seal time
+        std::fs::write(&path, json.replace("예시학과 공지사항", "다른 공지사항")).unwrap();
+        assert!(matches!(
+            verify(&path, None).unwrap(),
+            VerifyResult::ChecksumMismatch { .. }
+        ));
+        std::fs::write(&path, json.replace("12:00:00", "13:00:00")).unwrap();
+        assert!(matches!(
+            verify(&path, None).unwrap(),
+            VerifyResult::ChecksumMismatch { .. }
+        ));
+
+        let unsealed = serde_json::to_string_pretty(&self::site()).unwrap();
+        std::fs::write(&path, unsealed).unwrap();
+        assert_eq!(verify(&path, None).unwrap(), VerifyResult::Unsealed);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_items_only_checksum_of_older_outputs_still_verifies() {
+        let mut site = site();
+        site.integrity = Some(Integrity {
+            sha256: posts_checksum(site.items()).unwrap(),
+            signature: None,
+            sealed_at: None,
+        });
+        let path = write("legacy", &serde_json::to_string_pretty(&site).unwrap());
+        assert_eq!(
+            verify(&path, None).unwrap(),
+            VerifyResult::Valid { signed: false }
+        );
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_sealed_calendar_is_verified() {
+        let mut calendar = SsufidCalendarSiteData {
+            title: "학사일정".to_string(),
+            source: "https://example.com/calendar".to_string(),
+            description: "예시 학사일정".to_string(),
+            items: vec![],
+            integrity: None,
+        };
+        calendar.seal(None, SEALED_AT).unwrap();
+        let json = serde_json::to_string_pretty(&calendar).unwrap();
+        let path = write("calendar", &json);
+        assert_eq!(
+            verify(&path, None).unwrap(),
+            VerifyResult::Valid { signed: false }
+        );
+
+        std::fs::write(&path, json.replace("예시 학사일정", "다른 학사일정")).unwrap();
+        assert!(matches!(
+            verify(&path, None).unwrap(),
+            VerifyResult::ChecksumMismatch { .. }
+        ));
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[cfg(feature = "signing")]
+    #[test]
+    fn test_signature_is_verified_with_public_key() {
+        let key = SigningKey::from_base64(&STANDARD.encode([7u8; 32])).unwrap();
+        let public_key = VerifyingKey::from_base64(&key.verifying_key().to_base64()).unwrap();
+        let other_key = SigningKey::from_base64(&STANDARD.encode([8u8; 32]))
+            .unwrap()
+            .verifying_key();
+
+        let mut site = site();
+        site.seal(Some(&key), SEALED_AT).unwrap();
+        let path = write("signed", &serde_json::to_string_pretty(&site).unwrap());
+        assert_eq!(
+            verify(&path, Some(&public_key)).unwrap(),
+            VerifyResult::Valid { signed: true }
+        );
+        assert_eq!(
+            verify(&path, Some(&other_key)).unwrap(),
+            VerifyResult::InvalidSignature
+        );
+
+        // The signature fails unless it is recomputed along with the checksum
+        site.items_mut()[0].title = "수강신청 변경 안내".to_string();
+        let signature = site.integrity().unwrap().signature.clone();
+        site.seal(None, SEALED_AT).unwrap();
+        site.integrity.as_mut().unwrap().signature = signature;
+        std::fs::write(&path, serde_json::to_string_pretty(&site).unwrap()).unwrap();
+        assert_eq!(
+            verify(&path, Some(&public_key)).unwrap(),
+            VerifyResult::InvalidSignature
+        );
+
+        site.seal(None, SEALED_AT).unwrap();
+        std::fs::write(&path, serde_json::to_string_pretty(&site).unwrap()).unwrap();
+        assert_eq!(
+            verify(&path, Some(&public_key)).unwrap(),
+            VerifyResult::Unsigned
+        );
+        let _ = std::fs::remove_file(path);
+    }
+}
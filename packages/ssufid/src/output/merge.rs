@@ -0,0 +1,381 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::{PluginInfo, SsufidPost, SsufidSiteData};
+
+pub const MERGED_FEED_TITLE: &str = "SSUFID 통합 공지";
+pub const MERGED_FEED_DESCRIPTION: &str =
+    "숭실대학교 여러 사이트의 공지사항을 한데 모아 제공합니다.";
+
+/// Loads every `<in_dir>/<site>/data.json`, ordered by site directory name, skipping sites whose
+/// output is missing or broken.
+pub fn load_site_outputs(in_dir: &Path) -> std::io::Result<Vec<SsufidSiteData>> {
+    Ok(load_named_site_outputs(in_dir)?
+        .into_iter()
+        .map(|(_, site)| site)
+        .collect())
+}
+
+/// Like [`load_site_outputs`], pairing each site with its directory's path under `in_dir`,
+/// which is the identifier of the plugin that wrote it.
+pub fn load_named_site_outputs(in_dir: &Path) -> std::io::Result<Vec<(String, SsufidSiteData)>> {
+    let mut sites = Vec::new();
+    collect_site_outputs(in_dir, "", &mut sites)?;
+    Ok(sites)
+}
+
+fn collect_site_outputs(
+    dir: &Path,
+    name: &str,
+    sites: &mut Vec<(String, SsufidSiteData)>,
+) -> std::io::Result<()> {
+    let mut dirs = std::fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect::<Vec<_>>();
+    dirs.sort();
+
+    for dir in dirs {
+        let Some(file_name) = dir
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+        else {
+            continue;
+        };
+        let name = if name.is_empty() {
+            file_name
+        } else {
+            format!("{name}/{file_name}")
+        };
+        let path = dir.join("data.json");
+        if path.exists()
+            && let Some(json) = std::fs::read_to_string(&path)
+                .inspect_err(|e| {
+                    tracing::warn!(path = %path.display(), error = %e, "Skipping unreadable site output")
+                })
+                .ok()
+            && let Ok(site) = serde_json::from_str::<SsufidSiteData>(&json).inspect_err(
+                |e| tracing::warn!(path = %path.display(), error = %e, "Skipping corrupt site output"),
+            )
+        {
+            sites.push((name.clone(), site));
+        }
+        // A site's directory may also hold the output of plugins nested under its identifier.
+        collect_site_outputs(&dir, &name, sites)?;
+    }
+    Ok(())
+}
+
+/// Combines `sites` into one site, newest post first, adding each site's title to the
+/// categories of its posts so that readers can tell where a post came from.
+pub fn merge_sites(
+    sites: Vec<SsufidSiteData>,
+    title: &str,
+    source: &str,
+    description: &str,
+) -> SsufidSiteData {
+    let mut items = sites
+        .into_iter()
+        .flat_map(|site| {
+            let SsufidSiteData { title, items, .. } = site;
+            items.into_iter().map(move |mut post: SsufidPost| {
+                if !post.category.contains(&title) {
+                    post.category.insert(0, title.clone());
+                }
+                post
+            })
+        })
+        .collect::<Vec<_>>();
+    items.sort_by_key(|post| std::cmp::Reverse(post.created_at));
+    SsufidSiteData::new(title, source, description, items)
+}
+
+/// A post of a [`CombinedFeed`], tagged with the identifier of the plugin it came from.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct CombinedItem {
+    pub source: String,
+    #[serde(flatten)]
+    pub post: SsufidPost,
+}
+
+/// Posts of several plugins interleaved newest first, e.g. for a "my department, library and
+/// dormitory" view.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct CombinedFeed {
+    pub sources: Vec<PluginInfo>,
+    pub items: Vec<CombinedItem>,
+}
+
+/// Combines the posts of each plugin in `sources` into one feed, newest post first.
+/// Posts created at the same time keep the order of `sources`.
+pub fn combine(sources: Vec<(PluginInfo, Vec<SsufidPost>)>) -> CombinedFeed {
+    let mut infos = Vec::with_capacity(sources.len());
+    let mut items = Vec::new();
+    for (info, posts) in sources {
+        items.extend(posts.into_iter().map(|post| CombinedItem {
+            source: info.identifier.clone(),
+            post,
+        }));
+        infos.push(info);
+    }
+    items.sort_by_key(|item| std::cmp::Reverse(item.post.created_at));
+    CombinedFeed {
+        sources: infos,
+        items,
+    }
+}
+
+impl CombinedFeed {
+    /// The plugin with the given identifier.
+    pub fn source(&self, identifier: &str) -> Option<&PluginInfo> {
+        self.sources
+            .iter()
+            .find(|info| info.identifier == identifier)
+    }
+
+    /// Builds an RSS channel with `options` applied at `now`. Every item names its plugin in
+    /// `<source>`, and list-only posts link to their own plugin's site.
+    #[cfg(feature = "rss")]
+    pub fn to_channel(
+        &self,
+        title: &str,
+        link: &str,
+        description: &str,
+        options: &crate::core::rss::FeedOptions,
+        now: time::OffsetDateTime,
+    ) -> rss::Channel {
+        let cutoff = options.cutoff(now);
+        let items = self
+            .items
+            .iter()
+            .filter(|item| cutoff.is_none_or(|cutoff| item.post.created_at >= cutoff))
+            .take(options.max_items)
+            .map(|CombinedItem { source, post }| {
+                let source = self.source(source);
+                let base_url = source.map_or(link, |info| info.base_url.as_str());
+                let link = post.link(base_url);
+                let mut item: rss::Item = post.clone().into();
+                item.set_link(link);
+                item.set_source(source.map(|info| rss::Source {
+                    url: info.base_url.clone(),
+                    title: Some(info.title.clone()),
+                }));
+                item
+            })
+            .collect::<Vec<_>>();
+        rss::ChannelBuilder::default()
+            .title(title)
+            .link(link)
+            .description(description)
+            .last_build_date(
+                items
+                    .first()
+                    .and_then(|item| item.pub_date().map(str::to_string)),
+            )
+            .items(items)
+            .namespace((
+                "atom".to_string(),
+                crate::core::rss::ATOM_NAMESPACE.to_string(),
+            ))
+            .namespace((
+                "content".to_string(),
+                "http://purl.org/rss/1.0/modules/content/".to_string(),
+            ))
+            .build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+    use crate::core::{Cadence, UrlKind};
+
+    fn post(id: &str, created_at: time::OffsetDateTime) -> SsufidPost {
+        SsufidPost {
+            id: id.to_string(),
+            url: format!("https://example.com/{id}"),
+            url_kind: UrlKind::Canonical,
+            author: None,
+            title: id.to_string(),
+            description: None,
+            category: vec!["학사".to_string()],
+            created_at,
+            updated_at: None,
+            thumbnail: None,
+            content: String::new(),
+            attachments: vec![],
+            metadata: None,
+            author_detail: None,
+        }
+    }
+
+    fn info(identifier: &str, title: &str) -> PluginInfo {
+        PluginInfo {
+            version: 1,
+            identifier: identifier.to_string(),
+            title: title.to_string(),
+            description: String::new(),
+            base_url: format!("https://{identifier}"),
+            cadence: Cadence::Weekly,
+        }
+    }
+
+    #[test]
+    fn test_combine_interleaves_sources() {
+        let feed = combine(vec![
+            (
+                info("dept.ssu.ac.kr", "학과 공지"),
+                vec![
+                    post("dept-1", datetime!(2025-03-01 09:00 +9)),
+                    post("dept-2", datetime!(2025-03-03 09:00 +9)),
+                ],
+            ),
+            (
+                info("dorm.ssu.ac.kr", "기숙사 공지"),
+                vec![
+                    post("dorm-1", datetime!(2025-03-02 09:00 +9)),
+                    post("dorm-2", datetime!(2025-03-04 09:00 +9)),
+                ],
+            ),
+        ]);
+        let tagged = feed
+            .items
+            .iter()
+            .map(|item| (item.post.id.as_str(), item.source.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tagged,
+            [
+                ("dorm-2", "dorm.ssu.ac.kr"),
+                ("dept-2", "dept.ssu.ac.kr"),
+                ("dorm-1", "dorm.ssu.ac.kr"),
+                ("dept-1", "dept.ssu.ac.kr"),
+            ]
+        );
+
+        let json = serde_json::to_value(&feed.items[0]).unwrap();
+        assert_eq!(json["source"], "dorm.ssu.ac.kr");
+        assert_eq!(json["id"], "dorm-2");
+
+        let channel = feed.to_channel(
+            MERGED_FEED_TITLE,
+            "https://example.com",
+            MERGED_FEED_DESCRIPTION,
+            &crate::core::rss::FeedOptions::default().max_items(3),
+            datetime!(2025-03-05 00:00 +9),
+        );
+        let sources = channel
+            .items()
+            .iter()
+            .map(|item| item.source().unwrap().title().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(sources, ["기숙사 공지", "학과 공지", "기숙사 공지"]);
+        assert_eq!(
+            channel.items()[0].source().unwrap().url(),
+            "https://dorm.ssu.ac.kr"
+        );
+    }
+
+    fn write_site(dir: &Path, name: &str, contents: &str) {
+        std::fs::create_dir_all(dir.join(name)).unwrap();
+        std::fs::write(dir.join(name).join("data.json"), contents).unwrap();
+    }
+
+    #[test]
+    fn test_merge_site_outputs() {
+        let dir = std::env::temp_dir().join(format!("ssufid-merge-{}", std::process::id()));
+        let a = SsufidSiteData::new(
+            "A 공지사항",
+            "https://a.example.com",
+            "A",
+            vec![
+                post("a-1", datetime!(2025-03-01 09:00 +9)),
+                post("a-2", datetime!(2025-03-03 09:00 +9)),
+            ],
+        );
+        let b = SsufidSiteData::new(
+            "B 공지사항",
+            "https://b.example.com",
+            "B",
+            vec![post("b-1", datetime!(2025-03-02 09:00 +9))],
+        );
+        write_site(&dir, "a.example.com", &serde_json::to_string(&a).unwrap());
+        write_site(&dir, "b.example.com", &serde_json::to_string(&b).unwrap());
+        // Sites whose identifier has a path live in a nested directory.
+        let c = SsufidSiteData::new(
+            "C 학사 공지사항",
+            "https://c.example.com/bachelor",
+            "C",
+            vec![post("c-1", datetime!(2025-03-04 09:00 +9))],
+        );
+        write_site(
+            &dir,
+            "c.example.com/bachelor",
+            &serde_json::to_string(&c).unwrap(),
+        );
+        write_site(&dir, "broken.example.com", "{ not json");
+        std::fs::create_dir_all(dir.join("empty.example.com")).unwrap();
+
+        let named = load_named_site_outputs(&dir).unwrap();
+        let sites = load_site_outputs(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(sites.len(), 3);
+        let names = named
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            ["a.example.com", "b.example.com", "c.example.com/bachelor"]
+        );
+
+        let merged = merge_sites(
+            sites,
+            MERGED_FEED_TITLE,
+            "https://example.com",
+            MERGED_FEED_DESCRIPTION,
+        );
+        assert_eq!(merged.title(), MERGED_FEED_TITLE);
+        let ids = merged
+            .items()
+            .iter()
+            .map(|p| p.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, ["c-1", "a-2", "b-1", "a-1"]);
+        assert_eq!(merged.items()[0].category, ["C 학사 공지사항", "학사"]);
+        assert_eq!(merged.items()[1].category, ["A 공지사항", "학사"]);
+        assert_eq!(merged.items()[2].category, ["B 공지사항", "학사"]);
+    }
+
+    #[test]
+    fn test_load_site_outputs_nested_under_site() {
+        let dir = std::env::temp_dir().join(format!("ssufid-merge-nested-{}", std::process::id()));
+        let site = |title: &str, id: &str| {
+            serde_json::to_string(&SsufidSiteData::new(
+                title,
+                "https://a.example.com",
+                "A",
+                vec![post(id, datetime!(2025-03-01 09:00 +9))],
+            ))
+            .unwrap()
+        };
+        write_site(&dir, "a.example.com", &site("A 공지사항", "a-1"));
+        write_site(&dir, "a.example.com/job", &site("A 채용", "job-1"));
+        write_site(&dir, "b.example.com", &site("B 공지사항", "b-1"));
+
+        let named = load_named_site_outputs(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        let names = named
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            ["a.example.com", "a.example.com/job", "b.example.com"]
+        );
+    }
+}
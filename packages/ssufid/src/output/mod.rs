@@ -0,0 +1,21 @@
+//! Serializers for destinations other than the per-site JSON and RSS files.
+
+mod bulk;
+mod integrity;
+mod merge;
+mod ndjson;
+mod preview;
+
+pub use bulk::{
+    BulkError, BulkPusher, BulkRejection, BulkReport, bulk_document_id, bulk_ndjson,
+    parse_bulk_response,
+};
+pub use integrity::{
+    Integrity, SigningKey, VerifyResult, VerifyingKey, canonical_json, posts_checksum, verify,
+};
+pub use merge::{
+    CombinedFeed, CombinedItem, MERGED_FEED_DESCRIPTION, MERGED_FEED_TITLE, combine,
+    load_named_site_outputs, load_site_outputs, merge_sites,
+};
+pub use ndjson::write_ndjson;
+pub use preview::SocialPreview;
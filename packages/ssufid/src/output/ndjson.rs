@@ -0,0 +1,71 @@
+use std::io::Write;
+
+use crate::core::SsufidPost;
+
+/// Writes `posts` as newline-delimited JSON, one post per line, flushing after every line so
+/// that readers such as `jq` see each post as soon as it is written.
+///
+/// Returns the number of posts written.
+pub fn write_ndjson<'a, W: Write>(
+    mut writer: W,
+    posts: impl IntoIterator<Item = &'a SsufidPost>,
+) -> Result<usize, serde_json::Error> {
+    let mut count = 0;
+    for post in posts {
+        serde_json::to_writer(&mut writer, post)?;
+        writer.write_all(b"\n").map_err(serde_json::Error::io)?;
+        writer.flush().map_err(serde_json::Error::io)?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+    use crate::core::UrlKind;
+
+    fn post(id: &str) -> SsufidPost {
+        SsufidPost {
+            id: id.to_string(),
+            url: format!("https://example.com/{id}"),
+            url_kind: UrlKind::Canonical,
+            author: None,
+            title: format!("제목\n{id}"),
+            description: None,
+            category: vec![],
+            created_at: datetime!(2024-03-22 12:00:00 UTC),
+            updated_at: None,
+            thumbnail: None,
+            content: "<p>첫 줄</p>\n<p>둘째 줄</p>".to_string(),
+            attachments: vec![],
+            metadata: None,
+            author_detail: None,
+        }
+    }
+
+    #[test]
+    fn test_write_ndjson_one_post_per_line() {
+        let posts = vec![post("1"), post("2"), post("3")];
+        let mut buf = Vec::new();
+        let written = write_ndjson(&mut buf, &posts).unwrap();
+        assert_eq!(written, posts.len());
+
+        let output = String::from_utf8(buf).unwrap();
+        let lines = output.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), posts.len());
+        for (line, expected) in lines.iter().zip(&posts) {
+            let parsed: SsufidPost = serde_json::from_str(line).unwrap();
+            assert_eq!(&parsed, expected);
+        }
+    }
+
+    #[test]
+    fn test_write_ndjson_empty() {
+        let mut buf = Vec::new();
+        assert_eq!(write_ndjson(&mut buf, &[]).unwrap(), 0);
+        assert!(buf.is_empty());
+    }
+}
@@ -0,0 +1,251 @@
+use std::fmt::Write as _;
+
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+
+use super::CombinedFeed;
+use crate::core::SsufidPost;
+
+/// The link preview of a page, written as Open Graph and Twitter card `<meta>` tags.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SocialPreview {
+    pub title: String,
+    pub description: String,
+    pub url: String,
+    /// An absolute image URL.
+    pub image: Option<String>,
+    /// Set for a single post, which is then described as an article.
+    pub published_time: Option<OffsetDateTime>,
+}
+
+impl SocialPreview {
+    /// The longest description generated from post content, in characters.
+    pub const DESCRIPTION_LENGTH: usize = 160;
+
+    /// The preview of a page showing `post` alone. The description is the post's own, or the
+    /// start of its text.
+    pub fn of_post(post: &SsufidPost, base_url: &str) -> Self {
+        let url = post.link(base_url);
+        Self {
+            title: post.title.clone(),
+            description: describe(post),
+            image: post
+                .thumbnail
+                .as_deref()
+                .and_then(|thumbnail| absolute_url(&url, thumbnail)),
+            url,
+            published_time: Some(post.created_at),
+        }
+    }
+
+    /// The preview of a page listing `feed`, titled `title` and shared as `link`. The newest
+    /// post supplies the description and image, so the preview changes as the feed does;
+    /// an empty feed is described by `description`.
+    pub fn of_feed(feed: &CombinedFeed, title: &str, link: &str, description: &str) -> Self {
+        let Some(newest) = feed.items.first() else {
+            return Self {
+                title: title.to_string(),
+                description: description.to_string(),
+                url: link.to_string(),
+                image: None,
+                published_time: None,
+            };
+        };
+        let base_url = feed
+            .source(&newest.source)
+            .map_or(link, |info| info.base_url.as_str());
+        let post = Self::of_post(&newest.post, base_url);
+        Self {
+            title: title.to_string(),
+            description: format!("{}: {}", post.title, post.description),
+            url: link.to_string(),
+            image: post.image,
+            published_time: None,
+        }
+    }
+
+    /// The `<meta>` tags for the page's `<head>`, one per line, with values escaped for a
+    /// double-quoted attribute.
+    pub fn meta_tags(&self) -> String {
+        let mut tags = vec![
+            (
+                "og:type",
+                if self.published_time.is_some() {
+                    "article"
+                } else {
+                    "website"
+                }
+                .to_string(),
+            ),
+            ("og:title", self.title.clone()),
+            ("og:description", self.description.clone()),
+            ("og:url", self.url.clone()),
+        ];
+        if let Some(image) = &self.image {
+            tags.push(("og:image", image.clone()));
+        }
+        if let Some(published_time) = self.published_time.and_then(|at| at.format(&Rfc3339).ok()) {
+            tags.push(("article:published_time", published_time));
+        }
+        let card = if self.image.is_some() {
+            "summary_large_image"
+        } else {
+            "summary"
+        };
+        tags.push(("twitter:card", card.to_string()));
+        tags.push(("twitter:title", self.title.clone()));
+        tags.push(("twitter:description", self.description.clone()));
+        if let Some(image) = &self.image {
+            tags.push(("twitter:image", image.clone()));
+        }
+
+        let mut html = String::new();
+        for (property, content) in tags {
+            // Open Graph uses the property attribute and Twitter cards the name attribute
+            let attribute = if property.starts_with("twitter:") {
+                "name"
+            } else {
+                "property"
+            };
+            let _ = writeln!(
+                html,
+                r#"<meta {attribute}="{property}" content="{}">"#,
+                escape_attribute(&content)
+            );
+        }
+        html
+    }
+}
+
+fn describe(post: &SsufidPost) -> String {
+    let text = match post.description.as_deref().map(str::trim) {
+        Some(description) if !description.is_empty() => description.to_string(),
+        _ => crate::content::plain_text(&post.content),
+    };
+    match text.char_indices().nth(SocialPreview::DESCRIPTION_LENGTH) {
+        Some((end, _)) => format!("{}…", text[..end].trim_end()),
+        None => text,
+    }
+}
+
+fn absolute_url(base: &str, url: &str) -> Option<String> {
+    match url::Url::parse(url) {
+        Ok(url) => Some(url.into()),
+        Err(_) => url::Url::parse(base).ok()?.join(url).ok().map(String::from),
+    }
+}
+
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+    use crate::{
+        core::{Cadence, PluginInfo, UrlKind},
+        output::combine,
+    };
+
+    fn post() -> SsufidPost {
+        SsufidPost {
+            id: "1234".to_string(),
+            url: "https://example.com/board/view.php?idx=1234".to_string(),
+            url_kind: UrlKind::Canonical,
+            author: None,
+            title: r#"2025학년도 "전공 설명회" <안내>"#.to_string(),
+            description: None,
+            category: vec![],
+            created_at: datetime!(2025-03-10 09:00:00 +9),
+            updated_at: None,
+            thumbnail: Some("/data/editor/poster.png".to_string()),
+            content: "<p>전공 설명회를 개최합니다. 학생 여러분의 많은 참여 바랍니다.</p>"
+                .to_string(),
+            attachments: vec![],
+            metadata: None,
+            author_detail: None,
+        }
+    }
+
+    #[test]
+    fn test_post_meta_tags_are_escaped() {
+        let tags = SocialPreview::of_post(&post(), "https://example.com").meta_tags();
+        assert_eq!(
+            tags,
+            "<meta property=\"og:type\" content=\"article\">\n\
+             <meta property=\"og:title\" content=\"2025학년도 &quot;전공 설명회&quot; &lt;안내&gt;\">\n\
+             <meta property=\"og:description\" content=\"전공 설명회를 개최합니다. 학생 여러분의 많은 참여 바랍니다.\">\n\
+             <meta property=\"og:url\" content=\"https://example.com/board/view.php?idx=1234\">\n\
+             <meta property=\"og:image\" content=\"https://example.com/data/editor/poster.png\">\n\
+             <meta property=\"article:published_time\" content=\"2025-03-10T09:00:00+09:00\">\n\
+             <meta name=\"twitter:card\" content=\"summary_large_image\">\n\
+             <meta name=\"twitter:title\" content=\"2025학년도 &quot;전공 설명회&quot; &lt;안내&gt;\">\n\
+             <meta name=\"twitter:description\" content=\"전공 설명회를 개최합니다. 학생 여러분의 많은 참여 바랍니다.\">\n\
+             <meta name=\"twitter:image\" content=\"https://example.com/data/editor/poster.png\">\n"
+        );
+    }
+
+    #[test]
+    fn test_feed_preview_uses_newest_post() {
+        let mut older = post();
+        older.id = "1".to_string();
+        older.title = "이전 공지".to_string();
+        older.thumbnail = None;
+        older.created_at = datetime!(2025-03-01 09:00:00 +9);
+        let mut newest = post();
+        newest.description = Some("학생's 참여 & 문의는 학과 사무실로".to_string());
+        newest.thumbnail = None;
+        let info = PluginInfo {
+            identifier: "example.com".to_string(),
+            title: "예시학과".to_string(),
+            description: String::new(),
+            base_url: "https://example.com".to_string(),
+            version: 1,
+            cadence: Cadence::Weekly,
+        };
+        let feed = combine(vec![(info, vec![older, newest])]);
+
+        let preview = SocialPreview::of_feed(
+            &feed,
+            "숭실대학교 공지사항",
+            "https://feed.example.com/",
+            "학교 공지를 모아 봅니다.",
+        );
+        assert_eq!(preview.published_time, None);
+        let tags = preview.meta_tags();
+        assert!(tags.contains(r#"<meta property="og:type" content="website">"#));
+        assert!(tags.contains(r#"<meta property="og:title" content="숭실대학교 공지사항">"#));
+        assert!(tags.contains(
+            r#"<meta property="og:description" content="2025학년도 &quot;전공 설명회&quot; &lt;안내&gt;: 학생&#39;s 참여 &amp; 문의는 학과 사무실로">"#
+        ));
+        assert!(tags.contains(r#"<meta name="twitter:card" content="summary">"#));
+        assert!(!tags.contains("og:image"));
+
+        // An empty feed uses the fixed description
+        let empty = SocialPreview::of_feed(
+            &combine(vec![]),
+            "숭실대학교 공지사항",
+            "https://feed.example.com/",
+            "학교 공지를 모아 봅니다.",
+        );
+        assert_eq!(empty.description, "학교 공지를 모아 봅니다.");
+    }
+
+    #[test]
+    fn test_generated_description_is_truncated() {
+        let mut post = post();
+        post.content = format!("<p>{}</p>", "가".repeat(200));
+        let preview = SocialPreview::of_post(&post, "https://example.com");
+        assert_eq!(
+            preview.description.chars().count(),
+            SocialPreview::DESCRIPTION_LENGTH + 1
+        );
+        assert!(preview.description.ends_with('…'));
+    }
+}
@@ -0,0 +1,337 @@
+//! Shared pagination policy for list pages.
+
+use std::collections::HashSet;
+
+use time::OffsetDateTime;
+
+/// Position of a list page, in whatever form the board paginates by.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cursor {
+    /// A 1-based page number, e.g. `page=3`.
+    Page(u32),
+    /// An item offset advanced by `step` per page, e.g. `offset=20` with 10 posts per page.
+    Offset { start: u32, step: u32 },
+    /// An opaque token handed out by the previous page, e.g. an API's `next` field.
+    Token(String),
+}
+
+impl Cursor {
+    /// The offset cursor of the 1-based list page `page`, or `None` if it does not fit in `u32`.
+    pub fn offset_of_page(page: u32, step: u32) -> Option<Self> {
+        let start = page.checked_sub(1)?.checked_mul(step)?;
+        Some(Self::Offset { start, step })
+    }
+
+    /// The cursor of the page after this one. Token boards continue with `next_token`, the
+    /// token returned along with this page; other boards ignore it.
+    ///
+    /// Returns `None` at the end of a token chain or when the next page would overflow.
+    pub fn advance(&self, next_token: Option<&str>) -> Option<Self> {
+        match self {
+            Self::Page(page) => page.checked_add(1).map(Self::Page),
+            Self::Offset { start, step } => start
+                .checked_add(*step)
+                .map(|start| Self::Offset { start, step: *step }),
+            Self::Token(_) => next_token.map(|token| Self::Token(token.to_string())),
+        }
+    }
+
+    /// The value to put in the board's pagination query parameter.
+    pub fn query_value(&self) -> String {
+        match self {
+            Self::Page(page) => page.to_string(),
+            Self::Offset { start, .. } => start.to_string(),
+            Self::Token(token) => token.clone(),
+        }
+    }
+}
+
+/// Collects list items page by page until `limit` unique items are gathered, stopping early on an
+/// empty page or one that adds no new ids.
+#[derive(Debug)]
+pub struct Paginator<T> {
+    limit: usize,
+    max_pages: Option<u32>,
+    posts_per_page: Option<u32>,
+    first_page_rows: Option<u32>,
+    empty_page_tolerance: u32,
+    empty_pages: u32,
+    pages: u32,
+    exhausted: bool,
+    seen: HashSet<String>,
+    items: Vec<T>,
+}
+
+impl<T> Paginator<T> {
+    pub fn new(limit: u32) -> Self {
+        Self {
+            limit: limit as usize,
+            max_pages: None,
+            posts_per_page: None,
+            first_page_rows: None,
+            empty_page_tolerance: 0,
+            empty_pages: 0,
+            pages: 0,
+            exhausted: false,
+            seen: HashSet::new(),
+            items: Vec::new(),
+        }
+    }
+
+    /// Stops after `max_pages` pages even if the limit has not been reached.
+    pub fn max_pages(mut self, max_pages: u32) -> Self {
+        self.max_pages = Some(max_pages);
+        self
+    }
+
+    /// The page size the plugin declares, usually
+    /// [`SsufidPlugin::POSTS_PER_PAGE`](crate::core::SsufidPlugin::POSTS_PER_PAGE), for
+    /// [`estimated_pages`](Self::estimated_pages) until the first page has been fetched.
+    pub fn posts_per_page(mut self, posts_per_page: u32) -> Self {
+        self.posts_per_page = Some(posts_per_page);
+        self
+    }
+
+    /// Keeps paginating past up to `tolerance` consecutive empty pages. Pages that repeat only
+    /// ids seen before still stop pagination.
+    pub fn empty_page_tolerance(mut self, tolerance: u32) -> Self {
+        self.empty_page_tolerance = tolerance;
+        self
+    }
+
+    /// The 1-based number of the page to fetch next, or `None` once pagination should stop.
+    pub fn next_page(&self) -> Option<u32> {
+        let done = self.exhausted
+            || self.items.len() >= self.limit
+            || self.max_pages.is_some_and(|max| self.pages >= max);
+        (!done).then_some(self.pages + 1)
+    }
+
+    /// Records a fetched page, keeping the first occurrence of every id.
+    /// Returns the number of new items on the page.
+    pub fn push_page<I: Into<String>>(
+        &mut self,
+        page: impl IntoIterator<Item = T>,
+        id: impl Fn(&T) -> I,
+    ) -> usize {
+        self.pages += 1;
+        let before = self.items.len();
+        let mut count = 0;
+        for item in page {
+            count += 1;
+            if self.seen.insert(id(&item).into()) {
+                self.items.push(item);
+            }
+        }
+        let added = self.items.len() - before;
+        if self.pages == 1 && added > 0 {
+            self.first_page_rows = Some(added as u32);
+        }
+        if count == 0 {
+            self.empty_pages += 1;
+            if self.empty_pages > self.empty_page_tolerance {
+                self.exhausted = true;
+            }
+        } else {
+            self.empty_pages = 0;
+            if added == 0 {
+                self.exhausted = true;
+            }
+        }
+        added
+    }
+
+    /// Stops pagination after the current page, e.g. once the remaining pages are known to hold
+    /// only posts the caller is not interested in.
+    pub fn stop(&mut self) {
+        self.exhausted = true;
+    }
+
+    /// The number of posts a page holds: the number of unique ids on the first page once it has
+    /// been fetched, or else the [declared](Self::posts_per_page) page size.
+    pub fn page_size(&self) -> Option<u32> {
+        self.first_page_rows
+            .or(self.posts_per_page)
+            .filter(|&size| size > 0)
+    }
+
+    /// How many pages reaching the limit should take, capped by [`max_pages`](Self::max_pages). A
+    /// lower bound when pinned rows repeat on every page.
+    pub fn estimated_pages(&self) -> Option<u32> {
+        let limit = u32::try_from(self.limit).unwrap_or(u32::MAX);
+        let pages = limit.div_ceil(self.page_size()?).max(1);
+        Some(self.max_pages.map_or(pages, |max| pages.min(max)))
+    }
+
+    pub fn pages_fetched(&self) -> u32 {
+        self.pages
+    }
+
+    /// The first `limit` unique items, in the order they were first seen. The last page may
+    /// have added more, which are dropped.
+    pub fn into_items(mut self) -> Vec<T> {
+        self.items.truncate(self.limit);
+        self.items
+    }
+}
+
+/// The rows of a newest-first list page, with the pinned notices at its top (see
+/// [`crate::html::is_notice_row`]) kept apart from the regular, chronological rows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListPage<T> {
+    pub pinned: Vec<T>,
+    pub regular: Vec<T>,
+}
+
+impl<T> ListPage<T> {
+    pub fn split(rows: impl IntoIterator<Item = T>, is_pinned: impl Fn(&T) -> bool) -> Self {
+        let (pinned, regular) = rows.into_iter().partition(|row| is_pinned(row));
+        Self { pinned, regular }
+    }
+
+    /// Whether every later page is older than `cutoff` too: the page has regular rows and all of
+    /// them are older. Pinned rows are left out since an old notice can stay pinned above newer
+    /// posts on every page.
+    pub fn is_past(
+        &self,
+        cutoff: OffsetDateTime,
+        created_at: impl Fn(&T) -> OffsetDateTime,
+    ) -> bool {
+        !self.regular.is_empty() && self.regular.iter().all(|row| created_at(row) < cutoff)
+    }
+
+    /// The pinned rows followed by the regular ones.
+    pub fn into_rows(self) -> impl Iterator<Item = T> {
+        self.pinned.into_iter().chain(self.regular)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offset_cursor() {
+        let cursor = Cursor::offset_of_page(3, 15).unwrap();
+        assert_eq!(
+            cursor,
+            Cursor::Offset {
+                start: 30,
+                step: 15
+            }
+        );
+        assert_eq!(cursor.query_value(), "30");
+        assert_eq!(cursor.advance(None).unwrap().query_value(), "45");
+        assert_eq!(
+            Cursor::offset_of_page(1, 15),
+            Some(Cursor::Offset { start: 0, step: 15 })
+        );
+        assert_eq!(Cursor::offset_of_page(0, 15), None);
+        assert_eq!(Cursor::offset_of_page(u32::MAX, 15), None);
+        assert_eq!(
+            Cursor::Offset {
+                start: u32::MAX - 5,
+                step: 15
+            }
+            .advance(None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_page_cursor() {
+        assert_eq!(
+            Cursor::Page(1).advance(Some("ignored")),
+            Some(Cursor::Page(2))
+        );
+        assert_eq!(Cursor::Page(u32::MAX).advance(None), None);
+    }
+
+    #[test]
+    fn test_token_cursor_chaining() {
+        // Each response names the next page's token, and the last page has none
+        let responses = [("first", Some("b2")), ("b2", Some("c3")), ("c3", None)];
+        let mut cursor = Some(Cursor::Token("first".to_string()));
+        let mut visited = vec![];
+        while let Some(current) = cursor {
+            let (_, next) = responses
+                .iter()
+                .find(|(token, _)| *token == current.query_value())
+                .unwrap();
+            visited.push(current.query_value());
+            cursor = current.advance(*next);
+        }
+        assert_eq!(visited, ["first", "b2", "c3"]);
+    }
+
+    #[test]
+    fn test_stops_at_limit_counting_unique_ids() {
+        let pages = [vec!["a", "b"], vec!["a", "b", "c", "d"], vec!["e"]];
+        let mut paginator = Paginator::new(3);
+        while let Some(page) = paginator.next_page() {
+            paginator.push_page(pages[page as usize - 1].clone(), |s| *s);
+        }
+        assert_eq!(paginator.pages_fetched(), 2);
+        assert_eq!(paginator.into_items(), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_skips_tolerated_empty_pages() {
+        let pages = [vec!["a"], vec![], vec!["b"], vec![], vec![], vec!["c"]];
+        let mut paginator = Paginator::new(10).empty_page_tolerance(1);
+        while let Some(page) = paginator.next_page() {
+            paginator.push_page(pages[page as usize - 1].clone(), |s| *s);
+        }
+        assert_eq!(paginator.pages_fetched(), 5);
+        assert_eq!(paginator.into_items(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_stops_on_empty_or_repeated_page() {
+        let mut paginator = Paginator::new(10);
+        paginator.push_page(vec!["a"], |s| *s);
+        assert_eq!(paginator.next_page(), Some(2));
+        assert_eq!(paginator.push_page(vec!["a"], |s| *s), 0);
+        assert_eq!(paginator.next_page(), None);
+
+        let mut paginator = Paginator::<&str>::new(10);
+        paginator.push_page(vec![], |s| *s);
+        assert_eq!(paginator.next_page(), None);
+    }
+
+    #[test]
+    fn test_estimated_pages_samples_first_page() {
+        let mut paginator = Paginator::<String>::new(24).posts_per_page(10);
+        assert_eq!(paginator.estimated_pages(), Some(3));
+
+        // Page 1 holds 12 rows despite the declared size, so the estimate uses 12
+        paginator.push_page((1..=12).map(|i| i.to_string()), Clone::clone);
+        assert_eq!(paginator.page_size(), Some(12));
+        assert_eq!(paginator.estimated_pages(), Some(2));
+
+        let mut paginator = Paginator::<String>::new(25);
+        assert_eq!(paginator.estimated_pages(), None);
+        paginator.push_page((1..=12).map(|i| i.to_string()), Clone::clone);
+        assert_eq!(paginator.estimated_pages(), Some(3));
+        // Rows on later pages do not affect the estimate
+        paginator.push_page((13..=15).map(|i| i.to_string()), Clone::clone);
+        assert_eq!(paginator.page_size(), Some(12));
+
+        // Rows repeated within page 1 count once
+        let mut paginator = Paginator::<&str>::new(10);
+        paginator.push_page(vec!["a", "b", "a", "c"], |s| *s);
+        assert_eq!(paginator.page_size(), Some(3));
+
+        let paginator = Paginator::<String>::new(100)
+            .posts_per_page(12)
+            .max_pages(5);
+        assert_eq!(paginator.estimated_pages(), Some(5));
+    }
+
+    #[test]
+    fn test_max_pages() {
+        let mut paginator = Paginator::new(10).max_pages(1);
+        paginator.push_page(vec!["a"], |s| *s);
+        assert_eq!(paginator.next_page(), None);
+    }
+}
@@ -0,0 +1,51 @@
+//! JSON Schema documents for the wire types, for consumers that generate their own bindings.
+
+use schemars::{Schema, schema_for};
+
+use crate::core::{Attachment, CrawlReport, PluginInfo, SsufidPost};
+
+/// Names accepted by [`schema_of`].
+pub const SCHEMA_TYPES: &[&str] = &["SsufidPost", "Attachment", "PluginInfo", "CrawlReport"];
+
+/// Returns the JSON Schema of the type called `name`, or `None` for an unknown type.
+pub fn schema_of(name: &str) -> Option<Schema> {
+    match name {
+        "SsufidPost" => Some(schema_for!(SsufidPost)),
+        "Attachment" => Some(schema_for!(Attachment)),
+        "PluginInfo" => Some(schema_for!(PluginInfo)),
+        "CrawlReport" => Some(schema_for!(CrawlReport)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    /// Compares each schema against `schemas/<Type>.json`.
+    /// Run with `SSUFID_UPDATE_SCHEMAS=1` to rewrite the snapshots after an intended change.
+    #[test]
+    fn test_schema_snapshots() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("schemas");
+        let update = std::env::var_os("SSUFID_UPDATE_SCHEMAS").is_some();
+
+        for name in SCHEMA_TYPES {
+            let schema = serde_json::to_string_pretty(&schema_of(name).unwrap()).unwrap() + "\n";
+            let path = dir.join(format!("{name}.json"));
+            if update {
+                std::fs::write(&path, &schema).unwrap();
+                continue;
+            }
+            let snapshot = std::fs::read_to_string(&path)
+                .unwrap_or_else(|e| panic!("missing schema snapshot {}: {e}", path.display()));
+            assert_eq!(schema, snapshot, "schema of {name} changed");
+        }
+    }
+
+    #[test]
+    fn test_unknown_schema_type() {
+        assert!(schema_of("SsufidSiteData").is_none());
+    }
+}
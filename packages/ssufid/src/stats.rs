@@ -0,0 +1,207 @@
+//! Summary statistics over stored site outputs, e.g. for a yearly report of how many notices
+//! each department posted per month.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use serde::Serialize;
+
+use crate::{core::SsufidPost, datetime::KST};
+
+/// Counts of the posts given to [`aggregate`]. Months are `YYYY-MM` on the KST calendar, so a
+/// post from 00:30 on the 1st belongs to that month even though it is still the previous month
+/// in UTC.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ArchiveStats {
+    pub posts: usize,
+    pub per_month: BTreeMap<String, usize>,
+    pub per_category: BTreeMap<String, usize>,
+    pub plugins: BTreeMap<String, PluginStats>,
+    pub attachments_per_post: f64,
+    /// The most frequent title keywords, most frequent first, each counted once per title.
+    pub top_keywords: Vec<KeywordCount>,
+}
+
+/// The counts of one plugin's posts, see [`ArchiveStats`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct PluginStats {
+    pub posts: usize,
+    pub per_month: BTreeMap<String, usize>,
+    pub per_category: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct KeywordCount {
+    pub keyword: String,
+    pub titles: usize,
+}
+
+impl ArchiveStats {
+    /// The number of keywords kept in [`top_keywords`](Self::top_keywords).
+    pub const TOP_KEYWORDS: usize = 20;
+}
+
+/// Characters that separate title keywords besides whitespace, e.g. in `[학사]수강신청 안내`.
+const KEYWORD_SEPARATORS: &[char] = &[
+    '[', ']', '(', ')', '{', '}', '<', '>', '【', '】', '「', '」', '『', '』', '〈', '〉', '《',
+    '》', ',', '·', '/', ':',
+];
+
+/// Keywords shorter than this many characters, such as `및` or `-`, are not counted.
+const MIN_KEYWORD_CHARS: usize = 2;
+
+/// Computes the statistics of `posts`, each paired with the identifier of its plugin.
+pub fn aggregate<'a>(posts: impl IntoIterator<Item = (&'a str, &'a SsufidPost)>) -> ArchiveStats {
+    let mut stats = ArchiveStats::default();
+    let mut attachments = 0;
+    let mut keywords = HashMap::<String, usize>::new();
+    for (plugin, post) in posts {
+        let created_at = post.created_at.to_offset(KST);
+        let month = format!("{}-{:02}", created_at.year(), u8::from(created_at.month()));
+        let plugin = stats.plugins.entry(plugin.to_string()).or_default();
+
+        stats.posts += 1;
+        plugin.posts += 1;
+        *stats.per_month.entry(month.clone()).or_default() += 1;
+        *plugin.per_month.entry(month).or_default() += 1;
+        for category in &post.category {
+            *stats.per_category.entry(category.clone()).or_default() += 1;
+            *plugin.per_category.entry(category.clone()).or_default() += 1;
+        }
+        attachments += post.attachments.len();
+        for keyword in title_keywords(&post.title) {
+            *keywords.entry(keyword).or_default() += 1;
+        }
+    }
+    if stats.posts > 0 {
+        stats.attachments_per_post = attachments as f64 / stats.posts as f64;
+    }
+    let mut keywords = keywords
+        .into_iter()
+        .map(|(keyword, titles)| KeywordCount { keyword, titles })
+        .collect::<Vec<_>>();
+    keywords.sort_by(|a, b| {
+        b.titles
+            .cmp(&a.titles)
+            .then_with(|| a.keyword.cmp(&b.keyword))
+    });
+    keywords.truncate(ArchiveStats::TOP_KEYWORDS);
+    stats.top_keywords = keywords;
+    stats
+}
+
+/// The distinct keywords of `title`, lowercased.
+fn title_keywords(title: &str) -> BTreeSet<String> {
+    title
+        .split(|c: char| c.is_whitespace() || KEYWORD_SEPARATORS.contains(&c))
+        .map(|word| word.trim_matches(|c: char| c.is_ascii_punctuation()))
+        .filter(|word| word.chars().count() >= MIN_KEYWORD_CHARS)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+    use crate::core::{Attachment, UrlKind};
+
+    fn post(title: &str, category: &str, created_at: time::OffsetDateTime) -> SsufidPost {
+        SsufidPost {
+            id: title.to_string(),
+            url: "https://example.com/board/view.php?idx=1".to_string(),
+            url_kind: UrlKind::Canonical,
+            author: None,
+            title: title.to_string(),
+            description: None,
+            category: vec![category.to_string()],
+            created_at,
+            updated_at: None,
+            thumbnail: None,
+            content: String::new(),
+            attachments: vec![],
+            metadata: None,
+            author_detail: None,
+        }
+    }
+
+    #[test]
+    fn test_aggregate_counts_buckets() {
+        let mut with_file = post(
+            "[장학] 2025학년도 국가장학금 신청 안내",
+            "장학",
+            datetime!(2025-03-10 09:00:00 +9),
+        );
+        with_file.attachments = vec![
+            Attachment::from_guess("a.hwp".to_string(), "https://example.com/a.hwp".to_string()),
+            Attachment::from_guess("b.pdf".to_string(), "https://example.com/b.pdf".to_string()),
+        ];
+        let posts = [
+            ("ee.ssu.ac.kr", with_file),
+            (
+                "ee.ssu.ac.kr",
+                post(
+                    "교내 장학금 신청 안내",
+                    "장학",
+                    datetime!(2025-03-20 09:00:00 +9),
+                ),
+            ),
+            (
+                "cse.ssu.ac.kr",
+                post(
+                    "(학사) 수강신청 일정 안내",
+                    "학사",
+                    datetime!(2025-04-01 09:00:00 +9),
+                ),
+            ),
+        ];
+        let stats = aggregate(posts.iter().map(|(plugin, post)| (*plugin, post)));
+
+        assert_eq!(stats.posts, 3);
+        assert_eq!(
+            stats.per_month,
+            BTreeMap::from([("2025-03".to_string(), 2), ("2025-04".to_string(), 1)])
+        );
+        assert_eq!(
+            stats.per_category,
+            BTreeMap::from([("장학".to_string(), 2), ("학사".to_string(), 1)])
+        );
+        assert_eq!(
+            stats.plugins["ee.ssu.ac.kr"],
+            PluginStats {
+                posts: 2,
+                per_month: BTreeMap::from([("2025-03".to_string(), 2)]),
+                per_category: BTreeMap::from([("장학".to_string(), 2)]),
+            }
+        );
+        assert_eq!(stats.plugins["cse.ssu.ac.kr"].posts, 1);
+        assert!((stats.attachments_per_post - 2.0 / 3.0).abs() < f64::EPSILON);
+        assert_eq!(
+            stats.top_keywords[0],
+            KeywordCount {
+                keyword: "안내".to_string(),
+                titles: 3,
+            }
+        );
+        assert_eq!(
+            stats.top_keywords[1],
+            KeywordCount {
+                keyword: "신청".to_string(),
+                titles: 2,
+            }
+        );
+        assert!(stats.top_keywords.iter().any(|k| k.keyword == "학사"));
+    }
+
+    #[test]
+    fn test_months_are_bucketed_in_kst() {
+        // March 31 in UTC, but April 1 in KST
+        let post = post("4월 행사 안내", "행사", datetime!(2025-03-31 15:30:00 UTC));
+        let stats = aggregate([("ee.ssu.ac.kr", &post)]);
+        assert_eq!(
+            stats.per_month,
+            BTreeMap::from([("2025-04".to_string(), 1)])
+        );
+        assert_eq!(aggregate([]).attachments_per_post, 0.0);
+    }
+}
@@ -0,0 +1,316 @@
+//! Plain-text helpers for titles and other short post fields.
+
+use crate::core::Author;
+
+/// Splits leading bracketed tags such as `[장학]` or `【공지】` off `title`.
+///
+/// Returns the tags in order and the remaining title. A title made only of tags is returned
+/// unchanged with no tags, so that posts never end up with an empty title.
+pub fn split_title_tags(title: &str) -> (Vec<String>, String) {
+    let mut tags = Vec::new();
+    let mut rest = title.trim_start();
+    while let Some((tag, after)) = leading_tag(rest) {
+        tags.push(tag.to_string());
+        rest = after.trim_start();
+    }
+    let rest = rest.trim_end();
+    if rest.is_empty() {
+        return (vec![], title.trim().to_string());
+    }
+    (tags, rest.to_string())
+}
+
+fn leading_tag(s: &str) -> Option<(&str, &str)> {
+    let close = match s.chars().next()? {
+        '[' => ']',
+        '【' => '】',
+        _ => return None,
+    };
+    let open_len = s.chars().next()?.len_utf8();
+    let end = s[open_len..].find(close)? + open_len;
+    let tag = s[open_len..end].trim();
+    if tag.is_empty() {
+        return None;
+    }
+    Some((tag, &s[end + close.len_utf8()..]))
+}
+
+/// Cleans up a short scraped text such as a title or an author.
+///
+/// Non-breaking spaces count as whitespace, runs of whitespace collapse into a single space,
+/// zero-width characters are removed, and the result is trimmed along with any leading `|`
+/// separators left over from list markup.
+pub fn clean_text(s: &str) -> String {
+    s.chars()
+        .filter(|c| !is_zero_width(*c))
+        .collect::<String>()
+        .trim_start_matches(|c: char| c == '|' || c.is_whitespace())
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Labels boards put in front of the author's name, e.g. `작성자 : 관리자`.
+const AUTHOR_LABELS: &[&str] = &["작성자", "글쓴이", "등록자", "게시자", "writer", "author"];
+
+/// Cleans up a scraped author with [`clean_text`], then strips a leading label such as
+/// `작성자 :`, parenthetical notes such as `(학과사무실)` and trailing separators.
+///
+/// Returns `None` if nothing is left.
+pub fn clean_author(raw: &str) -> Option<String> {
+    let text = clean_text(raw);
+    let author = strip_author_label(&text);
+
+    let mut without_notes = String::with_capacity(author.len());
+    let mut depth = 0usize;
+    for c in author.chars() {
+        match c {
+            '(' | '（' => depth += 1,
+            ')' | '）' if depth > 0 => depth -= 1,
+            _ if depth == 0 => without_notes.push(c),
+            _ => {}
+        }
+    }
+
+    let author =
+        clean_text(without_notes.trim_end_matches(|c: char| {
+            matches!(c, '|' | '/' | '·' | ',' | ':') || c.is_whitespace()
+        }));
+    (!author.is_empty()).then_some(author)
+}
+
+fn strip_author_label(author: &str) -> &str {
+    let Some(label) = AUTHOR_LABELS.iter().find(|label| {
+        author
+            .get(..label.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(label))
+    }) else {
+        return author;
+    };
+    let rest = &author[label.len()..];
+    // Without a separator after the label, as in "작성자관리자", it is part of the name
+    if rest.is_empty() || rest.starts_with(|c: char| c == ':' || c.is_whitespace()) {
+        rest.trim_start_matches(|c: char| c == ':' || c.is_whitespace())
+    } else {
+        author
+    }
+}
+
+/// Separators boards put between a department and a person, e.g. `학사팀 / 김OO`.
+const AUTHOR_SEPARATORS: &[char] = &['/', '|', '·', ','];
+/// Endings that tell a department such as `학사팀` or `경영학부` apart from a person's name.
+const DEPARTMENT_SUFFIXES: &[&str] = &["팀", "과", "부", "처", "실", "센터", "원", "단", "위원회"];
+const HONORIFICS: &[&str] = &["선생님", "님"];
+
+/// Parses a Korean author string such as `학사팀 / 김OO` or `김OO(학사팀)` into an [`Author`].
+pub fn parse_author_kr(raw: &str) -> Option<Author> {
+    let text = clean_text(raw);
+    let text = strip_author_label(&text);
+    let (department, name) = match text.split_once(['(', '（']) {
+        Some((outside, inside)) => (inside.trim_end_matches([')', '）']), outside),
+        None => text.split_once(AUTHOR_SEPARATORS).unwrap_or(("", text)),
+    };
+    let (department, name) = (clean_author(department), clean_author(name));
+    let (department, name) = match (department, name) {
+        (Some(department), Some(name))
+            if looks_like_department(&name) && !looks_like_department(&department) =>
+        {
+            (Some(name), department)
+        }
+        (department, Some(name)) => (department, name),
+        (Some(department), None) => (None, department),
+        (None, None) => return None,
+    };
+    let name = HONORIFICS
+        .iter()
+        .find_map(|honorific| name.strip_suffix(honorific))
+        .map(str::trim_end)
+        .filter(|stripped| !stripped.is_empty())
+        .map_or(name.clone(), str::to_string);
+    Some(Author { name, department })
+}
+
+fn looks_like_department(s: &str) -> bool {
+    DEPARTMENT_SUFFIXES.iter().any(|suffix| s.ends_with(suffix))
+}
+
+/// Cheaply guesses whether `text` was decoded with the wrong charset, e.g. EUC-KR bytes read
+/// as UTF-8.
+pub fn looks_mojibake(text: &str) -> bool {
+    let (mut total, mut replacement, mut jamo, mut syllables) = (0usize, 0usize, 0usize, 0usize);
+    for c in text.chars().filter(|c| !c.is_whitespace()) {
+        total += 1;
+        match c {
+            '\u{fffd}' => replacement += 1,
+            '\u{3131}'..='\u{318e}' => jamo += 1,
+            '\u{ac00}'..='\u{d7a3}' => syllables += 1,
+            _ => {}
+        }
+    }
+    if total == 0 {
+        return false;
+    }
+    // A few garbled characters or bare jamo such as "ㅋㅋ" are tolerated
+    let replacement_heavy = replacement >= 3 && replacement * 20 >= total;
+    let jamo_heavy = jamo >= 3 && jamo * 2 > jamo + syllables;
+    replacement_heavy || jamo_heavy
+}
+
+static FILE_SIZE: std::sync::LazyLock<regex::Regex> = std::sync::LazyLock::new(|| {
+    regex::Regex::new(r"(?i)\(\s*(\d[\d,]*(?:\.\d+)?)\s*(?:([KMGT])(?:i?B)?|bytes?|B)\s*\)")
+        .unwrap()
+});
+
+/// Finds the last file size that a board shows in parentheses next to a file name, such as
+/// `신청 매뉴얼.pdf (374.8K)`, `(1.2 MB)` or `(512 bytes)`, and returns the text before it,
+/// trimmed, with the size in bytes. Units are powers of 1024 as boards compute them. A bare
+/// number in parentheses, like the `(2)` of `붙임 (2).hwp`, is not a size.
+pub fn split_file_size(text: &str) -> Option<(&str, u64)> {
+    let caps = FILE_SIZE.captures_iter(text).last()?;
+    let number = caps[1].replace(',', "").parse::<f64>().ok()?;
+    let exponent = match caps.get(2).map_or("", |unit| unit.as_str()) {
+        "" => 0,
+        "K" | "k" => 1,
+        "M" | "m" => 2,
+        "G" | "g" => 3,
+        _ => 4,
+    };
+    let bytes = (number * 1024f64.powi(exponent)).round() as u64;
+    Some((text[..caps.get(0)?.start()].trim_end(), bytes))
+}
+
+fn is_zero_width(c: char) -> bool {
+    matches!(
+        c,
+        '\u{200b}' | '\u{200c}' | '\u{200d}' | '\u{2060}' | '\u{feff}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_file_size() {
+        assert_eq!(
+            split_file_size("교내장학금 신청 매뉴얼.pdf (374.8K)"),
+            Some(("교내장학금 신청 매뉴얼.pdf", 383_795))
+        );
+        assert_eq!(
+            split_file_size("붙임(양식).hwp (1.5 MB) DATE : 2025-06-04"),
+            Some(("붙임(양식).hwp", 1_572_864))
+        );
+        assert_eq!(
+            split_file_size("a.txt (1,024 bytes)"),
+            Some(("a.txt", 1024))
+        );
+        assert_eq!(split_file_size("a.txt (512B)"), Some(("a.txt", 512)));
+        assert_eq!(split_file_size("붙임(양식).hwp"), None);
+        assert_eq!(split_file_size("신청서 (2)"), None);
+        assert_eq!(split_file_size("2025 학사일정 (2025)"), None);
+    }
+
+    #[test]
+    fn test_clean_text() {
+        assert_eq!(
+            clean_text("\u{a0}2025학년도\u{a0}\u{a0}2학기  졸업논문\u{200b} 제출 안내 "),
+            "2025학년도 2학기 졸업논문 제출 안내"
+        );
+        assert_eq!(clean_text(" | 학과사무실\t|\n조교"), "학과사무실 | 조교");
+        assert_eq!(clean_text("\u{feff}\u{a0}"), "");
+    }
+
+    #[test]
+    fn test_clean_author() {
+        assert_eq!(clean_author("작성자 : 관리자"), Some("관리자".to_string()));
+        assert_eq!(clean_author("Writer: admin |"), Some("admin".to_string()));
+        assert_eq!(
+            clean_author(" 관리자 (학과사무실) / "),
+            Some("관리자".to_string())
+        );
+        assert_eq!(
+            clean_author("작성자관리자"),
+            Some("작성자관리자".to_string())
+        );
+        assert_eq!(clean_author(""), None);
+        assert_eq!(clean_author("작성자 :"), None);
+        assert_eq!(clean_author("\u{a0}|"), None);
+    }
+
+    #[test]
+    fn test_parse_author_kr() {
+        let author = |name: &str, department: Option<&str>| {
+            Some(Author {
+                name: name.to_string(),
+                department: department.map(str::to_string),
+            })
+        };
+        assert_eq!(
+            parse_author_kr("학사팀 / 김OO"),
+            author("김OO", Some("학사팀"))
+        );
+        assert_eq!(
+            parse_author_kr("김OO | 학사팀"),
+            author("김OO", Some("학사팀"))
+        );
+        assert_eq!(
+            parse_author_kr("장학팀·이OO 선생님"),
+            author("이OO", Some("장학팀"))
+        );
+        assert_eq!(
+            parse_author_kr("작성자 : 김OO(학사팀)"),
+            author("김OO", Some("학사팀"))
+        );
+        assert_eq!(parse_author_kr("경영학부"), author("경영학부", None));
+        assert_eq!(parse_author_kr("관리자 /"), author("관리자", None));
+        assert_eq!(parse_author_kr("님"), author("님", None));
+        assert_eq!(parse_author_kr(" | "), None);
+    }
+
+    #[test]
+    fn test_looks_mojibake() {
+        assert!(!looks_mojibake("2025학년도 2학기 졸업논문 제출 안내 ㅋㅋ"));
+        assert!(!looks_mojibake("Call for papers: ICSE 2026 (deadline 3/1)"));
+        assert!(!looks_mojibake(""));
+
+        let (euc_kr, _, _) = encoding_rs::EUC_KR.encode("숭실대학교 기숙사 입사 안내");
+        assert!(looks_mojibake(&String::from_utf8_lossy(&euc_kr)));
+        assert!(looks_mojibake("ㅅㅜㅇㅅㅣㄹ ㄷㅐㅎㅏㄱㄱㅛ 공지"));
+    }
+
+    #[test]
+    fn test_split_title_tags() {
+        assert_eq!(
+            split_title_tags("[장학][긴급] 제목"),
+            (
+                vec!["장학".to_string(), "긴급".to_string()],
+                "제목".to_string()
+            )
+        );
+        assert_eq!(
+            split_title_tags(" 【공지】 [ 취업 ]  채용 설명회 안내 "),
+            (
+                vec!["공지".to_string(), "취업".to_string()],
+                "채용 설명회 안내".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_split_title_tags_keeps_inner_brackets() {
+        assert_eq!(
+            split_title_tags("2025학년도 [1학기] 수강신청"),
+            (vec![], "2025학년도 [1학기] 수강신청".to_string())
+        );
+        assert_eq!(split_title_tags("[] 제목"), (vec![], "[] 제목".to_string()));
+        assert_eq!(
+            split_title_tags("[미완 제목"),
+            (vec![], "[미완 제목".to_string())
+        );
+    }
+
+    #[test]
+    fn test_split_title_tags_only_tags() {
+        assert_eq!(split_title_tags("[공지]"), (vec![], "[공지]".to_string()));
+    }
+}
@@ -0,0 +1,67 @@
+//! Helpers for the URLs stored in posts.
+
+/// `url` with only the query parameters named in `keep_params`, so that a post's stored URL
+/// stays the same across crawls.
+pub fn canonicalize(url: &str, keep_params: &[&str]) -> String {
+    let Ok(mut parsed) = ::url::Url::parse(url) else {
+        return url.to_string();
+    };
+    let pairs = parsed.query_pairs().count();
+    let kept = parsed
+        .query_pairs()
+        .filter(|(key, _)| keep_params.contains(&key.as_ref()))
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect::<Vec<_>>();
+    if parsed.query().is_none() || kept.len() == pairs {
+        return url.to_string();
+    }
+    if kept.is_empty() {
+        parsed.set_query(None);
+    } else {
+        parsed.query_pairs_mut().clear().extend_pairs(kept);
+    }
+    parsed.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonicalize_keeps_identifying_params() {
+        assert_eq!(
+            canonicalize(
+                "https://example.com/board/view.php?page=2&idx=100",
+                &["idx"]
+            ),
+            "https://example.com/board/view.php?idx=100"
+        );
+        assert_eq!(
+            canonicalize(
+                "https://example.com/bbs/board.php?bo_table=notice&wr_id=7&sst=wr_hit&page=3&PHPSESSID=abc",
+                &["bo_table", "wr_id"]
+            ),
+            "https://example.com/bbs/board.php?bo_table=notice&wr_id=7"
+        );
+        // Drop the question mark too when no parameter is left
+        assert_eq!(
+            canonicalize("https://example.com/notice/12?utm_source=kakao", &["idx"]),
+            "https://example.com/notice/12"
+        );
+        assert_eq!(
+            canonicalize("https://example.com/notice/12", &["idx"]),
+            "https://example.com/notice/12"
+        );
+        assert_eq!(
+            canonicalize(
+                "https://example.com/view?idx=1&category=%ED%95%99%EC%82%AC",
+                &["idx", "category"]
+            ),
+            "https://example.com/view?idx=1&category=%ED%95%99%EC%82%AC"
+        );
+        assert_eq!(
+            canonicalize("/view?idx=1&page=2", &["idx"]),
+            "/view?idx=1&page=2"
+        );
+    }
+}
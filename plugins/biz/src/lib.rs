@@ -4,8 +4,11 @@ use thiserror::Error;
 use url::Url;
 
 use ssufid::{
-    core::{Attachment, SsufidPlugin, SsufidPost, SsufidPostPlugin},
+    core::{Attachment, SsufidPlugin, SsufidPost, SsufidPostPlugin, UrlKind},
     error::PluginError,
+    fetch::{fetch_content_type, send},
+    html::{ContentSource, select_content},
+    text::parse_author_kr,
 };
 use time::{
     Date,
@@ -58,8 +61,6 @@ struct BizMetadata {
 
 #[derive(Debug, Error)]
 enum BizScrapingError {
-    #[error("List page: Post link not found in list item (li > div > a)")]
-    LinkNotFound,
     // TitleNotFound commented out as empty titles on list are handled with a placeholder
     // #[error("List page: Post title not found in list item")]
     // TitleNotFound,
@@ -86,12 +87,68 @@ enum BizScrapingError {
     // AuthorExtractionErrorDetail(String),
 }
 
-pub struct BizPlugin {
+/// A board hosted on the biz.ssu.ac.kr BBS, distinguished by its `bId`.
+pub trait BizBoard {
+    const BID: &'static str;
+    const IDENTIFIER: &'static str;
+    const TITLE: &'static str;
+    const DESCRIPTION: &'static str;
+    /// The category of every post of the board, if it has one.
+    const CATEGORY: Option<&'static str>;
+
+    /// `aId` values are only unique within a board, so post ids are prefixed with the `bId`.
+    fn post_id(aid: &str) -> String {
+        format!("{}:{aid}", Self::BID)
+    }
+}
+
+pub struct NoticeBoard;
+
+impl BizBoard for NoticeBoard {
+    const BID: &'static str = "BBS_03_NOTICE";
+    const IDENTIFIER: &'static str = "biz.ssu.ac.kr";
+    const TITLE: &'static str = "숭실대학교 경영학부 공지사항";
+    const DESCRIPTION: &'static str = "숭실대학교 경영학부 홈페이지의 공지사항을 제공합니다.";
+    // No category or id prefix, to stay compatible with existing notice caches.
+    const CATEGORY: Option<&'static str> = None;
+
+    fn post_id(aid: &str) -> String {
+        aid.to_string()
+    }
+}
+
+pub struct JobBoard;
+
+impl BizBoard for JobBoard {
+    const BID: &'static str = "BBS_02_JOB";
+    const IDENTIFIER: &'static str = "biz.ssu.ac.kr-job";
+    const TITLE: &'static str = "숭실대학교 경영학부 취업정보";
+    const DESCRIPTION: &'static str = "숭실대학교 경영학부 홈페이지의 취업정보를 제공합니다.";
+    const CATEGORY: Option<&'static str> = Some("취업");
+}
+
+pub struct ScholarshipBoard;
+
+impl BizBoard for ScholarshipBoard {
+    const BID: &'static str = "BBS_04_SCHOLAR";
+    const IDENTIFIER: &'static str = "biz.ssu.ac.kr-scholarship";
+    const TITLE: &'static str = "숭실대학교 경영학부 장학정보";
+    const DESCRIPTION: &'static str = "숭실대학교 경영학부 홈페이지의 장학정보를 제공합니다.";
+    const CATEGORY: Option<&'static str> = Some("장학");
+}
+
+pub struct BizBoardPlugin<B: BizBoard> {
     selectors: Selectors,
     http_client: reqwest::Client,
+    base_url: String,
+    _board: std::marker::PhantomData<B>,
 }
 
-impl Default for BizPlugin {
+pub type BizPlugin = BizBoardPlugin<NoticeBoard>;
+pub type BizJobPlugin = BizBoardPlugin<JobBoard>;
+pub type BizScholarshipPlugin = BizBoardPlugin<ScholarshipBoard>;
+
+impl<B: BizBoard> Default for BizBoardPlugin<B> {
     fn default() -> Self {
         Self::new()
     }
@@ -100,49 +157,53 @@ impl Default for BizPlugin {
 const DATE_FORMAT_BIZ: &[time::format_description::FormatItem<'static>] =
     format_description!("[year]-[month]-[day]"); // format_description! macro is brought into scope by the use statement above
 
-fn parse_date_author_string(s: &str) -> Result<(String, String), PluginError> {
+fn parse_date_author_string<T: SsufidPlugin>(s: &str) -> Result<(String, String), PluginError> {
     let parts: Vec<&str> = s.splitn(2, '/').map(str::trim).collect();
     let date_str = parts
         .first()
         .map(|x| x.to_string())
-        .ok_or(PluginError::parse::<BizPlugin>(
+        .ok_or(PluginError::parse::<T>(
             BizScrapingError::DateExtractionErrorDetail(s.to_string()).to_string(),
         ))?;
     let author_str = parts
         .get(1)
         .map(|x| x.to_string())
-        .ok_or(PluginError::parse::<BizPlugin>(
+        .ok_or(PluginError::parse::<T>(
             BizScrapingError::DateExtractionErrorDetail(s.to_string()).to_string(),
         ))?;
     Ok((date_str, author_str))
 }
 
-impl BizPlugin {
-    const BIZ_BASE_URL: &'static str = "http://biz.ssu.ac.kr";
+const BIZ_BASE_URL: &str = "http://biz.ssu.ac.kr";
 
+impl<B: BizBoard> BizBoardPlugin<B> {
     pub fn new() -> Self {
+        Self::with_base_url(BIZ_BASE_URL)
+    }
+
+    /// Creates a plugin that crawls `base_url` instead of biz.ssu.ac.kr, e.g. a mock server.
+    pub fn with_base_url(base_url: impl Into<String>) -> Self {
         Self {
             selectors: Selectors::new(),
             http_client: reqwest::Client::builder()
                 .danger_accept_invalid_certs(true)
                 .build()
                 .unwrap(),
+            base_url: base_url.into(),
+            _board: std::marker::PhantomData,
         }
     }
 
+    fn list_url(&self, page: u32) -> String {
+        format!("{}/bbs/list.do?bId={}&page={}", self.base_url, B::BID, page)
+    }
+
     async fn fetch_page_posts_metadata(&self, page: u32) -> Result<Vec<BizMetadata>, PluginError> {
-        let list_url = format!(
-            "{}/bbs/list.do?bId=BBS_03_NOTICE&page={}",
-            Self::BIZ_BASE_URL,
-            page
-        );
+        let list_url = self.list_url(page);
 
         tracing::debug!("Fetching metadata from: {}", list_url);
 
-        let response_text = self
-            .http_client
-            .get(&list_url)
-            .send()
+        let response_text = send::<Self>(self.http_client.get(&list_url))
             .await
             .map_err(|e| PluginError::request::<Self>(e.to_string()))?
             .text()
@@ -173,10 +234,15 @@ impl BizPlugin {
             };
 
             let relative_url = link_element.value().attr("href").ok_or_else(|| {
-                PluginError::parse::<Self>(BizScrapingError::LinkNotFound.to_string())
+                PluginError::structure::<Self>(
+                    "an href on the post link (li > div > a)",
+                    format!("list page {list_url}"),
+                )
             })?;
 
-            let base_url_for_join = Url::parse(Self::BIZ_BASE_URL).unwrap();
+            let base_url_for_join = Url::parse(&self.base_url).map_err(|e| {
+                PluginError::parse::<Self>(format!("Invalid base URL '{}': {e}", self.base_url))
+            })?;
             let full_url = base_url_for_join
                 .join(relative_url)
                 .map_err(|e| {
@@ -190,7 +256,7 @@ impl BizPlugin {
                 PluginError::parse::<Self>(format!("URL re-parse error for '{full_url}': {e}"))
             })?;
 
-            let id = parsed_url
+            let aid = parsed_url
                 .query_pairs()
                 .find_map(|(key, value)| {
                     if key == "aId" || key == "seq" {
@@ -234,10 +300,10 @@ impl BizPlugin {
                 .collect::<String>()
                 .trim()
                 .to_string();
-            let (date_str, author) = parse_date_author_string(&date_author_str)?;
+            let (date_str, author) = parse_date_author_string::<Self>(&date_author_str)?;
 
             metadata_list.push(BizMetadata {
-                id,
+                id: B::post_id(&aid),
                 url: full_url,
                 date_str,
                 author,
@@ -248,17 +314,46 @@ impl BizPlugin {
 
     async fn fetch_post(&self, post_metadata: &BizMetadata) -> Result<SsufidPost, PluginError> {
         tracing::debug!("Fetching post content from: {}", post_metadata.url);
-        let response_text = self
-            .http_client
-            .get(&post_metadata.url)
-            .send()
+        let response_text = send::<Self>(self.http_client.get(&post_metadata.url))
             .await
             .map_err(|e| PluginError::request::<Self>(e.to_string()))?
             .text()
             .await
             .map_err(|e| PluginError::parse::<Self>(e.to_string()))?;
 
-        let document = Html::parse_document(&response_text);
+        let mut post = self.parse_post(post_metadata, &response_text)?;
+        // Only attachments whose name has no known extension need a HEAD request. The
+        // download is hotlink-protected, so it is sent from the post's page.
+        for attachment in post
+            .attachments
+            .iter_mut()
+            .filter(|a| a.mime_type.is_none())
+        {
+            attachment.mime_type = fetch_content_type::<Self>(
+                &self.http_client,
+                &attachment.url,
+                Some(&post_metadata.url),
+            )
+            .await
+            .inspect_err(|e| {
+                tracing::warn!(
+                    error = ?e,
+                    url = %attachment.url,
+                    "Failed to fetch attachment type"
+                )
+            })
+            .ok()
+            .flatten();
+        }
+        Ok(post)
+    }
+
+    fn parse_post(
+        &self,
+        post_metadata: &BizMetadata,
+        response_text: &str,
+    ) -> Result<SsufidPost, PluginError> {
+        let document = Html::parse_document(response_text);
 
         let title = document
             .select(&self.selectors.title_detail)
@@ -269,6 +364,7 @@ impl BizPlugin {
                 PluginError::parse::<Self>(
                     BizScrapingError::TitleNotFoundDetail(post_metadata.url.clone()).to_string(),
                 )
+                .with_snippet(&self.selectors.title_detail, response_text)
             })?;
 
         let created_at = Date::parse(&post_metadata.date_str, &DATE_FORMAT_BIZ)
@@ -284,15 +380,18 @@ impl BizPlugin {
             .midnight()
             .assume_offset(offset!(+9));
 
-        let content_html = document
-            .select(&self.selectors.content_detail)
-            .next()
-            .map(|el| el.html())
-            .ok_or_else(|| {
-                PluginError::parse::<Self>(
-                    BizScrapingError::ContentNotFoundDetail(post_metadata.url.clone()).to_string(),
-                )
-            })?;
+        let (content_html, content_source) = select_content::<Self>(
+            &document,
+            &self.selectors.content_detail,
+            &post_metadata.url,
+        )
+        .map(|(el, source)| (el.html(), source))
+        .ok_or_else(|| {
+            PluginError::parse::<Self>(
+                BizScrapingError::ContentNotFoundDetail(post_metadata.url.clone()).to_string(),
+            )
+            .with_snippet(&self.selectors.content_detail, response_text)
+        })?;
 
         let mut attachments = Vec::new();
         if let Some(container) = document
@@ -302,7 +401,12 @@ impl BizPlugin {
             for item_a in container.select(&self.selectors.attachment_item) {
                 if let Some(href) = item_a.value().attr("href") {
                     let attachment_base =
-                        Url::parse(&format!("{}/bbs/", Self::BIZ_BASE_URL)).unwrap();
+                        Url::parse(&format!("{}/bbs/", self.base_url)).map_err(|e| {
+                            PluginError::parse::<Self>(format!(
+                                "Invalid base URL '{}': {e}",
+                                self.base_url
+                            ))
+                        })?;
                     let attachment_url = attachment_base
                         .join(href)
                         .map_err(|e| {
@@ -313,40 +417,61 @@ impl BizPlugin {
                         .to_string();
 
                     let name = item_a.text().collect::<String>().trim().to_string();
-                    attachments.push(Attachment {
-                        name: Some(name),
-                        url: attachment_url,
-                        mime_type: None,
-                    });
+                    attachments.push(Attachment::from_guess(name, attachment_url));
                 }
             }
         }
 
-        Ok(SsufidPost {
+        let mut post = SsufidPost {
             id: post_metadata.id.clone(),
             url: post_metadata.url.clone(),
+            url_kind: UrlKind::Canonical,
             author: Some(post_metadata.author.clone()),
             title,
             description: None,
-            category: vec![],
+            category: B::CATEGORY.map(str::to_string).into_iter().collect(),
             created_at,
             updated_at: None,
             thumbnail: None,
             content: content_html,
             attachments,
             metadata: None,
-        })
+            author_detail: parse_author_kr(&post_metadata.author),
+        };
+        if content_source == ContentSource::Fallback {
+            post.mark_content_fallback();
+        }
+        Ok(post)
     }
 }
 
-impl SsufidPlugin for BizPlugin {
-    const IDENTIFIER: &'static str = "biz.ssu.ac.kr";
-    const TITLE: &'static str = "숭실대학교 경영학부 공지사항";
-    const DESCRIPTION: &'static str = "숭실대학교 경영학부 홈페이지의 공지사항을 제공합니다.";
-    const BASE_URL: &'static str = BizPlugin::BIZ_BASE_URL;
+impl<B: BizBoard> SsufidPlugin for BizBoardPlugin<B> {
+    const IDENTIFIER: &'static str = B::IDENTIFIER;
+    const TITLE: &'static str = B::TITLE;
+    const DESCRIPTION: &'static str = B::DESCRIPTION;
+    const BASE_URL: &'static str = BIZ_BASE_URL;
+    // 2: `author_detail` split from the department and staff name
+    // 3: attachment MIME types from their name or a HEAD request
+    const VERSION: u32 = 3;
+    // Attachments only download for requests opened from the site
+    const EXTRA_HEADERS: &'static [(&'static str, &'static str)] =
+        &[("Referer", "http://biz.ssu.ac.kr/")];
+    const CRITICAL_SELECTORS: &'static [(&'static str, &'static str)] = &[
+        ("list_item", "ul#bList01 li"),
+        ("title", "div#postTitle > span"),
+        ("content", "div#postContents"),
+    ];
+
+    fn base_url(&self) -> std::borrow::Cow<'_, str> {
+        std::borrow::Cow::Borrowed(&self.base_url)
+    }
+
+    fn http_client(&self) -> reqwest::Client {
+        self.http_client.clone()
+    }
 }
 
-impl SsufidPostPlugin for BizPlugin {
+impl<B: BizBoard + Send + Sync> SsufidPostPlugin for BizBoardPlugin<B> {
     async fn crawl(&self, posts_limit: u32) -> Result<Vec<SsufidPost>, PluginError> {
         if posts_limit == 0 {
             return Ok(vec![]);
@@ -417,6 +542,27 @@ impl SsufidPostPlugin for BizPlugin {
         );
         Ok(posts)
     }
+
+    async fn latest_post_id(&self) -> Result<Option<String>, PluginError> {
+        Ok(self
+            .fetch_page_posts_metadata(1)
+            .await?
+            .into_iter()
+            .next()
+            .map(|metadata| metadata.id))
+    }
+
+    /// The list page is not at `BASE_URL` but at `list_url`.
+    fn list_page_url(&self) -> String {
+        self.list_url(1)
+    }
+
+    async fn fixture_urls(&self, count: u32) -> Result<Vec<String>, PluginError> {
+        let metadata = self.fetch_page_posts_metadata(1).await?;
+        Ok(std::iter::once(self.list_page_url())
+            .chain(metadata.into_iter().take(count as usize).map(|md| md.url))
+            .collect())
+    }
 }
 
 #[cfg(test)]
@@ -430,6 +576,232 @@ mod tests {
         assert_eq!(BizPlugin::IDENTIFIER, "biz.ssu.ac.kr");
     }
 
+    #[test]
+    fn test_list_url_per_board() {
+        assert_eq!(
+            BizPlugin::new().list_url(2),
+            "http://biz.ssu.ac.kr/bbs/list.do?bId=BBS_03_NOTICE&page=2"
+        );
+        assert_eq!(
+            BizJobPlugin::new().list_url(1),
+            "http://biz.ssu.ac.kr/bbs/list.do?bId=BBS_02_JOB&page=1"
+        );
+        assert_eq!(
+            BizScholarshipPlugin::new().list_url(1),
+            "http://biz.ssu.ac.kr/bbs/list.do?bId=BBS_04_SCHOLAR&page=1"
+        );
+    }
+
+    #[test]
+    fn test_post_id_namespacing() {
+        assert_eq!(NoticeBoard::post_id("123"), "123");
+        assert_eq!(JobBoard::post_id("123"), "BBS_02_JOB:123");
+        assert_eq!(ScholarshipBoard::post_id("123"), "BBS_04_SCHOLAR:123");
+        assert_ne!(BizJobPlugin::IDENTIFIER, BizScholarshipPlugin::IDENTIFIER);
+        // Each board writes to its own output directory, not one nested in another's.
+        for identifier in [
+            BizPlugin::IDENTIFIER,
+            BizJobPlugin::IDENTIFIER,
+            BizScholarshipPlugin::IDENTIFIER,
+        ] {
+            assert!(!identifier.contains('/'), "{identifier}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_crawl_job_board_from_mock() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path, query_param},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/bbs/list.do"))
+            .and(query_param("bId", "BBS_02_JOB"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ul id="bList01">
+                    <li>
+                        <div><a href="/bbs/view.do?bId=BBS_02_JOB&aId=42">채용 공고</a></div>
+                        <div><span>2025-03-02 / 경영학부</span></div>
+                    </li>
+                </ul>"#,
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/bbs/view.do"))
+            .and(query_param("aId", "42"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<div id="postTitle"><span>채용 공고</span></div>
+                <div id="postContents"><p>본문</p></div>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let plugin = BizJobPlugin::with_base_url(server.uri());
+        let posts = plugin.crawl(1).await.unwrap();
+
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].id, "BBS_02_JOB:42");
+        assert_eq!(posts[0].title, "채용 공고");
+        assert_eq!(posts[0].category, vec!["취업".to_string()]);
+        assert_eq!(posts[0].author.as_deref(), Some("경영학부"));
+        assert_eq!(
+            posts[0]
+                .author_detail
+                .as_ref()
+                .map(|author| author.name.as_str()),
+            Some("경영학부")
+        );
+        assert!(posts[0].url.starts_with(&server.uri()));
+    }
+
+    #[tokio::test]
+    async fn test_attachment_type_from_name_or_head() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{header, method, path, query_param},
+        };
+
+        let server = MockServer::start().await;
+        let post_url = format!("{}/bbs/view.do?bId=BBS_03_NOTICE&aId=7", server.uri());
+        Mock::given(method("GET"))
+            .and(path("/bbs/list.do"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ul id="bList01">
+                    <li>
+                        <div><a href="/bbs/view.do?bId=BBS_03_NOTICE&aId=7">장학 안내</a></div>
+                        <div><span>2025-03-02 / 경영학부</span></div>
+                    </li>
+                </ul>"#,
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/bbs/view.do"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<div id="postTitle"><span>장학 안내</span></div>
+                <div id="postContents"><p>본문</p></div>
+                <ul id="postFileList">
+                    <li><a href="download.do?fId=1">신청서.pdf</a></li>
+                    <li><a href="download.do?fId=2">장학금 안내문</a></li>
+                </ul>"#,
+            ))
+            .mount(&server)
+            .await;
+        // Only the name without an extension needs a HEAD request, sent from the post's page
+        Mock::given(method("HEAD"))
+            .and(path("/bbs/download.do"))
+            .and(query_param("fId", "2"))
+            .and(header("referer", post_url.as_str()))
+            .respond_with(
+                ResponseTemplate::new(200).insert_header("Content-Type", "application/x-hwp"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let posts = BizPlugin::with_base_url(server.uri())
+            .crawl(1)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            posts[0]
+                .attachments
+                .iter()
+                .map(|attachment| attachment.mime_type.as_deref())
+                .collect::<Vec<_>>(),
+            [Some("application/pdf"), Some("application/x-hwp")]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_content_falls_back_after_redesign() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/bbs/list.do"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ul id="bList01">
+                    <li>
+                        <div><a href="/bbs/view.do?bId=BBS_03_NOTICE&aId=7">장학 안내</a></div>
+                        <div><span>2025-03-02 / 경영학부</span></div>
+                    </li>
+                </ul>"#,
+            ))
+            .mount(&server)
+            .await;
+        // The content area's id changed but the structure did not
+        Mock::given(method("GET"))
+            .and(path("/bbs/view.do"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<div id="postTitle"><span>장학 안내</span></div>
+                <div class="board-view-content"><p>2025학년도 1학기 장학금 신청을 안내합니다.</p></div>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let posts = BizPlugin::with_base_url(server.uri())
+            .crawl(1)
+            .await
+            .unwrap();
+
+        assert_eq!(posts.len(), 1);
+        assert!(posts[0].content.contains("장학금 신청을 안내합니다"));
+        // Notices still have no category
+        assert!(posts[0].category.is_empty());
+        assert_eq!(
+            posts[0]
+                .metadata
+                .as_ref()
+                .and_then(|metadata| metadata.get(SsufidPost::CONTENT_SOURCE_KEY))
+                .map(String::as_str),
+            Some("fallback")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_latest_post_id_from_mock() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/bbs/list.do"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<ul id="bList01">
+                    <li>
+                        <div><a href="/bbs/view.do?bId=BBS_03_NOTICE&aId=102">최신 공지</a></div>
+                        <div><span>2025-03-03 / 경영학부</span></div>
+                    </li>
+                    <li>
+                        <div><a href="/bbs/view.do?bId=BBS_03_NOTICE&aId=101">이전 공지</a></div>
+                        <div><span>2025-03-02 / 경영학부</span></div>
+                    </li>
+                </ul>"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let plugin = BizPlugin::with_base_url(server.uri());
+        assert_eq!(
+            plugin.latest_post_id().await.unwrap(),
+            Some("102".to_string())
+        );
+        // The detail page must not be requested
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
     #[test]
     fn test_selectors_creation() {
         let _selectors = Selectors::new();
@@ -437,7 +809,8 @@ mod tests {
 
     #[test]
     fn test_parse_date_author_string() {
-        let (date, author) = parse_date_author_string("2024-07-30 / 경영학부").unwrap();
+        let (date, author) =
+            parse_date_author_string::<BizPlugin>("2024-07-30 / 경영학부").unwrap();
         assert_eq!(date, "2024-07-30".to_string());
         assert_eq!(author, "경영학부".to_string());
     }
@@ -0,0 +1,284 @@
+//! Parsing for the `div.board-list2` / `div.board-view` board skin shared by ee and chemeng.
+//! Plugins fetch the pages themselves and map a [`ListRow`] and a [`BoardView`] to posts.
+
+use std::sync::LazyLock;
+
+use scraper::{ElementRef, Html, Selector};
+use ssufid::{core::Attachment, datetime::parse_kst};
+use time::OffsetDateTime;
+use url::Url;
+
+fn selector(s: &str) -> Selector {
+    Selector::parse(s).expect("board-view selectors are valid")
+}
+
+static LIST_ITEM: LazyLock<Selector> = LazyLock::new(|| selector("div.board-list2 > ul > li"));
+static LIST_LINK: LazyLock<Selector> = LazyLock::new(|| selector("div.subject > a"));
+static NEXT_PAGE: LazyLock<Selector> =
+    LazyLock::new(|| selector("div.paginate > a.next:not(.disabled)"));
+static TITLE: LazyLock<Selector> = LazyLock::new(|| selector("div.board-view > div.head > h3.tit"));
+static AUTHOR: LazyLock<Selector> =
+    LazyLock::new(|| selector("div.board-view > div.head > div.info > span.name"));
+static STRONG: LazyLock<Selector> = LazyLock::new(|| selector("strong"));
+static DATE: LazyLock<Selector> =
+    LazyLock::new(|| selector("div.board-view > div.head > div.info > span.date"));
+static CONTENT: LazyLock<Selector> = LazyLock::new(|| selector("div.board-view > div.body"));
+static ATTACHMENT: LazyLock<Selector> = LazyLock::new(|| {
+    selector(
+        "div.board-view > div.head > div.files a[onclick*='download'], \
+         div.board-view > div.head > div.files a[href*='download'], \
+         div.board-view > div.body a[href^='/uploaded/']",
+    )
+});
+
+/// The selectors a post page on this skin must match, for
+/// [`SsufidPlugin::CRITICAL_SELECTORS`](ssufid::core::SsufidPlugin::CRITICAL_SELECTORS).
+pub const CRITICAL_SELECTORS: &[(&str, &str)] = &[
+    ("title", "div.board-view > div.head > h3.tit"),
+    ("date", "div.board-view > div.head > div.info > span.date"),
+    ("content", "div.board-view > div.body"),
+];
+
+/// The board's download endpoint, which `download('<boardid>', '<b_idx>', '<idx>')` links
+/// point at.
+const DOWNLOAD_PATH: &str = "/module/board/download.php";
+
+/// A post in a list page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListRow {
+    /// Pinned notices carry the `label` class.
+    pub notice: bool,
+    /// The post link as written in the page, usually relative.
+    pub href: String,
+    pub title: String,
+}
+
+/// The posts of a list page, skipping items without a titled link.
+pub fn parse_list(document: &Html) -> Vec<ListRow> {
+    document
+        .select(&LIST_ITEM)
+        .filter_map(|item| {
+            let notice = item.attr("class").is_some_and(|c| c.contains("label"));
+            let link = item.select(&LIST_LINK).next()?;
+            let href = link.value().attr("href")?.trim().to_string();
+            let title = text_of(link);
+            (!title.is_empty()).then_some(ListRow {
+                notice,
+                href,
+                title,
+            })
+        })
+        .collect()
+}
+
+/// Whether the list page links an enabled next page.
+pub fn has_next_page(document: &Html) -> bool {
+    document.select(&NEXT_PAGE).next().is_some()
+}
+
+/// The parts of a post page, each `None` when the page lacks it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoardView {
+    pub title: Option<String>,
+    /// The `<strong>` name inside `span.name`.
+    pub author: Option<String>,
+    /// The whole text of `span.name`, for boards that do not always mark the name up.
+    pub author_line: Option<String>,
+    /// The date as the page shows it, see [`parse_date`].
+    pub date: Option<String>,
+    /// The inner HTML of `div.body`.
+    pub content: Option<String>,
+    /// `div.body` itself, markup included.
+    pub content_element: Option<String>,
+    pub attachments: Vec<Attachment>,
+}
+
+/// Parses the post page at `url`, resolving attachment links against it.
+pub fn parse_view(document: &Html, url: &Url) -> BoardView {
+    let first = |selector: &Selector| document.select(selector).next();
+    let content = first(&CONTENT);
+    BoardView {
+        title: first(&TITLE).map(text_of).filter(|s| !s.is_empty()),
+        author: first(&AUTHOR)
+            .and_then(|name| name.select(&STRONG).next())
+            .map(text_of)
+            .filter(|s| !s.is_empty()),
+        author_line: first(&AUTHOR).map(text_of).filter(|s| !s.is_empty()),
+        date: first(&DATE).map(text_of).filter(|s| !s.is_empty()),
+        content: content
+            .map(|el| el.inner_html().trim().to_string())
+            .filter(|s| !s.is_empty()),
+        content_element: content.map(|el| el.html()),
+        attachments: document
+            .select(&ATTACHMENT)
+            .filter_map(|link| {
+                let url = attachment_url(
+                    link.value().attr("href").unwrap_or_default(),
+                    link.value().attr("onclick").unwrap_or_default(),
+                    url,
+                )?;
+                let name = text_of(link);
+                Some(Attachment {
+                    url,
+                    name: (!name.is_empty()).then_some(name),
+                    mime_type: None,
+                    metadata: None,
+                })
+            })
+            .collect(),
+    }
+}
+
+/// Parses a post page date such as `2025-05-12 14:44:58` or `2025-05-12` in KST.
+pub fn parse_date(date: &str) -> Option<OffsetDateTime> {
+    parse_kst(date)
+}
+
+/// The URL an attachment link downloads from, via its `download(...)` call or a direct
+/// `/uploaded/` link.
+pub fn attachment_url(href: &str, onclick: &str, base: &Url) -> Option<String> {
+    let href = href.trim();
+    let call = [href, onclick.trim()]
+        .into_iter()
+        .find_map(|script| script.split_once("download(").map(|(_, args)| args));
+    let Some(args) = call else {
+        return base.join(href).ok().map(String::from);
+    };
+    let args = args.split(')').next()?;
+    let params = args
+        .split(',')
+        .map(|s| s.trim().trim_matches(['\'', '"']))
+        .collect::<Vec<_>>();
+    let [board_id, b_idx, idx] = params[..] else {
+        return None;
+    };
+    let mut url = base.join(DOWNLOAD_PATH).ok()?;
+    url.query_pairs_mut()
+        .append_pair("boardid", board_id)
+        .append_pair("b_idx", b_idx)
+        .append_pair("idx", idx);
+    Some(url.into())
+}
+
+fn text_of(element: ElementRef<'_>) -> String {
+    element.text().collect::<String>().trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base() -> Url {
+        Url::parse("http://ee.ssu.ac.kr/sub/sub05_02.php?boardid=major&mode=view&idx=232").unwrap()
+    }
+
+    #[test]
+    fn test_download_call_in_href() {
+        assert_eq!(
+            attachment_url("javascript:download('major','3','232')", "", &base()).as_deref(),
+            Some("http://ee.ssu.ac.kr/module/board/download.php?boardid=major&b_idx=3&idx=232")
+        );
+        // Double quotes, spaces and a trailing semicolon are allowed
+        assert_eq!(
+            attachment_url(r#"javascript:download("major", "3", "232");"#, "", &base()).as_deref(),
+            Some("http://ee.ssu.ac.kr/module/board/download.php?boardid=major&b_idx=3&idx=232")
+        );
+    }
+
+    #[test]
+    fn test_download_call_in_onclick() {
+        assert_eq!(
+            attachment_url("#", "download('notice1','1','77'); return false;", &base()).as_deref(),
+            Some("http://ee.ssu.ac.kr/module/board/download.php?boardid=notice1&b_idx=1&idx=77")
+        );
+    }
+
+    #[test]
+    fn test_direct_upload_link() {
+        assert_eq!(
+            attachment_url("/uploaded/board/major/신청서.hwp", "", &base()).as_deref(),
+            Some("http://ee.ssu.ac.kr/uploaded/board/major/%EC%8B%A0%EC%B2%AD%EC%84%9C.hwp")
+        );
+    }
+
+    #[test]
+    fn test_malformed_download_call_is_skipped() {
+        assert_eq!(
+            attachment_url("javascript:download('major','3')", "", &base()),
+            None
+        );
+        assert_eq!(attachment_url("#", "download(", &base()), None);
+    }
+
+    #[test]
+    fn test_author_without_strong_is_only_in_author_line() {
+        let view = Html::parse_document(
+            r#"<div class="board-view"><div class="head">
+                <div class="info"><span class="name">관리자</span></div>
+            </div></div>"#,
+        );
+        let parsed = parse_view(&view, &base());
+        assert_eq!(parsed.author, None);
+        assert_eq!(parsed.author_line.as_deref(), Some("관리자"));
+    }
+
+    #[test]
+    fn test_parse_view_and_list() {
+        let view = Html::parse_document(
+            r#"<div class="board-view">
+                <div class="head">
+                    <h3 class="tit">2025학년도 전공 설명회 안내</h3>
+                    <div class="info"><span class="name">작성자 <strong>학과사무실</strong></span><span class="date">2025-05-12 14:44:58</span></div>
+                    <div class="files"><a href="javascript:download('major','3','232')">설명회 자료.pdf</a></div>
+                </div>
+                <div class="body"><p>전공 설명회를 개최합니다.</p></div>
+            </div>"#,
+        );
+        let parsed = parse_view(&view, &base());
+        assert_eq!(parsed.title.as_deref(), Some("2025학년도 전공 설명회 안내"));
+        assert_eq!(parsed.author.as_deref(), Some("학과사무실"));
+        assert_eq!(parsed.author_line.as_deref(), Some("작성자 학과사무실"));
+        assert_eq!(
+            parsed.date.as_deref().and_then(parse_date),
+            Some(time::macros::datetime!(2025-05-12 14:44:58 +9))
+        );
+        assert_eq!(
+            parsed.content.as_deref(),
+            Some("<p>전공 설명회를 개최합니다.</p>")
+        );
+        assert_eq!(
+            parsed.content_element.as_deref(),
+            Some(r#"<div class="body"><p>전공 설명회를 개최합니다.</p></div>"#)
+        );
+        assert_eq!(parsed.attachments.len(), 1);
+        assert_eq!(
+            parsed.attachments[0].name.as_deref(),
+            Some("설명회 자료.pdf")
+        );
+
+        let list = Html::parse_document(
+            r##"<div class="board-list2"><ul>
+                <li class="label"><div class="subject"><a href="?mode=view&idx=1">고정 공지</a></div></li>
+                <li><div class="subject"><a href="?mode=view&idx=2">일반 공지</a></div></li>
+                <li><div class="subject"><a href="?mode=view&idx=3"> </a></div></li>
+            </ul></div>
+            <div class="paginate"><a class="next disabled" href="#">다음</a></div>"##,
+        );
+        assert_eq!(
+            parse_list(&list),
+            [
+                ListRow {
+                    notice: true,
+                    href: "?mode=view&idx=1".to_string(),
+                    title: "고정 공지".to_string(),
+                },
+                ListRow {
+                    notice: false,
+                    href: "?mode=view&idx=2".to_string(),
+                    title: "일반 공지".to_string(),
+                },
+            ]
+        );
+        assert!(!has_next_page(&list));
+    }
+}
@@ -1,52 +1,56 @@
 // Content for plugins/ssufid_chemeng/src/lib.rs
 
-use futures::{StreamExt, stream::FuturesOrdered};
+use std::collections::HashSet;
+
+use futures::{
+    Stream, StreamExt, TryStreamExt, future,
+    stream::{self, FuturesOrdered},
+};
 use scraper::{Html, Selector};
 use thiserror::Error;
 use url::Url;
 
 use ssufid::{
-    core::{SsufidPlugin, SsufidPost, SsufidPostPlugin},
+    core::{SsufidPlugin, SsufidPost, SsufidPostPlugin, UrlKind},
     error::PluginError,
+    fetch::send,
+    html::parse_selector,
+    paginate::{Cursor, Paginator},
+    text::parse_author_kr,
 };
+use ssufid_boardview as boardview;
 use time::{Date, macros::format_description, macros::offset};
 
 // Selector guesses - these will likely need refinement
 struct Selectors {
     // List page selectors
     notice_row: Selector,
+    header_cell: Selector,
+    cell: Selector,
+    first_cell: Selector,
     row_link_title: Selector,
     row_author: Selector,
     row_date: Selector,
-    // Detail page selectors
-    post_title: Selector,
-    post_author_info: Selector, // Changed from post_author_date_info
-    post_date_info: Selector,   // New selector for date
-    post_content: Selector,
 }
 
 impl Selectors {
-    fn new() -> Self {
-        Self {
+    fn new() -> Result<Self, PluginError> {
+        let parse = parse_selector::<ChemEngPlugin>;
+        Ok(Self {
             // --- List page selectors (verified from previous attempt) ---
-            notice_row: Selector::parse("table tr").unwrap(),
-            row_link_title: Selector::parse("td:nth-child(2) > a").unwrap(),
-            row_author: Selector::parse("td:nth-child(3)").unwrap(),
-            row_date: Selector::parse("td:nth-child(4)").unwrap(),
-
-            // --- Detail page selectors (newly provided) ---
-            post_title: Selector::parse("div.board-view > div.head > h3.tit").unwrap(),
-            post_author_info: Selector::parse("div.board-view > div.head > div.info > span.name")
-                .unwrap(),
-            post_date_info: Selector::parse("div.board-view > div.head > div.info > span.date")
-                .unwrap(),
-            post_content: Selector::parse("div.board-view > div.body").unwrap(),
-        }
+            notice_row: parse("table tr")?,
+            header_cell: parse("th")?,
+            cell: parse("td")?,
+            first_cell: parse("td:first-child")?,
+            row_link_title: parse("td:nth-child(2) > a")?,
+            row_author: parse("td:nth-child(3)")?,
+            row_date: parse("td:nth-child(4)")?,
+        })
     }
 }
 
 #[derive(Debug)]
-struct ChemEngPostMetadata {
+pub struct ChemEngPostMetadata {
     id: String,
     url: String,
     title_on_list: String,
@@ -86,96 +90,124 @@ pub struct ChemEngPlugin {
 
 impl Default for ChemEngPlugin {
     fn default() -> Self {
-        Self::new()
+        Self::new().expect("Failed to initialize selectors")
     }
 }
 
 impl ChemEngPlugin {
-    const POSTS_PER_PAGE: u32 = 10;
+    /// Far beyond the board's actual length; anything larger is a caller bug, not a backfill.
+    const MAX_PAGE: u32 = 100_000;
+    /// Hard safety limit on the number of list pages a single crawl walks through.
+    const MAX_CRAWL_PAGES: u32 = 200;
+    /// Detail pages fetched concurrently while streaming.
+    const DETAIL_CONCURRENCY: usize = 4;
+    const FIRST_PAGE: Cursor = Cursor::Offset {
+        start: 0,
+        step: Self::POSTS_PER_PAGE,
+    };
     const DATE_FORMAT_PARSE: &'static [time::format_description::FormatItem<'static>] =
         format_description!("[year]-[month]-[day]");
 
-    pub fn new() -> Self {
-        Self {
-            selectors: Selectors::new(),
-            client: reqwest::Client::new(),
-        }
+    pub fn new() -> Result<Self, PluginError> {
+        Ok(Self {
+            selectors: Selectors::new()?,
+            client: ssufid::fetch::default_client(),
+        })
     }
 
     fn get_base_url_object(&self) -> Url {
         Url::parse(Self::BASE_URL).expect("BASE_URL is invalid")
     }
 
-    fn get_list_page_url(&self, page_num: u32) -> Url {
+    /// The cursor of the 1-based list page `page_num`, which the board addresses by `offset`.
+    fn list_cursor(page_num: u32) -> Result<Cursor, PluginError> {
+        Cursor::offset_of_page(page_num, Self::POSTS_PER_PAGE)
+            .filter(|_| page_num <= Self::MAX_PAGE)
+            .ok_or_else(|| {
+                PluginError::custom::<Self>(
+                    "PageOutOfRange".to_string(),
+                    format!("List page {page_num} is outside 1..={}", Self::MAX_PAGE),
+                )
+            })
+    }
+
+    fn get_list_page_url(&self, cursor: &Cursor) -> Url {
         let mut url = self.get_base_url_object().join("sub/sub03_01.php").unwrap();
-        if page_num > 1 {
-            let offset_val = (page_num - 1) * Self::POSTS_PER_PAGE;
+        url.query_pairs_mut().append_pair("boardid", "notice1");
+        if *cursor != Self::FIRST_PAGE {
             url.query_pairs_mut()
-                .append_pair("boardid", "notice1")
-                .append_pair("offset", &offset_val.to_string());
-        } else {
-            url.query_pairs_mut().append_pair("boardid", "notice1");
+                .append_pair("offset", &cursor.query_value());
         }
         url
     }
 
+    /// Fetches the list page at `cursor`, returning its posts and the cursor of the next page.
     async fn fetch_page_posts_metadata(
         &self,
-        page_num: u32,
-    ) -> Result<Vec<ChemEngPostMetadata>, PluginError> {
-        let page_url = self.get_list_page_url(page_num);
+        cursor: &Cursor,
+    ) -> Result<(Vec<ChemEngPostMetadata>, Option<Cursor>), PluginError> {
+        let page_url = self.get_list_page_url(cursor);
         tracing::debug!(message = "Fetching metadata list", page_url = %page_url);
 
-        let response_text = self
-            .client
-            .get(page_url.clone())
-            .send()
+        let response_text = send::<Self>(self.client.get(page_url.clone()))
             .await
             .map_err(|e| {
-                PluginError::request::<Self>(format!("Requesting list page {page_num}: {e}"))
+                PluginError::request::<Self>(format!("Requesting list page {page_url}: {e}"))
             })?
             .text()
             .await
             .map_err(|e| {
-                PluginError::parse::<Self>(format!("Parsing list page {page_num}: {e}"))
+                PluginError::parse::<Self>(format!("Parsing list page {page_url}: {e}"))
             })?;
 
-        let document = Html::parse_document(&response_text);
+        let metadata = self.parse_list_page(&Html::parse_document(&response_text))?;
+        Ok((metadata, cursor.advance(None)))
+    }
+
+    /// Extracts the posts of a list page, skipping the header row and rows without a date.
+    pub fn parse_list_page(
+        &self,
+        document: &Html,
+    ) -> Result<Vec<ChemEngPostMetadata>, PluginError> {
         let mut posts_metadata = Vec::new();
 
-        for element in document.select(&self.selectors.notice_row) {
-            if element
-                .select(&Selector::parse("th").unwrap())
-                .next()
-                .is_some()
-            {
+        for (row_index, element) in document.select(&self.selectors.notice_row).enumerate() {
+            if element.select(&self.selectors.header_cell).next().is_some() {
                 tracing::trace!("Skipping header row: {:?}", element.html());
                 continue;
             }
-            if element.select(&Selector::parse("td").unwrap()).count() < 4 {
+            if element.select(&self.selectors.cell).count() < 4 {
                 tracing::trace!("Skipping row, not enough cells: {:?}", element.html());
                 continue;
             }
 
-            let first_cell_text_raw = element
-                .select(&Selector::parse("td:first-child").unwrap())
-                .next();
+            let first_cell_text_raw = element.select(&self.selectors.first_cell).next();
             let first_cell_text = first_cell_text_raw.map_or(String::new(), |el| {
                 el.text().collect::<String>().trim().to_string()
             });
 
             let is_announcement = first_cell_text == "공지";
 
-            let link_element = element.select(&self.selectors.row_link_title).next();
+            let link_element = element
+                .select(&self.selectors.row_link_title)
+                .next()
+                .ok_or_else(|| {
+                    PluginError::structure::<Self>(
+                        "a title link (td:nth-child(2) > a)",
+                        format!("list row {row_index}"),
+                    )
+                })?;
 
-            let title_on_list = link_element
-                .map(|el| el.text().collect::<String>().trim().to_string())
+            let title_on_list = Some(link_element.text().collect::<String>().trim().to_string())
                 .filter(|t| !t.is_empty())
-                .ok_or(MetadataError::TitleNotFound)?; // Clippy: unnecessary_lazy_evaluations
+                .ok_or(MetadataError::TitleNotFound)?;
 
-            let relative_url_str = link_element
-                .and_then(|el| el.value().attr("href"))
-                .ok_or(MetadataError::UrlNotFound)?;
+            let relative_url_str = link_element.value().attr("href").ok_or_else(|| {
+                PluginError::structure::<Self>(
+                    "an href on the title link",
+                    format!("list row {row_index}"),
+                )
+            })?;
 
             let post_url_obj = self
                 .get_base_url_object()
@@ -252,10 +284,7 @@ impl ChemEngPlugin {
         post_metadata: ChemEngPostMetadata, // Take ownership
     ) -> Result<SsufidPost, PluginError> {
         tracing::debug!(message="Fetching post content", url=%post_metadata.url);
-        let response_text = self
-            .client
-            .get(&post_metadata.url)
-            .send()
+        let response_text = send::<Self>(self.client.get(&post_metadata.url))
             .await
             .map_err(|e| {
                 PluginError::request::<Self>(format!("Requesting post {}: {}", post_metadata.id, e))
@@ -266,35 +295,20 @@ impl ChemEngPlugin {
                 PluginError::parse::<Self>(format!("Parsing post {}: {}", post_metadata.id, e))
             })?;
 
-        let document = Html::parse_document(&response_text);
+        let post_url = Url::parse(&post_metadata.url).map_err(|e| {
+            PluginError::parse::<Self>(format!("Invalid post URL {}: {e}", post_metadata.url))
+        })?;
+        let view = boardview::parse_view(&Html::parse_document(&response_text), &post_url);
 
-        let title = document
-            .select(&self.selectors.post_title)
-            .next()
-            .map(|el| el.text().collect::<String>().trim().to_string())
-            .filter(|t| !t.is_empty())
+        let title = view
+            .title
             .unwrap_or_else(|| post_metadata.title_on_list.clone());
-
-        let author_from_page = document
-            .select(&self.selectors.post_author_info)
-            .next()
-            .map(|el| {
-                // Try to get <strong> text, then fall back to span's text
-                el.select(&Selector::parse("strong").unwrap())
-                    .next()
-                    .map_or_else(
-                        || el.text().collect::<String>().trim().to_string(),
-                        |strong_el| strong_el.text().collect::<String>().trim().to_string(),
-                    )
-            })
-            .filter(|s| !s.is_empty())
+        let author_from_page = view
+            .author
+            .or(view.author_line)
             .unwrap_or_else(|| post_metadata.author_on_list.clone());
-
-        let date_str_from_page = document
-            .select(&self.selectors.post_date_info)
-            .next()
-            .map(|el| el.text().collect::<String>().trim().to_string())
-            .filter(|s| !s.is_empty())
+        let date_str_from_page = view
+            .date
             .unwrap_or_else(|| post_metadata.date_str_on_list.clone());
 
         let created_at_date = Date::parse(&date_str_from_page, Self::DATE_FORMAT_PARSE)
@@ -316,38 +330,29 @@ impl ChemEngPlugin {
             })?;
         let created_at = created_at_date.midnight().assume_offset(offset!(+9));
 
-        let content = document
-            .select(&self.selectors.post_content)
-            .next()
-            .map(|el| el.html())
-            .unwrap_or_default();
-
         Ok(SsufidPost {
             id: post_metadata.id.clone(),
             url: post_metadata.url.clone(),
+            url_kind: UrlKind::Canonical,
             title,
+            author_detail: parse_author_kr(&author_from_page),
             author: Some(author_from_page),
             description: None,
             category: vec!["학부공지사항".to_string()],
             created_at,
-            content,
+            content: view.content_element.unwrap_or_default(),
             updated_at: None,
             thumbnail: None,
-            attachments: vec![],
+            attachments: view.attachments,
             metadata: None,
         })
     }
 
-    fn get_total_pages_from_list_html(&self, document: &Html) -> u32 {
-        // Attempt to parse "페이지정보 : X / Y" from the raw text of the page
-        // This is fragile and depends on the exact text format.
-        let body_text_nodes = document
-            .select(&Selector::parse("body").unwrap())
-            .next()
-            .map(|b| b.text().collect::<String>());
-        if let Some(body_text) = body_text_nodes
-            && let Some(page_info_start_idx) = body_text.find("페이지정보 :")
-        {
+    /// Parses "페이지정보 : X / Y" from the raw text of the page.
+    /// This is fragile and depends on the exact text format, so callers must cope with `None`.
+    fn get_total_pages_from_list_html(&self, document: &Html) -> Option<u32> {
+        let body_text = document.root_element().text().collect::<String>();
+        if let Some(page_info_start_idx) = body_text.find("페이지정보 :") {
             let relevant_part = &body_text[page_info_start_idx + "페이지정보 :".len()..];
             if let Some(slash_idx) = relevant_part.find('/') {
                 let after_slash = &relevant_part[slash_idx + 1..];
@@ -361,14 +366,14 @@ impl ChemEngPlugin {
                     && num_pages > 0
                 {
                     tracing::debug!("Parsed total pages from '페이지정보' text: {}", num_pages);
-                    return num_pages;
+                    return Some(num_pages);
                 }
             }
         }
         tracing::warn!(
-            "Could not parse total pages from '페이지정보' text. Using fallback of 70 based on observation."
+            "Could not parse total pages from '페이지정보' text. Paginating until an empty page."
         );
-        70 // Fallback based on initial observation "1 / 69" implies around 69-70 pages.
+        None
     }
 }
 
@@ -377,6 +382,15 @@ impl SsufidPlugin for ChemEngPlugin {
     const TITLE: &'static str = "숭실대학교 화학공학과";
     const DESCRIPTION: &'static str = "숭실대학교 화학공학과 홈페이지의 공지사항을 제공합니다.";
     const BASE_URL: &'static str = "http://chemeng.ssu.ac.kr";
+    const POSTS_PER_PAGE: u32 = 10;
+    // 2: `author_detail`, and attachments parsed by the shared board-view skin
+    const VERSION: u32 = 2;
+    const CRITICAL_SELECTORS: &'static [(&'static str, &'static str)] =
+        boardview::CRITICAL_SELECTORS;
+
+    fn http_client(&self) -> reqwest::Client {
+        self.client.clone()
+    }
 }
 
 impl SsufidPostPlugin for ChemEngPlugin {
@@ -387,11 +401,8 @@ impl SsufidPostPlugin for ChemEngPlugin {
 
         let total_pages_on_site = {
             // Create a new scope to ensure first_page_document is dropped
-            let first_page_url = self.get_list_page_url(1);
-            let first_page_response_text = self
-                .client
-                .get(first_page_url)
-                .send()
+            let first_page_url = self.get_list_page_url(&Self::FIRST_PAGE);
+            let first_page_response_text = send::<Self>(self.client.get(first_page_url))
                 .await
                 .map_err(|e| {
                     PluginError::request::<Self>(format!(
@@ -407,63 +418,38 @@ impl SsufidPostPlugin for ChemEngPlugin {
             self.get_total_pages_from_list_html(&first_page_document)
         };
 
-        tracing::info!("Estimated total pages on site: {}", total_pages_on_site);
-
-        let mut all_posts_metadata: Vec<ChemEngPostMetadata> = Vec::new();
-        let mut current_page = 1;
-
-        // The comment below about re-fetching page 1 is addressed by the loop structure.
-        // Page 1 metadata will be fetched by fetch_page_posts_metadata in the first iteration.
-
-        loop {
-            if all_posts_metadata.len() >= posts_limit as usize {
-                tracing::debug!(
-                    "Reached posts_limit for metadata ({}) at page {}",
-                    posts_limit,
-                    current_page
-                );
-                break;
-            }
-            // Stop if current_page exceeds known total pages or a safety limit
-            if current_page > total_pages_on_site || current_page > 200 {
-                // 200 as a hard safety limit
-                tracing::debug!(
-                    "Stopping metadata collection: current_page ({}) > total_pages_on_site ({}) or safety limit.",
-                    current_page,
-                    total_pages_on_site
-                );
-                break;
-            }
-
-            tracing::debug!("Fetching metadata for page {}", current_page);
-            let metadata_from_page = match self.fetch_page_posts_metadata(current_page).await {
-                Ok(metadata) => metadata,
-                Err(e) => {
+        tracing::info!("Estimated total pages on site: {:?}", total_pages_on_site);
+
+        // Never walk past the site's last page, the safety limit or the last addressable page
+        let max_pages = total_pages_on_site
+            .unwrap_or(Self::MAX_CRAWL_PAGES)
+            .min(Self::MAX_CRAWL_PAGES)
+            .min(Self::MAX_PAGE);
+        let mut paginator = Paginator::new(posts_limit).max_pages(max_pages);
+        while let Some(page) = paginator.next_page() {
+            tracing::debug!("Fetching metadata for page {}", page);
+            let (metadata_from_page, next) = self
+                .fetch_page_posts_metadata(&Self::list_cursor(page)?)
+                .await
+                .inspect_err(|e| {
                     tracing::error!(
                         "Failed to fetch metadata from page {}: {:?}. Stopping crawl.",
-                        current_page,
+                        page,
                         e
                     );
-                    // Depending on severity, you might choose to return partial results or the error.
-                    // For now, let's stop and return the error to indicate an issue.
-                    return Err(e);
-                }
-            };
-
-            // If a page is empty (and it's not the first page trying to determine total pages), assume end of posts
-            if metadata_from_page.is_empty() && current_page > 1 {
-                tracing::debug!(
-                    "No more posts found on page {}. Stopping metadata collection.",
-                    current_page
-                );
-                break;
+                })?;
+            paginator.push_page(metadata_from_page, |m| m.id.clone());
+            if next.is_none() {
+                paginator.stop();
             }
-
-            all_posts_metadata.extend(metadata_from_page);
-            current_page += 1;
         }
+        tracing::debug!(
+            "Stopped metadata collection after {} pages (limit {})",
+            paginator.pages_fetched(),
+            max_pages
+        );
+        let all_posts_metadata = paginator.into_items();
 
-        all_posts_metadata.truncate(posts_limit as usize);
         tracing::info!(
             "Collected {} metadata items after truncation to limit {}.",
             all_posts_metadata.len(),
@@ -492,11 +478,52 @@ impl SsufidPostPlugin for ChemEngPlugin {
         tracing::info!("Successfully fetched {} full posts.", fetched_posts.len());
         Ok(fetched_posts)
     }
+
+    fn crawl_stream(
+        &self,
+        posts_limit: u32,
+    ) -> impl Stream<Item = Result<SsufidPost, PluginError>> + Send {
+        // Pinned notices repeat on every page but count once, as with `Paginator`
+        let mut seen_ids = HashSet::new();
+        stream::try_unfold(Some((Self::FIRST_PAGE, 1)), move |state| async move {
+            let Some((cursor, page)) = state else {
+                return Ok::<_, PluginError>(None);
+            };
+            tracing::debug!("Fetching metadata for page {}", page);
+            let (metadata_from_page, next) = self.fetch_page_posts_metadata(&cursor).await?;
+            // An empty page means we are past the last post
+            let next = next
+                .filter(|_| !metadata_from_page.is_empty() && page < Self::MAX_CRAWL_PAGES)
+                .map(|cursor| (cursor, page + 1));
+            Ok(Some((
+                stream::iter(metadata_from_page.into_iter().map(Ok)),
+                next,
+            )))
+        })
+        .try_flatten()
+        .try_filter(move |metadata_item| future::ready(seen_ids.insert(metadata_item.id.clone())))
+        .take(posts_limit as usize)
+        .map_ok(move |metadata_item| async move {
+            match self.fetch_post(metadata_item).await {
+                Ok(post) => Ok(Some(post)),
+                Err(e) => {
+                    tracing::warn!(
+                        "A post failed to fetch/parse fully: {:?}. It will be skipped.",
+                        e
+                    );
+                    Ok(None)
+                }
+            }
+        })
+        .try_buffered(Self::DETAIL_CONCURRENCY)
+        .try_filter_map(future::ok)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ssufid::error::PluginErrorKind;
     use time::OffsetDateTime; // Added for test_fetch_actual_post_content_and_details
     use tracing_subscriber::EnvFilter;
 
@@ -509,14 +536,99 @@ mod tests {
             .try_init();
     }
 
+    #[test]
+    fn test_parse_list_page_with_50_rows() {
+        let plugin = ChemEngPlugin::new().unwrap();
+        let rows = (1..=50)
+            .map(|i| {
+                format!(
+                    r#"<tr><td>{i}</td><td><a href="/sub/sub03_01.php?boardid=notice1&idx={i}">공지 {i}</a></td><td>관리자</td><td>2025-03-02</td></tr>"#
+                )
+            })
+            .collect::<String>();
+        let document = Html::parse_document(&format!(
+            "<table><tr><th>번호</th><th>제목</th><th>작성자</th><th>작성일</th></tr>{rows}</table>"
+        ));
+
+        let metadata = plugin.parse_list_page(&document).unwrap();
+        assert_eq!(metadata.len(), 50);
+        assert_eq!(metadata[0].id, "1");
+        assert_eq!(metadata[49].title_on_list, "공지 50");
+    }
+
+    #[test]
+    fn test_row_without_title_link_is_a_structure_error() {
+        let plugin = ChemEngPlugin::new().unwrap();
+        let document = Html::parse_document(
+            "<table><tr><th>번호</th><th>제목</th><th>작성자</th><th>작성일</th></tr>\
+             <tr><td>1</td><td>공지 1</td><td>관리자</td><td>2025-03-02</td></tr></table>",
+        );
+
+        let error = plugin.parse_list_page(&document).unwrap_err();
+        assert_eq!(error.kind(), &PluginErrorKind::Structure);
+        assert_eq!(
+            error.message(),
+            "expected a title link (td:nth-child(2) > a) in list row 1"
+        );
+    }
+
+    #[test]
+    fn test_list_page_offset_is_capped() {
+        let plugin = ChemEngPlugin::new().unwrap();
+        assert_eq!(
+            ChemEngPlugin::list_cursor(1).unwrap(),
+            ChemEngPlugin::FIRST_PAGE
+        );
+        let third = ChemEngPlugin::list_cursor(3).unwrap();
+        assert_eq!(third.query_value(), "20");
+        assert!(
+            plugin
+                .get_list_page_url(&third)
+                .as_str()
+                .ends_with("boardid=notice1&offset=20")
+        );
+        assert_eq!(
+            ChemEngPlugin::list_cursor(ChemEngPlugin::MAX_PAGE)
+                .unwrap()
+                .query_value(),
+            ((ChemEngPlugin::MAX_PAGE - 1) * ChemEngPlugin::POSTS_PER_PAGE).to_string()
+        );
+
+        for page_num in [0, ChemEngPlugin::MAX_PAGE + 1, u32::MAX / 2, u32::MAX] {
+            let error = ChemEngPlugin::list_cursor(page_num).unwrap_err();
+            assert_eq!(
+                error.kind(),
+                &PluginErrorKind::Custom("PageOutOfRange".into())
+            );
+        }
+    }
+
+    #[test]
+    fn test_total_pages_missing_is_none() {
+        let plugin = ChemEngPlugin::new().unwrap();
+        let document = Html::parse_document("<table></table>");
+        assert_eq!(plugin.get_total_pages_from_list_html(&document), None);
+
+        let document = Html::parse_document("<p>페이지정보 : 1 / 69</p>");
+        assert_eq!(plugin.get_total_pages_from_list_html(&document), Some(69));
+    }
+
+    #[test]
+    fn test_bad_selector_is_an_error() {
+        let error = parse_selector::<ChemEngPlugin>("td:nth-child(").unwrap_err();
+        assert_eq!(error.plugin(), ChemEngPlugin::IDENTIFIER);
+    }
+
     #[tokio::test]
     async fn test_fetch_page1_metadata_successfully_and_parses_data() {
         setup_tracing_subscriber_for_tests();
-        let plugin = ChemEngPlugin::new();
-        let metadata_result = plugin.fetch_page_posts_metadata(1).await;
+        let plugin = ChemEngPlugin::new().unwrap();
+        let metadata_result = plugin
+            .fetch_page_posts_metadata(&ChemEngPlugin::FIRST_PAGE)
+            .await;
 
         match metadata_result {
-            Ok(metadata) => {
+            Ok((metadata, _)) => {
                 assert!(
                     !metadata.is_empty(),
                     "Should retrieve some metadata from page 1. If this fails, list page selectors are likely incorrect or the page structure has significantly changed."
@@ -559,9 +671,9 @@ mod tests {
     #[tokio::test]
     async fn test_fetch_actual_post_content_and_details() {
         setup_tracing_subscriber_for_tests();
-        let plugin = ChemEngPlugin::new();
+        let plugin = ChemEngPlugin::new().unwrap();
 
-        let metadata_list = plugin.fetch_page_posts_metadata(1).await
+        let (metadata_list, _) = plugin.fetch_page_posts_metadata(&ChemEngPlugin::FIRST_PAGE).await
             .expect("Prerequisite for post content test: Failed to get metadata from page 1. Check list page selectors.");
         assert!(
             !metadata_list.is_empty(),
@@ -614,18 +726,12 @@ mod tests {
                 );
                 assert!(
                     !post.content.is_empty(),
-                    "Post content should not be empty. If empty, detail page content selector ('{:?}') might be wrong or content is indeed empty.",
-                    plugin.selectors.post_content
+                    "Post content should not be empty. If empty, the board-view content selector might be wrong or content is indeed empty."
                 );
             }
             Err(e) => {
                 panic!(
-                    "Failed to fetch the sample post (ID: {}): {:?}. Check detail page selectors ('title:{:?}', 'author_date_info:{:?}', 'content:{:?}'), network, or if the specific post structure is unusual.",
-                    sample_metadata_id_for_error,
-                    e,
-                    plugin.selectors.post_title,
-                    plugin.selectors.post_author_info, // Corrected to author_info
-                    plugin.selectors.post_content,
+                    "Failed to fetch the sample post (ID: {sample_metadata_id_for_error}): {e:?}. Check the board-view selectors, network, or if the specific post structure is unusual.",
                 );
             }
         }
@@ -634,8 +740,8 @@ mod tests {
     #[tokio::test]
     async fn test_get_total_pages_from_live_page() {
         setup_tracing_subscriber_for_tests();
-        let plugin = ChemEngPlugin::new();
-        let list_page_url = plugin.get_list_page_url(1);
+        let plugin = ChemEngPlugin::new().unwrap();
+        let list_page_url = plugin.get_list_page_url(&ChemEngPlugin::FIRST_PAGE);
         let response_text = plugin
             .client
             .get(list_page_url)
@@ -647,7 +753,9 @@ mod tests {
             .expect("Text parsing error for page 1 total_pages test");
         let document = Html::parse_document(&response_text);
 
-        let total_pages = plugin.get_total_pages_from_list_html(&document);
+        let total_pages = plugin
+            .get_total_pages_from_list_html(&document)
+            .expect("'페이지정보' text not found");
         tracing::info!(
             "Total pages reported by get_total_pages_from_list_html: {}",
             total_pages
@@ -666,7 +774,7 @@ mod tests {
     #[tokio::test]
     async fn test_crawl_limited_to_3_posts() {
         setup_tracing_subscriber_for_tests();
-        let plugin = ChemEngPlugin::new();
+        let plugin = ChemEngPlugin::new().unwrap();
         let limit = 3u32;
         let posts_result = plugin.crawl(limit).await;
 
@@ -713,7 +821,7 @@ mod tests {
     #[tokio::test]
     async fn test_crawl_with_limit_0_returns_empty_vec() {
         setup_tracing_subscriber_for_tests();
-        let plugin = ChemEngPlugin::new();
+        let plugin = ChemEngPlugin::new().unwrap();
         let limit = 0u32;
         let posts_result = plugin.crawl(limit).await;
         match posts_result {
@@ -729,4 +837,113 @@ mod tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn test_crawl_stops_at_total_pages() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path, query_param, query_param_is_missing},
+        };
+
+        let server = MockServer::start().await;
+        // Every page is full, so only the page count in "페이지정보" ends the crawl
+        let list_page = |first_id: u32| {
+            let rows = (first_id..first_id + ChemEngPlugin::POSTS_PER_PAGE)
+                .map(|i| {
+                    format!(
+                        r#"<tr><td>{i}</td><td><a href="/sub/sub03_01.php?boardid=notice1&idx={i}">공지 {i}</a></td><td>관리자</td><td>2025-03-02</td></tr>"#
+                    )
+                })
+                .collect::<String>();
+            ResponseTemplate::new(200).set_body_string(format!(
+                "<p>페이지정보 : 1 / 2</p><table><tr><th>번호</th><th>제목</th><th>작성자</th><th>작성일</th></tr>{rows}</table>"
+            ))
+        };
+        Mock::given(method("GET"))
+            .and(path("/sub/sub03_01.php"))
+            .and(query_param_is_missing("offset"))
+            .and(query_param_is_missing("idx"))
+            .respond_with(list_page(1))
+            .mount(&server)
+            .await;
+        for (page, offset) in [(2, "10"), (3, "20")] {
+            Mock::given(method("GET"))
+                .and(path("/sub/sub03_01.php"))
+                .and(query_param("offset", offset))
+                .respond_with(list_page((page - 1) * ChemEngPlugin::POSTS_PER_PAGE + 1))
+                .expect(if page == 2 { 1 } else { 0 })
+                .mount(&server)
+                .await;
+        }
+
+        let plugin = ChemEngPlugin::new().unwrap();
+        ssufid::fetch::with_origin_override(ChemEngPlugin::BASE_URL, &server.uri(), async {
+            plugin.crawl(100).await.unwrap();
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_crawl_stream_counts_repeated_pinned_post_once() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path, query_param, query_param_is_missing},
+        };
+
+        let server = MockServer::start().await;
+        let list_page = |ids: &[u32]| {
+            let rows = std::iter::once(
+                r#"<tr><td>공지</td><td><a href="/sub/sub03_01.php?boardid=notice1&idx=1">학사 일정 안내</a></td><td>관리자</td><td>2025-02-03</td></tr>"#.to_string(),
+            )
+            .chain(ids.iter().map(|i| {
+                format!(
+                    r#"<tr><td>{i}</td><td><a href="/sub/sub03_01.php?boardid=notice1&idx={i}">공지 {i}</a></td><td>관리자</td><td>2025-03-02</td></tr>"#
+                )
+            }))
+            .collect::<String>();
+            ResponseTemplate::new(200).set_body_string(format!(
+                "<table><tr><th>번호</th><th>제목</th><th>작성자</th><th>작성일</th></tr>{rows}</table>"
+            ))
+        };
+        Mock::given(method("GET"))
+            .and(path("/sub/sub03_01.php"))
+            .and(query_param_is_missing("offset"))
+            .and(query_param_is_missing("idx"))
+            .respond_with(list_page(&[10, 9]))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/sub/sub03_01.php"))
+            .and(query_param("offset", "10"))
+            .respond_with(list_page(&[8, 7]))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/sub/sub03_01.php"))
+            .and(query_param("offset", "20"))
+            .respond_with(list_page(&[]))
+            .mount(&server)
+            .await;
+        // Detail pages fall back to the list's title and date
+        Mock::given(method("GET"))
+            .and(path("/sub/sub03_01.php"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<div></div>"))
+            .mount(&server)
+            .await;
+
+        let plugin = ChemEngPlugin::new().unwrap();
+        let (streamed, crawled) =
+            ssufid::fetch::with_origin_override(ChemEngPlugin::BASE_URL, &server.uri(), async {
+                let streamed = plugin
+                    .crawl_stream(4)
+                    .try_collect::<Vec<_>>()
+                    .await
+                    .unwrap();
+                (streamed, plugin.crawl(4).await.unwrap())
+            })
+            .await;
+        let ids = |posts: &[SsufidPost]| posts.iter().map(|p| p.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&streamed), ["1", "10", "9", "8"]);
+        assert_eq!(ids(&streamed), ids(&crawled));
+    }
 }
@@ -1,6 +1,7 @@
 use std::sync::LazyLock;
 
 use scraper::Selector;
+use ssufid::text::clean_author;
 use url::Url;
 
 use crate::common::gnuboard::GnuboardMetadataError;
@@ -59,7 +60,7 @@ impl GnuboardMetadataResolver for ItGnuboardMetadataResolver {
         let author = element
             .select(&AUTHOR_SELECTOR)
             .next()
-            .map(|span| span.text().collect::<String>().trim().to_string());
+            .and_then(|span| clean_author(&span.text().collect::<String>()));
 
         Ok(GnuboardMetadata {
             category,
@@ -1,5 +1,6 @@
-//! IT대학의 컴퓨터학부, 소프트웨어학부, 정보보호학과에
-//! 해당하는 플러그인에서 사용되는 공통 모듈입니다.
+//! 기본 스킨의 그누보드(GNUBoard) 게시판을 쓰는 학과 사이트의 공통 모듈입니다.
+//! 새 사이트는 `sites` 모듈에서 [`gnuboard_plugin!`](crate::gnuboard_plugin)을 호출해 추가하고,
+//! 목록 행의 구성이 다르면 [`GnuboardMetadataResolver`]를 함께 넘깁니다(`sites/eco.rs` 참고).
 pub(crate) mod metadata;
 
 use futures::{TryStreamExt, stream::FuturesOrdered};
@@ -13,14 +14,44 @@ use time::{
 use scraper::Element;
 use ssufid::{
     PluginError,
-    core::{Attachment, SsufidPlugin, SsufidPost},
+    core::{Attachment, CrawlOptions, SsufidPlugin, SsufidPost, UrlKind},
+    fetch::{default_client, fetch_page},
+    html::{EmptyState, ListPage, classify_list_page},
+    paginate::Paginator,
 };
 
 use crate::common::gnuboard::metadata::{GnuboardMetadata, GnuboardMetadataResolver};
 
+const LIST_TABLE_SELECTOR: &str = "#bo_list table > tbody";
+const TITLE_SELECTOR: &str = "#bo_v_title > span.bo_v_tit";
+const CONTENT_SELECTOR: &str = "#bo_v_con";
+const CREATED_AT_SELECTOR: &str = "#bo_v_info .if_date";
+const CATEGORY_SELECTOR: &str = "#bo_cate a";
+
+/// 저장하는 글 URL에 남기는 쿼리 파라미터입니다.
+const POST_URL_PARAMS: &[&str] = &["bo_table", "wr_id"];
+
+// 2: authors cleaned with `clean_author`, post URLs reduced to `POST_URL_PARAMS`
+// 3: attachment sizes and upload dates
+pub(crate) const GNUBOARD_VERSION: u32 = 3;
+
+pub(crate) const GNUBOARD_CRITICAL_SELECTORS: &[(&str, &str)] = &[
+    ("list_item", "#bo_list table > tbody > tr"),
+    ("title", TITLE_SELECTOR),
+    ("date", CREATED_AT_SELECTOR),
+    ("content", CONTENT_SELECTOR),
+];
+
+/// 게시물이 없는 게시판은 목록 테이블에 `td.empty_table` 행 하나만 표시합니다.
+pub(crate) const GNUBOARD_EMPTY_STATE: EmptyState = EmptyState {
+    selector: Some("#bo_list td.empty_table"),
+    texts: EmptyState::DEFAULT_TEXTS,
+};
+
 struct GnuboardSelectors {
     // in the notice list page
     table: Selector,
+    category: Selector,
     // in the content page
     title: Selector,
     thumbnail: Selector,
@@ -32,12 +63,13 @@ struct GnuboardSelectors {
 impl GnuboardSelectors {
     fn new() -> Self {
         Self {
-            table: Selector::parse("#bo_list table > tbody").unwrap(),
-            title: Selector::parse("#bo_v_title > span.bo_v_tit").unwrap(),
+            table: Selector::parse(LIST_TABLE_SELECTOR).unwrap(),
+            category: Selector::parse(CATEGORY_SELECTOR).unwrap(),
+            title: Selector::parse(TITLE_SELECTOR).unwrap(),
             thumbnail: Selector::parse("#bo_v_con img").unwrap(),
-            content: Selector::parse("#bo_v_con").unwrap(),
+            content: Selector::parse(CONTENT_SELECTOR).unwrap(),
             attachments: Selector::parse("#bo_v_file > ul > li > a").unwrap(),
-            created_at: Selector::parse("#bo_v_info .if_date").unwrap(),
+            created_at: Selector::parse(CREATED_AT_SELECTOR).unwrap(),
         }
     }
 }
@@ -54,6 +86,7 @@ pub(crate) enum GnuboardMetadataError {
 
 pub(crate) struct GnuboardCrawler<T: SsufidPlugin, R: GnuboardMetadataResolver> {
     selectors: GnuboardSelectors,
+    client: reqwest::Client,
     _marker: std::marker::PhantomData<(T, R)>,
 }
 
@@ -65,63 +98,119 @@ where
     pub(crate) fn new() -> Self {
         Self {
             selectors: GnuboardSelectors::new(),
+            client: default_client(),
             _marker: std::marker::PhantomData,
         }
     }
 
     pub(crate) async fn crawl(&self, posts_limit: u32) -> Result<Vec<SsufidPost>, PluginError> {
-        let metadata_list = self.fetch_metadata_list(posts_limit).await?;
+        self.crawl_with_options(&CrawlOptions::new(posts_limit))
+            .await
+    }
+
+    pub(crate) async fn crawl_with_options(
+        &self,
+        options: &CrawlOptions,
+    ) -> Result<Vec<SsufidPost>, PluginError> {
+        let now = options.current_time();
+        let metadata_list = self
+            .fetch_metadata_list(options.posts_limit, options.empty_page_tolerance)
+            .await?;
         tracing::info!("fetch {} post contents", metadata_list.len());
-        metadata_list
+        let posts = metadata_list
             .iter()
             .map(|metadata| self.fetch_post(metadata))
             .collect::<FuturesOrdered<_>>()
             .try_collect::<Vec<_>>()
-            .await
+            .await?;
+        Ok(options.filter(posts, now))
+    }
+
+    /// 1 페이지 첫 게시글의 ID를 상세 페이지 요청 없이 반환합니다.
+    pub(crate) async fn latest_post_id(&self) -> Result<Option<String>, PluginError> {
+        Ok(self
+            .fetch_metadata(1)
+            .await?
+            .into_items()
+            .into_iter()
+            .next()
+            .map(|metadata| metadata.id))
+    }
+
+    /// 1 페이지 상단의 분류 탭(`#bo_cate`)에 있는 분류 이름을 순서대로 반환합니다.
+    pub(crate) async fn categories(&self) -> Result<Vec<String>, PluginError> {
+        let html = fetch_page::<T>(&self.client, T::BASE_URL).await?.text;
+
+        Ok(self.parse_categories(&Html::parse_document(&html)))
+    }
+
+    /// 분류 탭의 링크 중 `sca` 값이 있는 것만 분류로 봅니다.
+    /// `sca`가 비어 있는 "전체" 탭은 제외하며, 분류를 쓰지 않는 게시판은 빈 목록입니다.
+    fn parse_categories(&self, document: &Html) -> Vec<String> {
+        let base = url::Url::parse(T::BASE_URL).ok();
+        let mut categories: Vec<String> = vec![];
+        for link in document.select(&self.selectors.category) {
+            let has_category = link
+                .value()
+                .attr("href")
+                .and_then(|href| match &base {
+                    Some(base) => base.join(href).ok(),
+                    None => url::Url::parse(href).ok(),
+                })
+                .is_some_and(|url| {
+                    url.query_pairs()
+                        .any(|(key, value)| key == "sca" && !value.trim().is_empty())
+                });
+            // The selected tab also shows `<span class="sound_only">열린 분류</span>`.
+            let name = link
+                .text()
+                .filter(|text| text.trim() != "열린 분류")
+                .collect::<String>()
+                .trim()
+                .to_string();
+            if has_category && !name.is_empty() && !categories.contains(&name) {
+                categories.push(name);
+            }
+        }
+        categories
     }
 
     /// 1 페이지부터 순서대로 최대 `posts_limit`개의 메타데이터를 반환합니다.
     async fn fetch_metadata_list(
         &self,
         posts_limit: u32,
+        empty_page_tolerance: u32,
     ) -> Result<Vec<GnuboardMetadata>, PluginError> {
-        let mut remain = posts_limit as usize;
-        let mut page = 1;
-        let mut metadata_list: Vec<GnuboardMetadata> = vec![];
-
-        while remain > 0 {
+        // Some skins repeat notices on every page, so count distinct post ids.
+        let mut paginator = Paginator::new(posts_limit).empty_page_tolerance(empty_page_tolerance);
+        while let Some(page) = paginator.next_page() {
             tracing::info!(page);
-            let mut metadata = self
-                .fetch_metadata(page)
-                .await?
-                .into_iter()
-                .take(remain)
-                .collect::<Vec<GnuboardMetadata>>();
-
-            if metadata.is_empty() {
-                break;
-            }
-
-            remain -= metadata.len();
-            metadata_list.append(&mut metadata);
-            page += 1;
+            let metadata = self.fetch_metadata(page).await?.into_items();
+            paginator.push_page(metadata, |m| m.id.clone());
         }
 
-        Ok(metadata_list)
+        Ok(paginator.into_items())
     }
 
     /// `page` 페이지의 메타데이터 리스트를 반환합니다.
-    async fn fetch_metadata(&self, page: u32) -> Result<Vec<GnuboardMetadata>, PluginError> {
+    async fn fetch_metadata(&self, page: u32) -> Result<ListPage<GnuboardMetadata>, PluginError> {
         let page_url = format!("{}&page={}", T::BASE_URL, page);
 
-        let html = reqwest::get(page_url)
-            .await
-            .map_err(|e| PluginError::request::<T>(e.to_string()))?
-            .text()
-            .await
-            .map_err(|e| PluginError::parse::<T>(e.to_string()))?;
+        let html = fetch_page::<T>(&self.client, &page_url).await?.text;
 
-        let document = Html::parse_document(&html);
+        self.parse_metadata(&Html::parse_document(&html), &page_url)
+    }
+
+    /// 목록 페이지에서 메타데이터를 추출합니다.
+    /// 게시물이 없다는 안내만 있는 페이지는 [`ListPage::Empty`]로 구분합니다.
+    fn parse_metadata(
+        &self,
+        document: &Html,
+        page_url: &str,
+    ) -> Result<ListPage<GnuboardMetadata>, PluginError> {
+        if T::EMPTY_STATE.matches(document) {
+            return Ok(ListPage::Empty);
+        }
 
         let notice_list =
             document
@@ -143,17 +232,35 @@ where
             })
             .collect::<Vec<GnuboardMetadata>>();
 
-        Ok(posts_metadata)
+        classify_list_page::<T, _>(document, posts_metadata, page_url)
+    }
+
+    /// 게시글의 첨부파일 목록을 반환합니다. 목록 항목에 표시된 파일 크기와 등록일
+    /// (`(374.8K)`, `DATE : 2025-06-04 11:58:11`)도 함께 저장합니다.
+    fn parse_attachments(&self, document: &Html) -> Vec<Attachment> {
+        document
+            .select(&self.selectors.attachments)
+            .map(|a| {
+                let attachment = Attachment {
+                    url: a.value().attr("href").unwrap_or_default().to_string(),
+                    name: a
+                        .first_element_child()
+                        .map(|strong| strong.text().collect::<String>()),
+                    mime_type: None,
+                    metadata: None,
+                };
+                match a.parent_element() {
+                    Some(li) => attachment.with_listed_details(&li.text().collect::<String>()),
+                    None => attachment,
+                }
+            })
+            .collect()
     }
 
     /// `metadata`에 해당하는 게시글의 내용을 크롤링하여 반환합니다.
     async fn fetch_post(&self, metadata: &GnuboardMetadata) -> Result<SsufidPost, PluginError> {
-        let html = reqwest::get(&metadata.url)
-            .await
-            .map_err(|e| PluginError::request::<T>(e.to_string()))?
-            .text()
-            .await
-            .map_err(|e| PluginError::parse::<T>(e.to_string()))?;
+        let page = fetch_page::<T>(&self.client, &metadata.url).await?;
+        let html = page.text;
 
         let document = Html::parse_document(&html);
 
@@ -161,10 +268,10 @@ where
             .select(&self.selectors.title)
             .next()
             .map(|span| span.text().collect::<String>().trim().to_string())
-            .ok_or(PluginError::parse::<T>(format!(
-                "Title element not found: URL {}",
-                metadata.url
-            )))?;
+            .ok_or_else(|| {
+                PluginError::parse::<T>(format!("Title element not found: URL {}", metadata.url))
+                    .with_snippet(&self.selectors.title, &html)
+            })?;
 
         let thumbnail = document
             .select(&self.selectors.thumbnail)
@@ -174,34 +281,28 @@ where
         let content = document
             .select(&self.selectors.content)
             .next()
-            .ok_or(PluginError::parse::<T>(format!(
-                "Content element not found: URL {}",
-                metadata.url
-            )))?
+            .ok_or_else(|| {
+                PluginError::parse::<T>(format!("Content element not found: URL {}", metadata.url))
+                    .with_snippet(&self.selectors.content, &html)
+            })?
             .child_elements()
             .map(|p| p.html())
             .collect::<Vec<String>>()
             .join("\n");
 
-        let attachments = document
-            .select(&self.selectors.attachments)
-            .map(|a| Attachment {
-                url: a.value().attr("href").unwrap_or_default().to_string(),
-                name: a
-                    .first_element_child()
-                    .map(|strong| strong.text().collect::<String>()),
-                mime_type: None,
-            })
-            .collect();
+        let attachments = self.parse_attachments(&document);
 
         let created_at_str = document
             .select(&self.selectors.created_at)
             .next()
             .and_then(|el| el.text().last())
-            .ok_or(PluginError::parse::<T>(format!(
-                "Created date element not found: URL {}",
-                metadata.url
-            )))?
+            .ok_or_else(|| {
+                PluginError::parse::<T>(format!(
+                    "Created date element not found: URL {}",
+                    metadata.url
+                ))
+                .with_snippet(&self.selectors.created_at, &html)
+            })?
             .trim();
         const DATE_FORMAT: &[BorrowedFormatItem<'_>] =
             format_description!("[year]-[month]-[day] [hour]:[minute]");
@@ -215,9 +316,10 @@ where
                 })?
                 .assume_offset(offset!(+9));
 
-        Ok(SsufidPost {
+        let mut post = SsufidPost {
             id: metadata.id.clone(),
-            url: metadata.url.clone(),
+            url: ssufid::url::canonicalize(&metadata.url, POST_URL_PARAMS),
+            url_kind: UrlKind::Canonical,
             author: metadata.author.clone(),
             title,
             description: None,
@@ -228,7 +330,12 @@ where
             content,
             attachments,
             metadata: None,
-        })
+            author_detail: None,
+        };
+        if page.stripped {
+            post.mark_content_stripped();
+        }
+        Ok(post)
     }
 }
 
@@ -245,7 +352,7 @@ mod tests {
             GnuboardCrawler::new();
 
         // 1 페이지의 게시글 메타데이터 목록 가져오기
-        let metadata_list = crawler.fetch_metadata(1).await.unwrap();
+        let metadata_list = crawler.fetch_metadata(1).await.unwrap().into_items();
         assert!(!metadata_list.is_empty());
 
         for metadata in &metadata_list {
@@ -266,7 +373,7 @@ mod tests {
             GnuboardCrawler::new();
 
         // 1 페이지의 게시글 메타데이터 목록 가져오기
-        let metadata_list = crawler.fetch_metadata(1).await.unwrap();
+        let metadata_list = crawler.fetch_metadata(1).await.unwrap().into_items();
         assert!(!metadata_list.is_empty());
 
         let first_metadata = &metadata_list[0];
@@ -281,7 +388,85 @@ mod tests {
         let crawler: GnuboardCrawler<CseBachelorPlugin, ItGnuboardMetadataResolver> =
             GnuboardCrawler::new();
 
-        let metadata_list = crawler.fetch_metadata_list(posts_limit).await.unwrap();
+        let metadata_list = crawler.fetch_metadata_list(posts_limit, 1).await.unwrap();
         assert_eq!(metadata_list.len(), posts_limit as usize);
     }
+
+    #[test]
+    fn test_crawler_parse_categories() {
+        let crawler: GnuboardCrawler<CseBachelorPlugin, ItGnuboardMetadataResolver> =
+            GnuboardCrawler::new();
+
+        let document = Html::parse_document(
+            r#"<nav id="bo_cate"><h2>학사 공지사항 카테고리</h2><ul id="bo_cate_ul">
+                <li><a href="https://cse.ssu.ac.kr/bbs/board.php?bo_table=notice">전체</a></li>
+                <li><a href="https://cse.ssu.ac.kr/bbs/board.php?bo_table=notice&amp;sca=%ED%95%99%EC%82%AC" id="bo_cate_on"><span class="sound_only">열린 분류 </span>학사</a></li>
+                <li><a href="/bbs/board.php?bo_table=notice&amp;sca=%EC%9E%A5%ED%95%99">장학</a></li>
+                <li><a href="./board.php?bo_table=notice&amp;sca=%EA%B3%B5%EB%AA%A8%EC%A0%84">공모전</a></li>
+            </ul></nav>"#,
+        );
+        assert_eq!(
+            crawler.parse_categories(&document),
+            ["학사", "장학", "공모전"]
+        );
+
+        // A board without categories
+        let document =
+            Html::parse_document(r#"<div id="bo_list"><table><tbody></tbody></table></div>"#);
+        assert!(crawler.parse_categories(&document).is_empty());
+    }
+
+    #[test]
+    fn test_crawler_parse_attachments() {
+        let crawler: GnuboardCrawler<CseBachelorPlugin, ItGnuboardMetadataResolver> =
+            GnuboardCrawler::new();
+
+        let document = Html::parse_document(
+            r#"<section id="bo_v_file"><h2>첨부파일</h2><ul>
+                <li>
+                    <a href="https://cse.ssu.ac.kr/bbs/download.php?bo_table=notice&amp;wr_id=1&amp;no=0" class="view_file_download">
+                        <strong>교내장학금 신청 매뉴얼.pdf</strong>
+                    </a>
+                    (374.8K)
+                    <span class="bo_v_file_cnt">12회 다운로드 | DATE : 2025-06-04 11:58:11</span>
+                </li>
+            </ul></section>"#,
+        );
+        let attachments = crawler.parse_attachments(&document);
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(
+            attachments[0].name.as_deref(),
+            Some("교내장학금 신청 매뉴얼.pdf")
+        );
+        assert_eq!(attachments[0].size(), Some(383_795));
+        assert_eq!(
+            attachments[0].posted_at(),
+            Some(time::macros::datetime!(2025-06-04 11:58:11 +09:00))
+        );
+    }
+
+    #[test]
+    fn test_crawler_parse_empty_board() {
+        let crawler: GnuboardCrawler<CseBachelorPlugin, ItGnuboardMetadataResolver> =
+            GnuboardCrawler::new();
+
+        let document = Html::parse_document(
+            r#"<div id="bo_list"><table><tbody>
+                <tr><td colspan="5" class="empty_table">게시물이 없습니다.</td></tr>
+            </tbody></table></div>"#,
+        );
+        let page = crawler
+            .parse_metadata(&document, "https://example.com/list&page=99")
+            .unwrap();
+        assert!(matches!(page, ListPage::Empty));
+
+        // No rows and no empty-state notice means the layout changed.
+        let document =
+            Html::parse_document(r#"<div id="bo_list"><table><tbody></tbody></table></div>"#);
+        assert!(
+            crawler
+                .parse_metadata(&document, "https://example.com/list&page=1")
+                .is_err()
+        );
+    }
 }
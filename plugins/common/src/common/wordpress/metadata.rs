@@ -1,4 +1,4 @@
-use ssufid::{PluginError, core::SsufidPlugin};
+use ssufid::{PluginError, core::SsufidPlugin, html::is_notice_row};
 use time::{
     Date,
     macros::{format_description, offset},
@@ -21,7 +21,8 @@ pub(crate) trait WordpressMetadataResolver {
     ) -> Result<WordpressMetadata<T>, PluginError> {
         let mut childrens = element.child_elements().peekable();
 
-        let number_element = childrens.next().ok_or_else(|| {
+        // Number column; notices read "공지" instead (is_notice_row)
+        childrens.next().ok_or_else(|| {
             PluginError::parse::<T>("Failed to find number element in the board item".into())
         })?;
 
@@ -41,10 +42,7 @@ pub(crate) trait WordpressMetadataResolver {
             .nth(1)
             .ok_or_else(|| PluginError::parse::<T>("Failed to find date element".into()))?;
 
-        let is_announcement = number_element
-            .text()
-            .next()
-            .is_some_and(|text| text.contains("공지"));
+        let is_announcement = is_notice_row(element);
 
         let title = title_element.text().collect::<String>();
 
@@ -6,10 +6,13 @@ use futures::{TryStreamExt as _, stream::FuturesOrdered};
 use scraper::Selector;
 use ssufid::{
     PluginError, PluginErrorKind,
-    core::{SsufidPlugin, SsufidPost},
+    core::{CrawlOptions, SsufidPlugin, SsufidPost, UrlKind, report_pinned_post},
+    fetch::{client_builder, default_client, fetch_page, fetch_text},
+    html::is_restricted,
+    paginate::{ListPage, Paginator},
 };
 use time::{
-    Date,
+    Date, OffsetDateTime,
     macros::{format_description, offset},
 };
 use url::Url;
@@ -41,13 +44,15 @@ static CONTENT_SELECTOR: LazyLock<Selector> = LazyLock::new(|| {
     Selector::parse("table.t_view div.td_box").expect("Failed to parse content selector")
 });
 
-#[repr(transparent)]
 pub(crate) struct WordpressCrawler<
     T: SsufidPlugin,
     M: WordpressMetadataResolver = DefaultWordpressMetadataResolver,
     P: WordpressPostResolver = DefaultWordpressPostResolver,
 > {
     card: bool,
+    warmup: bool,
+    base_url: String,
+    client: reqwest::Client,
     _marker: std::marker::PhantomData<(T, M, P)>,
 }
 
@@ -60,6 +65,9 @@ where
     pub(crate) fn new() -> Self {
         Self {
             card: false,
+            warmup: false,
+            base_url: T::BASE_URL.to_string(),
+            client: default_client(),
             _marker: std::marker::PhantomData,
         }
     }
@@ -67,12 +75,62 @@ where
     pub(crate) fn card() -> Self {
         Self {
             card: true,
-            _marker: std::marker::PhantomData,
+            ..Self::new()
+        }
+    }
+
+    /// Crawls `base_url` instead of `T::BASE_URL`, e.g. a mock server.
+    pub(crate) fn with_base_url(self, base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            ..self
         }
     }
 
+    pub(crate) fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub(crate) fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    /// Requests the board page before the first list page, with a cookie store so that the
+    /// session it sets is sent along with the list requests.
+    pub(crate) fn with_warmup(self, warmup: bool) -> Self {
+        if !warmup {
+            return self;
+        }
+        Self {
+            warmup,
+            client: client_builder()
+                .cookie_store(true)
+                .build()
+                .expect("Failed to build HTTP client"),
+            ..self
+        }
+    }
+
+    pub(crate) async fn warmup(&self) -> Result<(), PluginError> {
+        if self.warmup {
+            fetch_text::<T>(&self.client, &self.base_url).await?;
+        }
+        Ok(())
+    }
+
     pub(crate) async fn crawl(&self, posts_limit: u32) -> Result<Vec<SsufidPost>, PluginError> {
-        let metadata = self.fetch_metadata_list(posts_limit).await?;
+        self.crawl_with_options(&CrawlOptions::new(posts_limit))
+            .await
+    }
+
+    pub(crate) async fn crawl_with_options(
+        &self,
+        options: &CrawlOptions,
+    ) -> Result<Vec<SsufidPost>, PluginError> {
+        let cutoff = options.cutoff(options.current_time());
+        let metadata = self
+            .fetch_metadata_list(options.posts_limit, options.empty_page_tolerance, cutoff)
+            .await?;
         tracing::info!("fetch {} posts", metadata.len());
         metadata
             .iter()
@@ -86,40 +144,47 @@ where
     async fn fetch_metadata_list(
         &self,
         posts_limit: u32,
+        empty_page_tolerance: u32,
+        cutoff: Option<OffsetDateTime>,
     ) -> Result<Vec<WordpressMetadata<T>>, PluginError> {
-        // Simulate fetching metadata from a WordPress site
-        let mut metadata_list = Vec::with_capacity(posts_limit as usize);
-        let mut page = 1;
-        let mut announcements = 0;
-        while metadata_list.len() < posts_limit as usize + announcements as usize {
-            let metadata = self.fetch_page(page).await?;
-            announcements += metadata.iter().filter(|m| m.is_announcement).count() as u32;
-            let empty = metadata.is_empty();
-            metadata_list.extend(metadata);
-            if empty {
-                break; // No more pages to fetch
+        // Notices repeat on every page, so only regular posts count toward `posts_limit`.
+        let mut paginator = Paginator::new(posts_limit).empty_page_tolerance(empty_page_tolerance);
+        let mut pinned_list: Vec<WordpressMetadata<T>> = vec![];
+        while let Some(page) = paginator.next_page() {
+            let metadata = ListPage::split(self.fetch_page(page).await?, |m| m.is_announcement);
+            // Newest first: once every regular post is past the cutoff, so is the next page.
+            let expired = cutoff.is_some_and(|cutoff| metadata.is_past(cutoff, |m| m.created_at));
+            for pinned in metadata.pinned {
+                if pinned_list.iter().any(|m| m.url == pinned.url) {
+                    continue;
+                }
+                if let Ok(id) = post_id::<T>(&pinned.url) {
+                    report_pinned_post(&id);
+                }
+                pinned_list.push(pinned);
             }
-
-            page += 1; // Simulate pagination
+            paginator.push_page(metadata.regular, |m| m.url.clone());
+            if expired {
+                paginator.stop();
+            }
+        }
+        let regular = paginator.into_items();
+        pinned_list.retain(|pinned| regular.iter().all(|m| m.url != pinned.url));
+        let mut metadata_list = pinned_list;
+        metadata_list.extend(regular);
+        if let Some(cutoff) = cutoff {
+            metadata_list.retain(|m| m.created_at >= cutoff);
         }
-        // Make sure announcements are sorted correctly
-        metadata_list.sort_by(|a, b| a.created_at.cmp(&b.created_at));
-        metadata_list.truncate(posts_limit as usize);
+        // Sort newest first so pinned notices land in place.
+        metadata_list.sort_by_key(|m| std::cmp::Reverse(m.created_at));
 
         Ok(metadata_list)
     }
 
     async fn fetch_page(&self, page: u32) -> Result<Vec<WordpressMetadata<T>>, PluginError> {
-        let page_url = format!("{}/page/{}", T::BASE_URL, page);
+        let page_url = format!("{}/page/{}", self.base_url, page);
 
-        let html = reqwest::get(page_url)
-            .await
-            .map_err(|e| PluginError::request::<T>(format!("Failed to request list page: {e:?}")))?
-            .text()
-            .await
-            .map_err(|e| {
-                PluginError::parse::<T>(format!("Failed to parse list html body: {e:?}"))
-            })?;
+        let html = fetch_page::<T>(&self.client, &page_url).await?.text;
         let document = scraper::Html::parse_document(&html);
 
         let selector: &Selector = if self.card {
@@ -144,17 +209,17 @@ where
 
     async fn fetch_post(&self, metadata: &WordpressMetadata<T>) -> Result<SsufidPost, PluginError> {
         tokio::time::sleep(std::time::Duration::from_millis(300)).await; // Rate limiting
-        let post_url = metadata.url.clone();
-        let html = reqwest::get(post_url)
-            .await
-            .map_err(|e| PluginError::request::<T>(format!("Failed to request post page: {e:?}")))?
-            .text()
-            .await
-            .map_err(|e| {
-                PluginError::parse::<T>(format!("Failed to parse post html body: {e:?}"))
-            })?;
-        let document = scraper::Html::parse_document(&html);
-        let post = P::resolve_post::<T>(metadata, document)?;
+        let page = fetch_page::<T>(&self.client, &metadata.url).await?;
+        let document = scraper::Html::parse_document(&page.text);
+        if is_restricted::<T>(&document, &CONTENT_SELECTOR) {
+            // Members-only posts keep the list's fields instead of the login notice.
+            tracing::info!(url = %metadata.url, "Post is members-only");
+            return restricted_post(metadata);
+        }
+        let mut post = P::resolve_post::<T>(metadata, document)?;
+        if page.stripped {
+            post.mark_content_stripped();
+        }
 
         // Here you would typically save the post to your database or process it further.
         tracing::info!(
@@ -168,33 +233,72 @@ where
     }
 }
 
+/// The `slug` query parameter of a post URL, which identifies the post.
+fn post_id<T: SsufidPlugin>(url: &str) -> Result<String, PluginError> {
+    Ok(Url::parse(url)
+        .map_err(|e| PluginError::parse::<T>(format!("Failed to parse URL: {e:?}")))?
+        .query_pairs()
+        .find(|(k, _)| k == "slug")
+        .ok_or_else(|| {
+            PluginError::parse::<T>("Failed to find 'slug' query parameter in the URL".into())
+        })?
+        .1
+        .to_string())
+}
+
+/// A members-only post with what the list shows about it and no content.
+fn restricted_post<T: SsufidPlugin>(
+    metadata: &WordpressMetadata<T>,
+) -> Result<SsufidPost, PluginError> {
+    let mut post = SsufidPost {
+        id: post_id::<T>(&metadata.url)?,
+        title: metadata.title.clone(),
+        url: metadata.url.clone(),
+        url_kind: UrlKind::Canonical,
+        content: String::new(),
+        created_at: metadata.created_at,
+        author: None,
+        description: None,
+        category: if metadata.is_announcement {
+            vec!["공지".to_string()]
+        } else {
+            vec![]
+        },
+        updated_at: None,
+        thumbnail: None,
+        attachments: vec![],
+        metadata: None,
+        author_detail: None,
+    };
+    post.mark_restricted();
+    Ok(post)
+}
+
 pub(crate) trait WordpressPostResolver {
     const DATE_FORMAT: &'static [time::format_description::FormatItem<'static>];
     fn resolve_post<T: SsufidPlugin>(
         metadata: &WordpressMetadata<T>,
         document: scraper::Html,
     ) -> Result<SsufidPost, PluginError> {
-        let id = Url::parse(&metadata.url)
-            .map_err(|e| PluginError::parse::<T>(format!("Failed to parse URL: {e:?}")))?
-            .query_pairs()
-            .find(|(k, _)| k == "slug")
-            .ok_or_else(|| {
-                PluginError::parse::<T>("Failed to find 'slug' query parameter in the URL".into())
-            })?
-            .1
-            .to_string();
+        let id = post_id::<T>(&metadata.url)?;
         let title = document
             .select(&TITLE_SELECTOR)
             .next()
             .and_then(|el| el.text().next())
-            .ok_or_else(|| PluginError::parse::<T>("Failed to find title in the post".into()))?
+            .ok_or_else(|| {
+                PluginError::parse::<T>("Failed to find title in the post".into())
+                    .with_snippet(&TITLE_SELECTOR, &document.html())
+            })?
             .to_string();
 
         let date_text = document
             .select(&DATE_SELECTOR)
             .next()
             .and_then(|el| el.text().next())
-            .ok_or_else(|| PluginError::parse::<T>("Failed to find date in the post".into()))?
+            .ok_or_else(|| {
+                PluginError::parse::<T>("Failed to find date in the post".into())
+                    .with_snippet(&DATE_SELECTOR, &document.html())
+            })?
             .trim();
         let created_at = Date::parse(date_text, Self::DATE_FORMAT)
             .map_err(|e| PluginError::parse::<T>(format!("Failed to parse date: {e:?}")))?
@@ -205,11 +309,15 @@ pub(crate) trait WordpressPostResolver {
             .select(&CONTENT_SELECTOR)
             .next()
             .map(|el| el.inner_html())
-            .ok_or_else(|| PluginError::parse::<T>("Failed to find content in the post".into()))?;
+            .ok_or_else(|| {
+                PluginError::parse::<T>("Failed to find content in the post".into())
+                    .with_snippet(&CONTENT_SELECTOR, &document.html())
+            })?;
         Ok(SsufidPost {
             id,
             title,
             url: metadata.url.clone(),
+            url_kind: UrlKind::Canonical,
             content,
             created_at,
             author: None,
@@ -223,6 +331,7 @@ pub(crate) trait WordpressPostResolver {
             thumbnail: None,
             attachments: vec![],
             metadata: None,
+            author_detail: None,
         })
     }
 }
@@ -247,3 +356,471 @@ impl WordpressPostResolver for KorDateWordpressPostResolver {
     const DATE_FORMAT: &'static [time::format_description::FormatItem<'static>] =
         format_description!("[year]년 [month padding:none]월 [day padding:none]일");
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    use ssufid::{
+        core::{CrawlOptions, SsufidPostPlugin},
+        fetch::DEFAULT_USER_AGENT,
+    };
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{header, method, path, query_param},
+    };
+
+    use time::{
+        Date, Duration, OffsetDateTime,
+        macros::{date, offset},
+    };
+
+    use crate::sites::{LawPlugin, LawyerPlugin, LifelongEduPlugin};
+
+    fn list_row(server: &str, number: &str, slug: &str) -> String {
+        dated_row(server, number, slug, date!(2025 - 03 - 02))
+    }
+
+    fn dated_row(server: &str, number: &str, slug: &str, date: Date) -> String {
+        format!(
+            r#"<tr><td>{number}</td><td><a href="{server}/post?slug={slug}">{slug}</a></td>
+            <td>학과사무실</td><td>{}년 {}월 {}일</td></tr>"#,
+            date.year(),
+            u8::from(date.month()),
+            date.day()
+        )
+    }
+
+    fn list_page(rows: &[String]) -> String {
+        format!(
+            r#"<div class="baord_table"><table><tbody>{}</tbody></table></div>"#,
+            rows.join("")
+        )
+    }
+
+    /// Forwards TCP connections to `target`, counting how many were opened.
+    async fn connection_counter(target: std::net::SocketAddr) -> (String, Arc<AtomicUsize>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let count = Arc::new(AtomicUsize::new(0));
+        let counter = count.clone();
+        tokio::spawn(async move {
+            while let Ok((mut inbound, _)) = listener.accept().await {
+                counter.fetch_add(1, Ordering::SeqCst);
+                tokio::spawn(async move {
+                    let mut outbound = tokio::net::TcpStream::connect(target).await.unwrap();
+                    let _ = tokio::io::copy_bidirectional(&mut inbound, &mut outbound).await;
+                });
+            }
+        });
+        (format!("http://{addr}"), count)
+    }
+
+    #[tokio::test]
+    async fn test_lifelongedu_reuses_configured_client() {
+        let server = MockServer::start().await;
+        let (uri, connections) = connection_counter(*server.address()).await;
+
+        Mock::given(method("GET"))
+            .and(path("/page/1"))
+            .and(header("user-agent", DEFAULT_USER_AGENT))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(
+                    r#"<div class="baord_table"><table><tbody>
+                    <tr><td>1</td><td><a href="URI/post?slug=notice">공지</a></td>
+                    <td>학과사무실</td><td>2025-03-02</td></tr>
+                </tbody></table></div>"#
+                        .replace("URI", &uri),
+                ),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/post"))
+            .and(header("user-agent", DEFAULT_USER_AGENT))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<table class="t_view"><tr><td>
+                    <p class="title">제목</p>
+                    <ul class="date_w"><li><dl><dt>작성일</dt><dd>2025-03-02</dd></dl></li></ul>
+                    <div class="td_box"><p>본문</p></div>
+                </td></tr></table>"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let posts = LifelongEduPlugin::with_base_url(&uri)
+            .crawl(1)
+            .await
+            .unwrap();
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].id, "notice");
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_lawyer_restricted_post_keeps_list_metadata() {
+        let server = MockServer::start().await;
+        let uri = server.uri();
+        Mock::given(method("GET"))
+            .and(path("/page/1"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(list_page(&[dated_row(
+                    &uri,
+                    "1",
+                    "members-only",
+                    date!(2025 - 03 - 04),
+                )])),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/post"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<div class="login_box"><p>로그인이 필요합니다.</p>
+                <form><input name="user_id"><input type="password" name="user_pw"></form></div>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let posts = LawyerPlugin::with_base_url(&uri).crawl(1).await.unwrap();
+        assert_eq!(posts.len(), 1);
+        let post = &posts[0];
+        assert_eq!(post.id, "members-only");
+        assert_eq!(post.title, "members-only");
+        assert_eq!(post.created_at.date(), date!(2025 - 03 - 04));
+        // The login notice is not stored as the content
+        assert!(post.content.is_empty());
+        assert!(post.is_restricted());
+    }
+
+    #[tokio::test]
+    async fn test_lawyer_large_post_page_is_stripped_before_parsing() {
+        let server = MockServer::start().await;
+        let uri = server.uri();
+        Mock::given(method("GET"))
+            .and(path("/page/1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(list_page(&[list_row(&uri, "1", "poster")])),
+            )
+            .mount(&server)
+            .await;
+        // A detail page with a 3MB base64 inline image
+        let image = "A".repeat(3 * 1024 * 1024);
+        Mock::given(method("GET"))
+            .and(path("/post"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                r#"<table class="t_view"><tr><td>
+                    <p class="title">포스터</p>
+                    <ul class="date_w"><li><dl><dt>작성일</dt><dd>2025년 3월 2일</dd></dl></li></ul>
+                    <div class="td_box"><p>포스터를 확인하세요.</p><img src="data:image/png;base64,{image}"></div>
+                </td></tr></table>"#
+            )))
+            .mount(&server)
+            .await;
+
+        let posts = LawyerPlugin::with_base_url(&uri).crawl(1).await.unwrap();
+        assert_eq!(posts.len(), 1);
+        assert!(posts[0].content.contains("포스터를 확인하세요."));
+        assert!(!posts[0].content.contains(&image));
+        assert_eq!(
+            posts[0]
+                .metadata
+                .as_ref()
+                .and_then(|metadata| metadata.get(ssufid::core::SsufidPost::CONTENT_STRIPPED_KEY))
+                .map(String::as_str),
+            Some("true")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lawyer_pagination_stops_at_limit() {
+        let server = MockServer::start().await;
+        let uri = server.uri();
+        let pinned = [
+            list_row(&uri, "공지", "pinned-1"),
+            list_row(&uri, "공지", "pinned-2"),
+        ];
+
+        // Page 1 holds only notices; from page 2 the same notices repeat before regular posts
+        Mock::given(method("GET"))
+            .and(path("/page/1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(list_page(&pinned)))
+            .mount(&server)
+            .await;
+        for page in 2..=5 {
+            let mut rows = pinned.to_vec();
+            rows.push(list_row(&uri, "1", &format!("post-{page}-1")));
+            rows.push(list_row(&uri, "2", &format!("post-{page}-2")));
+            Mock::given(method("GET"))
+                .and(path(format!("/page/{page}")))
+                .respond_with(ResponseTemplate::new(200).set_body_string(list_page(&rows)))
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/post"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<table class="t_view"><tr><td>
+                    <p class="title">제목</p>
+                    <ul class="date_w"><li><dl><dt>작성일</dt><dd>2025년 3월 2일</dd></dl></li></ul>
+                    <div class="td_box"><p>본문</p></div>
+                </td></tr></table>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        // Pinned notices do not count toward the limit: 3 regular posts and 2 notices
+        let posts = LawyerPlugin::with_base_url(&uri).crawl(3).await.unwrap();
+        assert_eq!(posts.len(), 5);
+        assert_eq!(
+            posts
+                .iter()
+                .filter(|post| post.id.starts_with("post-"))
+                .count(),
+            3
+        );
+
+        let list_requests = server
+            .received_requests()
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|r| r.url.path().starts_with("/page/"))
+            .count();
+        assert!(list_requests <= 3, "issued {list_requests} list requests");
+    }
+
+    #[tokio::test]
+    async fn test_lawyer_max_age_stops_at_old_page() {
+        let server = MockServer::start().await;
+        let uri = server.uri();
+        let pinned = list_row(&uri, "공지", "pinned-1");
+
+        Mock::given(method("GET"))
+            .and(path("/page/1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(list_page(std::slice::from_ref(&pinned))),
+            )
+            .mount(&server)
+            .await;
+        for page in 2..=5 {
+            let rows = [pinned.clone(), list_row(&uri, "1", &format!("post-{page}"))];
+            Mock::given(method("GET"))
+                .and(path(format!("/page/{page}")))
+                .respond_with(ResponseTemplate::new(200).set_body_string(list_page(&rows)))
+                .mount(&server)
+                .await;
+        }
+
+        // Every listed post is dated 2025-03-02, so a 30-day bound excludes them all
+        let options = CrawlOptions::new(10).max_age(std::time::Duration::from_secs(30 * 86400));
+        let posts = LawyerPlugin::with_base_url(&uri)
+            .crawl_with_options(&options)
+            .await
+            .unwrap();
+        assert!(posts.is_empty());
+
+        let requests = server.received_requests().await.unwrap();
+        let list_requests = requests
+            .iter()
+            .filter(|r| r.url.path().starts_with("/page/"))
+            .count();
+        assert_eq!(list_requests, 2);
+        assert!(requests.iter().all(|r| r.url.path() != "/post"));
+    }
+
+    #[tokio::test]
+    async fn test_lawyer_max_age_ignores_old_pinned_post() {
+        let server = MockServer::start().await;
+        let uri = server.uri();
+        let today = OffsetDateTime::now_utc().to_offset(offset!(+9)).date();
+        let days_ago = |days: i64| today - Duration::days(days);
+        // An old notice is pinned atop every page, with newer posts below it
+        let pinned = dated_row(&uri, "공지", "pinned-old", date!(2020 - 03 - 02));
+        let pages = [
+            vec![
+                dated_row(&uri, "5", "post-5", days_ago(1)),
+                dated_row(&uri, "4", "post-4", days_ago(2)),
+            ],
+            vec![
+                dated_row(&uri, "3", "post-3", days_ago(3)),
+                dated_row(&uri, "2", "post-2", days_ago(60)),
+            ],
+            vec![dated_row(&uri, "1", "post-1", days_ago(61))],
+            vec![dated_row(&uri, "0", "post-0", days_ago(62))],
+        ];
+        for (page, rows) in pages.iter().enumerate() {
+            let rows = [std::slice::from_ref(&pinned), rows].concat();
+            Mock::given(method("GET"))
+                .and(path(format!("/page/{}", page + 1)))
+                .respond_with(ResponseTemplate::new(200).set_body_string(list_page(&rows)))
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/post"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<table class="t_view"><tr><td>
+                    <p class="title">제목</p>
+                    <ul class="date_w"><li><dl><dt>작성일</dt><dd>2025년 3월 2일</dd></dl></li></ul>
+                    <div class="td_box"><p>본문</p></div>
+                </td></tr></table>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let options = CrawlOptions::new(10).max_age(std::time::Duration::from_secs(30 * 86400));
+        let posts = LawyerPlugin::with_base_url(&uri)
+            .crawl_with_options(&options)
+            .await
+            .unwrap();
+        let mut ids = posts
+            .iter()
+            .map(|post| post.id.as_str())
+            .collect::<Vec<_>>();
+        ids.sort();
+        assert_eq!(ids, ["post-3", "post-4", "post-5"]);
+
+        // Stops on page 3, where every regular post is past the cutoff, not at page 1's old notice
+        let list_requests = server
+            .received_requests()
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|r| r.url.path().starts_with("/page/"))
+            .map(|r| r.url.path().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(list_requests, ["/page/1", "/page/2", "/page/3"]);
+    }
+
+    #[tokio::test]
+    async fn test_lawyer_warmup_precedes_list_requests() {
+        let server = MockServer::start().await;
+        let uri = server.uri();
+
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(200).insert_header("set-cookie", "session=abc"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/page/1"))
+            .and(header("cookie", "session=abc"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(list_page(&[list_row(&uri, "1", "post-1")])),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/post"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<table class="t_view"><tr><td>
+                    <p class="title">제목</p>
+                    <ul class="date_w"><li><dl><dt>작성일</dt><dd>2025년 3월 2일</dd></dl></li></ul>
+                    <div class="td_box"><p>본문</p></div>
+                </td></tr></table>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let core = ssufid::SsufidCore::new("./lawyer_warmup_test");
+        let site = core
+            .run(&LawyerPlugin::with_base_url(&uri), &CrawlOptions::new(1))
+            .await
+            .unwrap();
+        assert_eq!(site.items().len(), 1);
+
+        let paths = server
+            .received_requests()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|r| r.url.path().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(paths[..2], ["/", "/page/1"]);
+    }
+
+    #[tokio::test]
+    async fn test_law_crawls_list_and_detail() {
+        let server = MockServer::start().await;
+        let uri = server.uri();
+        let row = |number: &str, slug: &str, date: &str| {
+            format!(
+                r#"<tr><td>{number}</td><td><a href="{uri}/post?slug={slug}">{slug}</a></td>
+                <td>법학과</td><td>{date}</td></tr>"#
+            )
+        };
+        // The law board writes dates as 2025-03-02
+        let pinned = row("공지", "exam-schedule", "2025-02-20");
+        Mock::given(method("GET"))
+            .and(path("/page/1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(list_page(&[
+                pinned.clone(),
+                row("12", "moot-court", "2025-03-05"),
+                row("11", "scholarship", "2025-03-04"),
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/page/2"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(list_page(&[pinned, row("10", "seminar", "2025-03-03")])),
+            )
+            .mount(&server)
+            .await;
+        for (slug, title, date) in [
+            ("exam-schedule", "2025학년도 1학기 시험 일정", "2025-02-20"),
+            ("moot-court", "모의재판 경연대회 참가 신청", "2025-03-05"),
+            ("scholarship", "법학과 장학생 선발 안내", "2025-03-04"),
+            ("seminar", "법학 세미나 개최 안내", "2025-03-03"),
+        ] {
+            Mock::given(method("GET"))
+                .and(path("/post"))
+                .and(query_param("slug", slug))
+                .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                    r#"<table class="t_view"><tr><td>
+                    <p class="title">{title}</p>
+                    <ul class="date_w"><li><dl><dt>작성일</dt><dd>{date}</dd></dl></li></ul>
+                    <div class="td_box"><p>{title} 본문</p></div>
+                    </td></tr></table>"#
+                )))
+                .mount(&server)
+                .await;
+        }
+
+        let posts = LawPlugin::with_base_url(&uri).crawl(3).await.unwrap();
+        let ids = posts.iter().map(|p| p.id.as_str()).collect::<Vec<_>>();
+        // Pinned notices are included regardless of the limit
+        assert_eq!(
+            ids,
+            ["moot-court", "scholarship", "seminar", "exam-schedule"]
+        );
+
+        let pinned = &posts[3];
+        assert_eq!(pinned.title, "2025학년도 1학기 시험 일정");
+        assert_eq!(pinned.category, ["공지"]);
+        assert_eq!(pinned.url, format!("{uri}/post?slug=exam-schedule"));
+        assert_eq!(
+            pinned.content.trim(),
+            "<p>2025학년도 1학기 시험 일정 본문</p>"
+        );
+        let moot_court = &posts[0];
+        assert!(moot_court.category.is_empty());
+        assert_eq!(
+            moot_court.created_at,
+            date!(2025 - 03 - 05)
+                .midnight()
+                .assume_offset(offset!(+09:00))
+        );
+    }
+}
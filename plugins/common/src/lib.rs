@@ -22,6 +22,11 @@ macro_rules! gnuboard_plugin {
             const TITLE: &'static str = $title;
             const DESCRIPTION: &'static str = $description;
             const BASE_URL: &'static str = $base_url;
+            const VERSION: u32 = $crate::common::gnuboard::GNUBOARD_VERSION;
+            const EMPTY_STATE: ssufid::html::EmptyState =
+                $crate::common::gnuboard::GNUBOARD_EMPTY_STATE;
+            const CRITICAL_SELECTORS: &'static [(&'static str, &'static str)] =
+                $crate::common::gnuboard::GNUBOARD_CRITICAL_SELECTORS;
         }
 
         impl ssufid::core::SsufidPostPlugin for $name {
@@ -31,6 +36,21 @@ macro_rules! gnuboard_plugin {
             ) -> Result<Vec<ssufid::core::SsufidPost>, ssufid::PluginError> {
                 self.crawler.crawl(posts_limit).await
             }
+
+            async fn crawl_with_options(
+                &self,
+                options: &ssufid::core::CrawlOptions,
+            ) -> Result<Vec<ssufid::core::SsufidPost>, ssufid::PluginError> {
+                self.crawler.crawl_with_options(options).await
+            }
+
+            async fn latest_post_id(&self) -> Result<Option<String>, ssufid::PluginError> {
+                self.crawler.latest_post_id().await
+            }
+
+            async fn categories(&self) -> Result<Vec<String>, ssufid::PluginError> {
+                self.crawler.categories().await
+            }
         }
 
         impl Default for $name {
@@ -77,7 +97,7 @@ macro_rules! wordpress_plugin {
             $crate::common::wordpress::DefaultWordpressPostResolver
         );
     };
-    ($name:ident, $identifier:expr, $title:expr, $description:expr, $base_url:expr, $meta_resolver:ty, $post_resolver:ty) => {
+    ($name:ident, $identifier:expr, $title:expr, $description:expr, $base_url:expr, $meta_resolver:ty, $post_resolver:ty $(, warmup = $warmup:literal)? $(, extra_headers = $extra_headers:expr)?) => {
         pub struct $name {
             crawler:
                 $crate::common::wordpress::WordpressCrawler<Self, $meta_resolver, $post_resolver>,
@@ -88,15 +108,35 @@ macro_rules! wordpress_plugin {
             const TITLE: &'static str = $title;
             const DESCRIPTION: &'static str = $description;
             const BASE_URL: &'static str = $base_url;
+            $(const EXTRA_HEADERS: &'static [(&'static str, &'static str)] = $extra_headers;)?
+
+            fn base_url(&self) -> std::borrow::Cow<'_, str> {
+                std::borrow::Cow::Borrowed(self.crawler.base_url())
+            }
+
+            fn http_client(&self) -> reqwest::Client {
+                self.crawler.client().clone()
+            }
         }
 
         impl ssufid::core::SsufidPostPlugin for $name {
+            async fn warmup(&self) -> Result<(), ssufid::PluginError> {
+                self.crawler.warmup().await
+            }
+
             async fn crawl(
                 &self,
                 posts_limit: u32,
             ) -> Result<Vec<ssufid::core::SsufidPost>, ssufid::PluginError> {
                 self.crawler.crawl(posts_limit).await
             }
+
+            async fn crawl_with_options(
+                &self,
+                options: &ssufid::core::CrawlOptions,
+            ) -> Result<Vec<ssufid::core::SsufidPost>, ssufid::PluginError> {
+                self.crawler.crawl_with_options(options).await
+            }
         }
 
         impl Default for $name {
@@ -108,7 +148,17 @@ macro_rules! wordpress_plugin {
         impl $name {
             pub fn new() -> Self {
                 Self {
-                    crawler: $crate::common::wordpress::WordpressCrawler::new(),
+                    crawler: $crate::common::wordpress::WordpressCrawler::new()
+                        $(.with_warmup($warmup))?,
+                }
+            }
+
+            /// Crawls `base_url` instead of the site, e.g. a mock server.
+            pub fn with_base_url(base_url: impl Into<String>) -> Self {
+                Self {
+                    crawler: $crate::common::wordpress::WordpressCrawler::new()
+                        $(.with_warmup($warmup))?
+                        .with_base_url(base_url),
                 }
             }
         }
@@ -30,6 +30,13 @@ impl ssufid::core::SsufidPostPlugin for MgmtPlugin {
     ) -> Result<Vec<ssufid::core::SsufidPost>, ssufid::PluginError> {
         self.crawler.crawl(posts_limit).await
     }
+
+    async fn crawl_with_options(
+        &self,
+        options: &ssufid::core::CrawlOptions,
+    ) -> Result<Vec<ssufid::core::SsufidPost>, ssufid::PluginError> {
+        self.crawler.crawl_with_options(options).await
+    }
 }
 
 impl Default for MgmtPlugin {
@@ -279,19 +279,27 @@ pub mod lawyer {
         "숭실대학교 국제법무학과 홈페이지의 공지사항을 제공합니다.",
         "https://lawyer.ssu.ac.kr/%ED%95%99%EA%B3%BC-%EC%86%8C%EC%8B%9D/%ED%95%99%EA%B3%BC-%EA%B3%B5%EC%A7%80/",
         KorDateWordpressMetadataResolver,
-        KorDateWordpressPostResolver
+        KorDateWordpressPostResolver,
+        warmup = true
     );
 }
 
 pub use lawyer::LawyerPlugin;
 
 pub mod lifelongedu {
+    use crate::common::wordpress::{
+        DefaultWordpressPostResolver, metadata::DefaultWordpressMetadataResolver,
+    };
+
     crate::wordpress_plugin!(
         LifelongEduPlugin,
         "lifelongedu.ssu.ac.kr",
         "숭실대학교 평생교육학과 공지사항",
         "숭실대학교 평생교육학과 홈페이지의 공지사항을 제공합니다.",
-        "https://lifelongedu.ssu.ac.kr/%ED%95%99%EA%B3%BC%EC%82%AC%EB%AC%B4%EC%8B%A4%EC%95%8C%EB%A6%BC/%EA%B3%B5%EC%A7%80%EC%82%AC%ED%95%AD"
+        "https://lifelongedu.ssu.ac.kr/%ED%95%99%EA%B3%BC%EC%82%AC%EB%AC%B4%EC%8B%A4%EC%95%8C%EB%A6%BC/%EA%B3%B5%EC%A7%80%EC%82%AC%ED%95%AD",
+        DefaultWordpressMetadataResolver,
+        DefaultWordpressPostResolver,
+        extra_headers = &[("Referer", "https://lifelongedu.ssu.ac.kr/")]
     );
 }
 
@@ -1,10 +1,12 @@
 use reqwest::Client;
-use scraper::{Html, Selector};
+use scraper::Html;
 use ssufid::{
-    core::{Attachment, SsufidPlugin, SsufidPost, SsufidPostPlugin},
+    core::{Attachment, SsufidPlugin, SsufidPost, SsufidPostPlugin, UrlKind},
     error::PluginError,
+    fetch::send,
 };
-use time::{Date, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset, macros::format_description};
+use ssufid_boardview as boardview;
+use time::OffsetDateTime;
 use url::Url;
 
 fn full_url(base: &str, path: &str) -> Result<String, PluginError> {
@@ -22,49 +24,13 @@ fn full_url(base: &str, path: &str) -> Result<String, PluginError> {
         .map(|u| u.to_string())
 }
 
-struct Selectors {
-    post_item: Selector,
-    post_link: Selector,
-    post_title_view: Selector,
-    post_author_view: Selector,
-    post_date_view: Selector,
-    post_content_view: Selector,
-    attachment_link: Selector,
-    next_page_link: Selector,
-}
-
-impl Selectors {
-    fn new() -> Result<Self, PluginError> {
-        Ok(Selectors {
-            post_item: Selector::parse("div.board-list2 > ul > li")
-                .map_err(|e| PluginError::custom::<EePlugin>("SelectorParse".to_string(), format!("Failed to parse post_item selector: {e}")))?,
-            post_link: Selector::parse("div.subject > a")
-                .map_err(|e| PluginError::custom::<EePlugin>("SelectorParse".to_string(), format!("Failed to parse post_link selector: {e}")))?,
-            post_title_view: Selector::parse("div.board-view > div.head > h3.tit")
-                .map_err(|e| PluginError::custom::<EePlugin>("SelectorParse".to_string(), format!("Failed to parse post_title_view selector: {e}")))?,
-            post_author_view: Selector::parse("div.board-view > div.head > div.info > span.name strong")
-                .map_err(|e| PluginError::custom::<EePlugin>("SelectorParse".to_string(), format!("Failed to parse post_author_view selector: {e}")))?,
-            post_date_view: Selector::parse("div.board-view > div.head > div.info > span.date")
-                .map_err(|e| PluginError::custom::<EePlugin>("SelectorParse".to_string(), format!("Failed to parse post_date_view selector: {e}")))?,
-            post_content_view: Selector::parse("div.board-view > div.body")
-                .map_err(|e| PluginError::custom::<EePlugin>("SelectorParse".to_string(), format!("Failed to parse post_content_view selector: {e}")))?,
-            attachment_link: Selector::parse("div.board-view > div.head > div.files a[onclick*='download'], div.board-view > div.body a[href^='/uploaded/']")
-                .map_err(|e| PluginError::custom::<EePlugin>("SelectorParse".to_string(), format!("Failed to parse attachment_link selector: {e}")))?,
-            next_page_link: Selector::parse("div.paginate > a.next:not(.disabled)")
-                .map_err(|e| PluginError::custom::<EePlugin>("SelectorParse".to_string(), format!("Failed to parse next_page_link selector: {e}")))?,
-        })
-    }
-}
-
 pub struct EePlugin {
-    selectors: Selectors,
     client: Client,
 }
 
 impl Default for EePlugin {
     fn default() -> Self {
         Self {
-            selectors: Selectors::new().expect("Failed to initialize selectors"),
             client: Client::builder()
                 .cookie_store(true)
                 .build()
@@ -74,15 +40,8 @@ impl Default for EePlugin {
 }
 
 impl EePlugin {
-    const KST_OFFSET: UtcOffset = match UtcOffset::from_hms(9, 0, 0) {
-        Ok(offset) => offset,
-        Err(_) => panic!("Invalid KST offset"),
-    };
-
     async fn fetch_page_html(&self, url: &str) -> Result<String, PluginError> {
-        self.client
-            .get(url)
-            .send()
+        send::<Self>(self.client.get(url))
             .await
             .map_err(|e| {
                 PluginError::request::<Self>(format!("Failed to send request to {url}: {e}"))
@@ -95,35 +54,9 @@ impl EePlugin {
     }
 
     fn parse_date_string(&self, date_str: &str) -> Result<OffsetDateTime, PluginError> {
-        let format_description_datetime =
-            format_description!("[year]-[month]-[day] [hour]:[minute]:[second]");
-        let format_description_date = format_description!("[year].[month].[day]");
-
-        if let Ok(dt) = PrimitiveDateTime::parse(date_str, &format_description_datetime) {
-            Ok(dt.assume_offset(Self::KST_OFFSET))
-        } else if let Ok(d) = Date::parse(date_str, &format_description_date) {
-            Ok(d.with_time(Time::MIDNIGHT).assume_offset(Self::KST_OFFSET))
-        } else {
-            Err(PluginError::parse::<Self>(format!(
-                "Failed to parse date string: {date_str}"
-            )))
-        }
-    }
-
-    fn extract_text(element: &scraper::ElementRef, selector: &Selector) -> Option<String> {
-        element
-            .select(selector)
-            .next()
-            .map(|el| el.text().collect::<String>().trim().to_string())
-            .filter(|s| !s.is_empty())
-    }
-
-    fn extract_html(element: &scraper::ElementRef, selector: &Selector) -> Option<String> {
-        element
-            .select(selector)
-            .next()
-            .map(|el| el.inner_html().trim().to_string())
-            .filter(|s| !s.is_empty())
+        boardview::parse_date(date_str).ok_or_else(|| {
+            PluginError::parse::<Self>(format!("Failed to parse date string: {date_str}"))
+        })
     }
 
     fn extract_idx_from_url(url_str: &str) -> Result<String, PluginError> {
@@ -132,18 +65,9 @@ impl EePlugin {
                 "Failed to parse URL for idx extraction {url_str}: {e}"
             ))
         })?;
-        parsed_url
-            .query_pairs()
-            .find_map(|key_value| {
-                if key_value.0 == "idx" {
-                    Some(key_value.1.into_owned())
-                } else {
-                    None
-                }
-            })
-            .ok_or_else(|| {
-                PluginError::parse::<EePlugin>(format!("Could not find 'idx' in URL: {url_str}"))
-            })
+        Self::query_post_id(&parsed_url, "idx").ok_or_else(|| {
+            PluginError::parse::<EePlugin>(format!("Could not find 'idx' in URL: {url_str}"))
+        })
     }
 }
 
@@ -153,9 +77,23 @@ impl SsufidPlugin for EePlugin {
     const TITLE: &'static str = "숭실대학교 전기공학부";
     const DESCRIPTION: &'static str = "숭실대학교 전기공학부 학부소식 공지사항을 제공합니다.";
     const BASE_URL: &'static str = "http://ee.ssu.ac.kr/sub/sub05_02.php";
+    const ID_CATEGORY_PARAM: Option<&'static str> = Some("category");
+    // 2: parsed by the shared board-view skin, with canonical post URLs
+    const VERSION: u32 = 2;
+    const CRITICAL_SELECTORS: &'static [(&'static str, &'static str)] =
+        boardview::CRITICAL_SELECTORS;
+
+    fn http_client(&self) -> reqwest::Client {
+        self.client.clone()
+    }
 }
 
 impl SsufidPostPlugin for EePlugin {
+    /// The board sets a session cookie on the first visit, which the list pages expect.
+    async fn warmup(&self) -> Result<(), PluginError> {
+        self.fetch_page_html(Self::BASE_URL).await.map(|_| ())
+    }
+
     // Kept `async fn` but without #[async_trait]
     // This requires the compiler to handle `async fn` in traits implicitly,
     // or match it with `impl Future` if the signatures are compatible.
@@ -163,13 +101,6 @@ impl SsufidPostPlugin for EePlugin {
         let mut results = Vec::new();
         let mut page = 1;
 
-        #[derive(Debug)]
-        struct PostListItemInfo {
-            notice: bool,
-            relative_url: String,
-            title_on_list: String,
-        }
-
         loop {
             if results.len() >= posts_limit as usize && posts_limit > 0 {
                 break;
@@ -184,135 +115,61 @@ impl SsufidPostPlugin for EePlugin {
 
             let (items_to_fetch, has_next_page) = {
                 let list_doc = Html::parse_document(&list_html);
-                let mut items_to_fetch_current_page = Vec::new();
-                for item_el in list_doc.select(&self.selectors.post_item) {
-                    let notice = item_el.attr("class").is_some_and(|c| c.contains("label"));
-                    if let Some(link_el) = item_el.select(&self.selectors.post_link).next()
-                        && let Some(href) = link_el.value().attr("href")
-                    {
-                        let title_on_list = link_el.text().collect::<String>().trim().to_string();
-                        if !title_on_list.is_empty() {
-                            items_to_fetch_current_page.push(PostListItemInfo {
-                                notice,
-                                relative_url: href.trim().to_string(),
-                                title_on_list,
-                            });
-                        }
-                    }
-                }
-                let has_next_page_current = list_doc
-                    .select(&self.selectors.next_page_link)
-                    .next()
-                    .is_some();
-                (items_to_fetch_current_page, has_next_page_current)
+                (
+                    boardview::parse_list(&list_doc),
+                    boardview::has_next_page(&list_doc),
+                )
             };
 
-            // Removed empty if block: if items_to_fetch.is_empty() && page == 1 {}
-
             let mut posts_found_on_current_page = 0;
             for item_info in items_to_fetch {
                 if results.len() >= posts_limit as usize && posts_limit > 0 {
                     break;
                 }
 
-                let post_view_url = full_url(Self::BASE_URL, &item_info.relative_url)?;
+                let post_view_url = full_url(Self::BASE_URL, &item_info.href)?;
                 let post_id = Self::extract_idx_from_url(&post_view_url)?;
 
                 let view_html = self.fetch_page_html(&post_view_url).await?;
-
-                let (title, author_str, created_date_str, content_str, attachments_data) = {
-                    let view_doc = Html::parse_document(&view_html);
-                    let title = Self::extract_text(
-                        &view_doc.root_element(),
-                        &self.selectors.post_title_view,
-                    )
-                    .unwrap_or_else(|| item_info.title_on_list.clone());
-
-                    let author_str = Self::extract_text(
-                        &view_doc.root_element(),
-                        &self.selectors.post_author_view,
-                    )
-                    .unwrap_or_else(|| "전기공학부".to_string());
-
-                    let created_date_str = Self::extract_text(
-                        &view_doc.root_element(),
-                        &self.selectors.post_date_view,
-                    )
-                    .ok_or_else(|| {
-                        PluginError::parse::<Self>("Could not find date string on view page".into())
-                    })?;
-
-                    let content_str = Self::extract_html(
-                        &view_doc.root_element(),
-                        &self.selectors.post_content_view,
-                    )
-                    .ok_or_else(|| {
-                        PluginError::parse::<Self>("Could not find content on view page".into())
-                    })?;
-
-                    let mut attachments_data_local = Vec::new();
-                    for att_el in view_doc.select(&self.selectors.attachment_link) {
-                        if let Some(href_attr) = att_el.value().attr("href") {
-                            let att_name_str = att_el.text().collect::<String>().trim().to_string();
-                            let att_url_res = if href_attr.starts_with("javascript:download") {
-                                let params_str = href_attr
-                                    .replace("javascript:download(", "")
-                                    .replace(")", "");
-                                let params: Vec<&str> = params_str
-                                    .split(',')
-                                    .map(|s| s.trim().trim_matches('\''))
-                                    .collect();
-                                if params.len() == 3 {
-                                    Ok(format!(
-                                        "{}/module/board/download.php?boardid={}&b_idx={}&idx={}",
-                                        Self::BASE_URL,
-                                        params[0],
-                                        params[1],
-                                        params[2]
-                                    ))
-                                } else {
-                                    full_url(Self::BASE_URL, href_attr)
-                                }
-                            } else {
-                                full_url(Self::BASE_URL, href_attr)
-                            };
-                            if let Ok(att_url) = att_url_res {
-                                attachments_data_local.push((att_name_str, att_url));
-                            }
-                        }
-                    }
-                    (
-                        title,
-                        author_str,
-                        created_date_str,
-                        content_str,
-                        attachments_data_local,
-                    )
-                };
-
+                let view = boardview::parse_view(
+                    &Html::parse_document(&view_html),
+                    &Url::parse(&post_view_url).map_err(|e| {
+                        PluginError::parse::<Self>(format!("Invalid post URL {post_view_url}: {e}"))
+                    })?,
+                );
+
+                let created_date_str = view.date.ok_or_else(|| {
+                    PluginError::parse::<Self>("Could not find date string on view page".into())
+                })?;
                 let created_at = self.parse_date_string(&created_date_str)?;
-                let mut final_attachments = Vec::new();
-                for (att_name_str, att_url) in attachments_data {
-                    final_attachments.push(Attachment {
-                        name: if att_name_str.is_empty() {
-                            Some(format!("Attachment for post {post_id}"))
-                        } else {
-                            Some(att_name_str)
-                        },
-                        url: att_url,
-                        mime_type: None,
-                    });
-                }
+                let content = view.content.ok_or_else(|| {
+                    PluginError::parse::<Self>("Could not find content on view page".into())
+                })?;
+                let attachments = view
+                    .attachments
+                    .into_iter()
+                    .map(|attachment| Attachment {
+                        name: attachment
+                            .name
+                            .or_else(|| Some(format!("Attachment for post {post_id}"))),
+                        ..attachment
+                    })
+                    .collect();
 
                 results.push(SsufidPost {
                     id: post_id,
-                    title,
-                    author: Some(author_str),
-                    content: content_str,
-                    url: post_view_url,
+                    title: view.title.unwrap_or(item_info.title),
+                    author: Some(view.author.unwrap_or_else(|| "전기공학부".to_string())),
+                    content,
+                    // The offset and search parameters on list links are not stored
+                    url: ssufid::url::canonicalize(
+                        &post_view_url,
+                        &["boardid", "mode", "idx", "category"],
+                    ),
+                    url_kind: UrlKind::Canonical,
                     created_at,
                     updated_at: None,
-                    attachments: final_attachments,
+                    attachments,
                     description: None,
                     category: item_info
                         .notice
@@ -320,6 +177,7 @@ impl SsufidPostPlugin for EePlugin {
                         .unwrap_or_default(),
                     thumbnail: None,
                     metadata: None,
+                    author_detail: None,
                 });
                 posts_found_on_current_page += 1;
             }
@@ -339,6 +197,7 @@ impl SsufidPostPlugin for EePlugin {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use time::{Date, PrimitiveDateTime, Time};
 
     #[tokio::test]
     async fn test_full_url_constructor() {
@@ -351,7 +210,7 @@ mod tests {
     #[tokio::test]
     async fn test_date_parsing() {
         let plugin = EePlugin::default();
-        let kst = EePlugin::KST_OFFSET;
+        let kst = ssufid::datetime::KST;
 
         let dt_str1 = "2025-05-12 14:44:58";
         let expected_dt1 = PrimitiveDateTime::new(
@@ -366,6 +225,9 @@ mod tests {
     async fn test_extract_idx_from_url() {
         let url1 = "http://ee.ssu.ac.kr/sub/sub05_01.php?boardid=notice&mode=view&idx=232&sk=&sw=&offset=&category=";
         assert_eq!(EePlugin::extract_idx_from_url(url1).unwrap(), "232");
+        let url2 =
+            "http://ee.ssu.ac.kr/sub/sub05_01.php?boardid=notice&mode=view&idx=232&category=2";
+        assert_eq!(EePlugin::extract_idx_from_url(url2).unwrap(), "2-232");
     }
 
     #[tokio::test]
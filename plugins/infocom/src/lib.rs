@@ -1,8 +1,18 @@
-use futures::stream::{FuturesOrdered, StreamExt};
-use scraper::{Html, Selector};
+use futures::{
+    Stream, TryStreamExt, future,
+    stream::{self, FuturesOrdered, StreamExt},
+};
+use scraper::{ElementRef, Html, Selector};
 use ssufid::{
-    core::{Attachment, SsufidPlugin, SsufidPost, SsufidPostPlugin},
+    SsufidCore,
+    core::{
+        Attachment, CrawlOptions, PostIdOrd, SsufidPlugin, SsufidPost, SsufidPostPlugin, UrlKind,
+        report_post_failure,
+    },
     error::PluginError,
+    fetch::{FetchedPage, send},
+    html::{is_notice_row, select_expecting},
+    paginate::{ListPage, Paginator},
 };
 use time::{
     Date, OffsetDateTime,
@@ -17,6 +27,7 @@ struct InfocomPostMetadata {
     url: String,
     title: String,
     date: OffsetDateTime,
+    pinned: bool,
 }
 
 // Selectors struct (defined earlier)
@@ -24,6 +35,8 @@ struct Selectors {
     post_container: Selector,
     title: Selector,
     date: Selector,
+    view_box: Selector,
+    detail_date: Selector,
     post_content_container: Selector,
     post_files: Selector,
 }
@@ -34,6 +47,8 @@ impl Selectors {
             post_container: Selector::parse("a.con_box").unwrap(),
             title: Selector::parse("div.subject span").unwrap(),
             date: Selector::parse("ul.info li.date").unwrap(),
+            view_box: Selector::parse("div.view_box").unwrap(),
+            detail_date: Selector::parse("div.view_box ul.info li.date").unwrap(),
             post_content_container: Selector::parse("div.view_box div.con").unwrap(),
             post_files: Selector::parse("div.view_box div.file a").unwrap(),
         }
@@ -43,12 +58,55 @@ impl Selectors {
 // PostDetailExtras struct (defined earlier)
 #[derive(Debug, Default)]
 struct PostDetailExtras {
+    /// The title on the detail page, used when a post is fetched without the list.
+    title: Option<String>,
+    /// The date on the detail page, preferred over the list's.
+    created_at: Option<OffsetDateTime>,
     content: String,
     attachments: Vec<Attachment>,
+    /// Whether the page was too large and parsed without styles and data: URIs.
+    content_stripped: bool,
+}
+
+impl InfocomPostMetadata {
+    /// Builds the post from list metadata, adding the detail page's content if it was fetched.
+    fn into_post(self, details: Option<PostDetailExtras>) -> SsufidPost {
+        let (created_at, content, attachments, content_stripped) = match details {
+            Some(details) => (
+                details.created_at.unwrap_or(self.date),
+                details.content,
+                details.attachments,
+                details.content_stripped,
+            ),
+            None => (self.date, String::new(), Vec::new(), false),
+        };
+        let mut post = SsufidPost {
+            id: self.id,
+            url: self.url,
+            url_kind: UrlKind::Canonical,
+            title: self.title,
+            created_at,
+            author: None,         // Author info is not available
+            description: None, // Description can be part of content if needed, or fetched separately
+            category: Vec::new(), // Category info is not available
+            updated_at: None,  // Updated at info is not available
+            thumbnail: None,   // Thumbnail info is not available
+            content,
+            attachments,
+            metadata: None, // No specific extra metadata for now
+            author_detail: None,
+        };
+        if content_stripped {
+            post.mark_content_stripped();
+        }
+        post
+    }
 }
 
 pub struct InfocomPlugin {
     selectors: Selectors,
+    host_url: String,
+    base_url: String,
 }
 
 impl Default for InfocomPlugin {
@@ -59,21 +117,63 @@ impl Default for InfocomPlugin {
 
 impl InfocomPlugin {
     const HOST_URL: &'static str = "http://infocom.ssu.ac.kr";
+    const LIST_PATH: &'static str = "/kor/notice/undergraduate.php";
     const DATE_FORMAT: &[BorrowedFormatItem<'_>] = format_description!("[year]. [month]. [day]");
+    /// Detail pages fetched concurrently while streaming.
+    const DETAIL_CONCURRENCY: usize = 4;
 
     pub fn new() -> Self {
         InfocomPlugin {
             selectors: Selectors::new(),
+            host_url: Self::HOST_URL.to_string(),
+            base_url: Self::BASE_URL.to_string(),
+        }
+    }
+
+    /// Crawls the site at `host_url` instead, e.g. a mock server.
+    pub fn with_base_url(host_url: impl Into<String>) -> Self {
+        let host_url = host_url.into();
+        InfocomPlugin {
+            base_url: format!("{}{}", host_url, Self::LIST_PATH),
+            host_url,
+            selectors: Selectors::new(),
         }
     }
 
+    fn client() -> Result<reqwest::Client, PluginError> {
+        reqwest::Client::builder()
+            .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36")
+            .build()
+            .map_err(|e| PluginError::request::<Self>(e.to_string()))
+    }
+
+    /// The URL of the post `id`, on this instance's base URL.
+    fn post_url(&self, id: &str) -> Result<String, PluginError> {
+        let mut url = Url::parse(&self.base_url)
+            .map_err(|e| PluginError::parse::<Self>(format!("Failed to parse BASE_URL: {e}")))?;
+        url.query_pairs_mut().append_pair("idx", id);
+        Ok(url.to_string())
+    }
+
+    /// Reads the title from a list item or the detail page's content box.
+    fn title_in(&self, element: ElementRef) -> Option<String> {
+        let title = element.select(&self.selectors.title).next()?;
+        Some(title.text().collect::<String>().trim().to_string())
+    }
+
+    fn parse_date(date_str: &str) -> Option<OffsetDateTime> {
+        Date::parse(date_str.trim(), Self::DATE_FORMAT)
+            .ok()
+            .map(|date| date.midnight().assume_offset(offset!(+09:00)))
+    }
+
     async fn fetch_page_posts_metadata(
         &self,
         page: u32,
         client: &reqwest::Client,
     ) -> Result<Vec<InfocomPostMetadata>, PluginError> {
-        let page_url = format!("{}?pNo={}&code=notice", Self::BASE_URL, page);
-        let response = client.get(&page_url).send().await.map_err(|e| {
+        let page_url = format!("{}?pNo={}&code=notice", self.base_url, page);
+        let response = send::<Self>(client.get(&page_url)).await.map_err(|e| {
             PluginError::request::<Self>(format!("Failed to fetch page {page_url}: {e}"))
         })?;
 
@@ -90,53 +190,42 @@ impl InfocomPlugin {
         })?;
 
         let document = Html::parse_document(&html_content);
-        let host_url_parsed = Url::parse(Self::HOST_URL)
+        let host_url_parsed = Url::parse(&self.host_url)
             .map_err(|e| PluginError::parse::<Self>(format!("Failed to parse HOST_URL: {e}")))?;
 
-        let posts_metadata = document
-            .select(&self.selectors.post_container)
-            .filter_map(|element| {
-                let relative_url = element.value().attr("href")?;
-                let post_url_obj = host_url_parsed.join(relative_url).ok()?;
-                let post_url = post_url_obj.to_string();
-
-                let id = post_url_obj.query_pairs().find_map(|(key, value)| {
-                    if key == "idx" {
-                        Some(value.into_owned())
-                    } else {
-                        None
-                    }
-                })?;
-
-                let title = element
-                    .select(&self.selectors.title)
-                    .next()?
-                    .text()
-                    .collect::<String>()
-                    .trim()
-                    .to_string();
-
-                let date_str = element
-                    .select(&self.selectors.date)
-                    .next()?
-                    .text()
-                    .collect::<String>()
-                    .trim()
-                    .to_string();
-
-                let date = Date::parse(&date_str, Self::DATE_FORMAT)
-                    .ok()?
-                    .midnight()
-                    .assume_offset(offset!(+09:00));
-
-                Some(InfocomPostMetadata {
-                    id,
-                    url: post_url,
-                    title,
-                    date,
+        let posts_metadata =
+            select_expecting::<Self>(&document, &self.selectors.post_container, &page_url)
+                .filter_map(|element| {
+                    let relative_url = element.value().attr("href")?;
+                    let post_url_obj = host_url_parsed.join(relative_url).ok()?;
+                    let post_url = post_url_obj.to_string();
+
+                    let id = post_url_obj.query_pairs().find_map(|(key, value)| {
+                        if key == "idx" {
+                            Some(value.into_owned())
+                        } else {
+                            None
+                        }
+                    })?;
+
+                    let title = self.title_in(element)?;
+
+                    let date_str = element
+                        .select(&self.selectors.date)
+                        .next()?
+                        .text()
+                        .collect::<String>();
+                    let date = Self::parse_date(&date_str)?;
+
+                    Some(InfocomPostMetadata {
+                        id,
+                        url: post_url,
+                        title,
+                        date,
+                        pinned: is_notice_row(element),
+                    })
                 })
-            })
-            .collect::<Vec<_>>();
+                .collect::<Vec<_>>();
 
         Ok(posts_metadata)
     }
@@ -146,12 +235,14 @@ impl InfocomPlugin {
         post_metadata: &InfocomPostMetadata,
         client: &reqwest::Client,
     ) -> Result<PostDetailExtras, PluginError> {
-        let response = client.get(&post_metadata.url).send().await.map_err(|e| {
-            PluginError::request::<Self>(format!(
-                "Failed to fetch post page {}: {}",
-                &post_metadata.url, e
-            ))
-        })?;
+        let response = send::<Self>(client.get(&post_metadata.url))
+            .await
+            .map_err(|e| {
+                PluginError::request::<Self>(format!(
+                    "Failed to fetch post page {}: {}",
+                    &post_metadata.url, e
+                ))
+            })?;
 
         if !response.status().is_success() {
             return Err(PluginError::request::<Self>(format!(
@@ -168,13 +259,27 @@ impl InfocomPlugin {
             ))
         })?;
 
-        let document = Html::parse_document(&html_content);
+        let page = FetchedPage::bounded::<Self>(html_content);
+        let document = Html::parse_document(&page.text);
         let mut attachments = Vec::new();
 
-        let content_html = document
-            .select(&self.selectors.post_content_container)
+        let title = document
+            .select(&self.selectors.view_box)
+            .next()
+            .and_then(|element| self.title_in(element))
+            .filter(|title| !title.is_empty());
+        let created_at = document
+            .select(&self.selectors.detail_date)
             .next()
-            .map_or(String::new(), |element| element.inner_html());
+            .and_then(|element| Self::parse_date(&element.text().collect::<String>()));
+
+        let content_html = select_expecting::<Self>(
+            &document,
+            &self.selectors.post_content_container,
+            &post_metadata.url,
+        )
+        .next()
+        .map_or(String::new(), |element| element.inner_html());
 
         for file_element in document.select(&self.selectors.post_files) {
             if let Some(href) = file_element.value().attr("href") {
@@ -189,15 +294,95 @@ impl InfocomPlugin {
                     name: if name.is_empty() { None } else { Some(name) },
                     url: attachment_url,
                     mime_type: None,
+                    metadata: None,
                 });
             }
         }
 
         Ok(PostDetailExtras {
+            title,
+            created_at,
             content: content_html,
             attachments,
+            content_stripped: page.stripped,
         })
     }
+
+    /// Streams list metadata page by page, fetching the next page only once the previous one
+    /// has been consumed.
+    fn metadata_stream(
+        &self,
+        client: reqwest::Client,
+        empty_page_tolerance: u32,
+    ) -> impl Stream<Item = Result<InfocomPostMetadata, PluginError>> + Send {
+        // (next page, consecutive empty pages so far), or None when there is nothing left
+        stream::try_unfold(Some((1, 0)), move |state| {
+            let client = client.clone();
+            async move {
+                let Some((page, empty_pages)) = state else {
+                    return Ok(None);
+                };
+                let page_metadata = self.fetch_page_posts_metadata(page, &client).await?;
+                let empty_pages = if page_metadata.is_empty() {
+                    empty_pages + 1
+                } else {
+                    0
+                };
+                let next = (empty_pages <= empty_page_tolerance).then_some((page + 1, empty_pages));
+                Ok(Some((
+                    stream::iter(page_metadata.into_iter().map(Ok)),
+                    next,
+                )))
+            }
+        })
+        .try_flatten()
+    }
+
+    /// Builds posts from list metadata, fetching detail pages if `detail` is set.
+    async fn posts_from_metadata(
+        &self,
+        all_metadata: Vec<InfocomPostMetadata>,
+        client: &reqwest::Client,
+        detail: bool,
+    ) -> Result<Vec<SsufidPost>, PluginError> {
+        if !detail {
+            // The list has the title and date, so the detail page is not needed
+            return Ok(all_metadata
+                .into_iter()
+                .map(|meta| meta.into_post(None))
+                .collect());
+        }
+
+        let mut fetch_futures = FuturesOrdered::new();
+        for meta in all_metadata {
+            let client_clone = client.clone();
+            // meta is already owned, no need to clone it here for moving into async block
+            fetch_futures.push_back(async move {
+                match self.fetch_full_post_details(&meta, &client_clone).await {
+                    Ok(details) => Ok((meta, details)), // Pass meta along
+                    Err(e) => Err((meta, e)),           // Propagate error
+                }
+            });
+        }
+
+        let mut final_posts = Vec::new();
+        while let Some(result) = fetch_futures.next().await {
+            match result {
+                Ok((meta, details)) => final_posts.push(meta.into_post(Some(details))),
+                Err((meta, e)) => {
+                    // Log the error and continue processing other posts
+                    // It's important to decide if one failure should fail all.
+                    // Here, we log and skip. `e` already contains post_id/url if it's from fetch_full_post_details
+                    tracing::warn!(
+                        "Failed to fetch or parse details for a post: {:?}. Skipping.",
+                        e
+                    );
+                    report_post_failure::<Self>(&meta.id, &meta.url, &e);
+                }
+            }
+        }
+        Ok(final_posts)
+    }
 }
 
 impl SsufidPlugin for InfocomPlugin {
@@ -205,17 +390,43 @@ impl SsufidPlugin for InfocomPlugin {
     const TITLE: &'static str = "숭실대학교 전자정보공학부 공지사항";
     const DESCRIPTION: &'static str = "숭실대학교 전자정보공학부 공지사항을 제공합니다.";
     const BASE_URL: &'static str = "http://infocom.ssu.ac.kr/kor/notice/undergraduate.php";
+    // 2: created_at from the detail page's date when it has one
+    const VERSION: u32 = 2;
+    const CRITICAL_SELECTORS: &'static [(&'static str, &'static str)] = &[
+        ("list_item", "a.con_box"),
+        ("title", "div.subject span"),
+        ("date", "ul.info li.date"),
+        ("content", "div.view_box div.con"),
+    ];
+
+    fn base_url(&self) -> std::borrow::Cow<'_, str> {
+        std::borrow::Cow::Borrowed(&self.base_url)
+    }
+
+    fn http_client(&self) -> reqwest::Client {
+        Self::client().unwrap_or_else(|_| ssufid::fetch::default_client())
+    }
 }
 
 impl SsufidPostPlugin for InfocomPlugin {
+    const SKIPS_DETAIL: bool = true;
+
     async fn crawl(&self, posts_limit: u32) -> Result<Vec<SsufidPost>, PluginError> {
-        let client = reqwest::Client::builder()
-            .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36")
-            .build()
-            .map_err(|e| PluginError::request::<Self>(e.to_string()))?;
+        self.crawl_with_options(&CrawlOptions::new(posts_limit))
+            .await
+    }
+
+    async fn crawl_with_options(
+        &self,
+        options: &CrawlOptions,
+    ) -> Result<Vec<SsufidPost>, PluginError> {
+        let posts_limit = options.posts_limit;
+        let client = Self::client()?;
 
+        let cutoff = options.cutoff(options.current_time());
         let mut all_metadata: Vec<InfocomPostMetadata> = Vec::new();
         let mut page = 1;
+        let mut empty_pages = 0;
 
         loop {
             if posts_limit > 0 && all_metadata.len() >= posts_limit as usize {
@@ -225,12 +436,26 @@ impl SsufidPostPlugin for InfocomPlugin {
                 break;
             }
 
-            let mut page_metadata = self.fetch_page_posts_metadata(page, &client).await?;
+            let page_metadata = self.fetch_page_posts_metadata(page, &client).await?;
             if page_metadata.is_empty() {
-                break; // No more posts on subsequent pages
+                empty_pages += 1;
+                if empty_pages > options.empty_page_tolerance {
+                    break; // No more posts on subsequent pages
+                }
+            } else {
+                empty_pages = 0;
             }
-            all_metadata.append(&mut page_metadata);
+            let page_metadata = ListPage::split(page_metadata, |meta| meta.pinned);
+            let expired = cutoff.is_some_and(|cutoff| page_metadata.is_past(cutoff, |m| m.date));
+            all_metadata.extend(
+                page_metadata
+                    .into_rows()
+                    .filter(|meta| cutoff.is_none_or(|cutoff| meta.date >= cutoff)),
+            );
             page += 1;
+            if expired {
+                break;
+            }
         }
 
         if posts_limit > 0 {
@@ -238,54 +463,118 @@ impl SsufidPostPlugin for InfocomPlugin {
             all_metadata.truncate(posts_limit as usize);
         }
 
-        let mut fetch_futures = FuturesOrdered::new();
-        for meta in all_metadata {
-            let client_clone = client.clone();
-            // meta is already owned, no need to clone it here for moving into async block
-            fetch_futures.push_back(async move {
-                match self.fetch_full_post_details(&meta, &client_clone).await {
-                    Ok(details) => Ok((meta, details)), // Pass meta along
-                    Err(e) => Err(e),                   // Propagate error
-                }
-            });
-        }
+        self.posts_from_metadata(all_metadata, &client, options.detail)
+            .await
+    }
 
-        let mut final_posts = Vec::new();
-        while let Some(result) = fetch_futures.next().await {
-            match result {
-                Ok((meta, details)) => {
-                    final_posts.push(SsufidPost {
-                        id: meta.id,
-                        url: meta.url,
-                        title: meta.title,
-                        created_at: meta.date,
-                        author: None,         // Author info is not available
-                        description: None, // Description can be part of content if needed, or fetched separately
-                        category: Vec::new(), // Category info is not available
-                        updated_at: None,  // Updated at info is not available
-                        thumbnail: None,   // Thumbnail info is not available
-                        content: details.content,
-                        attachments: details.attachments,
-                        metadata: None, // No specific extra metadata for now
-                    });
-                }
-                Err(e) => {
-                    // Log the error and continue processing other posts
-                    // It's important to decide if one failure should fail all.
-                    // Here, we log and skip. `e` already contains post_id/url if it's from fetch_full_post_details
-                    tracing::warn!(
-                        "Failed to fetch or parse details for a post: {:?}. Skipping.",
-                        e
-                    );
-                }
+    fn crawl_stream(
+        &self,
+        posts_limit: u32,
+    ) -> impl Stream<Item = Result<SsufidPost, PluginError>> + Send {
+        let options = CrawlOptions::new(posts_limit);
+        let limit = match posts_limit {
+            0 => usize::MAX,
+            limit => limit as usize,
+        };
+        stream::once(future::ready(Self::client()))
+            .map_ok(move |client| {
+                self.metadata_stream(client.clone(), options.empty_page_tolerance)
+                    .take(limit)
+                    .map_ok(move |meta| {
+                        let client = client.clone();
+                        async move {
+                            match self.fetch_full_post_details(&meta, &client).await {
+                                Ok(details) => Ok(Some(meta.into_post(Some(details)))),
+                                Err(e) => {
+                                    tracing::warn!(
+                                        "Failed to fetch or parse details for a post: {:?}. Skipping.",
+                                        e
+                                    );
+                                    report_post_failure::<Self>(&meta.id, &meta.url, &e);
+                                    Ok(None)
+                                }
+                            }
+                        }
+                    })
+                    .try_buffered(Self::DETAIL_CONCURRENCY)
+                    .try_filter_map(future::ok)
+            })
+            .try_flatten()
+    }
+
+    /// Fetches a single post by its `idx` or detail page URL, reading the title and date from
+    /// the detail page.
+    async fn fetch_single(&self, id_or_url: &str) -> Result<Option<SsufidPost>, PluginError> {
+        let url = if id_or_url.contains("://") {
+            id_or_url.to_string()
+        } else {
+            self.post_url(id_or_url)?
+        };
+        let id = Url::parse(&url)
+            .ok()
+            .and_then(|url| {
+                url.query_pairs()
+                    .find(|(key, _)| key == "idx")
+                    .map(|(_, value)| value.into_owned())
+            })
+            .ok_or_else(|| PluginError::parse::<Self>(format!("No idx in post URL {url}")))?;
+        let client = Self::client()?;
+        let mut meta = InfocomPostMetadata {
+            id,
+            url,
+            title: String::new(),
+            date: OffsetDateTime::UNIX_EPOCH,
+            pinned: false,
+        };
+        let mut details = self.fetch_full_post_details(&meta, &client).await?;
+        meta.title = details
+            .title
+            .take()
+            .ok_or_else(|| PluginError::structure::<Self>("the title", &meta.url))?;
+        meta.date = details
+            .created_at
+            .ok_or_else(|| PluginError::structure::<Self>("the date", &meta.url))?;
+        Ok(Some(meta.into_post(Some(details))))
+    }
+
+    async fn crawl_since_id(&self, last_id: &str) -> Result<Vec<SsufidPost>, PluginError> {
+        let client = Self::client()?;
+        let last_id = PostIdOrd(last_id);
+
+        // Bounded by a default crawl in case `last_id` is not on the list
+        let mut paginator = Paginator::new(SsufidCore::POST_COUNT_LIMIT);
+        while let Some(page) = paginator.next_page() {
+            let page_metadata = self.fetch_page_posts_metadata(page, &client).await?;
+            // Ids descend, so if the last post is already seen the next page is too
+            let reached_last = page_metadata
+                .last()
+                .is_none_or(|meta| PostIdOrd(&meta.id) <= last_id);
+            paginator.push_page(
+                page_metadata
+                    .into_iter()
+                    .filter(|meta| PostIdOrd(&meta.id) > last_id),
+                |meta| meta.id.clone(),
+            );
+            if reached_last {
+                paginator.stop();
             }
         }
-        Ok(final_posts)
+
+        self.posts_from_metadata(paginator.into_items(), &client, true)
+            .await
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
+    use time::macros::datetime;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path, query_param, query_param_is_missing},
+    };
+
     use super::*; // Imports SsuInfocomPlugin, SsufidPlugin, etc.
     // Tokio is brought in by the test macro
 
@@ -571,4 +860,471 @@ mod tests {
             post.id
         );
     }
+
+    #[tokio::test]
+    async fn test_crawl_without_detail_skips_post_pages() {
+        let server = MockServer::start().await;
+        let list_path = "/kor/notice/undergraduate.php";
+        Mock::given(method("GET"))
+            .and(path(list_path))
+            .and(query_param("pNo", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                r#"<div class="board_list">
+                    <a class="con_box" href="{list_path}?idx=102">
+                        <div class="subject"><span>캡스톤디자인 발표회 안내</span></div>
+                        <ul class="info"><li class="date">2025. 03. 04</li></ul>
+                    </a>
+                    <a class="con_box" href="{list_path}?idx=101">
+                        <div class="subject"><span>수강신청 안내</span></div>
+                        <ul class="info"><li class="date">2025. 03. 02</li></ul>
+                    </a>
+                </div>"#
+            )))
+            .mount(&server)
+            .await;
+
+        let plugin = InfocomPlugin::with_base_url(server.uri());
+        let posts = plugin
+            .crawl_with_options(&CrawlOptions::new(2).detail(false))
+            .await
+            .unwrap();
+
+        assert_eq!(posts.len(), 2);
+        assert_eq!(posts[0].id, "102");
+        assert_eq!(posts[0].title, "캡스톤디자인 발표회 안내");
+        assert_eq!(posts[0].created_at, datetime!(2025-03-04 00:00 +9));
+        assert!(posts.iter().all(|p| p.content.is_empty()));
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(
+            requests
+                .iter()
+                .all(|r| r.url.query_pairs().all(|(k, _)| k != "idx"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_detail_page_date_overrides_list_date() {
+        let server = MockServer::start().await;
+        let list_path = "/kor/notice/undergraduate.php";
+        Mock::given(method("GET"))
+            .and(path(list_path))
+            .and(query_param("pNo", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                r#"<div class="board_list">
+                    <a class="con_box" href="{list_path}?idx=102">
+                        <div class="subject"><span>캡스톤디자인 발표회 안내</span></div>
+                        <ul class="info"><li class="date">2025. 03. 04</li></ul>
+                    </a>
+                </div>"#
+            )))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(list_path))
+            .and(query_param("idx", "102"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<div class="view_box">
+                    <ul class="info"><li class="date">2025. 03. 06</li></ul>
+                    <div class="con"><p>발표회 일정이 변경되었습니다.</p></div>
+                </div>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let plugin = InfocomPlugin::with_base_url(server.uri());
+        let posts = plugin
+            .crawl_with_options(&CrawlOptions::new(1))
+            .await
+            .unwrap();
+
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].created_at, datetime!(2025-03-06 00:00 +9));
+        assert!(posts[0].content.contains("발표회 일정이 변경되었습니다."));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_single_reads_post_page_by_id() {
+        let server = MockServer::start().await;
+        let list_path = "/kor/notice/undergraduate.php";
+        Mock::given(method("GET"))
+            .and(path(list_path))
+            .and(query_param("idx", "102"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<div class="view_box">
+                    <div class="subject"><span>캡스톤디자인 발표회 안내</span></div>
+                    <ul class="info"><li class="date">2025. 03. 06</li></ul>
+                    <div class="con"><p>발표회 일정이 변경되었습니다.</p></div>
+                </div>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let plugin = InfocomPlugin::with_base_url(server.uri());
+        let post = plugin.fetch_single("102").await.unwrap().unwrap();
+        assert_eq!(post.id, "102");
+        assert_eq!(post.url, format!("{}{list_path}?idx=102", server.uri()));
+        assert_eq!(post.title, "캡스톤디자인 발표회 안내");
+        assert_eq!(post.created_at, datetime!(2025-03-06 00:00 +9));
+        assert!(post.content.contains("발표회 일정이 변경되었습니다."));
+
+        // It can also be fetched by the URL left in the retry queue
+        let by_url = plugin.fetch_single(&post.url).await.unwrap().unwrap();
+        assert_eq!(by_url, post);
+    }
+
+    #[tokio::test]
+    async fn test_very_large_detail_page_is_stripped_before_parsing() {
+        let server = MockServer::start().await;
+        let list_path = "/kor/notice/undergraduate.php";
+        Mock::given(method("GET"))
+            .and(path(list_path))
+            .and(query_param("pNo", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                r#"<div class="board_list">
+                    <a class="con_box" href="{list_path}?idx=102">
+                        <div class="subject"><span>캡스톤디자인 포스터</span></div>
+                        <ul class="info"><li class="date">2025. 03. 04</li></ul>
+                    </a>
+                </div>"#
+            )))
+            .mount(&server)
+            .await;
+        // A detail page with a 5MB base64 inline image
+        let image = "A".repeat(5 * 1024 * 1024);
+        Mock::given(method("GET"))
+            .and(path(list_path))
+            .and(query_param("idx", "102"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                r#"<div class="view_box">
+                    <div class="con"><p>포스터를 확인하세요.</p><img src="data:image/jpeg;base64,{image}"></div>
+                </div>"#
+            )))
+            .mount(&server)
+            .await;
+
+        let plugin = InfocomPlugin::with_base_url(server.uri());
+        let posts = plugin
+            .crawl_with_options(&CrawlOptions::new(1))
+            .await
+            .unwrap();
+
+        assert_eq!(posts.len(), 1);
+        assert!(posts[0].content.contains("포스터를 확인하세요."));
+        assert!(!posts[0].content.contains("base64"));
+        assert_eq!(
+            posts[0].metadata.as_ref().unwrap()[SsufidPost::CONTENT_STRIPPED_KEY],
+            "true"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_crawl_skips_tolerated_empty_page() {
+        let server = MockServer::start().await;
+        let list_path = "/kor/notice/undergraduate.php";
+        let list_page = |idx: u32, title: &str, date: &str| {
+            format!(
+                r#"<div class="board_list">
+                    <a class="con_box" href="{list_path}?idx={idx}">
+                        <div class="subject"><span>{title}</span></div>
+                        <ul class="info"><li class="date">{date}</li></ul>
+                    </a>
+                </div>"#
+            )
+        };
+        Mock::given(method("GET"))
+            .and(path(list_path))
+            .and(query_param("pNo", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(list_page(
+                102,
+                "캡스톤디자인 발표회 안내",
+                "2025. 03. 04",
+            )))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(list_path))
+            .and(query_param("pNo", "3"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(list_page(
+                101,
+                "수강신청 안내",
+                "2025. 03. 02",
+            )))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(list_path))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(r#"<div class="board_list"></div>"#),
+            )
+            .mount(&server)
+            .await;
+
+        let plugin = InfocomPlugin::with_base_url(server.uri());
+        let posts = plugin
+            .crawl_with_options(&CrawlOptions::new(10).detail(false).empty_page_tolerance(1))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            posts.iter().map(|p| p.id.as_str()).collect::<Vec<_>>(),
+            ["102", "101"]
+        );
+        // Stops after pages 4 and 5 are both empty
+        assert_eq!(server.received_requests().await.unwrap().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_crawl_stops_paging_past_max_age() {
+        let server = MockServer::start().await;
+        let list_path = "/kor/notice/undergraduate.php";
+        let list_page = |rows: &[(&str, u32, &str)]| {
+            let items = rows
+                .iter()
+                .map(|(class, idx, date)| {
+                    format!(
+                        r#"<a class="{class}" href="{list_path}?idx={idx}">
+                            <div class="subject"><span>공지 {idx}</span></div>
+                            <ul class="info"><li class="date">{date}</li></ul>
+                        </a>"#
+                    )
+                })
+                .collect::<String>();
+            format!(r#"<div class="board_list">{items}</div>"#)
+        };
+        let pinned = ("con_box notice", 90, "2025. 01. 02");
+        let pages = [
+            (
+                "1",
+                vec![
+                    pinned,
+                    ("con_box", 104, "2025. 03. 08"),
+                    ("con_box", 103, "2025. 03. 05"),
+                ],
+            ),
+            (
+                "2",
+                vec![
+                    pinned,
+                    ("con_box", 102, "2025. 03. 04"),
+                    ("con_box", 101, "2025. 02. 20"),
+                ],
+            ),
+            (
+                "3",
+                vec![
+                    pinned,
+                    ("con_box", 100, "2025. 02. 10"),
+                    ("con_box", 99, "2025. 02. 01"),
+                ],
+            ),
+        ];
+        for (page, rows) in pages {
+            Mock::given(method("GET"))
+                .and(path(list_path))
+                .and(query_param("pNo", page))
+                .respond_with(ResponseTemplate::new(200).set_body_string(list_page(&rows)))
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path(list_path))
+            .and(query_param("pNo", "4"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(list_page(&[(
+                "con_box",
+                98,
+                "2025. 01. 20",
+            )])))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let plugin = InfocomPlugin::with_base_url(server.uri());
+        // Page 3 has only old regular rows, so page 4 is never requested. With a limit of 3,
+        // the old pinned notice does not use up the limit either.
+        let mut requests = 0;
+        for (posts_limit, list_requests) in [(10, 3), (3, 2)] {
+            let posts = plugin
+                .crawl_with_options(
+                    &CrawlOptions::new(posts_limit)
+                        .detail(false)
+                        .max_age(Duration::from_secs(7 * 24 * 60 * 60))
+                        .now(datetime!(2025-03-10 12:00 +9)),
+                )
+                .await
+                .unwrap();
+            assert_eq!(
+                posts.iter().map(|p| p.id.as_str()).collect::<Vec<_>>(),
+                ["104", "103", "102"]
+            );
+            let received = server.received_requests().await.unwrap().len();
+            assert_eq!(received - requests, list_requests);
+            requests = received;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_crawl_since_id_stops_at_last_id() {
+        let server = MockServer::start().await;
+        let list_path = "/kor/notice/undergraduate.php";
+        let list_page = |ids: [u32; 2]| {
+            let items = ids
+                .iter()
+                .map(|idx| {
+                    format!(
+                        r#"<a class="con_box" href="{list_path}?idx={idx}">
+                            <div class="subject"><span>공지 {idx}</span></div>
+                            <ul class="info"><li class="date">2025. 03. 04</li></ul>
+                        </a>"#
+                    )
+                })
+                .collect::<String>();
+            format!(r#"<div class="board_list">{items}</div>"#)
+        };
+        for (page, ids) in [("1", [104, 103]), ("2", [102, 101])] {
+            Mock::given(method("GET"))
+                .and(path(list_path))
+                .and(query_param("pNo", page))
+                .respond_with(ResponseTemplate::new(200).set_body_string(list_page(ids)))
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path(list_path))
+            .and(query_param_is_missing("pNo"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<div class="view_box"><div class="con"><p>본문</p></div></div>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let plugin = InfocomPlugin::with_base_url(server.uri());
+        let posts = plugin.crawl_since_id("102").await.unwrap();
+
+        assert_eq!(
+            posts.iter().map(|p| p.id.as_str()).collect::<Vec<_>>(),
+            ["104", "103"]
+        );
+        let requests = server.received_requests().await.unwrap();
+        assert!(
+            requests
+                .iter()
+                .all(|r| r.url.query_pairs().all(|(k, v)| k != "pNo" || v != "3"))
+        );
+        // Only list page 2 and the two new posts' detail pages are requested
+        assert_eq!(requests.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_crawl_since_id_is_bounded() {
+        let server = MockServer::start().await;
+        let list_path = "/kor/notice/undergraduate.php";
+        // A list that only ever shows new posts
+        Mock::given(method("GET"))
+            .and(path(list_path))
+            .respond_with(move |request: &wiremock::Request| {
+                let Some(page) = request
+                    .url
+                    .query_pairs()
+                    .find(|(key, _)| key == "pNo")
+                    .and_then(|(_, value)| value.parse::<u32>().ok())
+                else {
+                    return ResponseTemplate::new(200).set_body_string(
+                        r#"<div class="view_box"><div class="con"><p>본문</p></div></div>"#,
+                    );
+                };
+                let items = [0, 1]
+                    .map(|i| {
+                        let idx = 100_000 - page * 2 - i;
+                        format!(
+                            r#"<a class="con_box" href="{list_path}?idx={idx}">
+                                <div class="subject"><span>공지 {idx}</span></div>
+                                <ul class="info"><li class="date">2025. 03. 04</li></ul>
+                            </a>"#
+                        )
+                    })
+                    .join("");
+                ResponseTemplate::new(200)
+                    .set_body_string(format!(r#"<div class="board_list">{items}</div>"#))
+            })
+            .mount(&server)
+            .await;
+
+        let plugin = InfocomPlugin::with_base_url(server.uri());
+        let posts = plugin.crawl_since_id("1").await.unwrap();
+
+        assert_eq!(posts.len(), SsufidCore::POST_COUNT_LIMIT as usize);
+        let list_requests = server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .filter(|r| r.url.query_pairs().any(|(k, _)| k == "pNo"))
+            .count();
+        assert_eq!(list_requests, SsufidCore::POST_COUNT_LIMIT as usize / 2);
+    }
+
+    #[tokio::test]
+    async fn test_crawl_stream_yields_posts_before_last_page() {
+        let server = MockServer::start().await;
+        let list_path = "/kor/notice/undergraduate.php";
+        let list_page = |idx: u32| {
+            format!(
+                r#"<div class="board_list">
+                    <a class="con_box" href="{list_path}?idx={idx}">
+                        <div class="subject"><span>공지 {idx}</span></div>
+                        <ul class="info"><li class="date">2025. 03. 04</li></ul>
+                    </a>
+                </div>"#
+            )
+        };
+        let page_delay = Duration::from_secs(2);
+        Mock::given(method("GET"))
+            .and(path(list_path))
+            .and(query_param("pNo", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(list_page(102)))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(list_path))
+            .and(query_param("pNo", "2"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(list_page(101))
+                    .set_delay(page_delay),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(list_path))
+            .and(query_param_is_missing("pNo"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<div class="view_box"><div class="con"><p>본문</p></div></div>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let plugin = InfocomPlugin::with_base_url(server.uri());
+        let started_at = std::time::Instant::now();
+        let mut stream = std::pin::pin!(plugin.crawl_stream(2));
+
+        let first = stream.next().await.unwrap().unwrap();
+        assert_eq!(first.id, "102");
+        assert!(first.content.contains("본문"));
+        // The first post arrives without waiting for page 2
+        assert!(started_at.elapsed() < page_delay);
+
+        let second = stream.next().await.unwrap().unwrap();
+        assert_eq!(second.id, "101");
+        assert!(started_at.elapsed() >= page_delay);
+        // Two posts fill the limit, so page 3 is never requested
+        assert!(stream.next().await.is_none());
+        let requests = server.received_requests().await.unwrap();
+        assert!(
+            requests
+                .iter()
+                .all(|r| r.url.query_pairs().all(|(k, v)| k != "pNo" || v != "3"))
+        );
+    }
 }
@@ -10,8 +10,9 @@ use url::Url; // Added OffsetDateTime
 
 // Use actual package name 'ssufid' and correct module path
 use ssufid::{
-    core::{Attachment, SsufidPlugin, SsufidPost, SsufidPostPlugin},
+    core::{Attachment, SsufidPlugin, SsufidPost, SsufidPostPlugin, UrlKind},
     error::PluginError,
+    fetch::send,
 };
 
 struct Selectors {
@@ -87,10 +88,7 @@ impl InsoPlugin {
 
         tracing::info!(url = %list_url, "Fetching post metadata list");
 
-        let response_text = self
-            .http_client
-            .get(&list_url)
-            .send()
+        let response_text = send::<Self>(self.http_client.get(&list_url))
             .await
             .map_err(|e| PluginError::request::<Self>(e.to_string()))?
             .text()
@@ -117,20 +115,11 @@ impl InsoPlugin {
                     .map_err(|e| InsoPluginError::UrlParseError(e.to_string()))
                     .map_err(|e| PluginError::parse::<Self>(e.to_string()))?;
 
-                let post_id = parsed_url
-                    .query_pairs()
-                    .find_map(|(key, value)| {
-                        if key == "idx" {
-                            Some(value.into_owned())
-                        } else {
-                            None
-                        }
-                    })
-                    .ok_or_else(|| {
-                        PluginError::parse::<Self>(format!(
-                            "Could not find 'idx' in post URL: {absolute_post_url}"
-                        ))
-                    })?;
+                let post_id = Self::query_post_id(&parsed_url, "idx").ok_or_else(|| {
+                    PluginError::parse::<Self>(format!(
+                        "Could not find 'idx' in post URL: {absolute_post_url}"
+                    ))
+                })?;
 
                 metadata_list.push(InsoPostMetadata {
                     id: post_id,
@@ -153,10 +142,7 @@ impl InsoPlugin {
     ) -> Result<SsufidPost, PluginError> {
         tracing::info!(url = %post_metadata.url, id = %post_metadata.id, "Fetching post content");
 
-        let response_text = self
-            .http_client
-            .get(&post_metadata.url)
-            .send()
+        let response_text = send::<Self>(self.http_client.get(&post_metadata.url))
             .await
             .map_err(|e| PluginError::request::<Self>(e.to_string()))?
             .text()
@@ -236,6 +222,7 @@ impl InsoPlugin {
                     name: Some(attachment_name).filter(|s| !s.is_empty()),
                     url: format!("http://inso.ssu.ac.kr/module/board/download.php?boardid={board_id}&b_idx={b_idx}&idx={idx}"),
                     mime_type: None,
+                    metadata: None,
                 });
             }
         }
@@ -251,6 +238,7 @@ impl InsoPlugin {
         Ok(SsufidPost {
             id: post_metadata.id.clone(),
             url: post_metadata.url.clone(),
+            url_kind: UrlKind::Canonical,
             title,
             author: Some(author).filter(|s| !s.is_empty()),
             description: None,
@@ -261,6 +249,7 @@ impl InsoPlugin {
             content,
             attachments,
             metadata: None,
+            author_detail: None,
         })
     }
 }
@@ -270,6 +259,12 @@ impl SsufidPlugin for InsoPlugin {
     const TITLE: &'static str = "정보사회학과 공지사항";
     const DESCRIPTION: &'static str = "숭실대학교 정보사회학과 공지사항을 제공합니다.";
     const BASE_URL: &'static str = "http://inso.ssu.ac.kr/sub/sub04_01.php";
+    const POSTS_PER_PAGE: u32 = 10;
+    const ID_CATEGORY_PARAM: Option<&'static str> = Some("category");
+
+    fn http_client(&self) -> reqwest::Client {
+        self.http_client.clone()
+    }
 }
 
 impl SsufidPostPlugin for InsoPlugin {
@@ -287,7 +282,6 @@ impl SsufidPostPlugin for InsoPlugin {
 
         let mut all_collected_metadata = Vec::new();
         let mut current_offset = 0;
-        const POSTS_PER_PAGE: u32 = 10;
 
         loop {
             tracing::debug!("Fetching metadata page with offset: {}", current_offset);
@@ -301,7 +295,8 @@ impl SsufidPostPlugin for InsoPlugin {
                 break;
             }
 
-            let was_last_page_from_source = metadata_from_page.len() < POSTS_PER_PAGE as usize;
+            let was_last_page_from_source =
+                metadata_from_page.len() < Self::POSTS_PER_PAGE as usize;
 
             for metadata_item in metadata_from_page {
                 if all_collected_metadata.len() < posts_limit as usize {
@@ -322,13 +317,13 @@ impl SsufidPostPlugin for InsoPlugin {
             if was_last_page_from_source {
                 tracing::info!(
                     "Fetched a page with fewer posts than posts_per_page ({} < {}), indicating it's the last page from source. Stopping metadata collection.",
-                    all_collected_metadata.len() % (POSTS_PER_PAGE as usize),
-                    POSTS_PER_PAGE
+                    all_collected_metadata.len() % (Self::POSTS_PER_PAGE as usize),
+                    Self::POSTS_PER_PAGE
                 );
                 break;
             }
 
-            current_offset += POSTS_PER_PAGE;
+            current_offset += Self::POSTS_PER_PAGE;
         }
 
         tracing::info!(
@@ -2,8 +2,9 @@ use futures::TryStreamExt;
 use futures::stream::FuturesOrdered;
 use reqwest::Client;
 use scraper::{Html, Selector};
-use ssufid::core::{Attachment, SsufidPlugin, SsufidPost, SsufidPostPlugin};
+use ssufid::core::{Attachment, SsufidPlugin, SsufidPost, SsufidPostPlugin, UrlKind};
 use ssufid::error::PluginError;
+use ssufid::fetch::send;
 use time::Date;
 use time::format_description::BorrowedFormatItem;
 use time::macros::{format_description, offset};
@@ -79,6 +80,7 @@ impl From<MaterialsPost> for SsufidPost {
         SsufidPost {
             id: post.id,
             url: post.url,
+            url_kind: UrlKind::Canonical,
             title: post.title,
             author: None,
             description: None,
@@ -92,6 +94,7 @@ impl From<MaterialsPost> for SsufidPost {
             content: post.content,
             attachments: post.attachments,
             metadata: None,
+            author_detail: None,
         }
     }
 }
@@ -105,7 +108,7 @@ impl Default for MaterialsPlugin {
     fn default() -> Self {
         Self {
             selectors: Selectors::new(),
-            client: Client::builder()
+            client: ssufid::fetch::client_builder()
                         .danger_accept_invalid_certs(true) // No trailing whitespace
                         .user_agent("Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/100.0.0.0 Safari/537.36")
                         .build()
@@ -119,6 +122,10 @@ impl SsufidPlugin for MaterialsPlugin {
     const TITLE: &'static str = "숭실대학교 신소재공학과 공지사항";
     const DESCRIPTION: &'static str = "숭실대학교 신소재공학과 홈페이지의 공지사항을 제공합니다.";
     const BASE_URL: &'static str = "https://materials.ssu.ac.kr/bbs/board.php?tbl=bbs51";
+
+    fn http_client(&self) -> reqwest::Client {
+        self.client.clone()
+    }
 }
 
 impl SsufidPostPlugin for MaterialsPlugin {
@@ -194,15 +201,16 @@ impl MaterialsPlugin {
     async fn fetch_post_metadata(&self, page: u32) -> Result<Vec<PostMetadata>, PluginError> {
         tracing::debug!(target: MaterialsPlugin::IDENTIFIER, "Fetching metadata from page: {}", page);
 
-        let response_text = self
-            .client
-            .get(format!("{}&page={}", MaterialsPlugin::BASE_URL, page))
-            .send()
-            .await
-            .map_err(|e| PluginError::request::<MaterialsPlugin>(e.to_string()))?
-            .text()
-            .await
-            .map_err(|e| PluginError::request::<MaterialsPlugin>(e.to_string()))?;
+        let response_text = send::<MaterialsPlugin>(self.client.get(format!(
+            "{}&page={}",
+            MaterialsPlugin::BASE_URL,
+            page
+        )))
+        .await
+        .map_err(|e| PluginError::request::<MaterialsPlugin>(e.to_string()))?
+        .text()
+        .await
+        .map_err(|e| PluginError::request::<MaterialsPlugin>(e.to_string()))?;
 
         let document = Html::parse_document(&response_text);
 
@@ -284,9 +292,7 @@ impl MaterialsPlugin {
         client: &Client,
     ) -> Result<MaterialsPost, PluginError> {
         tracing::debug!(target: MaterialsPlugin::IDENTIFIER, "Fetching full post details for ID {}: {}", meta.id, meta.url);
-        let response_text = client
-            .get(&meta.url)
-            .send()
+        let response_text = send::<MaterialsPlugin>(client.get(&meta.url))
             .await
             .map_err(|e| {
                 PluginError::request::<MaterialsPlugin>(format!(
@@ -354,6 +360,7 @@ impl MaterialsPlugin {
                     url,
                     name: Some(name),
                     mime_type: None,
+                    metadata: None,
                 })
             })
             .collect::<Result<Vec<Attachment>, _>>()?;
@@ -1,8 +1,10 @@
 use futures::{TryStreamExt as _, stream::FuturesOrdered};
 use scraper::{Html, Selector};
 use ssufid::{
-    core::{Attachment, SsufidPlugin, SsufidPost, SsufidPostPlugin},
+    core::{Attachment, SsufidPlugin, SsufidPost, SsufidPostPlugin, UrlKind},
     error::PluginError,
+    fetch::send,
+    text::clean_author,
 };
 use thiserror::Error;
 use time::{
@@ -105,10 +107,7 @@ impl MePlugin {
         let current_page_url = format!("{}?page={}", Self::BASE_URL, page_num);
         tracing::info!("Crawling page: {}", current_page_url);
 
-        let response_text = self
-            .http_client
-            .get(&current_page_url)
-            .send()
+        let response_text = send::<Self>(self.http_client.get(&current_page_url))
             .await
             .inspect_err(|e| {
                 tracing::error!(?e, "Failed to fetch posts: {}", e);
@@ -174,10 +173,7 @@ impl MePlugin {
         list_date_str: String,
     ) -> Result<SsufidPost, PluginError> {
         tracing::debug!("Fetching post details for URL: {}", post_url);
-        let response_text = self
-            .http_client
-            .get(&post_url)
-            .send()
+        let response_text = send::<Self>(self.http_client.get(&post_url))
             .await
             .inspect_err(|e| {
                 tracing::error!(?e, "Failed to fetch post details: {}", e);
@@ -200,8 +196,7 @@ impl MePlugin {
         let author = document
             .select(&self.selectors.view_author)
             .next()
-            .map(|el| el.text().collect::<String>().trim().to_string())
-            .filter(|s| !s.is_empty())
+            .and_then(|el| clean_author(&el.text().collect::<String>()))
             .unwrap_or(list_author);
 
         let date_str_on_page = document
@@ -237,6 +232,7 @@ impl MePlugin {
                     url: attachment_url,
                     name: Some(name.clone()).filter(|s| !s.is_empty()),
                     mime_type: mime_guess::from_path(&name).first_raw().map(str::to_string),
+                    metadata: None,
                 })
             })
             .collect();
@@ -244,6 +240,7 @@ impl MePlugin {
         Ok(SsufidPost {
             id: post_id,
             url: post_url,
+            url_kind: UrlKind::Canonical,
             author: Some(author).filter(|s| !s.is_empty()),
             title,
             description: None,
@@ -254,6 +251,7 @@ impl MePlugin {
             content: content_html,
             attachments,
             metadata: None,
+            author_detail: None,
         })
     }
 }
@@ -269,6 +267,12 @@ impl SsufidPlugin for MePlugin {
     const TITLE: &'static str = "숭실대학교 기계공학부";
     const DESCRIPTION: &'static str = "숭실대학교 기계공학부 홈페이지의 공지사항을 제공합니다.";
     const BASE_URL: &'static str = "https://me.ssu.ac.kr/notice/notice01.php";
+    // 2: authors cleaned with `clean_author`
+    const VERSION: u32 = 2;
+
+    fn http_client(&self) -> reqwest::Client {
+        self.http_client.clone()
+    }
 }
 
 impl SsufidPostPlugin for MePlugin {
@@ -311,7 +315,7 @@ impl SsufidPostPlugin for MePlugin {
             .try_collect()
             .await?;
 
-        all_posts.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        all_posts.sort_by_key(|p| std::cmp::Reverse(p.created_at));
         all_posts.truncate(posts_limit as usize);
         Ok(all_posts)
     }
@@ -4,7 +4,8 @@ use futures::{TryStreamExt, stream::FuturesOrdered};
 use serde::Deserialize;
 use ssufid::{
     PluginError,
-    core::{SsufidPlugin, SsufidPost, SsufidPostPlugin},
+    core::{SsufidPlugin, SsufidPost, SsufidPostPlugin, UrlKind, report_pinned_post},
+    fetch::{send, shared_client},
 };
 use time::{
     OffsetDateTime, PrimitiveDateTime,
@@ -19,9 +20,9 @@ impl MediaPlugin {
     const API_BASE_URL: &'static str = "https://api.mediamba.ssu.ac.kr";
 
     async fn list_posts(base_url: &str, posts_limit: u32) -> Result<Vec<MediaPost>, PluginError> {
-        let res = reqwest::get(format!(
+        let res = send::<Self>(shared_client().get(format!(
             "{base_url}/v1/board/?page=0&size={posts_limit}&menuId={NOTICE_MENU_ID}&content="
-        ))
+        )))
         .await
         .map_err(|e| PluginError::request::<Self>(e.to_string()))?
         .json::<MediaBoardResponse>()
@@ -33,6 +34,9 @@ impl MediaPlugin {
                 res.message,
             ));
         }
+        for post in res.data.boards.iter().filter(|post| post.is_pinned) {
+            report_pinned_post(&post.id.to_string());
+        }
         Ok(res.data.boards)
     }
 
@@ -143,11 +147,7 @@ impl MediaPost {
         &self,
         parser_host: &str,
     ) -> Result<ssufid::core::SsufidPost, PluginError> {
-        let client = reqwest::Client::new();
-        let res = client
-            .post(parser_host)
-            .body(self.content.clone())
-            .send()
+        let res = send::<MediaPlugin>(shared_client().post(parser_host).body(self.content.clone()))
             .await
             .map_err(|e| PluginError::request::<MediaPlugin>(e.to_string()))?;
         if !res.status().is_success() {
@@ -165,6 +165,7 @@ impl MediaPost {
         Ok(SsufidPost {
             id: self.id.to_string(),
             url: format!("{}/{}", MediaPlugin::BASE_URL, self.id),
+            url_kind: UrlKind::Canonical,
             author: Some(self.user_name.clone()),
             title: self.title.clone(),
             description: Some(content_html.clone()),
@@ -175,6 +176,7 @@ impl MediaPost {
             content: content_html,
             attachments: vec![],
             metadata: None,
+            author_detail: None,
         })
     }
 }
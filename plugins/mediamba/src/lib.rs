@@ -4,7 +4,8 @@ use futures::{TryStreamExt, stream::FuturesOrdered};
 use serde::Deserialize;
 use ssufid::{
     PluginError,
-    core::{SsufidPlugin, SsufidPost, SsufidPostPlugin},
+    core::{SsufidPlugin, SsufidPost, SsufidPostPlugin, UrlKind, report_pinned_post},
+    fetch::{send, shared_client},
 };
 use time::{
     OffsetDateTime, PrimitiveDateTime,
@@ -20,9 +21,9 @@ impl MediambaPlugin {
         base_url: &str,
         posts_limit: u32,
     ) -> Result<Vec<MediambaPost>, PluginError> {
-        let res = reqwest::get(format!(
+        let res = send::<Self>(shared_client().get(format!(
             "{base_url}/v1/board/?page=0&size={posts_limit}&menuId=89&content="
-        ))
+        )))
         .await
         .map_err(|e| PluginError::request::<Self>(e.to_string()))?
         .json::<MediambaBoardResponse>()
@@ -34,6 +35,9 @@ impl MediambaPlugin {
                 res.message,
             ));
         }
+        for post in res.data.boards.iter().filter(|post| post.is_pinned) {
+            report_pinned_post(&post.id.to_string());
+        }
         Ok(res.data.boards)
     }
 
@@ -143,13 +147,10 @@ impl MediambaPost {
         &self,
         parser_host: &str,
     ) -> Result<ssufid::core::SsufidPost, PluginError> {
-        let client = reqwest::Client::new();
-        let res = client
-            .post(parser_host)
-            .body(self.content.clone())
-            .send()
-            .await
-            .map_err(|e| PluginError::request::<MediambaPlugin>(e.to_string()))?;
+        let res =
+            send::<MediambaPlugin>(shared_client().post(parser_host).body(self.content.clone()))
+                .await
+                .map_err(|e| PluginError::request::<MediambaPlugin>(e.to_string()))?;
         if !res.status().is_success() {
             return Err(PluginError::parse::<MediambaPlugin>(format!(
                 "Failed to receive content: {}",
@@ -165,6 +166,7 @@ impl MediambaPost {
         Ok(SsufidPost {
             id: self.id.to_string(),
             url: format!("{}/{}", MediambaPlugin::BASE_URL, self.id),
+            url_kind: UrlKind::Canonical,
             author: Some(self.user_name.clone()),
             title: self.title.clone(),
             description: Some(content_html.clone()),
@@ -175,6 +177,7 @@ impl MediambaPost {
             content: content_html,
             attachments: vec![],
             metadata: None,
+            author_detail: None,
         })
     }
 }
@@ -2,7 +2,8 @@ use futures::{TryStreamExt, stream::FuturesOrdered};
 use serde::Deserialize;
 use ssufid::{
     PluginError,
-    core::{Attachment, SsufidPlugin, SsufidPost, SsufidPostPlugin},
+    core::{Attachment, SsufidPlugin, SsufidPost, SsufidPostPlugin, UrlKind},
+    fetch::{send, shared_client},
 };
 use time::{
     OffsetDateTime, PrimitiveDateTime,
@@ -18,9 +19,9 @@ impl OasisPlugin {
         base_url: &str,
         posts_limit: u32,
     ) -> Result<Vec<OasisPostMeta>, PluginError> {
-        let res = reqwest::get(format!(
+        let res = send::<Self>(shared_client().get(format!(
             "{base_url}/1/bulletin-boards/1/bulletins?nameOption=part&isSeq=false&onlyWriter=false&max={posts_limit}"
-        ))
+        )))
         .await
         .map_err(|e| PluginError::request::<Self>(e.to_string()))?
         .json::<OasisBoardResponse>()
@@ -114,19 +115,23 @@ struct OasisPostMeta {
 
 impl OasisPostMeta {
     async fn to_ssufid_post(&self) -> Result<SsufidPost, PluginError> {
-        let res = reqwest::get(format!(
+        let res = send::<OasisPlugin>(shared_client().get(format!(
             "{}/1/bulletins/1/{}?nameOption=part",
             OasisPlugin::API_BASE_URL,
             self.id
-        ))
+        )))
         .await
         .map_err(|e| {
             PluginError::request::<OasisPlugin>(format!("Failed to request to post api {e:?}"))
         })?
-        .json::<OasisPostResponse>()
+        .text()
         .await
         .map_err(|e| {
+            PluginError::request::<OasisPlugin>(format!("Failed to read post api body {e:?}"))
+        })?;
+        let res = serde_json::from_str::<OasisPostResponse>(&res).map_err(|e| {
             PluginError::parse::<OasisPlugin>(format!("Failed to parse post api body {e:?}"))
+                .with_detail(ssufid::html::html_snippet(&res))
         })?;
 
         if !res.success {
@@ -154,15 +159,15 @@ struct OasisAttachment {
 
 impl From<OasisAttachment> for Attachment {
     fn from(attachment: OasisAttachment) -> Self {
-        Attachment {
-            name: Some(attachment.logical_name),
-            url: format!(
+        Attachment::from_content_type(
+            Some(attachment.logical_name),
+            format!(
                 "{}{}",
                 OasisPlugin::API_BASE_URL,
                 attachment.original_image_url,
             ),
-            mime_type: Some(attachment.file_type),
-        }
+            &attachment.file_type,
+        )
     }
 }
 
@@ -196,6 +201,7 @@ impl From<OasisPost> for SsufidPost {
             id: post.id.to_string(),
             title: post.title,
             url: format!("{}/{}", OasisPlugin::BASE_URL, post.id),
+            url_kind: UrlKind::Canonical,
             author: Some(post.worker.name),
             description: None,
             category: vec![],
@@ -205,6 +211,7 @@ impl From<OasisPost> for SsufidPost {
             content: post.content,
             attachments: post.attachments.into_iter().map(Attachment::from).collect(),
             metadata: None,
+            author_detail: None,
         }
     }
 }
@@ -2,7 +2,8 @@ use reqwest::header::CONTENT_TYPE;
 use serde::Deserialize;
 use ssufid::{
     PluginError,
-    core::{Attachment, SsufidPlugin, SsufidPost, SsufidPostPlugin},
+    core::{Attachment, SsufidPlugin, SsufidPost, SsufidPostPlugin, UrlKind},
+    fetch::{send, shared_client},
 };
 use time::{
     OffsetDateTime, PrimitiveDateTime,
@@ -25,12 +26,13 @@ impl SsfilmPlugin {
                 base_url
             };
 
-            let response = reqwest::Client::new()
-                .get(url)
-                .header(CONTENT_TYPE, "application/json")
-                .send()
-                .await
-                .map_err(|e| PluginError::request::<Self>(format!("Failed to request: {e:?}")))?;
+            let response = send::<Self>(
+                shared_client()
+                    .get(url)
+                    .header(CONTENT_TYPE, "application/json"),
+            )
+            .await
+            .map_err(|e| PluginError::request::<Self>(format!("Failed to request: {e:?}")))?;
 
             if !response.status().is_success() {
                 return Err(PluginError::request::<Self>(format!(
@@ -121,6 +123,7 @@ impl From<SsfilmPost> for SsufidPost {
             id: post.notice_index,
             title: post.title,
             url: SsfilmPlugin::BASE_URL.to_string(),
+            url_kind: UrlKind::Canonical,
             author: Some(post.reg_id),
             description: None,
             category: vec![post.category],
@@ -133,10 +136,12 @@ impl From<SsfilmPost> for SsufidPost {
                     url: construct_file_url(&post.file_data, &post.org_file),
                     name: Some(post.org_file),
                     mime_type: None,
+                    metadata: None,
                 })
                 .into_iter()
                 .collect(),
             metadata: None,
+            author_detail: None,
         }
     }
 }
@@ -5,6 +5,7 @@ use scraper::{ElementRef, Html, Selector};
 use ssufid::{
     PluginError,
     core::{CalendarCrawlRange, SsufidCalendar, SsufidCalendarPlugin, SsufidPlugin},
+    fetch::send,
 };
 use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset, macros::offset};
 use url::Url;
@@ -81,9 +82,7 @@ impl SsuAcademicCalendarPlugin {
     }
 
     async fn fetch_year_page(client: &Client, page_url: &Url) -> Result<String, PluginError> {
-        client
-            .get(page_url.clone())
-            .send()
+        send::<Self>(client.get(page_url.clone()))
             .await
             .map_err(|e| {
                 PluginError::request::<Self>(format!("Failed to request {page_url}: {e}"))
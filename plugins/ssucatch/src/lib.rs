@@ -6,8 +6,13 @@ use thiserror::Error;
 use url::Url;
 
 use ssufid::{
-    core::{Attachment, SsufidPlugin, SsufidPost, SsufidPostPlugin},
+    core::{
+        Attachment, Cadence, CrawlOptions, SsufidPlugin, SsufidPost, SsufidPostPlugin, UrlKind,
+    },
     error::PluginError,
+    fetch::{default_client, fetch_page, fetch_text},
+    html::last_page,
+    paginate::Paginator,
 };
 use time::{Date, format_description, macros::offset};
 struct Selectors {
@@ -27,6 +32,8 @@ struct Selectors {
 
 pub struct SsuCatchPlugin {
     selectors: Selectors,
+    client: reqwest::Client,
+    base_url: String,
 }
 
 impl Selectors {
@@ -70,12 +77,18 @@ enum SsuCatchMetadataError {
 }
 
 impl SsuCatchPlugin {
-    const POSTS_PER_PAGE: u32 = 15; // 페이지당 게시글 수
     const DATE_FORMAT: &'static str = "[year]년 [month padding:none]월 [day padding:none]일";
 
     pub fn new() -> Self {
+        Self::with_base_url(Self::BASE_URL)
+    }
+
+    /// Creates a plugin that crawls `base_url` instead of scatch.ssu.ac.kr, e.g. a mock server.
+    pub fn with_base_url(base_url: impl Into<String>) -> Self {
         Self {
             selectors: Selectors::new(),
+            client: default_client(),
+            base_url: base_url.into(),
         }
     }
 
@@ -83,16 +96,9 @@ impl SsuCatchPlugin {
         &self,
         page: u32,
     ) -> Result<Vec<SsuCatchMetadata>, PluginError> {
-        let page_url = format!("{}/{}/page/{}", Self::BASE_URL, "공지사항", page);
+        let page_url = format!("{}/{}/page/{}", self.base_url, "공지사항", page);
 
-        let response = reqwest::get(page_url)
-            .await
-            .map_err(|e| PluginError::request::<Self>(e.to_string()))?;
-
-        let html = response
-            .text()
-            .await
-            .map_err(|e| PluginError::parse::<Self>(e.to_string()))?;
+        let html = fetch_text::<Self>(&self.client, &page_url).await?;
 
         let document = Html::parse_document(&html);
 
@@ -147,16 +153,9 @@ impl SsuCatchPlugin {
         &self,
         post_metadata: &SsuCatchMetadata,
     ) -> Result<SsufidPost, PluginError> {
-        let response = reqwest::get(&post_metadata.url)
-            .await
-            .map_err(|e| PluginError::request::<Self>(e.to_string()))?;
+        let page = fetch_page::<Self>(&self.client, &post_metadata.url).await?;
 
-        let html = response
-            .text()
-            .await
-            .map_err(|e| PluginError::parse::<Self>(e.to_string()))?;
-
-        let document = Html::parse_document(&html);
+        let document = Html::parse_document(&page.text);
 
         let title = document
             .select(&self.selectors.title)
@@ -201,20 +200,22 @@ impl SsuCatchPlugin {
             .select(&self.selectors.attachments)
             .filter_map(|element| {
                 element.value().attr("href").map(|href| {
-                    let url = format!("{}{}", Self::BASE_URL, href);
+                    let url = format!("{}{}", self.base_url, href);
                     let name = element.text().collect::<String>().trim().to_string();
                     Attachment {
                         url,
                         name: (!name.is_empty()).then_some(name),
                         mime_type: None,
+                        metadata: None,
                     }
                 })
             })
             .collect();
 
-        Ok(SsufidPost {
+        let mut post = SsufidPost {
             id: post_metadata.id.clone(),
             url: post_metadata.url.clone(),
+            url_kind: UrlKind::Canonical,
             author: Some(post_metadata.author.clone()),
             title,
             description: None,
@@ -225,28 +226,18 @@ impl SsuCatchPlugin {
             content,
             attachments,
             metadata: None,
-        })
+            author_detail: None,
+        };
+        if page.stripped {
+            post.mark_content_stripped();
+        }
+        Ok(post)
     }
 
     #[allow(dead_code)]
     fn get_last_page_number(&self, html: &str) -> u32 {
         let document = Html::parse_document(html);
-
-        let last_page_url = document
-            .select(&self.selectors.last_page)
-            .next()
-            .and_then(|element| element.value().attr("href"))
-            .unwrap_or_default();
-
-        let parsed_last_page_url = Url::parse(last_page_url).unwrap();
-
-        parsed_last_page_url
-            .path_segments()
-            .unwrap()
-            .skip_while(|&segment| segment != "page")
-            .nth(1)
-            .and_then(|segment| segment.parse().ok())
-            .unwrap_or(1)
+        last_page(&document, &self.selectors.last_page).unwrap_or(1)
     }
 }
 
@@ -255,14 +246,41 @@ impl SsufidPlugin for SsuCatchPlugin {
     const TITLE: &'static str = "숭실대학교 공지사항";
     const DESCRIPTION: &'static str = "숭실대학교 공식 홈페이지의 공지사항을 제공합니다.";
     const BASE_URL: &'static str = "https://scatch.ssu.ac.kr";
+    const CADENCE: Cadence = Cadence::Daily;
+    const POSTS_PER_PAGE: u32 = 15; // 페이지당 게시글 수
+
+    fn base_url(&self) -> Cow<'_, str> {
+        Cow::Borrowed(&self.base_url)
+    }
+
+    fn http_client(&self) -> reqwest::Client {
+        self.client.clone()
+    }
 }
 
 impl SsufidPostPlugin for SsuCatchPlugin {
     async fn crawl(&self, posts_limit: u32) -> Result<Vec<SsufidPost>, PluginError> {
-        let pages = posts_limit / Self::POSTS_PER_PAGE + 1;
+        self.crawl_with_options(&CrawlOptions::new(posts_limit))
+            .await
+    }
+
+    async fn crawl_with_options(
+        &self,
+        options: &CrawlOptions,
+    ) -> Result<Vec<SsufidPost>, PluginError> {
+        let now = options.current_time();
+        // Estimate the page count from page 1's rows, then request the rest at once
+        let mut paginator = Paginator::new(options.posts_limit)
+            .posts_per_page(Self::POSTS_PER_PAGE)
+            .empty_page_tolerance(options.empty_page_tolerance);
+        paginator.push_page(self.fetch_page_posts_metadata(1).await?, |m| m.id.clone());
+        let pages = match paginator.next_page() {
+            Some(_) => paginator.estimated_pages().unwrap_or(1),
+            None => 1,
+        };
 
         // 모든 페이지 크롤링이 완료될 때까지 대기
-        let metadata_results = (1..=pages)
+        let metadata_results = (2..=pages)
             .map(|page| {
                 tracing::info!("Crawling post metadata from page: {}/{}", page, pages);
                 self.fetch_page_posts_metadata(page)
@@ -270,14 +288,17 @@ impl SsufidPostPlugin for SsuCatchPlugin {
             .collect::<FuturesOrdered<_>>()
             .collect::<Vec<_>>()
             .await;
-
-        let all_metadata = metadata_results
-            .into_iter()
-            .collect::<Result<Vec<_>, PluginError>>()?
-            .into_iter()
-            .flatten()
-            .take(posts_limit as usize)
-            .collect::<Vec<SsuCatchMetadata>>();
+        for metadata in metadata_results {
+            paginator.push_page(metadata?, |m| m.id.clone());
+        }
+        // Pinned notices repeated on every page can need more pages than estimated
+        while let Some(page) = paginator.next_page() {
+            tracing::info!("Crawling post metadata from page: {}", page);
+            paginator.push_page(self.fetch_page_posts_metadata(page).await?, |m| {
+                m.id.clone()
+            });
+        }
+        let all_metadata = paginator.into_items();
 
         // 모든 포스트 크롤링이 완료될 때까지 대기
         let post_results = all_metadata
@@ -291,7 +312,7 @@ impl SsufidPostPlugin for SsuCatchPlugin {
             .into_iter()
             .collect::<Result<Vec<SsufidPost>, PluginError>>()?;
 
-        Ok(all_posts)
+        Ok(options.filter(all_posts, now))
     }
 }
 
@@ -383,4 +404,102 @@ mod tests {
         // 페이지 번호가 1 이상인지 확인
         assert!(last_page >= 1, "Last page number should be at least 1");
     }
+
+    #[tokio::test]
+    async fn test_crawl_counts_repeated_pinned_rows_once() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path, path_regex},
+        };
+
+        let server = MockServer::start().await;
+        let row = |slug: &str| {
+            format!(
+                r#"<li><div class="notice_col3"><a href="{}/view?slug={slug}">{slug}</a></div><div class="notice_col4">학사팀</div></li>"#,
+                server.uri()
+            )
+        };
+        // The same two pinned notices repeat atop every page
+        for (page, slug) in [(1, "a"), (2, "b"), (3, "c")] {
+            Mock::given(method("GET"))
+                .and(path_regex(format!("/page/{page}$")))
+                .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                    r#"<ul class="notice-lists"><li>header</li>{}{}{}</ul>"#,
+                    row("pinned-1"),
+                    row("pinned-2"),
+                    row(slug)
+                )))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/view"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<div class="bg-white"><h1>공지</h1><div class="clearfix"><div class="float-left mr-4">2025년 3월 2일</div></div><div><p>본문</p></div></div>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let posts = SsuCatchPlugin::with_base_url(server.uri())
+            .crawl(5)
+            .await
+            .unwrap();
+
+        // Page 1's 3 distinct rows estimate 2 pages, but filling 5 takes page 3
+        assert_eq!(
+            posts
+                .iter()
+                .map(|post| post.id.as_str())
+                .collect::<Vec<_>>(),
+            ["pinned-1", "pinned-2", "a", "b", "c"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_crawl_with_options_skips_empty_page_within_tolerance() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path, path_regex},
+        };
+
+        let server = MockServer::start().await;
+        let row = |slug: &str| {
+            format!(
+                r#"<li><div class="notice_col3"><a href="{}/view?slug={slug}">{slug}</a></div><div class="notice_col4">학사팀</div></li>"#,
+                server.uri()
+            )
+        };
+        // Page 2 is briefly empty
+        for (page, rows) in [(1, row("a")), (2, String::new()), (3, row("c"))] {
+            Mock::given(method("GET"))
+                .and(path_regex(format!("/page/{page}$")))
+                .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                    r#"<ul class="notice-lists"><li>header</li>{rows}</ul>"#
+                )))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/view"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<div class="bg-white"><h1>공지</h1><div class="clearfix"><div class="float-left mr-4">2025년 3월 2일</div></div><div><p>본문</p></div></div>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let posts = SsuCatchPlugin::with_base_url(server.uri())
+            .crawl_with_options(&CrawlOptions::new(2).empty_page_tolerance(1))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            posts
+                .iter()
+                .map(|post| post.id.as_str())
+                .collect::<Vec<_>>(),
+            ["a", "c"]
+        );
+    }
 }
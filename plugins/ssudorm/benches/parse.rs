@@ -0,0 +1,33 @@
+//! Parsing throughput of the ssudorm list page against `benches/fixtures/list.html`.
+//! Run with `cargo bench -p ssufid_ssudorm`.
+
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use scraper::Html;
+use ssufid_ssudorm::SsuDormPlugin;
+
+const LIST_PAGE: &str = include_str!("fixtures/list.html");
+
+fn list_page(c: &mut Criterion) {
+    let plugin = SsuDormPlugin::new();
+    let document = Html::parse_document(LIST_PAGE);
+    assert_eq!(plugin.parse_list_page(&document).unwrap().len(), 15);
+
+    let mut group = c.benchmark_group("ssudorm/list_page");
+    group.throughput(Throughput::Bytes(LIST_PAGE.len() as u64));
+    group.bench_function("parse_document", |b| {
+        b.iter(|| Html::parse_document(std::hint::black_box(LIST_PAGE)))
+    });
+    group.bench_function("parse_list_page", |b| {
+        b.iter(|| plugin.parse_list_page(std::hint::black_box(&document)))
+    });
+    group.bench_function("parse_document_and_list_page", |b| {
+        b.iter(|| {
+            let document = Html::parse_document(std::hint::black_box(LIST_PAGE));
+            plugin.parse_list_page(&document)
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, list_page);
+criterion_main!(benches);
@@ -1,14 +1,18 @@
 #![allow(dead_code)]
+use std::borrow::Cow;
+
 use encoding_rs::EUC_KR;
 use futures::TryStreamExt as _;
 use futures::stream::FuturesOrdered;
 use scraper::{Html, Selector};
-use ssufid::core::{SsufidPlugin, SsufidPost, SsufidPostPlugin};
+use ssufid::core::{SsufidPlugin, SsufidPost, SsufidPostPlugin, UrlKind};
+use ssufid::datetime::parse_kst_labeled;
 use ssufid::error::PluginError;
+use ssufid::fetch::{fetch_page, fetch_text};
+use ssufid::html::{ContentSource, close_unclosed_in_cells, lowercase_tag_names, select_content};
+use ssufid::paginate::Cursor;
+use ssufid::text::clean_author;
 use thiserror::Error;
-use time::format_description::BorrowedFormatItem;
-use time::macros::offset;
-use time::{PrimitiveDateTime, macros::format_description};
 
 struct Selectors {
     list_item_selector: Selector,
@@ -93,44 +97,34 @@ impl SsuDormPlugin {
     }
 
     const LIST_PAGE_URL: &'static str = "https://ssudorm.ssu.ac.kr:444/SShostel/mall_main.php?viewform=B0001_noticeboard_list&board_no=1";
-    const POST_VIEW_URL_BASE: &'static str = "https://ssudorm.ssu.ac.kr:444/SShostel/mall_main.php?viewform=B0001_noticeboard_view&board_no=1";
-
-    const DATETIME_FORMAT: &[BorrowedFormatItem<'_>] =
-        format_description!("[year]-[month]-[day] [hour]:[minute]");
-
-    // Function to decode EUC-KR bytes to String
-    fn decode_euc_kr(bytes: &[u8]) -> String {
-        EUC_KR.decode(bytes).0.into_owned()
-    }
 
-    async fn fetch_html_content(&self, url: &str) -> Result<String, PluginError> {
-        let response_bytes = self
-            .http_client
-            .get(url)
-            .send()
-            .await
-            .map_err(|e| PluginError::request::<Self>(e.to_string()))?
-            .bytes()
-            .await
-            .map_err(|e| PluginError::parse::<Self>(e.to_string()))?;
-        Ok(Self::decode_euc_kr(&response_bytes))
-    }
+    /// The first list page. The board paginates with a `next` offset in steps of
+    /// [`POSTS_PER_PAGE`](SsufidPlugin::POSTS_PER_PAGE).
+    const FIRST_PAGE: Cursor = Cursor::Offset {
+        start: 0,
+        step: Self::POSTS_PER_PAGE,
+    };
 
+    /// Fetches the list page at `cursor`, returning its posts and the cursor of the next page.
     async fn fetch_page_posts_metadata(
         &self,
-        page: u32, // page is 1-indexed
-    ) -> Result<Vec<SsuDormPostMetadata>, PluginError> {
-        // Dormitory website uses 'next' for pagination, which seems to be an offset (multiples of 15)
-        // page 1: next=0 (or not present)
-        // page 2: next=15
-        // page 3: next=30
-        let offset = if page > 0 { (page - 1) * 15 } else { 0 }; // Ensure page is positive
-        let page_url = format!("{}&next={}", Self::LIST_PAGE_URL, offset);
+        cursor: &Cursor,
+    ) -> Result<(Vec<SsuDormPostMetadata>, Option<Cursor>), PluginError> {
+        let page_url = format!("{}&next={}", Self::LIST_PAGE_URL, cursor.query_value());
 
         tracing::info!("Fetching metadata from URL: {}", page_url);
 
-        let html_content = self.fetch_html_content(&page_url).await?;
-        let document = Html::parse_document(&html_content);
+        let html_content = fetch_text::<Self>(&self.http_client, &page_url).await?;
+        let metadata_list =
+            self.parse_list_page(&Html::parse_document(&self.normalize_html(&html_content)))?;
+        Ok((metadata_list, cursor.advance(None)))
+    }
+
+    /// Extracts the posts of a list page, skipping rows whose id can not be read.
+    pub fn parse_list_page(
+        &self,
+        document: &Html,
+    ) -> Result<Vec<SsuDormPostMetadata>, PluginError> {
         let mut metadata_list = Vec::new();
         tracing::debug!("Using list_item_selector for actual post rows.");
 
@@ -138,9 +132,8 @@ impl SsuDormPlugin {
             .select(&self.selectors.list_item_selector)
             .collect::<Vec<_>>();
 
-        for row_element in post_rows.into_iter().skip(2) {
-            // The title_in_list_selector is now more specific: "td:nth-child(2) > a[onclick*='viewContent' i]"
-            // It's applied to the current row_element.
+        for row_element in post_rows {
+            // Rows without a viewContent link, such as the header and separator rows, are skipped
             let title_element_opt = row_element
                 .select(&self.selectors.title_in_list_selector)
                 .next();
@@ -166,7 +159,12 @@ impl SsuDormPlugin {
                     continue;
                 }
 
-                let post_url = format!("{}&idx={}", Self::POST_VIEW_URL_BASE, id);
+                let post_url = Self::detail_url(&id).ok_or_else(|| {
+                    PluginError::custom::<Self>(
+                        "DetailUrlMissing".to_string(),
+                        "DETAIL_URL_TEMPLATE is not set".to_string(),
+                    )
+                })?;
                 let title = title_element.text().collect::<String>().trim().to_string();
                 if title.is_empty() {
                     tracing::warn!("Empty title for ID {}: {}", id, title_element.html());
@@ -207,18 +205,23 @@ impl SsuDormPlugin {
     ) -> Result<Vec<SsuDormPostMetadata>, PluginError> {
         tracing::info!("Fetching all posts metadata with limit: {}", posts_limit);
         let mut all_metadata = Vec::new();
-        let mut current_page = 1;
+        let mut cursor = Some(Self::FIRST_PAGE);
+        let mut pages_fetched = 0;
         const MAX_PAGES_TO_TRY: u32 = 50; // Safety break for pagination
 
-        while all_metadata.len() < posts_limit as usize && current_page <= MAX_PAGES_TO_TRY {
-            tracing::debug!("Fetching metadata for page: {}", current_page);
-            let metadata_list = self.fetch_page_posts_metadata(current_page).await?;
+        while let Some(current) = cursor.take() {
+            if all_metadata.len() >= posts_limit as usize || pages_fetched >= MAX_PAGES_TO_TRY {
+                break;
+            }
+            tracing::debug!("Fetching metadata at cursor: {:?}", current);
+            let (metadata_list, next) = self.fetch_page_posts_metadata(&current).await?;
+            pages_fetched += 1;
             if metadata_list.is_empty() {
-                tracing::info!("No more metadata found on page {}. Stopping.", current_page);
+                tracing::info!("No more metadata found at cursor {:?}. Stopping.", current);
                 break; // No more posts found on this page
             }
             all_metadata.extend(metadata_list);
-            current_page += 1;
+            cursor = next;
         }
 
         if all_metadata.len() > posts_limit as usize {
@@ -232,48 +235,59 @@ impl SsuDormPlugin {
         metadata: SsuDormPostMetadata,
     ) -> Result<SsufidPost, PluginError> {
         tracing::debug!("Fetching post data for URL: {}", metadata.url);
-        let html_content = self.fetch_html_content(&metadata.url).await?;
+        let page = fetch_page::<Self>(&self.http_client, &metadata.url).await?;
+        let mut post = self.parse_post(metadata, &page.text)?;
+        if page.stripped {
+            post.mark_content_stripped();
+        }
+        Ok(post)
+    }
+
+    /// Builds the post of `metadata` from its page.
+    fn parse_post(
+        &self,
+        metadata: SsuDormPostMetadata,
+        html_content: &str,
+    ) -> Result<SsufidPost, PluginError> {
+        let html_content = self.normalize_html(html_content);
         let document = Html::parse_document(&html_content);
 
         let title = document
             .select(&self.selectors.title_selector)
             .next()
             .map(|el| el.text().collect::<String>().trim().to_string())
-            .ok_or_else(|| SsuDormError::TitleNotFound(metadata.url.clone()))?;
+            .ok_or_else(|| {
+                PluginError::from(SsuDormError::TitleNotFound(metadata.url.clone()))
+                    .with_snippet(&self.selectors.title_selector, &html_content)
+            })?;
 
         let mut metadata_elements = document.select(&self.selectors.metadata_selector);
 
         let author_str = metadata_elements
             .next()
-            .and_then(|el| el.text().next().and_then(|s| s.split(':').nth(1)))
-            .ok_or_else(|| SsuDormError::AuthorDateStringParse(metadata.url.clone()))?
-            .trim()
-            .to_string();
+            .and_then(|el| el.text().next().and_then(clean_author))
+            .ok_or_else(|| SsuDormError::AuthorDateStringParse(metadata.url.clone()))?;
 
         let date_str = metadata_elements
             .nth(1)
-            .and_then(|el| {
-                el.text()
-                    .next()
-                    .and_then(|s| s.split_once(':').map(|(_, v)| v))
-            })
-            .ok_or_else(|| SsuDormError::AuthorDateStringParse(metadata.url.clone()))?
-            .trim()
-            .to_string();
-
-        let created_at = PrimitiveDateTime::parse(&date_str, Self::DATETIME_FORMAT)
-            .map_err(|_| SsuDormError::DateParse(date_str.clone()))?
-            .assume_offset(offset!(+9));
-
-        let content_element = document
-            .select(&self.selectors.content_selector)
-            .next()
-            .ok_or_else(|| SsuDormError::ContentNotFound(metadata.url.clone()))?;
+            .and_then(|el| el.text().next())
+            .ok_or_else(|| SsuDormError::AuthorDateStringParse(metadata.url.clone()))?;
+
+        let created_at = parse_kst_labeled(date_str)
+            .ok_or_else(|| SsuDormError::DateParse(date_str.trim().to_string()))?;
+
+        let (content_element, content_source) =
+            select_content::<Self>(&document, &self.selectors.content_selector, &metadata.url)
+                .ok_or_else(|| {
+                    PluginError::from(SsuDormError::ContentNotFound(metadata.url.clone()))
+                        .with_snippet(&self.selectors.content_selector, &html_content)
+                })?;
         let content = content_element.html(); // Get inner HTML to preserve formatting
 
-        Ok(SsufidPost {
+        let mut post = SsufidPost {
             id: metadata.id.clone(),
             url: metadata.url.clone(),
+            url_kind: UrlKind::Canonical,
             author: Some(author_str),
             title,
             description: None,
@@ -284,7 +298,12 @@ impl SsuDormPlugin {
             content,
             attachments: vec![],
             metadata: None,
-        })
+            author_detail: None,
+        };
+        if content_source == ContentSource::Fallback {
+            post.mark_content_fallback();
+        }
+        Ok(post)
     }
 }
 
@@ -295,7 +314,7 @@ impl Default for SsuDormPlugin {
 }
 
 #[derive(Debug, Clone)]
-struct SsuDormPostMetadata {
+pub struct SsuDormPostMetadata {
     id: String,
     url: String,
     title_from_list: String,
@@ -308,6 +327,28 @@ impl SsufidPlugin for SsuDormPlugin {
     const DESCRIPTION: &'static str = "숭실대학교 기숙사 홈페이지의 공지사항을 제공합니다.";
     // Base URL for resolving relative links if necessary
     const BASE_URL: &'static str = "https://ssudorm.ssu.ac.kr:444/SShostel/mall_main.php?viewform=B0001_noticeboard_list&board_no=1";
+    // The site has always served EUC-KR, often without declaring it
+    const ENCODING: &'static encoding_rs::Encoding = EUC_KR;
+    // 2: authors cleaned with `clean_author`, pages normalized before parsing
+    const VERSION: u32 = 2;
+    const POSTS_PER_PAGE: u32 = 15;
+    const DETAIL_URL_TEMPLATE: Option<&'static str> = Some(
+        "https://ssudorm.ssu.ac.kr:444/SShostel/mall_main.php?viewform=B0001_noticeboard_view&board_no=1&idx={id}",
+    );
+
+    /// The board's editor writes uppercase tags and leaves `<font>` tags open in post cells.
+    fn normalize_html<'a>(&self, raw: &'a str) -> Cow<'a, str> {
+        match lowercase_tag_names(raw) {
+            Cow::Borrowed(raw) => close_unclosed_in_cells(raw, "font"),
+            Cow::Owned(lowercased) => {
+                Cow::Owned(close_unclosed_in_cells(&lowercased, "font").into_owned())
+            }
+        }
+    }
+
+    fn http_client(&self) -> reqwest::Client {
+        self.http_client.clone()
+    }
 }
 
 impl SsufidPostPlugin for SsuDormPlugin {
@@ -342,11 +383,94 @@ mod tests {
         let _ = tracing_subscriber::fmt::try_init();
     }
 
+    #[test]
+    fn test_decode_html_euc_kr_and_utf8() {
+        let html = r#"<td class="title"><a href="/notice">2025학년도 1학기 입사 안내</a></td>"#;
+        let decode = |bytes: &[u8], content_type: Option<&str>| {
+            ssufid::fetch::decode_html(bytes, content_type, SsuDormPlugin::ENCODING)
+        };
+
+        let (euc_kr, _, _) = EUC_KR.encode(html);
+        assert_eq!(decode(&euc_kr, Some("text/html")), html);
+        assert_eq!(decode(&euc_kr, None), html);
+
+        let utf8 = html.as_bytes();
+        assert_eq!(decode(utf8, Some("text/html")), html);
+        assert_eq!(decode(utf8, Some("text/html; charset=UTF-8")), html);
+    }
+
+    #[test]
+    fn test_header_rows_are_detected_structurally() {
+        let page = |header_rows: &str| {
+            Html::parse_document(&format!(
+                r##"<table bordercolor="#CCCCCC" frame="hsides"><tbody>
+                {header_rows}
+                <tr><td>512</td><td><a href="javascript:viewContent('B0001_noticeboard_view','1','512')">2025학년도 1학기 입사 안내</a></td><td>관리자</td><td>300</td><td>2025-02-28</td></tr>
+                <tr><td>511</td><td><a href="javascript:viewContent('B0001_noticeboard_view','1','511')">동계 방학 중 식당 운영 안내</a></td><td>관리자</td><td>311</td><td>2025-02-27</td></tr>
+                </tbody></table>"##
+            ))
+        };
+        let separator = r##"<tr><td colspan="5" height="2" bgcolor="#7a9bc4"></td></tr>"##;
+        let header =
+            "<tr><td>번호</td><td>제목</td><td>작성자</td><td>조회</td><td>등록일</td></tr>";
+        let plugin = SsuDormPlugin::default();
+        let ids = |document: &Html| {
+            plugin
+                .parse_list_page(document)
+                .unwrap()
+                .into_iter()
+                .map(|metadata| metadata.id)
+                .collect::<Vec<_>>()
+        };
+
+        let two_header_rows = ids(&page(&format!("{separator}{header}")));
+        assert_eq!(two_header_rows, ["512", "511"]);
+        // The first post survives a layout without the separator row
+        assert_eq!(ids(&page(header)), two_header_rows);
+    }
+
+    #[test]
+    fn test_tag_soup_post_parses_the_same_every_time() {
+        // 대문자 태그와 속성, 셀 안에서 닫히지 않은 font 태그가 섞인 글 페이지
+        let raw = r##"<HTML><BODY><TABLE><TBODY>
+            <TR><TD BGCOLOR="#edf8fc">2025학년도 1학기 입사 안내</TD></TR>
+            <TR><TD HEIGHT="38"><TABLE><TBODY><TR><TD>관리자</TD><TD>조회 300</TD><TD>2025-02-28</TD></TR></TBODY></TABLE></TD></TR>
+            <TR><TD CLASS="descript"><FONT COLOR="#ff0000"><B>입사 일정</B><BR>3월 1일 <FONT SIZE=2>10시부터</TD></TR>
+            <TR><TD>목록</FONT></TD></TR>
+            </TBODY></TABLE></BODY></HTML>"##;
+        let plugin = SsuDormPlugin::default();
+        let parse = || {
+            plugin
+                .parse_post(
+                    SsuDormPostMetadata {
+                        id: "512".to_string(),
+                        url: SsuDormPlugin::detail_url("512").unwrap(),
+                        title_from_list: String::new(),
+                        date_str_from_list: String::new(),
+                    },
+                    raw,
+                )
+                .unwrap()
+        };
+        let (first, second) = (parse(), parse());
+        assert_eq!(first.content, second.content);
+        assert_eq!(first.content_hash(), second.content_hash());
+        assert_eq!(
+            first.content,
+            r##"<td class="descript"><font color="#ff0000"><b>입사 일정</b><br>3월 1일 <font size="2">10시부터</font></font></td>"##
+        );
+        assert_eq!(first.title, "2025학년도 1학기 입사 안내");
+        assert_eq!(first.author.as_deref(), Some("관리자"));
+    }
+
     #[tokio::test]
     async fn test_fetch_page_posts_metadata_first_page() {
         setup_tracing();
         let plugin = SsuDormPlugin::default();
-        let metadata = plugin.fetch_page_posts_metadata(1).await.unwrap();
+        let (metadata, _) = plugin
+            .fetch_page_posts_metadata(&SsuDormPlugin::FIRST_PAGE)
+            .await
+            .unwrap();
         assert!(
             !metadata.is_empty(),
             "Should fetch some metadata from the first page."
@@ -365,7 +489,10 @@ mod tests {
         setup_tracing();
         let plugin = SsuDormPlugin::default();
         // Fetch metadata for the first page first to get a valid post to test
-        let metadata_list = plugin.fetch_page_posts_metadata(1).await.unwrap();
+        let (metadata_list, _) = plugin
+            .fetch_page_posts_metadata(&SsuDormPlugin::FIRST_PAGE)
+            .await
+            .unwrap();
         assert!(
             !metadata_list.is_empty(),
             "Need metadata to test fetching a post."
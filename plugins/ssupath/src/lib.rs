@@ -12,7 +12,8 @@ use utils::default_header;
 
 use ssufid::{
     PluginError,
-    core::{SsufidPlugin, SsufidPost, SsufidPostPlugin},
+    core::{SsufidPlugin, SsufidPost, SsufidPostPlugin, UrlKind},
+    fetch::send,
 };
 
 pub mod model;
@@ -96,16 +97,14 @@ impl SsuPathPlugin {
             .user_agent(utils::DEFAULT_USER_AGENT)
             .default_headers(default_header())
             .build()?;
-        let res = client.get("https://path.ssu.ac.kr/").send().await?;
+        let res = send::<SsuPathPlugin>(client.get("https://path.ssu.ac.kr/")).await?;
         let Some((_, rtn_url)) = res.url().query_pairs().find(|(k, _)| k == "rtnUrl") else {
             return Err(SsuSsoError::CantLoadForm);
         };
-        let res = client
-            .get(format!(
-                "https://path.ssu.ac.kr/comm/login/user/loginChk.do?rtnUrl={rtn_url}"
-            ))
-            .send()
-            .await?;
+        let res = send::<SsuPathPlugin>(client.get(format!(
+            "https://path.ssu.ac.kr/comm/login/user/loginChk.do?rtnUrl={rtn_url}"
+        )))
+        .await?;
         let Some((_, api_return_url)) = res.url().query_pairs().find(|(k, _)| k == "apiReturnUrl")
         else {
             return Err(SsuSsoError::CantLoadForm);
@@ -115,11 +114,12 @@ impl SsuPathPlugin {
             &"https://path.ssu.ac.kr".parse::<Url>().unwrap(),
         );
         tracing::info!("{api_return_url}?sToken={token}&sIdno={id}");
-        let res = client
-            .get(format!("{api_return_url}?sToken={token}&sIdno={id}"))
-            .header("Referer", "https://smartid.ssu.ac.kr/")
-            .send()
-            .await?;
+        let res = send::<SsuPathPlugin>(
+            client
+                .get(format!("{api_return_url}?sToken={token}&sIdno={id}"))
+                .header("Referer", "https://smartid.ssu.ac.kr/"),
+        )
+        .await?;
         if res.status() != reqwest::StatusCode::OK {
             return Err(SsuSsoError::CantFindToken(
                 "Authorization failed".to_string(),
@@ -175,7 +175,7 @@ async fn entries(
 ) -> Result<Vec<SsuPathProgram>, SsuPathPluginError> {
     let url = format!("{PATH_LIST_URL}{page}");
     tracing::info!("Crawling entries from {url}");
-    let response = client.get(url).send().await?.text().await?;
+    let response = send::<SsuPathPlugin>(client.get(url)).await?.text().await?;
     let document = Html::parse_document(&response);
     document
         .select(&ENTRIES_SELECTOR)
@@ -193,7 +193,10 @@ async fn post(
 ) -> Result<SsufidPost, SsuPathPluginError> {
     tracing::info!("Crawling program {}", program.id);
     let url = format!("{PATH_ENTRY_URL}{}", program.id);
-    let response = client.get(&url).send().await?.text().await?;
+    let response = send::<SsuPathPlugin>(client.get(&url))
+        .await?
+        .text()
+        .await?;
     let document = Html::parse_document(&response);
     let program_table = SsuPathProgramTable::from_document(&document)?;
     let course_table = match program.kind {
@@ -212,6 +215,7 @@ async fn post(
         description: Some(program.description.clone()),
         category: vec![program.label.clone()],
         url,
+        url_kind: UrlKind::Canonical,
         created_at: program.create_at(),
         content,
         updated_at: None,
@@ -219,6 +223,7 @@ async fn post(
         thumbnail: Some(program.thumbnail.clone()),
         attachments: Vec::default(),
         metadata: Some(frontmatters),
+        author_detail: None,
     })
 }
 
@@ -1,9 +1,13 @@
 use std::sync::Arc;
 
 use reqwest::{Client, cookie::Jar};
+use ssufid::fetch::send;
 use thiserror::Error;
 
-use super::utils::{DEFAULT_USER_AGENT, default_header};
+use super::{
+    SsuPathPlugin,
+    utils::{DEFAULT_USER_AGENT, default_header},
+};
 
 const SMARTID_LOGIN_URL: &str = "https://smartid.ssu.ac.kr/Symtra_sso/smln.asp";
 const SMARTID_LOGIN_FORM_REQUEST_URL: &str = "https://smartid.ssu.ac.kr/Symtra_sso/smln_pcs.asp";
@@ -30,10 +34,7 @@ pub(super) async fn obtain_ssu_sso_token(id: &str, password: &str) -> Result<Str
         .cookie_store(true)
         .user_agent(DEFAULT_USER_AGENT)
         .build()?;
-    let body = client
-        .get(SMARTID_LOGIN_URL)
-        .headers(default_header())
-        .send()
+    let body = send::<SsuPathPlugin>(client.get(SMARTID_LOGIN_URL).headers(default_header()))
         .await?
         .text()
         .await?;
@@ -44,12 +45,13 @@ pub(super) async fn obtain_ssu_sso_token(id: &str, password: &str) -> Result<Str
         ("userid", id),
         ("pwd", password),
     ];
-    let res = client
-        .post(SMARTID_LOGIN_FORM_REQUEST_URL)
-        .headers(default_header())
-        .form(&params)
-        .send()
-        .await?;
+    let res = send::<SsuPathPlugin>(
+        client
+            .post(SMARTID_LOGIN_FORM_REQUEST_URL)
+            .headers(default_header())
+            .form(&params),
+    )
+    .await?;
     let cookie_token = {
         res.cookies()
             .find(|cookie| cookie.name() == "sToken" && !cookie.value().is_empty())
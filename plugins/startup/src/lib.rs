@@ -2,7 +2,8 @@ use reqwest::header::CONTENT_TYPE;
 use serde::Deserialize;
 use ssufid::{
     PluginError,
-    core::{Attachment, SsufidPlugin, SsufidPost, SsufidPostPlugin},
+    core::{Attachment, SsufidPlugin, SsufidPost, SsufidPostPlugin, UrlKind},
+    fetch::{send, shared_client},
 };
 use time::{
     OffsetDateTime, PrimitiveDateTime,
@@ -15,11 +16,10 @@ impl StartupPlugin {
     const API_BASE_URL: &'static str = "https://startup.ssu.ac.kr/api";
 
     async fn list_posts(base_url: &str, posts_limit: u32) -> Result<Vec<StartupPost>, PluginError> {
-        let res = reqwest::Client::new()
+        let res = send::<Self>(shared_client()
         .get(format!(
             "{base_url}/board/content/list?boardEnName=notice&categoryCodeId&pageNum=1&pageSize={posts_limit}&searchMonth="
-        )).header(CONTENT_TYPE, "application/json")
-        .send()
+        )).header(CONTENT_TYPE, "application/json"))
         .await
         .map_err(|e| {tracing::error!(?e); PluginError::request::<Self>(e.to_string())})?
         .json::<StartupBoardResponse>()
@@ -127,6 +127,7 @@ impl From<StartupFile> for Attachment {
                 file.file_id,
             ),
             mime_type: None,
+            metadata: None,
         }
     }
 }
@@ -168,6 +169,7 @@ impl From<StartupPost> for SsufidPost {
                 StartupPlugin::BASE_URL,
                 post.board_content_id
             ),
+            url_kind: UrlKind::Canonical,
             author: Some(post.user_id),
             description: None,
             category: vec![post.board_category.category_name],
@@ -177,6 +179,7 @@ impl From<StartupPost> for SsufidPost {
             content: post.board_content,
             attachments: post.file_list.into_iter().map(Attachment::from).collect(),
             metadata: None,
+            author_detail: None,
         }
     }
 }
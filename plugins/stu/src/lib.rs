@@ -2,7 +2,8 @@ use reqwest::{Url, header::CONTENT_TYPE};
 use serde::Deserialize;
 use ssufid::{
     PluginError,
-    core::{SsufidPlugin, SsufidPost, SsufidPostPlugin},
+    core::{SsufidPlugin, SsufidPost, SsufidPostPlugin, UrlKind},
+    fetch::{send, shared_client},
 };
 use time::{
     OffsetDateTime, PrimitiveDateTime,
@@ -30,21 +31,22 @@ impl StuPlugin {
             .append_pair("groupCode", Self::GROUP_CODE)
             .append_pair("memberCode", Self::MEMBER_CODE);
 
-        let res = reqwest::Client::new()
-            .get(url)
-            .header(CONTENT_TYPE, "application/json")
-            .send()
-            .await
-            .map_err(|e| {
-                tracing::error!(?e);
-                PluginError::request::<Self>(e.to_string())
-            })?
-            .json::<StuBoardResponse>()
-            .await
-            .map_err(|e| {
-                tracing::error!(?e);
-                PluginError::parse::<Self>(e.to_string())
-            })?;
+        let res = send::<Self>(
+            shared_client()
+                .get(url)
+                .header(CONTENT_TYPE, "application/json"),
+        )
+        .await
+        .map_err(|e| {
+            tracing::error!(?e);
+            PluginError::request::<Self>(e.to_string())
+        })?
+        .json::<StuBoardResponse>()
+        .await
+        .map_err(|e| {
+            tracing::error!(?e);
+            PluginError::parse::<Self>(e.to_string())
+        })?;
         if !res.is_success {
             return Err(PluginError::custom::<Self>(
                 "Failed to fetch posts".to_string(),
@@ -124,6 +126,7 @@ impl From<StuPost> for SsufidPost {
             id: post.post_id.to_string(),
             title: post.title,
             url: format!("{}/{}", StuPlugin::BASE_URL, post.post_id),
+            url_kind: UrlKind::Canonical,
             author: Some(post.author),
             description: None,
             category: post.category.into_iter().collect::<Vec<_>>(),
@@ -133,6 +136,7 @@ impl From<StuPost> for SsufidPost {
             content: post.content,
             attachments: vec![],
             metadata: None,
+            author_detail: None,
         }
     }
 }
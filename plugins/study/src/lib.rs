@@ -9,6 +9,7 @@ use scraper::Selector;
 use ssufid::{
     PluginError,
     core::{SsufidPlugin, SsufidPost, SsufidPostPlugin},
+    fetch::send,
 };
 use url::Url;
 
@@ -47,14 +48,15 @@ impl StudyPlugin {
     async fn compressed_request(url: &str, body: &str) -> Result<String, PluginError> {
         let client = reqwest::Client::new();
         let req = lz_str::compress_to_utf16(body);
-        let res = client
-            .post(url)
-            .header(CONTENT_TYPE, "application/json")
-            .header(REFERER, Self::BASE_URL)
-            .body(req)
-            .send()
-            .await
-            .map_err(|e| PluginError::request::<Self>(e.to_string()))?;
+        let res = send::<Self>(
+            client
+                .post(url)
+                .header(CONTENT_TYPE, "application/json")
+                .header(REFERER, Self::BASE_URL)
+                .body(req),
+        )
+        .await
+        .map_err(|e| PluginError::request::<Self>(e.to_string()))?;
         let text = res
             .text()
             .await
@@ -64,9 +66,11 @@ impl StudyPlugin {
 
     async fn initial_response() -> Result<StudyPostListResponse, PluginError> {
         let client = reqwest::Client::new();
-        let initial_res = client.get(Self::BASE_URL).send().await.map_err(|e| {
-            PluginError::request::<Self>(format!("Failed to request to initial page {e:?}"))
-        })?;
+        let initial_res = send::<Self>(client.get(Self::BASE_URL))
+            .await
+            .map_err(|e| {
+                PluginError::request::<Self>(format!("Failed to request to initial page {e:?}"))
+            })?;
 
         let text = initial_res.text().await.map_err(|e| {
             PluginError::parse::<Self>(format!("Failed to parse initial page body {e:?}"))
@@ -125,9 +129,7 @@ impl StudyPlugin {
 
     async fn post(sb_seq: u32) -> Result<StudyPost, PluginError> {
         let client = reqwest::Client::new();
-        let res = client
-            .get(construct_post_url(sb_seq))
-            .send()
+        let res = send::<Self>(client.get(construct_post_url(sb_seq)))
             .await
             .map_err(|e| {
                 PluginError::request::<Self>(format!("Failed to get post {sb_seq}: {e:?}"))
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use ssufid::core::{Attachment, SsufidPost};
+use ssufid::core::{Attachment, SsufidPost, UrlKind};
 use time::{
     Date, OffsetDateTime,
     macros::{format_description, offset},
@@ -148,6 +148,7 @@ impl StudyFile {
             name: Some(self.file_nm.clone()),
             url: post_url,
             mime_type: None,
+            metadata: None,
         }
     }
 }
@@ -168,6 +169,7 @@ impl From<StudyPost> for SsufidPost {
             id: post.sb_seq,
             title: post.info.title,
             url: post_url.clone(),
+            url_kind: UrlKind::Canonical,
             author: Some(post.info.user_nm),
             description: None,
             category: vec![],
@@ -181,6 +183,7 @@ impl From<StudyPost> for SsufidPost {
                 .map(|f| f.to_attachment(post_url.clone()))
                 .collect(),
             metadata: None,
+            author_detail: None,
         }
     }
 }